@@ -0,0 +1,140 @@
+//! Benchmarks of the bytecode VM, at each optimization level of the bytecode, and of the
+//! stack-based VM against the register-based VM.
+
+use boa::{context::Backend, vm::OptimizationLevel, Context};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+#[cfg(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"))]
+#[cfg_attr(
+    all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"),
+    global_allocator
+)]
+static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
+
+/// The optimization levels that are compared, with their names.
+const LEVELS: [(OptimizationLevel, &str); 3] = [
+    (OptimizationLevel::None, "None"),
+    (OptimizationLevel::Basic, "Basic"),
+    (OptimizationLevel::Full, "Full"),
+];
+
+/// Benchmarks the execution of a script, compiled at each optimization level.
+fn bench_script(c: &mut Criterion, name: &str, src: &str) {
+    for (level, level_name) in LEVELS {
+        let mut context = Context::new();
+        context.set_optimization_level(level);
+
+        // Compile the script once, to only measure the execution of the bytecode.
+        let code_block = context.compile(src).unwrap();
+
+        c.bench_function(&format!("{} (VM, {})", name, level_name), move |b| {
+            b.iter(|| {
+                context
+                    .execute_bytecode(black_box(code_block.clone()))
+                    .unwrap()
+            })
+        });
+    }
+}
+
+/// The VM backends that are compared, with their names.
+const BACKENDS: [(Backend, &str); 2] = [
+    (Backend::Vm, "Stack VM"),
+    (Backend::Register, "Register VM"),
+];
+
+/// Benchmarks the execution of a script on the stack-based and the register-based VM.
+fn bench_backends(c: &mut Criterion, name: &str, src: &str) {
+    for (backend, backend_name) in BACKENDS {
+        let mut context = Context::new();
+        context.set_backend(backend);
+
+        let code_block = context.compile(src).unwrap();
+
+        c.bench_function(&format!("{} ({})", name, backend_name), move |b| {
+            b.iter(|| {
+                context
+                    .execute_bytecode(black_box(code_block.clone()))
+                    .unwrap()
+            })
+        });
+    }
+}
+
+/// Benchmarks the compilation of a script, including the optimization passes.
+fn bench_compilation(c: &mut Criterion, name: &str, src: &str) {
+    for (level, level_name) in LEVELS {
+        let mut context = Context::new();
+        context.set_optimization_level(level);
+
+        c.bench_function(&format!("{} (Compile, {})", name, level_name), move |b| {
+            b.iter(|| context.compile(black_box(src)).unwrap())
+        });
+    }
+}
+
+static ARITHMETIC_OPERATIONS: &str = include_str!("bench_scripts/arithmetic_operations.js");
+
+fn arithmetic_operations(c: &mut Criterion) {
+    bench_script(c, "Arithmetic operations", ARITHMETIC_OPERATIONS);
+    bench_backends(c, "Arithmetic operations", ARITHMETIC_OPERATIONS);
+}
+
+static FOR_LOOP: &str = include_str!("bench_scripts/for_loop.js");
+
+fn for_loop(c: &mut Criterion) {
+    bench_script(c, "For loop", FOR_LOOP);
+    bench_backends(c, "For loop", FOR_LOOP);
+}
+
+static FIBONACCI: &str = include_str!("bench_scripts/fibonacci.js");
+
+fn fibonacci(c: &mut Criterion) {
+    bench_script(c, "Fibonacci", FIBONACCI);
+    bench_backends(c, "Fibonacci", FIBONACCI);
+}
+
+static CLEAN_JS: &str = include_str!("bench_scripts/clean_js.js");
+
+fn clean_js(c: &mut Criterion) {
+    bench_script(c, "Clean js", CLEAN_JS);
+    bench_compilation(c, "Clean js", CLEAN_JS);
+    bench_backends(c, "Clean js", CLEAN_JS);
+}
+
+static MINI_JS: &str = include_str!("bench_scripts/mini_js.js");
+
+fn mini_js(c: &mut Criterion) {
+    bench_backends(c, "Mini js", MINI_JS);
+}
+
+static STRING_CONCAT: &str = include_str!("bench_scripts/string_concat.js");
+
+fn string_concat(c: &mut Criterion) {
+    bench_backends(c, "String concatenation", STRING_CONCAT);
+}
+
+static OBJECT_PROP_ACCESS_CONST: &str = include_str!("bench_scripts/object_prop_access_const.js");
+
+fn object_prop_access_const(c: &mut Criterion) {
+    bench_backends(c, "Static Object Property Access", OBJECT_PROP_ACCESS_CONST);
+}
+
+static ARRAY_ACCESS: &str = include_str!("bench_scripts/array_access.js");
+
+fn array_access(c: &mut Criterion) {
+    bench_backends(c, "Array access", ARRAY_ACCESS);
+}
+
+criterion_group!(
+    vm,
+    arithmetic_operations,
+    for_loop,
+    fibonacci,
+    clean_js,
+    mini_js,
+    string_concat,
+    object_prop_access_const,
+    array_access,
+);
+criterion_main!(vm);
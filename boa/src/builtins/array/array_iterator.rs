@@ -77,7 +77,7 @@ impl ArrayIterator {
         }
 
         let len = if let Some(f) = array_iterator.array.borrow().as_typed_array() {
-            if f.is_detached() {
+            if f.is_out_of_bounds() {
                 return context.throw_type_error(
                     "Cannot get value from typed array that has a detached array buffer",
                 );
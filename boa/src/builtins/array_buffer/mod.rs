@@ -1,7 +1,9 @@
 use crate::{
-    builtins::{typed_array::TypedArrayName, BuiltIn, JsArgs},
+    builtins::{
+        shared_array_buffer::SharedDataBlock, typed_array::TypedArrayName, BuiltIn, JsArgs,
+    },
     context::StandardObjects,
-    gc::{Finalize, Trace},
+    gc::{empty_trace, track_allocation, track_deallocation, Finalize, Trace},
     object::{
         internal_methods::get_prototype_from_constructor, ConstructorBuilder, FunctionBuilder,
         JsObject, ObjectData,
@@ -13,19 +15,142 @@ use crate::{
     Context, JsResult, JsValue,
 };
 use num_traits::{Signed, ToPrimitive};
-use std::convert::TryInto;
+use std::{
+    convert::TryInto,
+    ops::{Deref, DerefMut},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The maximum length of a data block, in bytes (8 GiB).
+///
+/// `CreateByteDataBlock` throws a `RangeError` if the data block can't be allocated. Since a
+/// failed allocation aborts the process instead of returning an error, larger data blocks are
+/// refused up front, including the maximum byte length of resizable buffers.
+const MAX_BYTE_LENGTH: usize = 1 << 33;
+
+/// The bytes of a Data Block owned by a single `ArrayBuffer` object.
+///
+/// The memory of the data block is counted in the heap statistics of the thread, see
+/// [`HeapStatistics::bytes_allocated`](crate::gc::HeapStatistics::bytes_allocated).
+#[derive(Debug, Default, Finalize)]
+pub struct DataBlock(Vec<u8>);
+
+// Safety: `DataBlock` does not contain any garbage collected values.
+unsafe impl Trace for DataBlock {
+    empty_trace!();
+}
+
+impl DataBlock {
+    /// Resizes the data block, filling the new bytes with zeros.
+    #[inline]
+    pub fn resize(&mut self, new_len: usize) {
+        track_deallocation(self.0.capacity());
+        self.0.resize(new_len, 0);
+        track_allocation(self.0.capacity());
+    }
+}
+
+impl From<Vec<u8>> for DataBlock {
+    #[inline]
+    fn from(bytes: Vec<u8>) -> Self {
+        track_allocation(bytes.capacity());
+        Self(bytes)
+    }
+}
+
+impl Clone for DataBlock {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.0.clone().into()
+    }
+}
+
+impl Drop for DataBlock {
+    #[inline]
+    fn drop(&mut self) {
+        track_deallocation(self.0.capacity());
+    }
+}
+
+impl Deref for DataBlock {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl DerefMut for DataBlock {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+/// The data block stored in the `[[ArrayBufferData]]` internal slot.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub enum ArrayBufferData {
+    /// A Data Block owned by a single `ArrayBuffer` object.
+    Owned(DataBlock),
+
+    /// A Shared Data Block of a `SharedArrayBuffer` object.
+    Shared(SharedDataBlock),
+}
 
 #[derive(Debug, Clone, Trace, Finalize)]
 pub struct ArrayBuffer {
-    pub array_buffer_data: Option<Vec<u8>>,
+    pub array_buffer_data: Option<ArrayBufferData>,
     pub array_buffer_byte_length: usize,
     pub array_buffer_detach_key: JsValue,
+    pub array_buffer_max_byte_length: Option<usize>,
+}
+
+impl ArrayBufferData {
+    /// Gets the number of bytes of the data block.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Owned(block) => block.len(),
+            Self::Shared(block) => block.byte_length(),
+        }
+    }
+
+    /// Returns `true` if the data block has no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 impl ArrayBuffer {
     pub(crate) fn array_buffer_byte_length(&self) -> usize {
         self.array_buffer_byte_length
     }
+
+    /// `IsFixedLengthArrayBuffer ( arrayBuffer )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-isfixedlengtharraybuffer
+    #[inline]
+    pub(crate) fn is_fixed_length(&self) -> bool {
+        // 1. If arrayBuffer has an [[ArrayBufferMaxByteLength]] internal slot, return false.
+        // 2. Return true.
+        self.array_buffer_max_byte_length.is_none()
+    }
+
+    /// Gets the shared data block of this buffer, if it is a `SharedArrayBuffer`.
+    #[inline]
+    pub fn shared_data_block(&self) -> Option<&SharedDataBlock> {
+        match &self.array_buffer_data {
+            Some(ArrayBufferData::Shared(block)) => Some(block),
+            _ => None,
+        }
+    }
 }
 
 impl BuiltIn for ArrayBuffer {
@@ -43,6 +168,28 @@ impl BuiltIn for ArrayBuffer {
             .constructor(false)
             .build();
 
+        let get_byte_length = FunctionBuilder::native(context, Self::byte_length)
+            .name("get byteLength")
+            .constructor(false)
+            .build();
+
+        let get_max_byte_length = FunctionBuilder::native(context, Self::max_byte_length)
+            .name("get maxByteLength")
+            .constructor(false)
+            .build();
+
+        let get_resizable = FunctionBuilder::native(context, Self::resizable)
+            .name("get resizable")
+            .constructor(false)
+            .build();
+
+        let get_detached = FunctionBuilder::native(context, Self::detached)
+            .name("get detached")
+            .constructor(false)
+            .build();
+
+        let flag_attributes = Attribute::CONFIGURABLE | Attribute::NON_ENUMERABLE;
+
         ConstructorBuilder::with_standard_object(
             context,
             Self::constructor,
@@ -57,8 +204,19 @@ impl BuiltIn for ArrayBuffer {
             Attribute::CONFIGURABLE,
         )
         .static_method(Self::is_view, "isView", 1)
-        .method(Self::byte_length, "byteLength", 0)
+        .accessor("byteLength", Some(get_byte_length), None, flag_attributes)
+        .accessor(
+            "maxByteLength",
+            Some(get_max_byte_length),
+            None,
+            flag_attributes,
+        )
+        .accessor("resizable", Some(get_resizable), None, flag_attributes)
+        .accessor("detached", Some(get_detached), None, flag_attributes)
+        .method(Self::resize, "resize", 1)
         .method(Self::slice, "slice", 2)
+        .method(Self::transfer, "transfer", 0)
+        .method(Self::transfer_to_fixed_length, "transferToFixedLength", 0)
         .property(
             WellKnownSymbols::to_string_tag(),
             Self::NAME,
@@ -72,7 +230,7 @@ impl BuiltIn for ArrayBuffer {
 impl ArrayBuffer {
     const LENGTH: usize = 1;
 
-    /// `25.1.3.1 ArrayBuffer ( length )`
+    /// `25.1.3.1 ArrayBuffer ( length [ , options ] )`
     ///
     /// More information:
     ///  - [ECMAScript reference][spec]
@@ -92,8 +250,41 @@ impl ArrayBuffer {
         // 2. Let byteLength be ? ToIndex(length).
         let byte_length = args.get_or_undefined(0).to_index(context)?;
 
-        // 3. Return ? AllocateArrayBuffer(NewTarget, byteLength).
-        Ok(Self::allocate(new_target, byte_length, context)?.into())
+        // 3. Let requestedMaxByteLength be ? GetArrayBufferMaxByteLengthOption(options).
+        let requested_max_byte_length =
+            Self::get_max_byte_length_option(args.get_or_undefined(1), context)?;
+
+        // 4. Return ? AllocateArrayBuffer(NewTarget, byteLength, requestedMaxByteLength).
+        Ok(Self::allocate(new_target, byte_length, requested_max_byte_length, context)?.into())
+    }
+
+    /// `GetArrayBufferMaxByteLengthOption ( options )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-getarraybuffermaxbytelengthoption
+    fn get_max_byte_length_option(
+        options: &JsValue,
+        context: &mut Context,
+    ) -> JsResult<Option<usize>> {
+        // 1. If options is not an Object, return empty.
+        let options = if let Some(options) = options.as_object() {
+            options
+        } else {
+            return Ok(None);
+        };
+
+        // 2. Let maxByteLength be ? Get(options, "maxByteLength").
+        let max_byte_length = options.get("maxByteLength", context)?;
+
+        // 3. If maxByteLength is undefined, return empty.
+        if max_byte_length.is_undefined() {
+            return Ok(None);
+        }
+
+        // 4. Return ? ToIndex(maxByteLength).
+        max_byte_length.to_index(context).map(Some)
     }
 
     /// `25.1.4.3 get ArrayBuffer [ @@species ]`
@@ -125,6 +316,38 @@ impl ArrayBuffer {
             .into())
     }
 
+    /// Gets the `ArrayBuffer` of the `this` value of one of the methods of
+    /// `ArrayBuffer.prototype`, throwing a `TypeError` if it isn't a non-shared `ArrayBuffer`.
+    ///
+    /// This implements the `RequireInternalSlot(O, [[ArrayBufferData]])` and
+    /// `If IsSharedArrayBuffer(O) is true, throw a TypeError exception.` steps.
+    fn this_array_buffer<'a>(
+        this: &'a JsValue,
+        method: &str,
+        context: &mut Context,
+    ) -> JsResult<&'a JsObject> {
+        let obj = if let Some(obj) = this.as_object() {
+            obj
+        } else {
+            return Err(context.construct_type_error(format!(
+                "ArrayBuffer.prototype.{} called with non-object value",
+                method
+            )));
+        };
+
+        match obj.borrow().as_array_buffer() {
+            Some(buffer) if !buffer.is_shared_array_buffer() => Ok(obj),
+            Some(_) => Err(context.construct_type_error(format!(
+                "ArrayBuffer.prototype.{} called with SharedArrayBuffer",
+                method
+            ))),
+            None => Err(context.construct_type_error(format!(
+                "ArrayBuffer.prototype.{} called with invalid object",
+                method
+            ))),
+        }
+    }
+
     /// `25.1.5.1 get ArrayBuffer.prototype.byteLength`
     ///
     /// More information:
@@ -134,20 +357,11 @@ impl ArrayBuffer {
     fn byte_length(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         // 1. Let O be the this value.
         // 2. Perform ? RequireInternalSlot(O, [[ArrayBufferData]]).
-        let obj = if let Some(obj) = this.as_object() {
-            obj
-        } else {
-            return context.throw_type_error("ArrayBuffer.byteLength called with non-object value");
-        };
-        let obj = obj.borrow();
-        let o = if let Some(o) = obj.as_array_buffer() {
-            o
-        } else {
-            return context.throw_type_error("ArrayBuffer.byteLength called with invalid object");
-        };
-
-        // TODO: Shared Array Buffer
         // 3. If IsSharedArrayBuffer(O) is true, throw a TypeError exception.
+        let obj = Self::this_array_buffer(this, "byteLength", context)?.borrow();
+        let o = obj
+            .as_array_buffer()
+            .expect("checked that `this` is an ArrayBuffer");
 
         // 4. If IsDetachedBuffer(O) is true, return +0𝔽.
         if Self::is_detached_buffer(o) {
@@ -159,37 +373,299 @@ impl ArrayBuffer {
         Ok(o.array_buffer_byte_length.into())
     }
 
-    /// `25.1.5.3 ArrayBuffer.prototype.slice ( start, end )`
+    /// `get ArrayBuffer.prototype.maxByteLength`
     ///
     /// More information:
     ///  - [ECMAScript reference][spec]
     ///
-    /// [spec]: https://tc39.es/ecma262/#sec-arraybuffer.prototype.slice
-    fn slice(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    /// [spec]: https://tc39.es/ecma262/#sec-get-arraybuffer.prototype.maxbytelength
+    fn max_byte_length(
+        this: &JsValue,
+        _args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
         // 1. Let O be the this value.
         // 2. Perform ? RequireInternalSlot(O, [[ArrayBufferData]]).
-        let obj = if let Some(obj) = this.as_object() {
-            obj
+        // 3. If IsSharedArrayBuffer(O) is true, throw a TypeError exception.
+        let obj = Self::this_array_buffer(this, "maxByteLength", context)?.borrow();
+        let o = obj
+            .as_array_buffer()
+            .expect("checked that `this` is an ArrayBuffer");
+
+        // 4. If IsDetachedBuffer(O) is true, return +0𝔽.
+        if Self::is_detached_buffer(o) {
+            return Ok(0.into());
+        }
+
+        // 5. If IsFixedLengthArrayBuffer(O) is false, then
+        //     a. Let length be O.[[ArrayBufferMaxByteLength]].
+        // 6. Else,
+        //     a. Let length be O.[[ArrayBufferByteLength]].
+        // 7. Return 𝔽(length).
+        Ok(o.array_buffer_max_byte_length
+            .unwrap_or(o.array_buffer_byte_length)
+            .into())
+    }
+
+    /// `get ArrayBuffer.prototype.resizable`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-get-arraybuffer.prototype.resizable
+    fn resizable(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[ArrayBufferData]]).
+        // 3. If IsSharedArrayBuffer(O) is true, throw a TypeError exception.
+        let obj = Self::this_array_buffer(this, "resizable", context)?.borrow();
+        let o = obj
+            .as_array_buffer()
+            .expect("checked that `this` is an ArrayBuffer");
+
+        // 4. If IsFixedLengthArrayBuffer(O) is false, return true; otherwise return false.
+        Ok((!o.is_fixed_length()).into())
+    }
+
+    /// `get ArrayBuffer.prototype.detached`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-get-arraybuffer.prototype.detached
+    fn detached(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[ArrayBufferData]]).
+        // 3. If IsSharedArrayBuffer(O) is true, throw a TypeError exception.
+        let obj = Self::this_array_buffer(this, "detached", context)?.borrow();
+        let o = obj
+            .as_array_buffer()
+            .expect("checked that `this` is an ArrayBuffer");
+
+        // 4. Return IsDetachedBuffer(O).
+        Ok(o.is_detached_buffer().into())
+    }
+
+    /// `ArrayBuffer.prototype.resize ( newLength )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-arraybuffer.prototype.resize
+    fn resize(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[ArrayBufferMaxByteLength]]).
+        // 3. If IsSharedArrayBuffer(O) is true, throw a TypeError exception.
+        let obj = Self::this_array_buffer(this, "resize", context)?;
+        let max_byte_length = if let Some(max_byte_length) = obj
+            .borrow()
+            .as_array_buffer()
+            .expect("checked that `this` is an ArrayBuffer")
+            .array_buffer_max_byte_length
+        {
+            max_byte_length
+        } else {
+            return context.throw_type_error(
+                "ArrayBuffer.prototype.resize called with fixed-length ArrayBuffer",
+            );
+        };
+
+        // 4. Let newByteLength be ? ToIndex(newLength).
+        let new_byte_length = args.get_or_undefined(0).to_index(context)?;
+
+        let mut obj = obj.borrow_mut();
+        let o = obj
+            .as_array_buffer_mut()
+            .expect("checked that `this` is an ArrayBuffer");
+
+        // 5. If IsDetachedBuffer(O) is true, throw a TypeError exception.
+        let block = if let Some(ArrayBufferData::Owned(block)) = &mut o.array_buffer_data {
+            block
         } else {
-            return context.throw_type_error("ArrayBuffer.slice called with non-object value");
+            return context
+                .throw_type_error("ArrayBuffer.prototype.resize called with detached buffer");
         };
-        let obj_borrow = obj.borrow();
-        let o = if let Some(o) = obj_borrow.as_array_buffer() {
-            o
+
+        // 6. If newByteLength > O.[[ArrayBufferMaxByteLength]], throw a RangeError exception.
+        if new_byte_length > max_byte_length {
+            return context.throw_range_error("new byte length exceeds the maximum byte length");
+        }
+
+        // 7. Let hostHandled be ? HostResizeArrayBuffer(O, newByteLength).
+        // 8. If hostHandled is handled, return undefined.
+        // 9. Let oldBlock be O.[[ArrayBufferData]].
+        // 10. Let newBlock be ? CreateByteDataBlock(newByteLength).
+        // 11. Let copyLength be min(newByteLength, O.[[ArrayBufferByteLength]]).
+        // 12. Perform CopyDataBlockBytes(newBlock, 0, oldBlock, 0, copyLength).
+        // 13. NOTE: Neither creation of the new Data Block nor copying from the old Data Block are observable.
+        //     Implementations may implement this method as in-place growth or shrinkage.
+        // 14. Set O.[[ArrayBufferData]] to newBlock.
+        block.resize(new_byte_length);
+
+        // 15. Set O.[[ArrayBufferByteLength]] to newByteLength.
+        o.array_buffer_byte_length = new_byte_length;
+
+        // 16. Return undefined.
+        Ok(JsValue::undefined())
+    }
+
+    /// `ArrayBuffer.prototype.transfer ( [ newLength ] )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-arraybuffer.prototype.transfer
+    fn transfer(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let O be the this value.
+        // 2. Return ? ArrayBufferCopyAndDetach(O, newLength, preserve-resizability).
+        Self::copy_and_detach(this, args.get_or_undefined(0), true, context).map(Into::into)
+    }
+
+    /// `ArrayBuffer.prototype.transferToFixedLength ( [ newLength ] )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-arraybuffer.prototype.transfertofixedlength
+    fn transfer_to_fixed_length(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let O be the this value.
+        // 2. Return ? ArrayBufferCopyAndDetach(O, newLength, fixed-length).
+        Self::copy_and_detach(this, args.get_or_undefined(0), false, context).map(Into::into)
+    }
+
+    /// `ArrayBufferCopyAndDetach ( arrayBuffer, newLength, preserveResizability )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-arraybuffercopyanddetach
+    fn copy_and_detach(
+        array_buffer: &JsValue,
+        new_length: &JsValue,
+        preserve_resizability: bool,
+        context: &mut Context,
+    ) -> JsResult<JsObject> {
+        // 1. Perform ? RequireInternalSlot(arrayBuffer, [[ArrayBufferData]]).
+        // 2. If IsSharedArrayBuffer(arrayBuffer) is true, throw a TypeError exception.
+        let obj = Self::this_array_buffer(
+            array_buffer,
+            if preserve_resizability {
+                "transfer"
+            } else {
+                "transferToFixedLength"
+            },
+            context,
+        )?;
+
+        // 3. If newLength is undefined, then
+        let new_byte_length = if new_length.is_undefined() {
+            // a. Let newByteLength be arrayBuffer.[[ArrayBufferByteLength]].
+            obj.borrow()
+                .as_array_buffer()
+                .expect("checked that `this` is an ArrayBuffer")
+                .array_buffer_byte_length
         } else {
-            return context.throw_type_error("ArrayBuffer.slice called with invalid object");
+            // 4. Else,
+            // a. Let newByteLength be ? ToIndex(newLength).
+            new_length.to_index(context)?
         };
 
-        // TODO: Shared Array Buffer
-        // 3. If IsSharedArrayBuffer(O) is true, throw a TypeError exception.
+        let mut obj_borrow = obj.borrow_mut();
+        let o = obj_borrow
+            .as_array_buffer_mut()
+            .expect("checked that `this` is an ArrayBuffer");
 
-        // 4. If IsDetachedBuffer(O) is true, throw a TypeError exception.
-        if Self::is_detached_buffer(o) {
-            return context.throw_type_error("ArrayBuffer.slice called with detached buffer");
+        // 5. If IsDetachedBuffer(arrayBuffer) is true, throw a TypeError exception.
+        if o.is_detached_buffer() {
+            return Err(context.construct_type_error("cannot transfer a detached ArrayBuffer"));
+        }
+
+        // 6. If preserveResizability is preserve-resizability and IsFixedLengthArrayBuffer(arrayBuffer) is false, then
+        //     a. Let newMaxByteLength be arrayBuffer.[[ArrayBufferMaxByteLength]].
+        // 7. Else,
+        //     a. Let newMaxByteLength be empty.
+        let new_max_byte_length = if preserve_resizability {
+            o.array_buffer_max_byte_length
+        } else {
+            None
+        };
+
+        // 8. If arrayBuffer.[[ArrayBufferDetachKey]] is not undefined, throw a TypeError exception.
+        if !o.array_buffer_detach_key.is_undefined() {
+            return Err(
+                context.construct_type_error("cannot transfer a non-detachable ArrayBuffer")
+            );
+        }
+
+        // 9. Let newBuffer be ? AllocateArrayBuffer(%ArrayBuffer%, newByteLength, newMaxByteLength).
+        if matches!(new_max_byte_length, Some(max) if new_byte_length > max) {
+            return Err(
+                context.construct_range_error("new byte length exceeds the maximum byte length")
+            );
+        }
+        if new_byte_length > MAX_BYTE_LENGTH {
+            return Err(context.construct_range_error("ArrayBuffer allocation failed"));
         }
 
-        // 5. Let len be O.[[ArrayBufferByteLength]].
-        let len = o.array_buffer_byte_length as i64;
+        // 10. Let copyLength be min(newByteLength, arrayBuffer.[[ArrayBufferByteLength]]).
+        // 11. Let fromBlock be arrayBuffer.[[ArrayBufferData]].
+        // 12. Let toBlock be newBuffer.[[ArrayBufferData]].
+        // 13. Perform CopyDataBlockBytes(toBlock, 0, fromBlock, 0, copyLength).
+        // 14. NOTE: Neither creation of the new Data Block nor copying from the old Data Block are observable.
+        //     Implementations may implement this method as a zero-copy move or a realloc.
+        // 15. Perform ! DetachArrayBuffer(arrayBuffer).
+        let mut block = match &mut o.array_buffer_data {
+            Some(ArrayBufferData::Owned(block)) => std::mem::take(block),
+            _ => unreachable!("checked that the buffer is a non-detached ArrayBuffer"),
+        };
+        o.array_buffer_data = None;
+        o.array_buffer_byte_length = 0;
+        block.resize(new_byte_length);
+        drop(obj_borrow);
+
+        let prototype = context.standard_objects().array_buffer_object().prototype();
+
+        // 16. Return newBuffer.
+        Ok(JsObject::from_proto_and_data(
+            prototype,
+            ObjectData::array_buffer(Self {
+                array_buffer_data: Some(ArrayBufferData::Owned(block)),
+                array_buffer_byte_length: new_byte_length,
+                array_buffer_detach_key: JsValue::Undefined,
+                array_buffer_max_byte_length: new_max_byte_length,
+            }),
+        ))
+    }
+
+    /// `25.1.5.3 ArrayBuffer.prototype.slice ( start, end )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-arraybuffer.prototype.slice
+    fn slice(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[ArrayBufferData]]).
+        // 3. If IsSharedArrayBuffer(O) is true, throw a TypeError exception.
+        let obj = Self::this_array_buffer(this, "slice", context)?;
+
+        let len = {
+            let obj_borrow = obj.borrow();
+            let o = obj_borrow
+                .as_array_buffer()
+                .expect("checked that `this` is an ArrayBuffer");
+
+            // 4. If IsDetachedBuffer(O) is true, throw a TypeError exception.
+            if Self::is_detached_buffer(o) {
+                return context.throw_type_error("ArrayBuffer.slice called with detached buffer");
+            }
+
+            // 5. Let len be O.[[ArrayBufferByteLength]].
+            o.array_buffer_byte_length as i64
+        };
 
         // 6. Let relativeStart be ? ToIntegerOrInfinity(start).
         let relative_start = args.get_or_undefined(0).to_integer_or_infinity(context)?;
@@ -242,8 +718,11 @@ impl ArrayBuffer {
                 context.construct_type_error("ArrayBuffer constructor returned invalid object")
             })?;
 
-            // TODO: Shared Array Buffer
             // 18. If IsSharedArrayBuffer(new) is true, throw a TypeError exception.
+            if new_array_buffer.is_shared_array_buffer() {
+                return context
+                    .throw_type_error("ArrayBuffer constructor returned SharedArrayBuffer");
+            }
 
             // 19. If IsDetachedBuffer(new) is true, throw a TypeError exception.
             if new_array_buffer.is_detached_buffer() {
@@ -266,8 +745,12 @@ impl ArrayBuffer {
             return context.throw_type_error("New ArrayBuffer length too small");
         }
 
-        // 22. NOTE: Side-effects of the above steps may have detached O.
+        // 22. NOTE: Side-effects of the above steps may have detached or resized O.
         // 23. If IsDetachedBuffer(O) is true, throw a TypeError exception.
+        let obj_borrow = obj.borrow();
+        let o = obj_borrow
+            .as_array_buffer()
+            .expect("checked that `this` is an ArrayBuffer");
         if Self::is_detached_buffer(o) {
             return context
                 .throw_type_error("ArrayBuffer detached while ArrayBuffer.slice was running");
@@ -285,14 +768,24 @@ impl ArrayBuffer {
             .as_mut()
             .expect("ArrayBuffer cannot be detached here");
 
-        // 26. Perform CopyDataBlockBytes(toBuf, 0, fromBuf, first, newLen).
-        copy_data_block_bytes(to_buf, 0, from_buf, first as usize, new_len);
+        // 26. Let currentLen be O.[[ArrayBufferByteLength]].
+        let current_len = o.array_buffer_byte_length;
 
-        // 27. Return new.
+        // 27. If first < currentLen, then
+        let first = first as usize;
+        if first < current_len {
+            // a. Let count be min(newLen, currentLen - first).
+            let count = std::cmp::min(new_len, current_len - first);
+
+            // b. Perform CopyDataBlockBytes(toBuf, 0, fromBuf, first, count).
+            copy_data_block_bytes(to_buf, 0, from_buf, first, count);
+        }
+
+        // 28. Return new.
         Ok(new)
     }
 
-    /// `25.1.2.1 AllocateArrayBuffer ( constructor, byteLength )`
+    /// `25.1.2.1 AllocateArrayBuffer ( constructor, byteLength [ , maxByteLength ] )`
     ///
     /// More information:
     ///  - [ECMAScript reference][spec]
@@ -301,9 +794,24 @@ impl ArrayBuffer {
     pub(crate) fn allocate(
         constructor: &JsValue,
         byte_length: usize,
+        max_byte_length: Option<usize>,
         context: &mut Context,
     ) -> JsResult<JsObject> {
-        // 1. Let obj be ? OrdinaryCreateFromConstructor(constructor, "%ArrayBuffer.prototype%", « [[ArrayBufferData]], [[ArrayBufferByteLength]], [[ArrayBufferDetachKey]] »).
+        // 1. Let slots be « [[ArrayBufferData]], [[ArrayBufferByteLength]], [[ArrayBufferDetachKey]] ».
+        // 2. If maxByteLength is present and maxByteLength is not empty, let allocatingResizableBuffer be true; otherwise let allocatingResizableBuffer be false.
+        // 3. If allocatingResizableBuffer is true, then
+        if let Some(max_byte_length) = max_byte_length {
+            // a. If byteLength > maxByteLength, throw a RangeError exception.
+            if byte_length > max_byte_length {
+                return Err(
+                    context.construct_range_error("byte length exceeds the maximum byte length")
+                );
+            }
+
+            // b. Append [[ArrayBufferMaxByteLength]] to slots.
+        }
+
+        // 4. Let obj be ? OrdinaryCreateFromConstructor(constructor, "%ArrayBuffer.prototype%", slots).
         let prototype = get_prototype_from_constructor(
             constructor,
             StandardObjects::array_buffer_object,
@@ -312,22 +820,29 @@ impl ArrayBuffer {
         let obj = context.construct_object();
         obj.set_prototype(prototype.into());
 
-        // 2. Let block be ? CreateByteDataBlock(byteLength).
-        // TODO: for now just a arbitrary limit to not OOM.
-        if byte_length > 8589934592 {
+        // 5. Let block be ? CreateByteDataBlock(byteLength).
+        if byte_length > MAX_BYTE_LENGTH {
+            return Err(context.construct_range_error("ArrayBuffer allocation failed"));
+        }
+        let block = DataBlock::from(vec![0; byte_length]);
+
+        // 6. Set obj.[[ArrayBufferData]] to block.
+        // 7. Set obj.[[ArrayBufferByteLength]] to byteLength.
+        // 8. If allocatingResizableBuffer is true, then
+        //     a. If it is not possible to create a Data Block block consisting of maxByteLength bytes, throw a RangeError exception.
+        //     b. NOTE: Resizable ArrayBuffers are designed to be implementable with in-place growth. Implementations may throw if, for example, virtual memory cannot be reserved up front.
+        //     c. Set obj.[[ArrayBufferMaxByteLength]] to maxByteLength.
+        if matches!(max_byte_length, Some(max) if max > MAX_BYTE_LENGTH) {
             return Err(context.construct_range_error("ArrayBuffer allocation failed"));
         }
-        let block = vec![0; byte_length];
-
-        // 3. Set obj.[[ArrayBufferData]] to block.
-        // 4. Set obj.[[ArrayBufferByteLength]] to byteLength.
         obj.borrow_mut().data = ObjectData::array_buffer(ArrayBuffer {
-            array_buffer_data: Some(block),
+            array_buffer_data: Some(ArrayBufferData::Owned(block)),
             array_buffer_byte_length: byte_length,
             array_buffer_detach_key: JsValue::Undefined,
+            array_buffer_max_byte_length: max_byte_length,
         });
 
-        // 5. Return obj.
+        // 9. Return obj.
         Ok(obj)
     }
 
@@ -343,6 +858,21 @@ impl ArrayBuffer {
         self.array_buffer_data.is_none()
     }
 
+    /// `25.2.1.2 IsSharedArrayBuffer ( obj )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-issharedarraybuffer
+    pub(crate) fn is_shared_array_buffer(&self) -> bool {
+        // 1. Let bufferData be obj.[[ArrayBufferData]].
+        // 2. If bufferData is null, return false.
+        // 3. If bufferData is a Data Block, return false.
+        // 4. Assert: bufferData is a Shared Data Block.
+        // 5. Return true.
+        matches!(self.array_buffer_data, Some(ArrayBufferData::Shared(_)))
+    }
+
     /// `25.1.2.4 CloneArrayBuffer ( srcBuffer, srcByteOffset, srcLength, cloneConstructor )`
     ///
     /// More information:
@@ -357,7 +887,7 @@ impl ArrayBuffer {
         context: &mut Context,
     ) -> JsResult<JsObject> {
         // 1. Let targetBuffer be ? AllocateArrayBuffer(cloneConstructor, srcLength).
-        let target_buffer = Self::allocate(clone_constructor, src_length, context)?;
+        let target_buffer = Self::allocate(clone_constructor, src_length, None, context)?;
 
         // 2. If IsDetachedBuffer(srcBuffer) is true, throw a TypeError exception.
         // 3. Let srcBlock be srcBuffer.[[ArrayBufferData]].
@@ -397,7 +927,7 @@ impl ArrayBuffer {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-isunclampedintegerelementtype
-    fn is_unclamped_integer_element_type(t: TypedArrayName) -> bool {
+    pub(crate) fn is_unclamped_integer_element_type(t: TypedArrayName) -> bool {
         // 1. If type is Int8, Uint8, Int16, Uint16, Int32, or Uint32, return true.
         // 2. Return false.
         matches!(
@@ -417,7 +947,7 @@ impl ArrayBuffer {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-isbigintelementtype
-    fn is_big_int_element_type(t: TypedArrayName) -> bool {
+    pub(crate) fn is_big_int_element_type(t: TypedArrayName) -> bool {
         // 1. If type is BigUint64 or BigInt64, return true.
         // 2. Return false.
         matches!(
@@ -432,7 +962,7 @@ impl ArrayBuffer {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-isnotearconfiguration
-    // TODO: Allow unused function until shared array buffers are implemented.
+    // TODO: Allow unused function until the memory model is implemented.
     #[allow(dead_code)]
     fn is_no_tear_configuration(t: TypedArrayName, order: SharedMemoryOrder) -> bool {
         // 1. If ! IsUnclampedIntegerElementType(type) is true, return true.
@@ -460,7 +990,11 @@ impl ArrayBuffer {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-rawbytestonumeric
-    fn raw_bytes_to_numeric(t: TypedArrayName, bytes: &[u8], is_little_endian: bool) -> JsValue {
+    pub(crate) fn raw_bytes_to_numeric(
+        t: TypedArrayName,
+        bytes: &[u8],
+        is_little_endian: bool,
+    ) -> JsValue {
         let n: Numeric = match t {
             TypedArrayName::Int8Array => {
                 if is_little_endian {
@@ -564,7 +1098,7 @@ impl ArrayBuffer {
         byte_index: usize,
         t: TypedArrayName,
         _is_typed_array: bool,
-        _order: SharedMemoryOrder,
+        order: SharedMemoryOrder,
         is_little_endian: Option<bool>,
     ) -> JsValue {
         // 1. Assert: IsDetachedBuffer(arrayBuffer) is false.
@@ -578,12 +1112,26 @@ impl ArrayBuffer {
         // 4. Let elementSize be the Element Size value specified in Table 73 for Element Type type.
         let element_size = t.element_size();
 
-        // TODO: Shared Array Buffer
-        // 5. If IsSharedArrayBuffer(arrayBuffer) is true, then
+        let mut shared_raw_value = [0; 8];
+        let raw_value = match block {
+            // 5. If IsSharedArrayBuffer(arrayBuffer) is true, then
+            ArrayBufferData::Shared(block) => {
+                // a. Let execution be the [[CandidateExecution]] field of the surrounding agent's Agent Record.
+                // b. Let eventList be the [[EventList]] field of the element in execution.[[EventsRecords]] whose [[AgentSignifier]] is AgentSignifier().
+                // c. If isTypedArray is true and IsNoTearConfiguration(type, order) is true, let noTear be true; otherwise let noTear be false.
+                // d. Let rawValue be a List of length elementSize whose elements are nondeterministically chosen byte values.
+                // e. NOTE: In implementations, rawValue is the result of a non-atomic or atomic read instruction on the underlying hardware. The nondeterminism is a semantic prescription of the memory model to describe observable behaviour of hardware with weak consistency.
+                // f. Let readEvent be ReadSharedMemory { [[Order]]: order, [[NoTear]]: noTear, [[Block]]: block, [[ByteIndex]]: byteIndex, [[ElementSize]]: elementSize }.
+                // g. Append readEvent to eventList.
+                // h. Append Chosen Value Record { [[Event]]: readEvent, [[ChosenValue]]: rawValue } to execution.[[ChosenValues]].
+                block.load(byte_index, &mut shared_raw_value[..element_size], &order);
+                &shared_raw_value[..element_size]
+            }
+            // 6. Else, let rawValue be a List whose elements are bytes from block at indices byteIndex (inclusive) through byteIndex + elementSize (exclusive).
+            ArrayBufferData::Owned(block) => &block[byte_index..byte_index + element_size],
+        };
 
-        // 6. Else, let rawValue be a List whose elements are bytes from block at indices byteIndex (inclusive) through byteIndex + elementSize (exclusive).
         // 7. Assert: The number of elements in rawValue is elementSize.
-        let raw_value = &block[byte_index..byte_index + element_size];
 
         // TODO: Agent Record [[LittleEndian]] filed
         // 8. If isLittleEndian is not present, set isLittleEndian to the value of the [[LittleEndian]] field of the surrounding agent's Agent Record.
@@ -599,7 +1147,7 @@ impl ArrayBuffer {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-numerictorawbytes
-    fn numeric_to_raw_bytes(
+    pub(crate) fn numeric_to_raw_bytes(
         t: TypedArrayName,
         value: JsValue,
         is_little_endian: bool,
@@ -698,7 +1246,7 @@ impl ArrayBuffer {
         byte_index: usize,
         t: TypedArrayName,
         value: JsValue,
-        _order: SharedMemoryOrder,
+        order: SharedMemoryOrder,
         is_little_endian: Option<bool>,
         context: &mut Context,
     ) -> JsResult<JsValue> {
@@ -720,17 +1268,106 @@ impl ArrayBuffer {
         // 7. Let rawBytes be NumericToRawBytes(type, value, isLittleEndian).
         let raw_bytes = Self::numeric_to_raw_bytes(t, value, is_little_endian, context)?;
 
-        // TODO: Shared Array Buffer
-        // 8. If IsSharedArrayBuffer(arrayBuffer) is true, then
-
-        // 9. Else, store the individual bytes of rawBytes into block, starting at block[byteIndex].
-        for (i, raw_byte) in raw_bytes.iter().enumerate() {
-            block[byte_index + i] = *raw_byte;
+        match block {
+            // 8. If IsSharedArrayBuffer(arrayBuffer) is true, then
+            ArrayBufferData::Shared(block) => {
+                // a. Let execution be the [[CandidateExecution]] field of the surrounding agent's Agent Record.
+                // b. Let eventList be the [[EventList]] field of the element in execution.[[EventsRecords]] whose [[AgentSignifier]] is AgentSignifier().
+                // c. If isTypedArray is true and IsNoTearConfiguration(type, order) is true, let noTear be true; otherwise let noTear be false.
+                // d. Append WriteSharedMemory { [[Order]]: order, [[NoTear]]: noTear, [[Block]]: block, [[ByteIndex]]: byteIndex, [[ElementSize]]: elementSize, [[Payload]]: rawBytes } to eventList.
+                block.store(byte_index, &raw_bytes, &order);
+            }
+            // 9. Else, store the individual bytes of rawBytes into block, starting at block[byteIndex].
+            ArrayBufferData::Owned(block) => {
+                for (i, raw_byte) in raw_bytes.iter().enumerate() {
+                    block[byte_index + i] = *raw_byte;
+                }
+            }
         }
 
         // 10. Return NormalCompletion(undefined).
         Ok(JsValue::undefined())
     }
+
+    /// `25.1.2.13 GetModifySetValueInBuffer ( arrayBuffer, byteIndex, type, value, op [ , isLittleEndian ] )`
+    ///
+    /// The modification function `op` receives the bytes read from the buffer and the bytes of
+    /// `value` as little endian integers, zero-extended to 64 bits. Its result is truncated to the
+    /// element size of `t`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-getmodifysetvalueinbuffer
+    pub(crate) fn get_modify_set_value_in_buffer<F>(
+        &mut self,
+        byte_index: usize,
+        t: TypedArrayName,
+        value: JsValue,
+        op: F,
+        context: &mut Context,
+    ) -> JsResult<JsValue>
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        // 1. Assert: IsDetachedBuffer(arrayBuffer) is false.
+        // 2. Assert: There are sufficient bytes in arrayBuffer starting at byteIndex to represent a value of type.
+        // 3. Assert: Type(value) is BigInt if ! IsBigIntElementType(type) is true; otherwise, Type(value) is Number.
+        // 4. Let block be arrayBuffer.[[ArrayBufferData]].
+        let block = self
+            .array_buffer_data
+            .as_mut()
+            .expect("ArrayBuffer cannot be detached here");
+
+        // 5. Let elementSize be the Element Size value specified in Table 73 for Element Type type.
+        let element_size = t.element_size();
+
+        // TODO: Agent Record [[LittleEndian]] filed
+        // 6. If isLittleEndian is not present, set isLittleEndian to the value of the [[LittleEndian]] field of the surrounding agent's Agent Record.
+        // 7. Let rawBytes be NumericToRawBytes(type, value, isLittleEndian).
+        let raw_bytes = Self::numeric_to_raw_bytes(t, value, true, context)?;
+        let mut operand = [0; 8];
+        operand[..element_size].copy_from_slice(&raw_bytes);
+        let operand = u64::from_le_bytes(operand);
+
+        let raw_bytes_read = match block {
+            // 8. If IsSharedArrayBuffer(arrayBuffer) is true, then
+            ArrayBufferData::Shared(block) => {
+                // a. Let execution be the [[CandidateExecution]] field of the surrounding agent's Agent Record.
+                // b. Let eventList be the [[EventList]] field of the element in execution.[[EventsRecords]] whose [[AgentSignifier]] is AgentSignifier().
+                // c. Let rawBytesRead be a List of length elementSize whose elements are nondeterministically chosen byte values.
+                // d. NOTE: In implementations, rawBytesRead is the result of a load-link, of a load-exclusive, or of an operand of a read-modify-write instruction on the underlying hardware. The nondeterminism is a semantic prescription of the memory model to describe observable behaviour of hardware with weak consistency.
+                // e. Let rmwEvent be ReadModifyWriteSharedMemory { [[Order]]: SeqCst, [[NoTear]]: true, [[Block]]: block, [[ByteIndex]]: byteIndex, [[ElementSize]]: elementSize, [[Payload]]: rawBytes, [[ModifyOp]]: op }.
+                // f. Append rmwEvent to eventList.
+                // g. Append Chosen Value Record { [[Event]]: rmwEvent, [[ChosenValue]]: rawBytesRead } to execution.[[ChosenValues]].
+                block.fetch_update(byte_index, element_size, |x| op(x, operand))
+            }
+            // 9. Else,
+            ArrayBufferData::Owned(block) => {
+                // a. Let rawBytesRead be a List of length elementSize whose elements are the sequence of elementSize bytes starting with block[byteIndex].
+                let mut raw_bytes_read = [0; 8];
+                raw_bytes_read[..element_size]
+                    .copy_from_slice(&block[byte_index..byte_index + element_size]);
+                let raw_bytes_read = u64::from_le_bytes(raw_bytes_read);
+
+                // b. Let rawBytesModified be op(rawBytesRead, rawBytes).
+                let raw_bytes_modified = op(raw_bytes_read, operand).to_le_bytes();
+
+                // c. Store the individual bytes of rawBytesModified into block, starting at block[byteIndex].
+                block[byte_index..byte_index + element_size]
+                    .copy_from_slice(&raw_bytes_modified[..element_size]);
+
+                raw_bytes_read
+            }
+        };
+
+        // 10. Return RawBytesToNumeric(type, rawBytesRead, isLittleEndian).
+        Ok(Self::raw_bytes_to_numeric(
+            t,
+            &raw_bytes_read.to_le_bytes()[..element_size],
+            true,
+        ))
+    }
 }
 
 /// `6.2.8.3 CopyDataBlockBytes ( toBlock, toIndex, fromBlock, fromIndex, count )`
@@ -740,9 +1377,9 @@ impl ArrayBuffer {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-copydatablockbytes
 fn copy_data_block_bytes(
-    to_block: &mut Vec<u8>,
+    to_block: &mut ArrayBufferData,
     mut to_index: usize,
-    from_block: &[u8],
+    from_block: &ArrayBufferData,
     mut from_index: usize,
     mut count: usize,
 ) {
@@ -761,13 +1398,36 @@ fn copy_data_block_bytes(
 
     // 6. Repeat, while count > 0,
     while count > 0 {
-        // a. If fromBlock is a Shared Data Block, then
-        // TODO: Shared Data Block
+        let byte = match from_block {
+            // a. If fromBlock is a Shared Data Block, then
+            ArrayBufferData::Shared(block) => {
+                // i. Let execution be the [[CandidateExecution]] field of the surrounding agent's Agent Record.
+                // ii. Let eventList be the [[EventList]] field of the element in execution.[[EventsRecords]] whose [[AgentSignifier]] is AgentSignifier().
+                // iii. Let bytes be a List whose sole element is a nondeterministically chosen byte value.
+                // iv. NOTE: In implementations, bytes is the result of a non-atomic read instruction on the underlying hardware. The nondeterminism is a semantic prescription of the memory model to describe observable behaviour of hardware with weak consistency.
+                // v. Let readEvent be ReadSharedMemory { [[Order]]: Unordered, [[NoTear]]: true, [[Block]]: fromBlock, [[ByteIndex]]: fromIndex, [[ElementSize]]: 1 }.
+                // vi. Append readEvent to eventList.
+                // vii. Append Chosen Value Record { [[Event]]: readEvent, [[ChosenValue]]: bytes } to execution.[[ChosenValues]].
+                let mut bytes = [0];
+                block.load(from_index, &mut bytes, &SharedMemoryOrder::Unordered);
+                bytes[0]
+            }
+            // b. Else,
+            ArrayBufferData::Owned(block) => block[from_index],
+        };
 
-        // b. Else,
-        // i. Assert: toBlock is not a Shared Data Block.
-        // ii. Set toBlock[toIndex] to fromBlock[fromIndex].
-        to_block[to_index] = from_block[from_index];
+        match to_block {
+            // a.viii. If toBlock is a Shared Data Block, then
+            //     1. Append WriteSharedMemory { [[Order]]: Unordered, [[NoTear]]: true, [[Block]]: toBlock, [[ByteIndex]]: toIndex, [[ElementSize]]: 1, [[Payload]]: bytes } to eventList.
+            ArrayBufferData::Shared(block) => {
+                block.store(to_index, &[byte], &SharedMemoryOrder::Unordered);
+            }
+            // a.ix. Else,
+            //     1. Set toBlock[toIndex] to bytes[0].
+            // b.i. Assert: toBlock is not a Shared Data Block.
+            // b.ii. Set toBlock[toIndex] to fromBlock[fromIndex].
+            ArrayBufferData::Owned(block) => block[to_index] = byte,
+        }
 
         // c. Set toIndex to toIndex + 1.
         to_index += 1;
@@ -782,7 +1442,7 @@ fn copy_data_block_bytes(
     // 7. Return NormalCompletion(empty).
 }
 
-// TODO: Allow unused variants until shared array buffers are implemented.
+// TODO: Allow unused variants until the `Init` order is used by the memory model.
 #[allow(dead_code)]
 #[derive(Debug, PartialEq)]
 pub(crate) enum SharedMemoryOrder {
@@ -0,0 +1,140 @@
+use crate::{forward, Context};
+
+#[test]
+fn resizable() {
+    let mut context = Context::new();
+
+    let init = r#"
+        var buffer = new ArrayBuffer(4, { maxByteLength: 16 });
+        var fixed = new ArrayBuffer(4);
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "buffer.resizable"), "true");
+    assert_eq!(forward(&mut context, "buffer.maxByteLength"), "16");
+    assert_eq!(forward(&mut context, "fixed.resizable"), "false");
+    assert_eq!(forward(&mut context, "fixed.maxByteLength"), "4");
+
+    forward(
+        &mut context,
+        "new Uint8Array(buffer)[3] = 7; buffer.resize(12)",
+    );
+    assert_eq!(forward(&mut context, "buffer.byteLength"), "12");
+    assert_eq!(forward(&mut context, "new Uint8Array(buffer)[3]"), "7");
+    assert_eq!(forward(&mut context, "new Uint8Array(buffer)[11]"), "0");
+
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { buffer.resize(17) } catch (e) { e.name }"
+        ),
+        "\"RangeError\""
+    );
+    assert_eq!(
+        forward(&mut context, "try { fixed.resize(2) } catch (e) { e.name }"),
+        "\"TypeError\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { new ArrayBuffer(8, { maxByteLength: 4 }) } catch (e) { e.name }"
+        ),
+        "\"RangeError\""
+    );
+}
+
+#[test]
+fn length_tracking_views() {
+    let mut context = Context::new();
+
+    let init = r#"
+        var buffer = new ArrayBuffer(8, { maxByteLength: 16 });
+        var tracking = new Int16Array(buffer, 2);
+        var fixed = new Int16Array(buffer, 0, 2);
+        var view = new DataView(buffer, 4);
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "tracking.length"), "3");
+    assert_eq!(forward(&mut context, "view.byteLength"), "4");
+
+    forward(&mut context, "buffer.resize(16)");
+    assert_eq!(forward(&mut context, "tracking.length"), "7");
+    assert_eq!(forward(&mut context, "tracking.byteLength"), "14");
+    assert_eq!(forward(&mut context, "view.byteLength"), "12");
+    assert_eq!(forward(&mut context, "fixed.length"), "2");
+    assert_eq!(forward(&mut context, "tracking.subarray(1).length"), "6");
+
+    forward(&mut context, "buffer.resize(3)");
+    assert_eq!(forward(&mut context, "tracking.length"), "0");
+    assert_eq!(forward(&mut context, "fixed.length"), "0");
+    assert_eq!(forward(&mut context, "fixed[0]"), "undefined");
+    assert_eq!(
+        forward(&mut context, "try { fixed.fill(1) } catch (e) { e.name }"),
+        "\"TypeError\""
+    );
+    assert_eq!(
+        forward(&mut context, "try { view.byteLength } catch (e) { e.name }"),
+        "\"TypeError\""
+    );
+
+    forward(&mut context, "buffer.resize(4)");
+    assert_eq!(forward(&mut context, "fixed.length"), "2");
+    assert_eq!(forward(&mut context, "tracking.length"), "1");
+}
+
+#[test]
+fn transfer() {
+    let mut context = Context::new();
+
+    let init = r#"
+        var buffer = new ArrayBuffer(4, { maxByteLength: 8 });
+        var array = new Uint8Array(buffer);
+        array[1] = 42;
+        var moved = buffer.transfer(6);
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "buffer.detached"), "true");
+    assert_eq!(forward(&mut context, "buffer.byteLength"), "0");
+    assert_eq!(forward(&mut context, "array.length"), "0");
+    assert_eq!(forward(&mut context, "moved.detached"), "false");
+    assert_eq!(forward(&mut context, "moved.byteLength"), "6");
+    assert_eq!(forward(&mut context, "moved.resizable"), "true");
+    assert_eq!(forward(&mut context, "new Uint8Array(moved)[1]"), "42");
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { buffer.transfer() } catch (e) { e.name }"
+        ),
+        "\"TypeError\""
+    );
+
+    forward(&mut context, "var fixed = moved.transferToFixedLength(2)");
+    assert_eq!(forward(&mut context, "fixed.resizable"), "false");
+    assert_eq!(forward(&mut context, "fixed.byteLength"), "2");
+    assert_eq!(forward(&mut context, "new Uint8Array(fixed)[1]"), "42");
+}
+
+#[test]
+fn slice_shrunk_buffer() {
+    let mut context = Context::new();
+
+    let init = r#"
+        var buffer = new ArrayBuffer(8, { maxByteLength: 8 });
+        new Uint8Array(buffer).set([1, 2, 3, 4, 5, 6, 7, 8]);
+        buffer.constructor = {};
+        buffer.constructor[Symbol.species] = function (length) {
+            buffer.resize(4);
+            return new ArrayBuffer(length);
+        };
+        var sliced = buffer.slice(2);
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "sliced.byteLength"), "6");
+    assert_eq!(
+        forward(&mut context, "new Uint8Array(sliced).join()"),
+        "\"3,4,0,0,0,0\""
+    );
+}
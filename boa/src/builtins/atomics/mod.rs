@@ -0,0 +1,713 @@
+//! This module implements the global `Atomics` object.
+//!
+//! The `Atomics` object provides atomic operations as static methods. They are used with
+//! `SharedArrayBuffer` objects to synchronize agents that share memory.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-atomics-object
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics
+
+use crate::{
+    builtins::{
+        array_buffer::{ArrayBuffer, SharedMemoryOrder},
+        shared_array_buffer::SharedDataBlock,
+        typed_array::{integer_indexed_object::ContentType, TypedArrayName},
+        BuiltIn, JsArgs,
+    },
+    object::{JsObject, ObjectInitializer},
+    property::Attribute,
+    symbol::WellKnownSymbols,
+    value::IntegerOrInfinity,
+    BoaProfiler, Context, JsBigInt, JsResult, JsValue,
+};
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests;
+
+/// Javascript `Atomics` object.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Atomics;
+
+impl BuiltIn for Atomics {
+    const NAME: &'static str = "Atomics";
+
+    const ATTRIBUTE: Attribute = Attribute::WRITABLE
+        .union(Attribute::NON_ENUMERABLE)
+        .union(Attribute::CONFIGURABLE);
+
+    fn init(context: &mut Context) -> JsValue {
+        let _timer = BoaProfiler::global().start_event(Self::NAME, "init");
+
+        let to_string_tag = WellKnownSymbols::to_string_tag();
+
+        let object = ObjectInitializer::new(context)
+            .function(Self::add, "add", 3)
+            .function(Self::and, "and", 3)
+            .function(Self::compare_exchange, "compareExchange", 4)
+            .function(Self::exchange, "exchange", 3)
+            .function(Self::is_lock_free, "isLockFree", 1)
+            .function(Self::load, "load", 2)
+            .function(Self::or, "or", 3)
+            .function(Self::store, "store", 3)
+            .function(Self::sub, "sub", 3)
+            .function(Self::wait, "wait", 4)
+            .function(Self::notify, "notify", 3)
+            .function(Self::xor, "xor", 3)
+            .property(
+                to_string_tag,
+                Self::NAME,
+                Attribute::READONLY | Attribute::NON_ENUMERABLE | Attribute::CONFIGURABLE,
+            )
+            .build();
+
+        object.into()
+    }
+}
+
+impl Atomics {
+    /// `25.4.14 Atomics.add ( typedArray, index, value )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.add
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/add
+    pub(crate) fn add(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let type be the Element Type value in Table 70 for typedArray.[[TypedArrayName]].
+        // 2. Let isLittleEndian be the value of the [[LittleEndian]] field of the surrounding agent's Agent Record.
+        // 3. Let add be a new read-modify-write modification function with parameters (xBytes, yBytes) that captures type and isLittleEndian and performs the following steps atomically when called:
+        //     a. Let x be RawBytesToNumeric(type, xBytes, isLittleEndian).
+        //     b. Let y be RawBytesToNumeric(type, yBytes, isLittleEndian).
+        //     c. Let T be Type(x).
+        //     d. Let sum be T::add(x, y).
+        //     e. Let sumBytes be NumericToRawBytes(type, sum, isLittleEndian).
+        //     f. Assert: sumBytes, xBytes, and yBytes have the same number of elements.
+        //     g. Return sumBytes.
+        // 4. Return ? AtomicReadModifyWrite(typedArray, index, value, add).
+        Self::atomic_read_modify_write(args, u64::wrapping_add, context)
+    }
+
+    /// `25.4.4 Atomics.and ( typedArray, index, value )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.and
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/and
+    pub(crate) fn and(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let and be a new read-modify-write modification function with parameters (xBytes, yBytes) that captures nothing and performs the following steps atomically when called:
+        //     a. Return ByteListBitwiseOp(&, xBytes, yBytes).
+        // 2. Return ? AtomicReadModifyWrite(typedArray, index, value, and).
+        Self::atomic_read_modify_write(args, |x, y| x & y, context)
+    }
+
+    /// `25.4.5 Atomics.compareExchange ( typedArray, index, expectedValue, replacementValue )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.compareexchange
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/compareExchange
+    pub(crate) fn compare_exchange(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let buffer be ? ValidateIntegerTypedArray(typedArray).
+        // 2. Let block be buffer.[[ArrayBufferData]].
+        let typed_array =
+            Self::validate_integer_typed_array(args.get_or_undefined(0), false, context)?;
+
+        // 3. Let indexedPosition be ? ValidateAtomicAccess(typedArray, index).
+        let indexed_position =
+            Self::validate_atomic_access(&typed_array, args.get_or_undefined(1), context)?;
+
+        // 4. If typedArray.[[ContentType]] is BigInt, then
+        //     a. Let expected be ? ToBigInt(expectedValue).
+        //     b. Let replacement be ? ToBigInt(replacementValue).
+        // 5. Else,
+        //     a. Let expected be 𝔽(? ToIntegerOrInfinity(expectedValue)).
+        //     b. Let replacement be 𝔽(? ToIntegerOrInfinity(replacementValue)).
+        let expected = Self::to_element_value(&typed_array, args.get_or_undefined(2), context)?;
+        let replacement = Self::to_element_value(&typed_array, args.get_or_undefined(3), context)?;
+
+        // 6. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
+        // 7. NOTE: The above check is not redundant with the check in ValidateIntegerTypedArray because the call to ToBigInt or ToIntegerOrInfinity on the preceding lines can have arbitrary side effects, which could cause the buffer to become detached.
+        let (buffer, element_type) = Self::revalidate(&typed_array, indexed_position, context)?;
+
+        // 8. Let elementType be the Element Type value in Table 70 for typedArray.[[TypedArrayName]].
+        // 9. Let elementSize be the Element Size value specified in Table 70 for Element Type elementType.
+        // 10. Let isLittleEndian be the value of the [[LittleEndian]] field of the surrounding agent's Agent Record.
+        // 11. Let expectedBytes be NumericToRawBytes(elementType, expected, isLittleEndian).
+        let expected = ArrayBuffer::numeric_to_raw_bytes(element_type, expected, true, context)?;
+        let mut expected_bytes = [0; 8];
+        expected_bytes[..expected.len()].copy_from_slice(&expected);
+        let expected = u64::from_le_bytes(expected_bytes);
+
+        // 12. Let replacementBytes be NumericToRawBytes(elementType, replacement, isLittleEndian).
+        // 13. If IsSharedArrayBuffer(buffer) is true, then
+        //     a. Let execution be the [[CandidateExecution]] field of the surrounding agent's Agent Record.
+        //     b. Let eventList be the [[EventList]] field of the element in execution.[[EventsRecords]] whose [[AgentSignifier]] is AgentSignifier().
+        //     c. Let rawBytesRead be a List of length elementSize whose elements are nondeterministically chosen byte values.
+        //     d. NOTE: In implementations, rawBytesRead is the result of a load-link, of a load-exclusive, or of an operand of a read-modify-write instruction on the underlying hardware. The nondeterminism is a semantic prescription of the memory model to describe observable behaviour of hardware with weak consistency.
+        //     e. NOTE: The comparison of the expected value and the read value is performed outside of the read-modify-write modification function to avoid needlessly strong synchronization when the expected value is not equal to the read value.
+        //     f. If ByteListEqual(rawBytesRead, expectedBytes) is true, then
+        //         i. Let second be a new read-modify-write modification function with parameters (oldBytes, newBytes) that captures nothing and performs the following steps atomically when called:
+        //             1. Return newBytes.
+        //         ii. Let event be ReadModifyWriteSharedMemory { [[Order]]: SeqCst, [[NoTear]]: true, [[Block]]: block, [[ByteIndex]]: indexedPosition, [[ElementSize]]: elementSize, [[Payload]]: replacementBytes, [[ModifyOp]]: second }.
+        //     g. Else,
+        //         i. Let event be ReadSharedMemory { [[Order]]: SeqCst, [[NoTear]]: true, [[Block]]: block, [[ByteIndex]]: indexedPosition, [[ElementSize]]: elementSize }.
+        //     h. Append event to eventList.
+        //     i. Append Chosen Value Record { [[Event]]: event, [[ChosenValue]]: rawBytesRead } to execution.[[ChosenValues]].
+        // 14. Else,
+        //     a. Let rawBytesRead be a List of length elementSize whose elements are the sequence of elementSize bytes starting with block[indexedPosition].
+        //     b. If ByteListEqual(rawBytesRead, expectedBytes) is true, then
+        //         i. Store the individual bytes of replacementBytes into block, starting at block[indexedPosition].
+        // 15. Return RawBytesToNumeric(elementType, rawBytesRead, isLittleEndian).
+        let mut buffer = buffer.borrow_mut();
+        let buffer = buffer
+            .as_array_buffer_mut()
+            .expect("Already checked for a typed array");
+        buffer.get_modify_set_value_in_buffer(
+            indexed_position,
+            element_type,
+            replacement,
+            |x, y| if x == expected { y } else { x },
+            context,
+        )
+    }
+
+    /// `25.4.6 Atomics.exchange ( typedArray, index, value )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.exchange
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/exchange
+    pub(crate) fn exchange(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let second be a new read-modify-write modification function with parameters (oldBytes, newBytes) that captures nothing and performs the following steps atomically when called:
+        //     a. Return newBytes.
+        // 2. Return ? AtomicReadModifyWrite(typedArray, index, value, second).
+        Self::atomic_read_modify_write(args, |_, y| y, context)
+    }
+
+    /// `25.4.7 Atomics.isLockFree ( size )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.islockfree
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/isLockFree
+    pub(crate) fn is_lock_free(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let n be ? ToIntegerOrInfinity(size).
+        let n = args.get_or_undefined(0).to_integer_or_infinity(context)?;
+
+        // 2. Let AR be the Agent Record of the surrounding agent.
+        // 3. If n = 1, return AR.[[IsLockFree1]].
+        // 4. If n = 2, return AR.[[IsLockFree2]].
+        // 5. If n = 4, return true.
+        // 6. If n = 8, return AR.[[IsLockFree8]].
+        // 7. Return false.
+        // Every element is accessed with the atomic integer of the same size, which are lock free
+        // on all the platforms supported by Boa.
+        Ok(matches!(n, IntegerOrInfinity::Integer(1 | 2 | 4 | 8)).into())
+    }
+
+    /// `25.4.8 Atomics.load ( typedArray, index )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.load
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/load
+    pub(crate) fn load(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let buffer be ? ValidateIntegerTypedArray(typedArray).
+        let typed_array =
+            Self::validate_integer_typed_array(args.get_or_undefined(0), false, context)?;
+
+        // 2. Let indexedPosition be ? ValidateAtomicAccess(typedArray, index).
+        let indexed_position =
+            Self::validate_atomic_access(&typed_array, args.get_or_undefined(1), context)?;
+
+        // 3. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
+        // 4. NOTE: The above check is not redundant with the check in ValidateIntegerTypedArray because the call to ValidateAtomicAccess on the preceding line can have arbitrary side effects, which could cause the buffer to become detached.
+        let (buffer, element_type) = Self::revalidate(&typed_array, indexed_position, context)?;
+
+        // 5. Let elementType be the Element Type value in Table 70 for typedArray.[[TypedArrayName]].
+        // 6. Return GetValueFromBuffer(buffer, indexedPosition, elementType, true, SeqCst).
+        let buffer = buffer.borrow();
+        Ok(buffer
+            .as_array_buffer()
+            .expect("Already checked for a typed array")
+            .get_value_from_buffer(
+                indexed_position,
+                element_type,
+                true,
+                SharedMemoryOrder::SeqCst,
+                None,
+            ))
+    }
+
+    /// `25.4.9 Atomics.or ( typedArray, index, value )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.or
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/or
+    pub(crate) fn or(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let or be a new read-modify-write modification function with parameters (xBytes, yBytes) that captures nothing and performs the following steps atomically when called:
+        //     a. Return ByteListBitwiseOp(|, xBytes, yBytes).
+        // 2. Return ? AtomicReadModifyWrite(typedArray, index, value, or).
+        Self::atomic_read_modify_write(args, |x, y| x | y, context)
+    }
+
+    /// `25.4.10 Atomics.store ( typedArray, index, value )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.store
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/store
+    pub(crate) fn store(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let buffer be ? ValidateIntegerTypedArray(typedArray).
+        let typed_array =
+            Self::validate_integer_typed_array(args.get_or_undefined(0), false, context)?;
+
+        // 2. Let indexedPosition be ? ValidateAtomicAccess(typedArray, index).
+        let indexed_position =
+            Self::validate_atomic_access(&typed_array, args.get_or_undefined(1), context)?;
+
+        // 3. If typedArray.[[ContentType]] is BigInt, let v be ? ToBigInt(value).
+        // 4. Otherwise, let v be 𝔽(? ToIntegerOrInfinity(value)).
+        let v = Self::to_element_value(&typed_array, args.get_or_undefined(2), context)?;
+
+        // 5. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
+        // 6. NOTE: The above check is not redundant with the check in ValidateIntegerTypedArray because the call to ToBigInt or ToIntegerOrInfinity on the preceding lines can have arbitrary side effects, which could cause the buffer to become detached.
+        let (buffer, element_type) = Self::revalidate(&typed_array, indexed_position, context)?;
+
+        // 7. Let elementType be the Element Type value in Table 70 for typedArray.[[TypedArrayName]].
+        // 8. Perform SetValueInBuffer(buffer, indexedPosition, elementType, v, true, SeqCst).
+        buffer
+            .borrow_mut()
+            .as_array_buffer_mut()
+            .expect("Already checked for a typed array")
+            .set_value_in_buffer(
+                indexed_position,
+                element_type,
+                v.clone(),
+                SharedMemoryOrder::SeqCst,
+                None,
+                context,
+            )?;
+
+        // 9. Return v.
+        Ok(v)
+    }
+
+    /// `25.4.11 Atomics.sub ( typedArray, index, value )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.sub
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/sub
+    pub(crate) fn sub(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let type be the Element Type value in Table 70 for typedArray.[[TypedArrayName]].
+        // 2. Let isLittleEndian be the value of the [[LittleEndian]] field of the surrounding agent's Agent Record.
+        // 3. Let subtract be a new read-modify-write modification function with parameters (xBytes, yBytes) that captures type and isLittleEndian and performs the following steps atomically when called:
+        //     a. Let x be RawBytesToNumeric(type, xBytes, isLittleEndian).
+        //     b. Let y be RawBytesToNumeric(type, yBytes, isLittleEndian).
+        //     c. Let T be Type(x).
+        //     d. Let difference be T::subtract(x, y).
+        //     e. Let differenceBytes be NumericToRawBytes(type, difference, isLittleEndian).
+        //     f. Assert: differenceBytes, xBytes, and yBytes have the same number of elements.
+        //     g. Return differenceBytes.
+        // 4. Return ? AtomicReadModifyWrite(typedArray, index, value, subtract).
+        Self::atomic_read_modify_write(args, u64::wrapping_sub, context)
+    }
+
+    /// `25.4.12 Atomics.wait ( typedArray, index, value, timeout )`
+    ///
+    /// Blocks the current thread until it is woken up by `Atomics.notify` from another agent, or
+    /// until the timeout expires.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.wait
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/wait
+    pub(crate) fn wait(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Return ? DoWait(sync, typedArray, index, value, timeout).
+
+        // DoWait ( mode, typedArray, index, value, timeout )
+        // 1. Let buffer be ? ValidateIntegerTypedArray(typedArray, true).
+        let typed_array =
+            Self::validate_integer_typed_array(args.get_or_undefined(0), true, context)?;
+
+        // 2. If IsSharedArrayBuffer(buffer) is false, throw a TypeError exception.
+        let block = Self::shared_data_block(&typed_array).ok_or_else(|| {
+            context.construct_type_error("Atomics.wait can only be used on a SharedArrayBuffer")
+        })?;
+
+        // 3. Let i be ? ValidateAtomicAccess(typedArray, index).
+        let indexed_position =
+            Self::validate_atomic_access(&typed_array, args.get_or_undefined(1), context)?;
+
+        // 4. Let arrayTypeName be typedArray.[[TypedArrayName]].
+        let array_type_name = Self::typed_array_name(&typed_array);
+
+        // 5. If arrayTypeName is "BigInt64Array", let v be ? ToBigInt64(value).
+        // 6. Otherwise, let v be ? ToInt32(value).
+        let value = args.get_or_undefined(2);
+        let v: JsValue = if array_type_name == TypedArrayName::BigInt64Array {
+            JsBigInt::new(value.to_big_int64(context)?).into()
+        } else {
+            value.to_i32(context)?.into()
+        };
+
+        // 7. Let q be ? ToNumber(timeout).
+        let q = args.get_or_undefined(3).to_number(context)?;
+
+        // 8. If q is NaN or +∞𝔽, let t be +∞; else if q is -∞𝔽, let t be 0; else let t be max(ℝ(q), 0).
+        let timeout = if q.is_nan() || q == f64::INFINITY {
+            None
+        } else {
+            Some(Duration::from_nanos((q.max(0.0) * 1_000_000.0) as u64))
+        };
+
+        // 9. If mode is sync and AgentCanSuspend() is false, throw a TypeError exception.
+        // 10. Let block be buffer.[[ArrayBufferData]].
+        // 11. Let offset be typedArray.[[ByteOffset]].
+        // 12. Let byteIndexInBuffer be (i × 4) + offset.
+        // 13. Let WL be GetWaiterList(block, byteIndexInBuffer).
+        let expected = ArrayBuffer::numeric_to_raw_bytes(array_type_name, v, true, context)?;
+        let mut expected_bytes = [0; 8];
+        expected_bytes[..expected.len()].copy_from_slice(&expected);
+
+        // 14-31. Suspend the agent until it is notified or the timeout expires.
+        let result = block.wait(
+            indexed_position,
+            array_type_name.element_size(),
+            u64::from_le_bytes(expected_bytes),
+            timeout,
+        );
+
+        Ok(result.as_str().into())
+    }
+
+    /// `25.4.13 Atomics.notify ( typedArray, index, count )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.notify
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/notify
+    pub(crate) fn notify(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let indexedPosition be ? ValidateAtomicAccessOnIntegerTypedArray(typedArray, index, true).
+        let typed_array =
+            Self::validate_integer_typed_array(args.get_or_undefined(0), true, context)?;
+        let indexed_position =
+            Self::validate_atomic_access(&typed_array, args.get_or_undefined(1), context)?;
+
+        // 2. If count is undefined, let c be +∞.
+        // 3. Else,
+        //     a. Let intCount be ? ToIntegerOrInfinity(count).
+        //     b. Let c be max(intCount, 0).
+        let count = args.get_or_undefined(2);
+        let count = if count.is_undefined() {
+            None
+        } else {
+            match count.to_integer_or_infinity(context)? {
+                IntegerOrInfinity::PositiveInfinity => None,
+                IntegerOrInfinity::Integer(i) if i > 0 => Some(i as usize),
+                _ => Some(0),
+            }
+        };
+
+        // 4. Let buffer be typedArray.[[ViewedArrayBuffer]].
+        // 5. Let block be buffer.[[ArrayBufferData]].
+        // 6. If IsSharedArrayBuffer(buffer) is false, return +0𝔽.
+        let block = if let Some(block) = Self::shared_data_block(&typed_array) {
+            block
+        } else {
+            return Ok(0.into());
+        };
+
+        // 7. Let WL be GetWaiterList(block, indexedPosition).
+        // 8. Perform EnterCriticalSection(WL).
+        // 9. Let S be RemoveWaiters(WL, c).
+        // 10. For each element W of S, do
+        //     a. Perform NotifyWaiter(WL, W).
+        // 11. Perform LeaveCriticalSection(WL).
+        // 12. Let n be the number of elements in S.
+        // 13. Return 𝔽(n).
+        Ok(block.notify(indexed_position, count).into())
+    }
+
+    /// `25.4.15 Atomics.xor ( typedArray, index, value )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.xor
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/xor
+    pub(crate) fn xor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let xor be a new read-modify-write modification function with parameters (xBytes, yBytes) that captures nothing and performs the following steps atomically when called:
+        //     a. Return ByteListBitwiseOp(^, xBytes, yBytes).
+        // 2. Return ? AtomicReadModifyWrite(typedArray, index, value, xor).
+        Self::atomic_read_modify_write(args, |x, y| x ^ y, context)
+    }
+
+    /// `25.4.2.1 ValidateIntegerTypedArray ( typedArray [ , waitable ] )`
+    ///
+    /// Returns the validated typed array object.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-validateintegertypedarray
+    fn validate_integer_typed_array(
+        typed_array: &JsValue,
+        waitable: bool,
+        context: &mut Context,
+    ) -> JsResult<JsObject> {
+        // 1. If waitable is not present, set waitable to false.
+        // 2. Let buffer be ? ValidateTypedArray(typedArray).
+        let obj = typed_array
+            .as_object()
+            .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
+        let type_name = {
+            let obj = obj.borrow();
+            let o = obj
+                .as_typed_array()
+                .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
+            if o.is_out_of_bounds() {
+                return Err(context.construct_type_error("Buffer of the typed array is detached"));
+            }
+            o.typed_array_name()
+        };
+
+        // 3. Let typeName be typedArray.[[TypedArrayName]].
+        // 4. Let type be the Element Type value in Table 70 for typeName.
+        if waitable {
+            // 5. If waitable is true, then
+            //     a. If typeName is not "Int32Array" or "BigInt64Array", throw a TypeError exception.
+            if !matches!(
+                type_name,
+                TypedArrayName::Int32Array | TypedArrayName::BigInt64Array
+            ) {
+                return Err(context.construct_type_error(
+                    "Atomics.wait and Atomics.notify require an Int32Array or a BigInt64Array",
+                ));
+            }
+        } else if !ArrayBuffer::is_unclamped_integer_element_type(type_name)
+            && !ArrayBuffer::is_big_int_element_type(type_name)
+        {
+            // 6. Else,
+            //     a. If ! IsUnclampedIntegerElementType(type) is false and ! IsBigIntElementType(type) is false, throw a TypeError exception.
+            return Err(
+                context.construct_type_error("Atomics operations require an integer typed array")
+            );
+        }
+
+        // 7. Return buffer.
+        Ok(obj.clone())
+    }
+
+    /// `25.4.2.2 ValidateAtomicAccess ( typedArray, requestIndex )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-validateatomicaccess
+    fn validate_atomic_access(
+        typed_array: &JsObject,
+        request_index: &JsValue,
+        context: &mut Context,
+    ) -> JsResult<usize> {
+        // 1. Assert: typedArray is an Object that has a [[ViewedArrayBuffer]] internal slot.
+        // 2. Let length be typedArray.[[ArrayLength]].
+        let length = typed_array
+            .borrow()
+            .as_typed_array()
+            .expect("Already checked for a typed array")
+            .array_length();
+
+        // 3. Let accessIndex be ? ToIndex(requestIndex).
+        let access_index = request_index.to_index(context)?;
+
+        // 4. Assert: accessIndex ≥ 0.
+        // 5. If accessIndex ≥ length, throw a RangeError exception.
+        if access_index >= length {
+            return Err(context.construct_range_error("Index out of range for typed array"));
+        }
+
+        // 6. Let arrayTypeName be typedArray.[[TypedArrayName]].
+        // 7. Let elementSize be the Element Size value specified in Table 70 for arrayTypeName.
+        // 8. Let offset be typedArray.[[ByteOffset]].
+        // 9. Return (accessIndex × elementSize) + offset.
+        let typed_array = typed_array.borrow();
+        let typed_array = typed_array
+            .as_typed_array()
+            .expect("Already checked for a typed array");
+        Ok(
+            access_index * typed_array.typed_array_name().element_size()
+                + typed_array.byte_offset(),
+        )
+    }
+
+    /// `25.4.2.17 AtomicReadModifyWrite ( typedArray, index, value, op )`
+    ///
+    /// The modification function `op` receives the current and the given value as little endian
+    /// integers, zero-extended to 64 bits.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomicreadmodifywrite
+    fn atomic_read_modify_write<F>(
+        args: &[JsValue],
+        op: F,
+        context: &mut Context,
+    ) -> JsResult<JsValue>
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        // 1. Let buffer be ? ValidateIntegerTypedArray(typedArray).
+        let typed_array =
+            Self::validate_integer_typed_array(args.get_or_undefined(0), false, context)?;
+
+        // 2. Let indexedPosition be ? ValidateAtomicAccess(typedArray, index).
+        let indexed_position =
+            Self::validate_atomic_access(&typed_array, args.get_or_undefined(1), context)?;
+
+        // 3. If typedArray.[[ContentType]] is BigInt, let v be ? ToBigInt(value).
+        // 4. Otherwise, let v be 𝔽(? ToIntegerOrInfinity(value)).
+        let v = Self::to_element_value(&typed_array, args.get_or_undefined(2), context)?;
+
+        // 5. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
+        // 6. NOTE: The above check is not redundant with the check in ValidateIntegerTypedArray because the call to ToBigInt or ToIntegerOrInfinity on the preceding lines can have arbitrary side effects, which could cause the buffer to become detached.
+        let (buffer, element_type) = Self::revalidate(&typed_array, indexed_position, context)?;
+
+        // 7. Let elementType be the Element Type value in Table 70 for typedArray.[[TypedArrayName]].
+        // 8. Return GetModifySetValueInBuffer(buffer, indexedPosition, elementType, v, op).
+        let mut buffer = buffer.borrow_mut();
+        let buffer = buffer
+            .as_array_buffer_mut()
+            .expect("Already checked for a typed array");
+        buffer.get_modify_set_value_in_buffer(indexed_position, element_type, v, op, context)
+    }
+
+    /// Converts `value` to the numeric type of the elements of `typed_array`.
+    ///
+    /// If the content type is BigInt this is `? ToBigInt(value)`, otherwise it is
+    /// `𝔽(? ToIntegerOrInfinity(value))`.
+    fn to_element_value(
+        typed_array: &JsObject,
+        value: &JsValue,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        if Self::typed_array_name(typed_array).content_type() == ContentType::BigInt {
+            Ok(value.to_bigint(context)?.into())
+        } else {
+            Ok(match value.to_integer_or_infinity(context)? {
+                IntegerOrInfinity::Integer(i) => i.into(),
+                IntegerOrInfinity::PositiveInfinity => f64::INFINITY.into(),
+                IntegerOrInfinity::NegativeInfinity => f64::NEG_INFINITY.into(),
+            })
+        }
+    }
+
+    /// Checks again that the buffer of `typed_array` is not detached and that
+    /// `byte_index_in_buffer` is still inside of it, returning the buffer and the element type of
+    /// the typed array.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-revalidateatomicaccess
+    fn revalidate(
+        typed_array: &JsObject,
+        byte_index_in_buffer: usize,
+        context: &mut Context,
+    ) -> JsResult<(JsObject, TypedArrayName)> {
+        let typed_array = typed_array.borrow();
+        let typed_array = typed_array
+            .as_typed_array()
+            .expect("Already checked for a typed array");
+
+        // 1. Let taRecord be MakeTypedArrayWithBufferWitnessRecord(typedArray, unordered).
+        // 2. NOTE: Bounds checking is not a synchronizing operation when typedArray's backing buffer is a growable SharedArrayBuffer.
+        // 3. If IsTypedArrayOutOfBounds(taRecord) is true, throw a TypeError exception.
+        if typed_array.is_out_of_bounds() {
+            return Err(context.construct_type_error("Buffer of the typed array is detached"));
+        }
+
+        // 4. Assert: byteIndexInBuffer ≥ typedArray.[[ByteOffset]].
+        // 5. If byteIndexInBuffer ≥ taRecord.[[CachedBufferByteLength]], throw a RangeError exception.
+        if byte_index_in_buffer >= typed_array.byte_offset() + typed_array.byte_length() {
+            return Err(context.construct_range_error("Index out of range for typed array"));
+        }
+
+        // 6. Return unused.
+        let buffer = typed_array
+            .viewed_array_buffer()
+            .expect("Already checked for detached buffer")
+            .clone();
+        Ok((buffer, typed_array.typed_array_name()))
+    }
+
+    /// Gets the name of the typed array.
+    fn typed_array_name(typed_array: &JsObject) -> TypedArrayName {
+        typed_array
+            .borrow()
+            .as_typed_array()
+            .expect("Already checked for a typed array")
+            .typed_array_name()
+    }
+
+    /// Gets the shared data block viewed by the typed array, if its buffer is a
+    /// `SharedArrayBuffer`.
+    fn shared_data_block(typed_array: &JsObject) -> Option<SharedDataBlock> {
+        typed_array
+            .borrow()
+            .as_typed_array()
+            .and_then(|o| o.viewed_array_buffer())
+            .and_then(|buffer| {
+                buffer
+                    .borrow()
+                    .as_array_buffer()?
+                    .shared_data_block()
+                    .cloned()
+            })
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    builtins::shared_array_buffer::{SharedArrayBuffer, SharedDataBlock},
+    forward,
+    property::Attribute,
+    Context,
+};
+
+#[test]
+fn shared_array_buffer() {
+    let mut context = Context::new();
+
+    let init = r#"
+        var sab = new SharedArrayBuffer(8);
+        var view = new Int32Array(sab);
+        view[1] = 42;
+        var slice = sab.slice(4);
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "sab.byteLength"), "8");
+    assert_eq!(
+        forward(&mut context, "Object.prototype.toString.call(sab)"),
+        "\"[object SharedArrayBuffer]\""
+    );
+    assert_eq!(forward(&mut context, "slice.byteLength"), "4");
+    assert_eq!(forward(&mut context, "new Int32Array(slice)[0]"), "42");
+    assert_eq!(forward(&mut context, "view.buffer === sab"), "true");
+    assert_eq!(
+        forward(&mut context, "new DataView(sab).getInt32(4, true)"),
+        "42"
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { ArrayBuffer.prototype.slice.call(sab) } catch (e) { e.name }"
+        ),
+        "\"TypeError\""
+    );
+}
+
+#[test]
+fn read_modify_write() {
+    let mut context = Context::new();
+
+    let init = r#"
+        var view = new Int16Array(new SharedArrayBuffer(8));
+        var bigView = new BigInt64Array(new SharedArrayBuffer(16));
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "Atomics.store(view, 0, 5)"), "5");
+    assert_eq!(forward(&mut context, "Atomics.add(view, 0, 2)"), "5");
+    assert_eq!(forward(&mut context, "Atomics.sub(view, 0, 10)"), "7");
+    assert_eq!(forward(&mut context, "Atomics.load(view, 0)"), "-3");
+    assert_eq!(forward(&mut context, "Atomics.add(view, 1, 32767)"), "0");
+    assert_eq!(forward(&mut context, "Atomics.add(view, 1, 1)"), "32767");
+    assert_eq!(forward(&mut context, "Atomics.load(view, 1)"), "-32768");
+    assert_eq!(
+        forward(&mut context, "Atomics.store(view, 2, 0b1100)"),
+        "12"
+    );
+    assert_eq!(forward(&mut context, "Atomics.and(view, 2, 0b1010)"), "12");
+    assert_eq!(forward(&mut context, "Atomics.or(view, 2, 0b0001)"), "8");
+    assert_eq!(forward(&mut context, "Atomics.xor(view, 2, 0b1111)"), "9");
+    assert_eq!(forward(&mut context, "Atomics.exchange(view, 2, 100)"), "6");
+    assert_eq!(
+        forward(&mut context, "Atomics.compareExchange(view, 2, 1, 200)"),
+        "100"
+    );
+    assert_eq!(
+        forward(&mut context, "Atomics.compareExchange(view, 2, 100, 200)"),
+        "100"
+    );
+    assert_eq!(forward(&mut context, "view[2]"), "200");
+    assert_eq!(forward(&mut context, "Atomics.add(bigView, 1, -1n)"), "0n");
+    assert_eq!(forward(&mut context, "Atomics.load(bigView, 1)"), "-1n");
+}
+
+#[test]
+fn non_shared_buffers() {
+    let mut context = Context::new();
+
+    let init = r#"
+        var view = new Uint8Array(4);
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "Atomics.add(view, 3, 300)"), "0");
+    assert_eq!(forward(&mut context, "Atomics.load(view, 3)"), "44");
+    assert_eq!(
+        forward(&mut context, "Atomics.notify(new Int32Array(4), 0)"),
+        "0"
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { Atomics.wait(new Int32Array(4), 0, 0) } catch (e) { e.name }"
+        ),
+        "\"TypeError\""
+    );
+}
+
+#[test]
+fn validation() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { Atomics.load(new Float64Array(1), 0) } catch (e) { e.name }"
+        ),
+        "\"TypeError\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { Atomics.load(new Uint8ClampedArray(1), 0) } catch (e) { e.name }"
+        ),
+        "\"TypeError\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { Atomics.load(new Int8Array(1), 1) } catch (e) { e.name }"
+        ),
+        "\"RangeError\""
+    );
+    assert_eq!(forward(&mut context, "Atomics.isLockFree(4)"), "true");
+    assert_eq!(forward(&mut context, "Atomics.isLockFree(3)"), "false");
+}
+
+#[test]
+fn wait_without_notification() {
+    let mut context = Context::new();
+
+    let init = r#"
+        var view = new Int32Array(new SharedArrayBuffer(8));
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(
+        forward(&mut context, "Atomics.wait(view, 0, 1)"),
+        "\"not-equal\""
+    );
+    assert_eq!(
+        forward(&mut context, "Atomics.wait(view, 0, 0, 10)"),
+        "\"timed-out\""
+    );
+}
+
+#[test]
+fn wait_and_notify_between_contexts() {
+    let block = SharedDataBlock::new(16);
+
+    let waiter = {
+        let block = block.clone();
+        std::thread::spawn(move || {
+            let mut context = Context::new();
+            let buffer = SharedArrayBuffer::from_data_block(block, &mut context);
+            context.register_global_property("buffer", buffer, Attribute::all());
+            forward(
+                &mut context,
+                r#"
+                var view = new Int32Array(buffer);
+                Atomics.store(view, 1, 1);
+                var result = Atomics.wait(view, 0, 0);
+                Atomics.add(view, 2, 1);
+                result
+                "#,
+            )
+        })
+    };
+
+    let mut context = Context::new();
+    let buffer = SharedArrayBuffer::from_data_block(block.clone(), &mut context);
+    context.register_global_property("buffer", buffer, Attribute::all());
+    forward(&mut context, "var view = new Int32Array(buffer)");
+
+    // Wait until the other thread is about to block.
+    while forward(&mut context, "Atomics.load(view, 1)") != "1" {
+        std::thread::yield_now();
+    }
+
+    // The waiter could still be between the store and the wait, so keep notifying until it wakes.
+    while forward(&mut context, "Atomics.notify(view, 0)") == "0" {
+        std::thread::yield_now();
+    }
+
+    assert_eq!(waiter.join().unwrap(), "\"ok\"");
+    assert_eq!(forward(&mut context, "Atomics.load(view, 2)"), "1");
+    assert_eq!(block.to_vec()[8], 1);
+}
@@ -19,15 +19,15 @@ mod tests;
 use crate::{
     builtins::{BuiltIn, JsArgs},
     object::ObjectInitializer,
-    property::Attribute,
+    property::{Attribute, PropertyNameKind},
     value::{display::display_obj, JsValue},
     BoaProfiler, Context, JsResult, JsString,
 };
 use rustc_hash::FxHashMap;
-use std::time::SystemTime;
+use std::fmt;
 
 /// This represents the different types of log messages.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LogMessage {
     Log(String),
     Info(String),
@@ -35,20 +35,69 @@ pub enum LogMessage {
     Error(String),
 }
 
-/// Helper function for logging messages.
-pub(crate) fn logger(msg: LogMessage, console_state: &Console) {
-    let indent = 2 * console_state.groups.len();
+/// The backend that receives the messages of the `console` object.
+///
+/// It is set with [`Context::set_console_logger`], and defaults to [`StdoutLogger`]. It is also
+/// implemented for closures taking the same arguments as [`ConsoleLogger::log`].
+///
+/// # Examples
+/// ```
+///# use boa::{builtins::console::LogMessage, Context};
+///# use std::{cell::RefCell, rc::Rc};
+/// let messages = Rc::new(RefCell::new(Vec::new()));
+///
+/// let mut context = Context::new();
+/// let captured = messages.clone();
+/// context.set_console_logger(move |message: LogMessage, _indent: usize| {
+///     captured.borrow_mut().push(message);
+/// });
+///
+/// context.eval("console.log('%s + %d', 'one', 2)").unwrap();
+/// assert_eq!(messages.borrow()[0], LogMessage::Log("one + 2".to_string()));
+/// ```
+pub trait ConsoleLogger {
+    /// Logs a formatted message, indented by `indent` spaces for the currently open groups.
+    fn log(&mut self, message: LogMessage, indent: usize);
+}
 
-    match msg {
-        LogMessage::Error(msg) => {
-            eprintln!("{:>width$}", msg, width = indent);
-        }
-        LogMessage::Log(msg) | LogMessage::Info(msg) | LogMessage::Warn(msg) => {
-            println!("{:>width$}", msg, width = indent);
+impl<F> ConsoleLogger for F
+where
+    F: FnMut(LogMessage, usize),
+{
+    #[inline]
+    fn log(&mut self, message: LogMessage, indent: usize) {
+        self(message, indent)
+    }
+}
+
+/// The default console logger, which prints errors to the standard error and all other messages
+/// to the standard output.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdoutLogger;
+
+impl ConsoleLogger for StdoutLogger {
+    fn log(&mut self, message: LogMessage, indent: usize) {
+        match message {
+            LogMessage::Error(msg) => {
+                for line in msg.lines() {
+                    eprintln!("{:width$}{}", "", line, width = indent);
+                }
+            }
+            LogMessage::Log(msg) | LogMessage::Info(msg) | LogMessage::Warn(msg) => {
+                for line in msg.lines() {
+                    println!("{:width$}{}", "", line, width = indent);
+                }
+            }
         }
     }
 }
 
+/// Helper function for logging messages.
+pub(crate) fn logger(msg: LogMessage, console_state: &mut Console) {
+    let indent = 2 * console_state.groups.len();
+    console_state.logger.log(msg, indent);
+}
+
 /// This represents the `console` formatter.
 pub fn formatter(data: &[JsValue], context: &mut Context) -> JsResult<String> {
     let target = data
@@ -88,12 +137,20 @@ pub fn formatter(data: &[JsValue], context: &mut Context) -> JsResult<String> {
                             formatted.push_str(&format!("{number:.prec$}", number = arg, prec = 6));
                             arg_index += 1
                         }
-                        /* object, FIXME: how to render this properly? */
-                        'o' | 'O' => {
+                        /* object, with its internals */
+                        'o' => {
+                            let arg = data.get_or_undefined(arg_index);
+                            formatted.push_str(&display_obj(arg, true));
+                            arg_index += 1
+                        }
+                        /* object */
+                        'O' => {
                             let arg = data.get_or_undefined(arg_index);
                             formatted.push_str(&format!("{}", arg.display()));
                             arg_index += 1
                         }
+                        /* CSS styles, which are ignored */
+                        'c' => arg_index += 1,
                         /* string */
                         's' => {
                             let arg = data
@@ -105,7 +162,6 @@ pub fn formatter(data: &[JsValue], context: &mut Context) -> JsResult<String> {
                             arg_index += 1
                         }
                         '%' => formatted.push('%'),
-                        /* TODO: %c is not implemented */
                         c => {
                             formatted.push('%');
                             formatted.push(c);
@@ -127,11 +183,32 @@ pub fn formatter(data: &[JsValue], context: &mut Context) -> JsResult<String> {
 }
 
 /// This is the internal console object state.
-#[derive(Debug, Default)]
 pub(crate) struct Console {
     count_map: FxHashMap<JsString, u32>,
-    timer_map: FxHashMap<JsString, u128>,
+    timer_map: FxHashMap<JsString, i64>,
     groups: Vec<String>,
+    logger: Box<dyn ConsoleLogger>,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self {
+            count_map: FxHashMap::default(),
+            timer_map: FxHashMap::default(),
+            groups: Vec::new(),
+            logger: Box::new(StdoutLogger),
+        }
+    }
+}
+
+impl fmt::Debug for Console {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Console")
+            .field("count_map", &self.count_map)
+            .field("timer_map", &self.timer_map)
+            .field("groups", &self.groups)
+            .finish()
+    }
 }
 
 impl BuiltIn for Console {
@@ -163,6 +240,7 @@ impl BuiltIn for Console {
             .function(Self::time_end, "timeEnd", 0)
             .function(Self::dir, "dir", 0)
             .function(Self::dir, "dirxml", 0)
+            .function(Self::table, "table", 0)
             .build();
 
         console.into()
@@ -173,6 +251,11 @@ impl Console {
     /// The name of the object.
     pub(crate) const NAME: &'static str = "console";
 
+    /// Sets the backend that receives the logged messages.
+    pub(crate) fn set_logger(&mut self, logger: Box<dyn ConsoleLogger>) {
+        self.logger = logger;
+    }
+
     /// `console.assert(condition, ...data)`
     ///
     /// Prints a JavaScript value to the standard error if first argument evaluates to `false` or there
@@ -205,7 +288,7 @@ impl Console {
 
             logger(
                 LogMessage::Error(formatter(&args, context)?),
-                context.console(),
+                context.console_mut(),
             );
         }
 
@@ -240,7 +323,7 @@ impl Console {
     pub(crate) fn debug(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         logger(
             LogMessage::Log(formatter(args, context)?),
-            context.console(),
+            context.console_mut(),
         );
         Ok(JsValue::undefined())
     }
@@ -258,7 +341,7 @@ impl Console {
     pub(crate) fn error(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         logger(
             LogMessage::Error(formatter(args, context)?),
-            context.console(),
+            context.console_mut(),
         );
         Ok(JsValue::undefined())
     }
@@ -276,7 +359,7 @@ impl Console {
     pub(crate) fn info(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         logger(
             LogMessage::Info(formatter(args, context)?),
-            context.console(),
+            context.console_mut(),
         );
         Ok(JsValue::undefined())
     }
@@ -294,7 +377,7 @@ impl Console {
     pub(crate) fn log(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         logger(
             LogMessage::Log(formatter(args, context)?),
-            context.console(),
+            context.console_mut(),
         );
         Ok(JsValue::undefined())
     }
@@ -313,13 +396,13 @@ impl Console {
         if !args.is_empty() {
             logger(
                 LogMessage::Log(formatter(args, context)?),
-                context.console(),
+                context.console_mut(),
             );
 
             /* TODO: get and print stack trace */
             logger(
                 LogMessage::Log("Not implemented: <stack trace>".to_string()),
-                context.console(),
+                context.console_mut(),
             )
         }
 
@@ -339,7 +422,7 @@ impl Console {
     pub(crate) fn warn(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         logger(
             LogMessage::Warn(formatter(args, context)?),
-            context.console(),
+            context.console_mut(),
         );
         Ok(JsValue::undefined())
     }
@@ -363,10 +446,11 @@ impl Console {
         let msg = format!("count {}:", &label);
         let c = context.console_mut().count_map.entry(label).or_insert(0);
         *c += 1;
+        let c = *c;
 
         logger(
             LogMessage::Info(format!("{} {}", msg, c)),
-            context.console(),
+            context.console_mut(),
         );
         Ok(JsValue::undefined())
     }
@@ -395,20 +479,12 @@ impl Console {
 
         logger(
             LogMessage::Warn(format!("countReset {}", label)),
-            context.console(),
+            context.console_mut(),
         );
 
         Ok(JsValue::undefined())
     }
 
-    /// Returns current system time in ms.
-    fn system_time_in_ms() -> u128 {
-        let now = SystemTime::now();
-        now.duration_since(SystemTime::UNIX_EPOCH)
-            .expect("negative duration")
-            .as_millis()
-    }
-
     /// `console.time(label)`
     ///
     /// Starts the timer for given label.
@@ -428,10 +504,10 @@ impl Console {
         if context.console().timer_map.get(&label).is_some() {
             logger(
                 LogMessage::Warn(format!("Timer '{}' already exist", label)),
-                context.console(),
+                context.console_mut(),
             );
         } else {
-            let time = Self::system_time_in_ms();
+            let time = context.now();
             context.console_mut().timer_map.insert(label, time);
         }
 
@@ -458,17 +534,17 @@ impl Console {
             None => "default".into(),
         };
 
-        if let Some(t) = context.console().timer_map.get(&label) {
-            let time = Self::system_time_in_ms();
+        if let Some(t) = context.console().timer_map.get(&label).copied() {
+            let time = context.now();
             let mut concat = format!("{}: {} ms", label, time - t);
             for msg in args.iter().skip(1) {
                 concat = concat + " " + &msg.display().to_string();
             }
-            logger(LogMessage::Log(concat), context.console());
+            logger(LogMessage::Log(concat), context.console_mut());
         } else {
             logger(
                 LogMessage::Warn(format!("Timer '{}' doesn't exist", label)),
-                context.console(),
+                context.console_mut(),
             );
         }
 
@@ -496,15 +572,15 @@ impl Console {
         };
 
         if let Some(t) = context.console_mut().timer_map.remove(label.as_str()) {
-            let time = Self::system_time_in_ms();
+            let time = context.now();
             logger(
                 LogMessage::Info(format!("{}: {} ms - timer removed", label, time - t)),
-                context.console(),
+                context.console_mut(),
             );
         } else {
             logger(
                 LogMessage::Warn(format!("Timer '{}' doesn't exist", label)),
-                context.console(),
+                context.console_mut(),
             );
         }
 
@@ -526,7 +602,7 @@ impl Console {
 
         logger(
             LogMessage::Info(format!("group: {}", &group_label)),
-            context.console(),
+            context.console_mut(),
         );
         context.console_mut().groups.push(group_label);
 
@@ -566,8 +642,132 @@ impl Console {
     pub(crate) fn dir(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         logger(
             LogMessage::Info(display_obj(args.get_or_undefined(0), true)),
-            context.console(),
+            context.console_mut(),
+        );
+        Ok(JsValue::undefined())
+    }
+
+    /// `console.table(tabularData, properties)`
+    ///
+    /// Prints the properties of the tabular data as a table, with a row for each of its properties
+    /// and a column for each of their properties. The columns can be restricted to the
+    /// `properties` array. Data that isn't an object is logged like `console.log` does.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `console` specification][spec]
+    ///
+    /// [spec]: https://console.spec.whatwg.org/#table
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/table
+    pub(crate) fn table(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let data = match args.get_or_undefined(0).as_object() {
+            Some(data) => data.clone(),
+            None => return Self::log(this, args, context),
+        };
+
+        let properties = match args.get_or_undefined(1).as_object() {
+            Some(properties) if properties.is_array() => Some(
+                properties
+                    .enumerable_own_property_names(PropertyNameKind::Value, context)?
+                    .iter()
+                    .map(|property| property.to_string(context))
+                    .collect::<JsResult<Vec<_>>>()?,
+            ),
+            _ => None,
+        };
+
+        let mut columns = properties.clone().unwrap_or_default();
+        let mut has_values = false;
+        let mut rows = Vec::new();
+        for index in data.enumerable_own_property_names(PropertyNameKind::Key, context)? {
+            let index = index.to_string(context)?;
+            let value = data.get(index.clone(), context)?;
+
+            let mut cells = FxHashMap::default();
+            let mut row_value = None;
+            if let Some(row) = value.as_object() {
+                for column in row.enumerable_own_property_names(PropertyNameKind::Key, context)? {
+                    let column = column.to_string(context)?;
+                    if properties.is_none() && !columns.contains(&column) {
+                        columns.push(column.clone());
+                    }
+                    if columns.contains(&column) {
+                        let cell = row.get(column.clone(), context)?;
+                        cells.insert(column, cell.display().to_string());
+                    }
+                }
+            } else {
+                has_values = true;
+                row_value = Some(value.display().to_string());
+            }
+            rows.push((index, cells, row_value));
+        }
+
+        let mut header = vec!["(index)".to_string()];
+        header.extend(columns.iter().map(ToString::to_string));
+        if has_values {
+            header.push("Values".to_string());
+        }
+        let body: Vec<Vec<String>> = rows
+            .into_iter()
+            .map(|(index, mut cells, row_value)| {
+                let mut line = vec![index.to_string()];
+                line.extend(
+                    columns
+                        .iter()
+                        .map(|column| cells.remove(column).unwrap_or_default()),
+                );
+                if has_values {
+                    line.push(row_value.unwrap_or_default());
+                }
+                line
+            })
+            .collect();
+
+        logger(
+            LogMessage::Log(Self::render_table(&header, &body)),
+            context.console_mut(),
         );
         Ok(JsValue::undefined())
     }
+
+    /// Renders the header and body rows of `console.table` with box-drawing characters.
+    fn render_table(header: &[String], body: &[Vec<String>]) -> String {
+        let widths: Vec<usize> = (0..header.len())
+            .map(|column| {
+                std::iter::once(header)
+                    .chain(body.iter().map(Vec::as_slice))
+                    .map(|row| row[column].chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    + 2
+            })
+            .collect();
+
+        let border = |left: &str, middle: &str, right: &str| {
+            let lines: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+            format!("{}{}{}", left, lines.join(middle), right)
+        };
+        let row = |cells: &[String]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| {
+                    let padding = width - cell.chars().count();
+                    let left = padding / 2;
+                    format!("{}{}{}", " ".repeat(left), cell, " ".repeat(padding - left))
+                })
+                .collect();
+            format!("│{}│", cells.join("│"))
+        };
+
+        let mut lines = vec![border("┌", "┬", "┐"), row(header), border("├", "┼", "┤")];
+        lines.extend(body.iter().map(|cells| row(cells)));
+        lines.push(border("└", "┴", "┘"));
+        lines.join("\n")
+    }
 }
@@ -1,4 +1,22 @@
-use crate::{builtins::console::formatter, Context, JsValue};
+use crate::{
+    builtins::console::{formatter, LogMessage},
+    Context, JsValue,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// The console messages captured by a test, with their indentation.
+type Messages = Rc<RefCell<Vec<(LogMessage, usize)>>>;
+
+/// Creates a context whose console messages are captured.
+fn capturing_context() -> (Context, Messages) {
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    let mut context = Context::new();
+    let captured = messages.clone();
+    context.set_console_logger(move |message, indent| {
+        captured.borrow_mut().push((message, indent));
+    });
+    (context, messages)
+}
 
 #[test]
 fn formatter_no_args_is_empty_string() {
@@ -66,3 +84,94 @@ fn formatter_float_format_works() {
     let res = formatter(&val, &mut context).unwrap();
     assert_eq!(res, "3.141500");
 }
+
+#[test]
+fn formatter_css_and_object_specifiers() {
+    let mut context = Context::new();
+
+    let val = [
+        JsValue::new("%cstyled%c %O"),
+        JsValue::new("color: red"),
+        JsValue::new("color: blue"),
+        JsValue::new("text"),
+    ];
+    let res = formatter(&val, &mut context).unwrap();
+    assert_eq!(res, "styled \"text\"");
+
+    let object = context.eval("({ a: 1 })").unwrap();
+    let res = formatter(&[JsValue::new("%o"), object], &mut context).unwrap();
+    assert!(res.contains("a: 1"));
+}
+
+#[test]
+fn console_logger_receives_messages() {
+    let (mut context, messages) = capturing_context();
+
+    context
+        .eval(
+            r#"
+            console.log("top");
+            console.group("outer");
+            console.warn("nested %d", 1);
+            console.groupEnd();
+            console.error("failed");
+        "#,
+        )
+        .unwrap();
+
+    assert_eq!(
+        *messages.borrow(),
+        vec![
+            (LogMessage::Log("top".to_string()), 0),
+            (LogMessage::Info("group: outer".to_string()), 0),
+            (LogMessage::Warn("nested 1".to_string()), 2),
+            (LogMessage::Error("failed".to_string()), 0),
+        ]
+    );
+}
+
+#[test]
+fn console_table() {
+    let (mut context, messages) = capturing_context();
+
+    context
+        .eval(
+            r#"
+            console.table([{ a: 1, b: "Y" }, { a: 10 }, 5]);
+            console.table({ x: { a: 1, b: 2 } }, ["b"]);
+            console.table("not tabular");
+        "#,
+        )
+        .unwrap();
+
+    let messages = messages.borrow();
+    assert_eq!(
+        messages[0].0,
+        LogMessage::Log(
+            [
+                "┌─────────┬────┬─────┬────────┐",
+                "│ (index) │ a  │  b  │ Values │",
+                "├─────────┼────┼─────┼────────┤",
+                "│    0    │ 1  │ \"Y\" │        │",
+                "│    1    │ 10 │     │        │",
+                "│    2    │    │     │   5    │",
+                "└─────────┴────┴─────┴────────┘",
+            ]
+            .join("\n")
+        )
+    );
+    assert_eq!(
+        messages[1].0,
+        LogMessage::Log(
+            [
+                "┌─────────┬───┐",
+                "│ (index) │ b │",
+                "├─────────┼───┤",
+                "│    x    │ 2 │",
+                "└─────────┴───┘",
+            ]
+            .join("\n")
+        )
+    );
+    assert_eq!(messages[2].0, LogMessage::Log("not tabular".to_string()));
+}
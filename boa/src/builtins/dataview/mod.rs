@@ -1,5 +1,9 @@
 use crate::{
-    builtins::{array_buffer::SharedMemoryOrder, typed_array::TypedArrayName, BuiltIn, JsArgs},
+    builtins::{
+        array_buffer::{ArrayBuffer, SharedMemoryOrder},
+        typed_array::TypedArrayName,
+        BuiltIn, JsArgs,
+    },
     context::StandardObjects,
     object::{
         internal_methods::get_prototype_from_constructor, ConstructorBuilder, FunctionBuilder,
@@ -15,7 +19,9 @@ use gc::{Finalize, Trace};
 #[derive(Debug, Clone, Trace, Finalize)]
 pub struct DataView {
     viewed_array_buffer: JsObject,
-    byte_length: usize,
+    /// The `[[ByteLength]]` of the view, or `None` if it is `auto` and tracks the length of a
+    /// resizable buffer.
+    byte_length: Option<usize>,
     byte_offset: usize,
 }
 
@@ -85,6 +91,60 @@ impl BuiltIn for DataView {
 impl DataView {
     pub(crate) const LENGTH: usize = 1;
 
+    /// Abstract operation `GetViewByteLength ( viewRecord )`.
+    ///
+    /// The view must not be out of bounds of `buffer`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-getviewbytelength
+    fn view_byte_length(&self, buffer: &ArrayBuffer) -> usize {
+        // 1. Assert: IsViewOutOfBounds(viewRecord) is false.
+        // 2. Let view be viewRecord.[[Object]].
+        // 3. If view.[[ByteLength]] is not auto, return view.[[ByteLength]].
+        // 4. Assert: IsFixedLengthArrayBuffer(view.[[ViewedArrayBuffer]]) is false.
+        // 5. Let byteOffset be view.[[ByteOffset]].
+        // 6. Let byteLength be viewRecord.[[CachedBufferByteLength]].
+        // 7. Assert: byteLength is not detached.
+        // 8. Return byteLength - byteOffset.
+        self.byte_length
+            .unwrap_or_else(|| buffer.array_buffer_byte_length() - self.byte_offset)
+    }
+
+    /// Abstract operation `IsViewOutOfBounds ( viewRecord )`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-isviewoutofbounds
+    fn is_view_out_of_bounds(&self, buffer: &ArrayBuffer) -> bool {
+        // 1. Let view be viewRecord.[[Object]].
+        // 2. Let bufferByteLength be viewRecord.[[CachedBufferByteLength]].
+        // 3. Assert: IsDetachedBuffer(view.[[ViewedArrayBuffer]]) is true if and only if bufferByteLength is detached.
+        // 4. If bufferByteLength is detached, return true.
+        if buffer.is_detached_buffer() {
+            return true;
+        }
+        let buffer_byte_length = buffer.array_buffer_byte_length();
+
+        // 5. Let byteOffsetStart be view.[[ByteOffset]].
+        let byte_offset_start = self.byte_offset;
+
+        // 6. If view.[[ByteLength]] is auto, then
+        //     a. Let byteOffsetEnd be bufferByteLength.
+        // 7. Else,
+        //     a. Let byteOffsetEnd be byteOffsetStart + view.[[ByteLength]].
+        let byte_offset_end = self.byte_length.map_or(buffer_byte_length, |byte_length| {
+            byte_offset_start + byte_length
+        });
+
+        // 8. If byteOffsetStart > bufferByteLength or byteOffsetEnd > bufferByteLength, return true.
+        // 9. NOTE: 0-length DataViews are not considered out-of-bounds.
+        // 10. Return false.
+        byte_offset_start > buffer_byte_length || byte_offset_end > buffer_byte_length
+    }
+
     /// `25.3.2.1 DataView ( buffer [ , byteOffset [ , byteLength ] ] )`
     ///
     /// More information:
@@ -125,57 +185,79 @@ impl DataView {
             if buffer.is_detached_buffer() {
                 return context.throw_type_error("ArrayBuffer is detached");
             }
-            // 5. Let bufferByteLength be buffer.[[ArrayBufferByteLength]].
+            // 5. Let bufferByteLength be ArrayBufferByteLength(buffer, seq-cst).
             let buffer_byte_length = buffer.array_buffer_byte_length();
             // 6. If offset > bufferByteLength, throw a RangeError exception.
             if offset > buffer_byte_length {
                 return context
                     .throw_range_error("Start offset is outside the bounds of the buffer");
             }
-            // 7. If byteLength is undefined, then
+            // 7. Let bufferIsFixedLength be IsFixedLengthArrayBuffer(buffer).
+            // 8. If byteLength is undefined, then
             let view_byte_length = if byte_length.is_undefined() {
-                // a. Let viewByteLength be bufferByteLength - offset.
-                buffer_byte_length - offset
+                // a. If bufferIsFixedLength is true, then
+                //     i. Let viewByteLength be bufferByteLength - offset.
+                // b. Else,
+                //     i. Let viewByteLength be auto.
+                buffer
+                    .is_fixed_length()
+                    .then(|| buffer_byte_length - offset)
             } else {
-                // 8.a. Let viewByteLength be ? ToIndex(byteLength).
+                // 9.a. Let viewByteLength be ? ToIndex(byteLength).
                 let view_byte_length = byte_length.to_index(context)?;
-                // 8.b. If offset + viewByteLength > bufferByteLength, throw a RangeError exception.
+                // 9.b. If offset + viewByteLength > bufferByteLength, throw a RangeError exception.
                 if offset + view_byte_length > buffer_byte_length {
                     return context.throw_range_error("Invalid data view length");
-                } else {
-                    view_byte_length
                 }
+                Some(view_byte_length)
             };
             (offset, view_byte_length)
         };
 
-        // 9. Let O be ? OrdinaryCreateFromConstructor(NewTarget, "%DataView.prototype%", « [[DataView]], [[ViewedArrayBuffer]], [[ByteLength]], [[ByteOffset]] »).
+        // 10. Let O be ? OrdinaryCreateFromConstructor(NewTarget, "%DataView.prototype%", « [[DataView]], [[ViewedArrayBuffer]], [[ByteLength]], [[ByteOffset]] »).
         let prototype =
             get_prototype_from_constructor(new_target, StandardObjects::data_view_object, context)?;
 
-        // 10. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
-        if buffer_obj
-            .borrow()
-            .as_array_buffer()
-            .ok_or_else(|| context.construct_type_error("buffer must be an ArrayBuffer"))?
-            .is_detached_buffer()
         {
-            return context.throw_type_error("ArrayBuffer can't be detached");
+            let buffer_borrow = buffer_obj.borrow();
+            let buffer = buffer_borrow
+                .as_array_buffer()
+                .ok_or_else(|| context.construct_type_error("buffer must be an ArrayBuffer"))?;
+
+            // 11. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
+            if buffer.is_detached_buffer() {
+                return context.throw_type_error("ArrayBuffer can't be detached");
+            }
+
+            // 12. Set bufferByteLength to ArrayBufferByteLength(buffer, seq-cst).
+            let buffer_byte_length = buffer.array_buffer_byte_length();
+
+            // 13. If offset > bufferByteLength, throw a RangeError exception.
+            if offset > buffer_byte_length {
+                return context
+                    .throw_range_error("Start offset is outside the bounds of the buffer");
+            }
+
+            // 14. If byteLength is not undefined, then
+            //     a. If offset + viewByteLength > bufferByteLength, throw a RangeError exception.
+            if matches!(view_byte_length, Some(length) if offset + length > buffer_byte_length) {
+                return context.throw_range_error("Invalid data view length");
+            }
         }
 
         let obj = JsObject::from_proto_and_data(
             prototype,
             ObjectData::data_view(DataView {
-                // 11. Set O.[[ViewedArrayBuffer]] to buffer.
+                // 15. Set O.[[ViewedArrayBuffer]] to buffer.
                 viewed_array_buffer: buffer_obj.to_owned(),
-                // 12. Set O.[[ByteLength]] to viewByteLength.
+                // 16. Set O.[[ByteLength]] to viewByteLength.
                 byte_length: view_byte_length,
-                // 13. Set O.[[ByteOffset]] to offset.
+                // 17. Set O.[[ByteOffset]] to offset.
                 byte_offset: offset,
             }),
         );
 
-        // 14. Return O.
+        // 18. Return O.
         Ok(obj.into())
     }
 
@@ -236,13 +318,14 @@ impl DataView {
         let borrow = buffer_borrow
             .as_array_buffer()
             .expect("DataView must be constructed with an ArrayBuffer");
-        // 5. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
-        if borrow.is_detached_buffer() {
-            return context.throw_type_error("ArrayBuffer is detached");
+        // 5. Let viewRecord be MakeDataViewWithBufferWitnessRecord(O, seq-cst).
+        // 6. If IsViewOutOfBounds(viewRecord) is true, throw a TypeError exception.
+        if dataview.is_view_out_of_bounds(borrow) {
+            return context.throw_type_error("DataView is out of bounds of its ArrayBuffer");
         }
-        // 6. Let size be O.[[ByteLength]].
-        let size = dataview.byte_length;
-        // 7. Return 𝔽(size).
+        // 7. Let size be GetViewByteLength(viewRecord).
+        let size = dataview.view_byte_length(borrow);
+        // 8. Return 𝔽(size).
         Ok(size.into())
     }
 
@@ -274,13 +357,14 @@ impl DataView {
         let borrow = buffer_borrow
             .as_array_buffer()
             .expect("DataView must be constructed with an ArrayBuffer");
-        // 5. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
-        if borrow.is_detached_buffer() {
-            return context.throw_type_error("ArrayBuffer is detached");
+        // 5. Let viewRecord be MakeDataViewWithBufferWitnessRecord(O, seq-cst).
+        // 6. If IsViewOutOfBounds(viewRecord) is true, throw a TypeError exception.
+        if dataview.is_view_out_of_bounds(borrow) {
+            return context.throw_type_error("DataView is out of bounds of its ArrayBuffer");
         }
-        // 6. Let offset be O.[[ByteOffset]].
+        // 7. Let offset be O.[[ByteOffset]].
         let offset = dataview.byte_offset;
-        // 7. Return 𝔽(offset).
+        // 8. Return 𝔽(offset).
         Ok(offset.into())
     }
 
@@ -320,15 +404,15 @@ impl DataView {
             .as_array_buffer()
             .expect("Should be unreachable");
 
-        // 6. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
-        if buffer.is_detached_buffer() {
-            return context.throw_type_error("ArrayBuffer is detached");
+        // 6. If IsViewOutOfBounds(viewRecord) is true, throw a TypeError exception.
+        if view.is_view_out_of_bounds(buffer) {
+            return context.throw_type_error("DataView is out of bounds of its ArrayBuffer");
         }
         // 7. Let viewOffset be view.[[ByteOffset]].
         let view_offset = view.byte_offset;
 
-        // 8. Let viewSize be view.[[ByteLength]].
-        let view_size = view.byte_length;
+        // 8. Let viewSize be GetViewByteLength(viewRecord).
+        let view_size = view.view_byte_length(buffer);
 
         // 9. Let elementSize be the Element Size value specified in Table 72 for Element Type type.
         let element_size = t.element_size();
@@ -675,16 +759,16 @@ impl DataView {
             .as_array_buffer_mut()
             .expect("Should be unreachable");
 
-        // 8. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
-        if buffer.is_detached_buffer() {
-            return context.throw_type_error("ArrayBuffer is detached");
+        // 8. If IsViewOutOfBounds(viewRecord) is true, throw a TypeError exception.
+        if view.is_view_out_of_bounds(buffer) {
+            return context.throw_type_error("DataView is out of bounds of its ArrayBuffer");
         }
 
         // 9. Let viewOffset be view.[[ByteOffset]].
         let view_offset = view.byte_offset;
 
-        // 10. Let viewSize be view.[[ByteLength]].
-        let view_size = view.byte_length;
+        // 10. Let viewSize be GetViewByteLength(viewRecord).
+        let view_size = view.view_byte_length(buffer);
 
         // 11. Let elementSize be the Element Size value specified in Table 72 for Element Type type.
         let element_size = t.element_size();
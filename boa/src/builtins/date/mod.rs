@@ -5,6 +5,7 @@ use crate::{
     builtins::BuiltIn,
     context::StandardObjects,
     gc::{empty_trace, Finalize, Trace},
+    host::{self, LocalTimeZone, SystemTimeZone},
     object::{
         internal_methods::get_prototype_from_constructor, ConstructorBuilder, JsObject, ObjectData,
     },
@@ -13,7 +14,7 @@ use crate::{
     value::{JsValue, PreferredType},
     BoaProfiler, Context, JsResult, JsString,
 };
-use chrono::{prelude::*, Duration, LocalResult};
+use chrono::{prelude::*, Duration};
 use std::fmt::Display;
 
 use super::JsArgs;
@@ -40,16 +41,22 @@ macro_rules! check_normal_opt {
     };
 }
 
+/// Converts a date and time in the local time zone to UTC.
 #[inline]
-fn ignore_ambiguity<T>(result: LocalResult<T>) -> Option<T> {
-    match result {
-        LocalResult::Ambiguous(v, _) => Some(v),
-        LocalResult::Single(v) => Some(v),
-        LocalResult::None => None,
-    }
+fn local_to_utc(local: &NaiveDateTime, time_zone: &dyn LocalTimeZone) -> Option<NaiveDateTime> {
+    let offset = time_zone.offset_from_local(local.timestamp_millis())?;
+    local.checked_sub_signed(Duration::seconds(offset.into()))
 }
 
 macro_rules! getter_method {
+    (local $name:ident) => {{
+        fn get_value(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+            Ok(JsValue::new(
+                this_time_value(this, context)?.$name(context.time_zone()),
+            ))
+        }
+        get_value
+    }};
     ($name:ident) => {{
         fn get_value(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
             Ok(JsValue::new(this_time_value(this, context)?.$name()))
@@ -69,7 +76,8 @@ pub struct Date(Option<NaiveDateTime>);
 
 impl Display for Date {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.to_local() {
+        // There is no `Context` to get the time zone from, so the time zone of the system is used.
+        match self.to_local(&SystemTimeZone) {
             Some(v) => write!(f, "{}", v.format("%a %b %d %Y %H:%M:%S GMT%:z")),
             _ => write!(f, "Invalid Date"),
         }
@@ -98,66 +106,70 @@ impl BuiltIn for Date {
     fn init(context: &mut Context) -> JsValue {
         let _timer = BoaProfiler::global().start_event(Self::NAME, "init");
 
-        let date_object = ConstructorBuilder::new(context, Self::constructor)
-            .name(Self::NAME)
-            .length(Self::LENGTH)
-            .method(getter_method!(get_date), "getDate", 0)
-            .method(getter_method!(get_day), "getDay", 0)
-            .method(getter_method!(get_full_year), "getFullYear", 0)
-            .method(getter_method!(get_hours), "getHours", 0)
-            .method(getter_method!(get_milliseconds), "getMilliseconds", 0)
-            .method(getter_method!(get_minutes), "getMinutes", 0)
-            .method(getter_method!(get_month), "getMonth", 0)
-            .method(getter_method!(get_seconds), "getSeconds", 0)
-            .method(getter_method!(get_time), "getTime", 0)
-            .method(getter_method!(get_year), "getYear", 0)
-            .method(Self::get_timezone_offset, "getTimezoneOffset", 0)
-            .method(getter_method!(get_utc_date), "getUTCDate", 0)
-            .method(getter_method!(get_utc_day), "getUTCDay", 0)
-            .method(getter_method!(get_utc_full_year), "getUTCFullYear", 0)
-            .method(getter_method!(get_utc_hours), "getUTCHours", 0)
-            .method(
-                getter_method!(get_utc_milliseconds),
-                "getUTCMilliseconds",
-                0,
-            )
-            .method(getter_method!(get_utc_minutes), "getUTCMinutes", 0)
-            .method(getter_method!(get_utc_month), "getUTCMonth", 0)
-            .method(getter_method!(get_utc_seconds), "getUTCSeconds", 0)
-            .method(Self::set_date, "setDate", 1)
-            .method(Self::set_full_year, "setFullYear", 3)
-            .method(Self::set_hours, "setHours", 4)
-            .method(Self::set_milliseconds, "setMilliseconds", 1)
-            .method(Self::set_minutes, "setMinutes", 3)
-            .method(Self::set_month, "setMonth", 2)
-            .method(Self::set_seconds, "setSeconds", 2)
-            .method(Self::set_year, "setYear", 1)
-            .method(Self::set_time, "setTime", 1)
-            .method(Self::set_utc_date, "setUTCDate", 1)
-            .method(Self::set_utc_full_year, "setUTCFullYear", 3)
-            .method(Self::set_utc_hours, "setUTCHours", 4)
-            .method(Self::set_utc_milliseconds, "setUTCMilliseconds", 1)
-            .method(Self::set_utc_minutes, "setUTCMinutes", 3)
-            .method(Self::set_utc_month, "setUTCMonth", 2)
-            .method(Self::set_utc_seconds, "setUTCSeconds", 2)
-            .method(Self::to_date_string, "toDateString", 0)
-            .method(getter_method!(to_gmt_string), "toGMTString", 0)
-            .method(Self::to_iso_string, "toISOString", 0)
-            .method(Self::to_json, "toJSON", 1)
-            // Locale strings
-            .method(Self::to_string, "toString", 0)
-            .method(Self::to_time_string, "toTimeString", 0)
-            .method(getter_method!(to_utc_string), "toUTCString", 0)
-            .method(getter_method!(value_of), "valueOf", 0)
-            .method(
-                Self::to_primitive,
-                (WellKnownSymbols::to_primitive(), "[Symbol.toPrimitive]"),
-                1,
-            )
-            .static_method(Self::now, "now", 0)
-            .static_method(Self::parse, "parse", 1)
-            .static_method(Self::utc, "UTC", 7)
-            .build();
+        let date_object = ConstructorBuilder::with_standard_object(
+            context,
+            Self::constructor,
+            context.standard_objects().date_object().clone(),
+        )
+        .name(Self::NAME)
+        .length(Self::LENGTH)
+        .method(getter_method!(local get_date), "getDate", 0)
+        .method(getter_method!(local get_day), "getDay", 0)
+        .method(getter_method!(local get_full_year), "getFullYear", 0)
+        .method(getter_method!(local get_hours), "getHours", 0)
+        .method(getter_method!(local get_milliseconds), "getMilliseconds", 0)
+        .method(getter_method!(local get_minutes), "getMinutes", 0)
+        .method(getter_method!(local get_month), "getMonth", 0)
+        .method(getter_method!(local get_seconds), "getSeconds", 0)
+        .method(getter_method!(get_time), "getTime", 0)
+        .method(getter_method!(local get_year), "getYear", 0)
+        .method(Self::get_timezone_offset, "getTimezoneOffset", 0)
+        .method(getter_method!(get_utc_date), "getUTCDate", 0)
+        .method(getter_method!(get_utc_day), "getUTCDay", 0)
+        .method(getter_method!(get_utc_full_year), "getUTCFullYear", 0)
+        .method(getter_method!(get_utc_hours), "getUTCHours", 0)
+        .method(
+            getter_method!(get_utc_milliseconds),
+            "getUTCMilliseconds",
+            0,
+        )
+        .method(getter_method!(get_utc_minutes), "getUTCMinutes", 0)
+        .method(getter_method!(get_utc_month), "getUTCMonth", 0)
+        .method(getter_method!(get_utc_seconds), "getUTCSeconds", 0)
+        .method(Self::set_date, "setDate", 1)
+        .method(Self::set_full_year, "setFullYear", 3)
+        .method(Self::set_hours, "setHours", 4)
+        .method(Self::set_milliseconds, "setMilliseconds", 1)
+        .method(Self::set_minutes, "setMinutes", 3)
+        .method(Self::set_month, "setMonth", 2)
+        .method(Self::set_seconds, "setSeconds", 2)
+        .method(Self::set_year, "setYear", 1)
+        .method(Self::set_time, "setTime", 1)
+        .method(Self::set_utc_date, "setUTCDate", 1)
+        .method(Self::set_utc_full_year, "setUTCFullYear", 3)
+        .method(Self::set_utc_hours, "setUTCHours", 4)
+        .method(Self::set_utc_milliseconds, "setUTCMilliseconds", 1)
+        .method(Self::set_utc_minutes, "setUTCMinutes", 3)
+        .method(Self::set_utc_month, "setUTCMonth", 2)
+        .method(Self::set_utc_seconds, "setUTCSeconds", 2)
+        .method(Self::to_date_string, "toDateString", 0)
+        .method(getter_method!(to_gmt_string), "toGMTString", 0)
+        .method(Self::to_iso_string, "toISOString", 0)
+        .method(Self::to_json, "toJSON", 1)
+        // Locale strings
+        .method(Self::to_string, "toString", 0)
+        .method(Self::to_time_string, "toTimeString", 0)
+        .method(getter_method!(to_utc_string), "toUTCString", 0)
+        .method(getter_method!(value_of), "valueOf", 0)
+        .method(
+            Self::to_primitive,
+            (WellKnownSymbols::to_primitive(), "[Symbol.toPrimitive]"),
+            1,
+        )
+        .static_method(Self::now, "now", 0)
+        .static_method(Self::parse, "parse", 1)
+        .static_method(Self::utc, "UTC", 7)
+        .build();
 
         date_object.into()
     }
@@ -183,13 +195,25 @@ impl Date {
         }
     }
 
-    /// Converts the `Date` to a local `DateTime`.
+    /// Creates a `Date` with the current time of the clock of the context.
+    #[inline]
+    pub(crate) fn current(context: &Context) -> Self {
+        Self(
+            host::from_millis(context.now())
+                .filter(|time| Self::time_clip(time.timestamp_millis() as f64).is_some()),
+        )
+    }
+
+    /// Converts the `Date` to a `DateTime` in the given local time zone.
     ///
     /// If the `Date` is invalid (i.e. NAN), this function will return `None`.
     #[inline]
-    pub fn to_local(self) -> Option<DateTime<Local>> {
-        self.0
-            .map(|utc| Local::now().timezone().from_utc_datetime(&utc))
+    pub fn to_local(self, time_zone: &dyn LocalTimeZone) -> Option<DateTime<FixedOffset>> {
+        self.0.map(|utc| {
+            let offset = FixedOffset::east_opt(time_zone.offset_from_utc(utc.timestamp_millis()))
+                .unwrap_or_else(|| FixedOffset::east(0));
+            DateTime::from_utc(utc, offset)
+        })
     }
 
     /// Converts the `Date` to a UTC `DateTime`.
@@ -215,6 +239,7 @@ impl Date {
         minute: Option<f64>,
         second: Option<f64>,
         millisecond: Option<f64>,
+        time_zone: &dyn LocalTimeZone,
     ) {
         #[inline]
         fn num_days_in(year: i32, month: u32) -> Option<u32> {
@@ -286,7 +311,7 @@ impl Date {
         let naive = if utc {
             self.to_utc().map(|dt| dt.naive_utc())
         } else {
-            self.to_local().map(|dt| dt.naive_local())
+            self.to_local(time_zone).map(|dt| dt.naive_local())
         };
 
         self.0 = naive.and_then(|naive| {
@@ -318,7 +343,7 @@ impl Date {
                     if utc {
                         Some(Utc.from_utc_datetime(&dt).naive_utc())
                     } else {
-                        ignore_ambiguity(Local.from_local_datetime(&dt)).map(|dt| dt.naive_utc())
+                        local_to_utc(&dt, time_zone)
                     }
                 })
                 .filter(|dt| Self::time_clip(dt.timestamp_millis() as f64).is_some())
@@ -341,15 +366,12 @@ impl Date {
         context: &mut Context,
     ) -> JsResult<JsValue> {
         if new_target.is_undefined() {
-            Ok(Self::make_date_string())
+            Ok(Self::make_date_string(context))
         } else {
-            let prototype = get_prototype_from_constructor(
-                new_target,
-                StandardObjects::object_object,
-                context,
-            )?;
+            let prototype =
+                get_prototype_from_constructor(new_target, StandardObjects::date_object, context)?;
             Ok(if args.is_empty() {
-                Self::make_date_now(prototype)
+                Self::make_date_now(prototype, context)
             } else if args.len() == 1 {
                 Self::make_date_single(prototype, args, context)?
             } else {
@@ -369,8 +391,11 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date-constructor
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/Date
-    pub(crate) fn make_date_string() -> JsValue {
-        JsValue::new(Local::now().to_rfc3339())
+    pub(crate) fn make_date_string(context: &Context) -> JsValue {
+        match Self::current(context).to_local(context.time_zone()) {
+            Some(now) => JsValue::new(now.to_rfc3339()),
+            None => JsValue::new("Invalid Date"),
+        }
     }
 
     /// `Date()`
@@ -383,8 +408,8 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date-constructor
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/Date
-    pub(crate) fn make_date_now(prototype: JsObject) -> JsObject {
-        JsObject::from_proto_and_data(prototype, ObjectData::date(Date::default()))
+    pub(crate) fn make_date_now(prototype: JsObject, context: &Context) -> JsObject {
+        JsObject::from_proto_and_data(prototype, ObjectData::date(Self::current(context)))
     }
 
     /// `Date(value)`
@@ -477,8 +502,7 @@ impl Date {
 
         let mut date = Self(
             NaiveDateTime::from_timestamp_opt(0, 0)
-                .and_then(|local| ignore_ambiguity(Local.from_local_datetime(&local)))
-                .map(|local| local.naive_utc())
+                .and_then(|local| local_to_utc(&local, context.time_zone()))
                 .filter(|time| Self::time_clip(time.timestamp_millis() as f64).is_some()),
         );
 
@@ -491,6 +515,7 @@ impl Date {
             Some(min),
             Some(sec),
             Some(milli),
+            context.time_zone(),
         );
 
         Ok(JsObject::from_proto_and_data(
@@ -553,8 +578,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getdate
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getDate
-    pub fn get_date(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.day() as f64)
+    pub fn get_date(&self, time_zone: &dyn LocalTimeZone) -> f64 {
+        self.to_local(time_zone)
+            .map_or(f64::NAN, |dt| dt.day() as f64)
     }
 
     /// `Date.prototype.getDay()`
@@ -568,8 +594,8 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getday
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getDay
-    pub fn get_day(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| {
+    pub fn get_day(&self, time_zone: &dyn LocalTimeZone) -> f64 {
+        self.to_local(time_zone).map_or(f64::NAN, |dt| {
             let weekday = dt.weekday() as u32;
             let weekday = (weekday + 1) % 7; // 0 represents Monday in Chrono
             weekday as f64
@@ -586,8 +612,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getfullyear
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getFullYear
-    pub fn get_full_year(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.year() as f64)
+    pub fn get_full_year(&self, time_zone: &dyn LocalTimeZone) -> f64 {
+        self.to_local(time_zone)
+            .map_or(f64::NAN, |dt| dt.year() as f64)
     }
 
     /// `Date.prototype.getHours()`
@@ -600,8 +627,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.gethours
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getHours
-    pub fn get_hours(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.hour() as f64)
+    pub fn get_hours(&self, time_zone: &dyn LocalTimeZone) -> f64 {
+        self.to_local(time_zone)
+            .map_or(f64::NAN, |dt| dt.hour() as f64)
     }
 
     /// `Date.prototype.getMilliseconds()`
@@ -614,8 +642,8 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getmilliseconds
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getMilliseconds
-    pub fn get_milliseconds(&self) -> f64 {
-        self.to_local()
+    pub fn get_milliseconds(&self, time_zone: &dyn LocalTimeZone) -> f64 {
+        self.to_local(time_zone)
             .map_or(f64::NAN, |dt| dt.nanosecond() as f64 / NANOS_PER_MS as f64)
     }
 
@@ -629,8 +657,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getminutes
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getMinutes
-    pub fn get_minutes(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.minute() as f64)
+    pub fn get_minutes(&self, time_zone: &dyn LocalTimeZone) -> f64 {
+        self.to_local(time_zone)
+            .map_or(f64::NAN, |dt| dt.minute() as f64)
     }
 
     /// `Date.prototype.getMonth()`
@@ -644,8 +673,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getmonth
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getMonth
-    pub fn get_month(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.month0() as f64)
+    pub fn get_month(&self, time_zone: &dyn LocalTimeZone) -> f64 {
+        self.to_local(time_zone)
+            .map_or(f64::NAN, |dt| dt.month0() as f64)
     }
 
     /// `Date.prototype.getSeconds()`
@@ -658,8 +688,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getseconds
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getSeconds
-    pub fn get_seconds(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.second() as f64)
+    pub fn get_seconds(&self, time_zone: &dyn LocalTimeZone) -> f64 {
+        self.to_local(time_zone)
+            .map_or(f64::NAN, |dt| dt.second() as f64)
     }
 
     /// `Date.prototype.getYear()`
@@ -673,8 +704,8 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getyear
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getYear
-    pub fn get_year(&self) -> f64 {
-        self.to_local()
+    pub fn get_year(&self, time_zone: &dyn LocalTimeZone) -> f64 {
+        self.to_local(time_zone)
             .map_or(f64::NAN, |dt| dt.year() as f64 - 1900f64)
     }
 
@@ -714,14 +745,14 @@ impl Date {
         let t = this_time_value(this, context)?;
 
         // 2. If t is NaN, return NaN.
-        if t.0.is_none() {
-            return Ok(JsValue::nan());
-        }
+        let t = match t.0 {
+            Some(t) => t,
+            None => return Ok(JsValue::nan()),
+        };
 
         // 3. Return (t - LocalTime(t)) / msPerMinute.
-        Ok(JsValue::new(
-            -Local::now().offset().local_minus_utc() as f64 / 60f64,
-        ))
+        let offset = context.time_zone().offset_from_utc(t.timestamp_millis());
+        Ok(JsValue::new(-offset as f64 / 60f64))
     }
 
     /// `Date.prototype.getUTCDate()`
@@ -866,7 +897,17 @@ impl Date {
             .to_number(context)?;
 
         // 3. Let newDate be MakeDate(MakeDay(YearFromTime(t), MonthFromTime(t), dt), TimeWithinDay(t)).
-        t.set_components(false, None, None, Some(dt), None, None, None, None);
+        t.set_components(
+            false,
+            None,
+            None,
+            Some(dt),
+            None,
+            None,
+            None,
+            None,
+            context.time_zone(),
+        );
 
         // 4. Let u be TimeClip(UTC(newDate)).
         let u = t.get_time();
@@ -900,8 +941,7 @@ impl Date {
         // 2. If t is NaN, set t to +0𝔽; otherwise, set t to LocalTime(t).
         if t.0.is_none() {
             t.0 = NaiveDateTime::from_timestamp_opt(0, 0)
-                .and_then(|local| ignore_ambiguity(Local.from_local_datetime(&local)))
-                .map(|local| local.naive_utc())
+                .and_then(|local| local_to_utc(&local, context.time_zone()))
                 .filter(|time| Self::time_clip(time.timestamp_millis() as f64).is_some());
         }
 
@@ -927,7 +967,17 @@ impl Date {
         };
 
         // 6. Let newDate be MakeDate(MakeDay(y, m, dt), TimeWithinDay(t)).
-        t.set_components(false, Some(y), m, dt, None, None, None, None);
+        t.set_components(
+            false,
+            Some(y),
+            m,
+            dt,
+            None,
+            None,
+            None,
+            None,
+            context.time_zone(),
+        );
 
         // 7. Let u be TimeClip(UTC(newDate)).
         let u = t.get_time();
@@ -984,7 +1034,17 @@ impl Date {
         };
 
         // 6. Let date be MakeDate(Day(t), MakeTime(h, m, s, milli)).
-        t.set_components(false, None, None, None, Some(h), m, sec, milli);
+        t.set_components(
+            false,
+            None,
+            None,
+            None,
+            Some(h),
+            m,
+            sec,
+            milli,
+            context.time_zone(),
+        );
 
         // 7. Let u be TimeClip(UTC(date)).
         let u = t.get_time();
@@ -1022,7 +1082,17 @@ impl Date {
             .to_number(context)?;
 
         // 3. Let time be MakeTime(HourFromTime(t), MinFromTime(t), SecFromTime(t), ms).
-        t.set_components(false, None, None, None, None, None, None, Some(ms));
+        t.set_components(
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(ms),
+            context.time_zone(),
+        );
 
         // 4. Let u be TimeClip(UTC(MakeDate(Day(t), time))).
         let u = t.get_time();
@@ -1074,7 +1144,17 @@ impl Date {
         };
 
         // 5. Let date be MakeDate(Day(t), MakeTime(HourFromTime(t), m, s, milli)).
-        t.set_components(false, None, None, None, None, Some(m), s, milli);
+        t.set_components(
+            false,
+            None,
+            None,
+            None,
+            None,
+            Some(m),
+            s,
+            milli,
+            context.time_zone(),
+        );
 
         // 6. Let u be TimeClip(UTC(date)).
         let u = t.get_time();
@@ -1115,7 +1195,17 @@ impl Date {
         };
 
         // 4. Let newDate be MakeDate(MakeDay(YearFromTime(t), m, dt), TimeWithinDay(t)).
-        t.set_components(false, None, Some(m), dt, None, None, None, None);
+        t.set_components(
+            false,
+            None,
+            Some(m),
+            dt,
+            None,
+            None,
+            None,
+            None,
+            context.time_zone(),
+        );
 
         // 5. Let u be TimeClip(UTC(newDate)).
         let u = t.get_time();
@@ -1160,7 +1250,17 @@ impl Date {
         };
 
         // 4. Let date be MakeDate(Day(t), MakeTime(HourFromTime(t), MinFromTime(t), s, milli)).
-        t.set_components(false, None, None, None, None, None, Some(s), milli);
+        t.set_components(
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(s),
+            milli,
+            context.time_zone(),
+        );
 
         // 5. Let u be TimeClip(UTC(date)).
         let u = t.get_time();
@@ -1189,8 +1289,7 @@ impl Date {
         // 2. If t is NaN, set t to +0𝔽; otherwise, set t to LocalTime(t).
         if t.0.is_none() {
             t.0 = NaiveDateTime::from_timestamp_opt(0, 0)
-                .and_then(|local| ignore_ambiguity(Local.from_local_datetime(&local)))
-                .map(|local| local.naive_utc())
+                .and_then(|local| local_to_utc(&local, context.time_zone()))
                 .filter(|time| Self::time_clip(time.timestamp_millis() as f64).is_some());
         }
 
@@ -1219,7 +1318,17 @@ impl Date {
 
         // 8. Let d be MakeDay(yyyy, MonthFromTime(t), DateFromTime(t)).
         // 9. Let date be UTC(MakeDate(d, TimeWithinDay(t))).
-        t.set_components(false, Some(y), None, None, None, None, None, None);
+        t.set_components(
+            false,
+            Some(y),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            context.time_zone(),
+        );
 
         // 10. Set the [[DateValue]] internal slot of this Date object to TimeClip(date).
         this.set_data(ObjectData::date(t));
@@ -1248,10 +1357,7 @@ impl Date {
             let t = t.to_number(context)?;
             let seconds = (t / 1_000f64) as i64;
             let nanoseconds = ((t % 1_000f64) * 1_000_000f64) as u32;
-            Date(
-                ignore_ambiguity(Local.timestamp_opt(seconds, nanoseconds))
-                    .map(|dt| dt.naive_utc()),
-            )
+            Date(NaiveDateTime::from_timestamp_opt(seconds, nanoseconds))
         } else {
             Date(None)
         };
@@ -1292,7 +1398,17 @@ impl Date {
             .to_number(context)?;
 
         // 3. Let newDate be MakeDate(MakeDay(YearFromTime(t), MonthFromTime(t), dt), TimeWithinDay(t)).
-        t.set_components(true, None, None, Some(dt), None, None, None, None);
+        t.set_components(
+            true,
+            None,
+            None,
+            Some(dt),
+            None,
+            None,
+            None,
+            None,
+            context.time_zone(),
+        );
 
         // 4. Let v be TimeClip(newDate).
         let v = t.get_time();
@@ -1326,8 +1442,7 @@ impl Date {
         // 2. If t is NaN, set t to +0𝔽.
         if t.0.is_none() {
             t.0 = NaiveDateTime::from_timestamp_opt(0, 0)
-                .and_then(|local| ignore_ambiguity(Local.from_local_datetime(&local)))
-                .map(|local| local.naive_utc())
+                .and_then(|local| local_to_utc(&local, context.time_zone()))
                 .filter(|time| Self::time_clip(time.timestamp_millis() as f64).is_some());
         }
 
@@ -1353,7 +1468,17 @@ impl Date {
         };
 
         // 6. Let newDate be MakeDate(MakeDay(y, m, dt), TimeWithinDay(t)).
-        t.set_components(true, Some(y), m, dt, None, None, None, None);
+        t.set_components(
+            true,
+            Some(y),
+            m,
+            dt,
+            None,
+            None,
+            None,
+            None,
+            context.time_zone(),
+        );
 
         // 7. Let v be TimeClip(newDate).
         let v = t.get_time();
@@ -1414,7 +1539,17 @@ impl Date {
         };
 
         // 6. Let newDate be MakeDate(Day(t), MakeTime(h, m, s, milli)).
-        t.set_components(true, None, None, None, Some(h), m, sec, ms);
+        t.set_components(
+            true,
+            None,
+            None,
+            None,
+            Some(h),
+            m,
+            sec,
+            ms,
+            context.time_zone(),
+        );
 
         // 7. Let v be TimeClip(newDate).
         let v = t.get_time();
@@ -1452,7 +1587,17 @@ impl Date {
             .to_number(context)?;
 
         // 3. Let time be MakeTime(HourFromTime(t), MinFromTime(t), SecFromTime(t), milli).
-        t.set_components(true, None, None, None, None, None, None, Some(ms));
+        t.set_components(
+            true,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(ms),
+            context.time_zone(),
+        );
 
         // 4. Let v be TimeClip(MakeDate(Day(t), time)).
         let v = t.get_time();
@@ -1508,7 +1653,17 @@ impl Date {
         };
 
         // 7. Let date be MakeDate(Day(t), MakeTime(HourFromTime(t), m, s, milli)).
-        t.set_components(true, None, None, None, None, Some(m), s, milli);
+        t.set_components(
+            true,
+            None,
+            None,
+            None,
+            None,
+            Some(m),
+            s,
+            milli,
+            context.time_zone(),
+        );
 
         // 8. Let v be TimeClip(date).
         let v = t.get_time();
@@ -1555,7 +1710,17 @@ impl Date {
         };
 
         // 5. Let newDate be MakeDate(MakeDay(YearFromTime(t), m, dt), TimeWithinDay(t)).
-        t.set_components(true, None, Some(m), dt, None, None, None, None);
+        t.set_components(
+            true,
+            None,
+            Some(m),
+            dt,
+            None,
+            None,
+            None,
+            None,
+            context.time_zone(),
+        );
 
         // 6. Let v be TimeClip(newDate).
         let v = t.get_time();
@@ -1602,7 +1767,17 @@ impl Date {
         };
 
         // 5. Let date be MakeDate(Day(t), MakeTime(HourFromTime(t), MinFromTime(t), s, milli)).
-        t.set_components(true, None, None, None, None, None, Some(s), milli);
+        t.set_components(
+            true,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(s),
+            milli,
+            context.time_zone(),
+        );
 
         // 6. Let v be TimeClip(date).
         let v = t.get_time();
@@ -1637,7 +1812,7 @@ impl Date {
         // 3. If tv is NaN, return "Invalid Date".
         // 4. Let t be LocalTime(tv).
         // 5. Return DateString(t).
-        if let Some(t) = tv.0 {
+        if let Some(t) = tv.to_local(context.time_zone()) {
             Ok(t.format("%a %b %d %Y").to_string().into())
         } else {
             Ok(JsString::from("Invalid Date").into())
@@ -1734,13 +1909,8 @@ impl Date {
         let tv = this_time_value(this, context)?;
 
         // 2. Return ToDateString(tv).
-        if let Some(t) = tv.0 {
-            Ok(Local::now()
-                .timezone()
-                .from_utc_datetime(&t)
-                .format("%a %b %d %Y %H:%M:%S GMT%z")
-                .to_string()
-                .into())
+        if let Some(t) = tv.to_local(context.time_zone()) {
+            Ok(t.format("%a %b %d %Y %H:%M:%S GMT%z").to_string().into())
         } else {
             Ok(JsString::from("Invalid Date").into())
         }
@@ -1770,13 +1940,8 @@ impl Date {
         // 3. If tv is NaN, return "Invalid Date".
         // 4. Let t be LocalTime(tv).
         // 5. Return the string-concatenation of TimeString(t) and TimeZoneString(tv).
-        if let Some(t) = tv.0 {
-            Ok(Local::now()
-                .timezone()
-                .from_utc_datetime(&t)
-                .format("%H:%M:%S GMT%z")
-                .to_string()
-                .into())
+        if let Some(t) = tv.to_local(context.time_zone()) {
+            Ok(t.format("%H:%M:%S GMT%z").to_string().into())
         } else {
             Ok(JsString::from("Invalid Date").into())
         }
@@ -1822,8 +1987,8 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.now
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/now
-    pub(crate) fn now(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
-        Ok(JsValue::new(Utc::now().timestamp_millis() as f64))
+    pub(crate) fn now(_: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Ok(JsValue::new(context.now() as f64))
     }
 
     /// `Date.parse()`
@@ -45,7 +45,9 @@ fn date_display() {
     assert_eq!(
         format!(
             "[{}]",
-            cd.to_local().unwrap().format("%a %b %d %Y %H:%M:%S GMT%:z")
+            cd.to_local(&crate::host::SystemTimeZone)
+                .unwrap()
+                .format("%a %b %d %Y %H:%M:%S GMT%:z")
         ),
         format!("[{}]", cd)
     );
@@ -1377,3 +1379,43 @@ fn date_json() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn date_host_clock_and_time_zone() {
+    use crate::host::{FixedClock, FixedTimeZone};
+
+    let mut context = Context::new();
+    context.set_clock(FixedClock::new(1594199775779));
+    context.set_time_zone(FixedTimeZone::new(-5 * 3600));
+
+    assert_eq!(forward(&mut context, "Date.now()"), "1594199775779");
+    assert_eq!(
+        forward(&mut context, "new Date().getTime()"),
+        "1594199775779"
+    );
+    assert_eq!(
+        forward(&mut context, "new Date().toString()"),
+        "\"Wed Jul 08 2020 04:16:15 GMT-0500\""
+    );
+    assert_eq!(
+        forward(&mut context, "new Date().toDateString()"),
+        "\"Wed Jul 08 2020\""
+    );
+    assert_eq!(forward(&mut context, "new Date().getHours()"), "4");
+    assert_eq!(forward(&mut context, "new Date().getUTCHours()"), "9");
+    assert_eq!(
+        forward(&mut context, "new Date().getTimezoneOffset()"),
+        "300"
+    );
+    assert_eq!(
+        forward(&mut context, "new Date(2020, 6, 8, 4, 16).getUTCHours()"),
+        "9"
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "let d = new Date(0); d.setHours(23); d.getTime()"
+        ),
+        "14400000"
+    );
+}
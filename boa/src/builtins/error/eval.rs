@@ -16,7 +16,7 @@ use crate::object::internal_methods::get_prototype_from_constructor;
 use crate::object::JsObject;
 
 use crate::{
-    builtins::BuiltIn,
+    builtins::{error::Error, BuiltIn, JsArgs},
     object::{ConstructorBuilder, ObjectData},
     profiler::BoaProfiler,
     property::Attribute,
@@ -70,9 +70,10 @@ impl EvalError {
         let obj = JsObject::from_proto_and_data(prototype, ObjectData::error());
         if let Some(message) = args.get(0) {
             if !message.is_undefined() {
-                obj.set("message", message.to_string(context)?, false, context)?;
+                Error::install_error_message(&obj, message, context)?;
             }
         }
+        Error::install_error_cause(&obj, args.get_or_undefined(1), context)?;
         Ok(obj.into())
     }
 }
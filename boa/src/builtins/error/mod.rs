@@ -11,16 +11,17 @@
 //! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Error
 
 use crate::{
-    builtins::BuiltIn,
+    builtins::{BuiltIn, JsArgs},
     context::StandardObjects,
     object::{
         internal_methods::get_prototype_from_constructor, ConstructorBuilder, JsObject, ObjectData,
     },
     profiler::BoaProfiler,
-    property::Attribute,
+    property::{Attribute, PropertyDescriptor},
     Context, JsResult, JsValue,
 };
 
+pub(crate) mod aggregate;
 pub(crate) mod eval;
 pub(crate) mod range;
 pub(crate) mod reference;
@@ -31,6 +32,7 @@ pub(crate) mod uri;
 #[cfg(test)]
 mod tests;
 
+pub(crate) use self::aggregate::AggregateError;
 pub(crate) use self::eval::EvalError;
 pub(crate) use self::r#type::TypeError;
 pub(crate) use self::range::RangeError;
@@ -73,7 +75,67 @@ impl Error {
     /// The amount of arguments this function object takes.
     pub(crate) const LENGTH: usize = 1;
 
-    /// `Error( message )`
+    /// Creates the `message` property of a new error object from its `message` argument.
+    ///
+    /// The property is defined rather than assigned, so that a non-writable `message` of a
+    /// frozen prototype does not prevent it.
+    pub(crate) fn install_error_message(
+        o: &JsObject,
+        message: &JsValue,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let message = message.to_string(context)?;
+        o.define_property_or_throw(
+            "message",
+            PropertyDescriptor::builder()
+                .value(message)
+                .writable(true)
+                .enumerable(false)
+                .configurable(true),
+            context,
+        )
+        .expect("should not fail according to spec");
+        Ok(())
+    }
+
+    /// `InstallErrorCause ( O, options )`
+    ///
+    /// Creates the `cause` property of a new error object from its `options` argument.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-installerrorcause
+    pub(crate) fn install_error_cause(
+        o: &JsObject,
+        options: &JsValue,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        // 1. If Type(options) is Object and ? HasProperty(options, "cause") is true, then
+        if let Some(options) = options.as_object() {
+            if options.has_property("cause", context)? {
+                // a. Let cause be ? Get(options, "cause").
+                let cause = options.get("cause", context)?;
+
+                // b. Perform ! CreateNonEnumerableDataPropertyOrThrow(O, "cause", cause).
+                o.define_property_or_throw(
+                    "cause",
+                    PropertyDescriptor::builder()
+                        .value(cause)
+                        .writable(true)
+                        .enumerable(false)
+                        .configurable(true),
+                    context,
+                )
+                .expect("should not fail according to spec");
+            }
+        }
+
+        // 2. Return NormalCompletion(undefined).
+        Ok(())
+    }
+
+    /// `Error( message [ , options ] )`
     ///
     /// Create a new error object.
     pub(crate) fn constructor(
@@ -86,9 +148,10 @@ impl Error {
         let obj = JsObject::from_proto_and_data(prototype, ObjectData::error());
         if let Some(message) = args.get(0) {
             if !message.is_undefined() {
-                obj.set("message", message.to_string(context)?, false, context)?;
+                Self::install_error_message(&obj, message, context)?;
             }
         }
+        Self::install_error_cause(&obj, args.get_or_undefined(1), context)?;
         Ok(obj.into())
     }
 
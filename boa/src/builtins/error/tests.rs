@@ -1,4 +1,4 @@
-use crate::{forward, Context};
+use crate::{forward, property::Attribute, Context};
 
 #[test]
 fn error_to_string() {
@@ -84,3 +84,107 @@ fn uri_error_to_string() {
         "\"URIError\""
     );
 }
+
+#[test]
+fn aggregate_error_name() {
+    let mut context = Context::new();
+    assert_eq!(
+        forward(&mut context, "AggregateError.name"),
+        "\"AggregateError\""
+    );
+}
+
+#[test]
+fn aggregate_error_length() {
+    let mut context = Context::new();
+    assert_eq!(forward(&mut context, "AggregateError.length"), "2");
+}
+
+#[test]
+fn aggregate_error_errors() {
+    let mut context = Context::new();
+    let init = r#"
+        let e = new AggregateError(new Set([1, 'two']), 'hello');
+    "#;
+    forward(&mut context, init);
+    assert_eq!(
+        forward(&mut context, "e.toString()"),
+        "\"AggregateError: hello\""
+    );
+    assert_eq!(forward(&mut context, "e instanceof Error"), "true");
+    assert_eq!(forward(&mut context, "Array.isArray(e.errors)"), "true");
+    assert_eq!(forward(&mut context, "e.errors.join()"), "\"1,two\"");
+    assert_eq!(
+        forward(&mut context, "Object.keys(e).includes('errors')"),
+        "false"
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { new AggregateError(1) } catch (e) { e.name }"
+        ),
+        "\"TypeError\""
+    );
+}
+
+#[test]
+fn error_cause() {
+    let mut context = Context::new();
+    let init = r#"
+        let cause = new Error('inner');
+        let e = new TypeError('outer', { cause });
+        let a = new AggregateError([], 'outer', { cause: undefined });
+        let none = new RangeError('outer', {});
+    "#;
+    forward(&mut context, init);
+    assert_eq!(forward(&mut context, "e.cause === cause"), "true");
+    assert_eq!(
+        forward(&mut context, "Object.keys(e).includes('cause')"),
+        "false"
+    );
+    assert_eq!(forward(&mut context, "'cause' in a"), "true");
+    assert_eq!(forward(&mut context, "'cause' in none"), "false");
+    assert_eq!(
+        forward(&mut context, "'cause' in new Error('outer', 'cause')"),
+        "false"
+    );
+}
+
+#[test]
+fn construct_aggregate_error() {
+    let mut context = Context::new();
+    let errors = vec![
+        context.construct_type_error("first"),
+        context.construct_range_error("second"),
+    ];
+    let error = context.construct_aggregate_error(errors, "both failed");
+    context.register_global_property("error", error, Attribute::all());
+
+    assert_eq!(
+        forward(&mut context, "error instanceof AggregateError"),
+        "true"
+    );
+    assert_eq!(forward(&mut context, "error.message"), "\"both failed\"");
+    assert_eq!(
+        forward(&mut context, "error.errors.map(e => e.name).join()"),
+        "\"TypeError,RangeError\""
+    );
+}
+
+#[test]
+fn construct_aggregate_error_ignores_array_iterator() {
+    let mut context = Context::new();
+    forward(
+        &mut context,
+        "Array.prototype[Symbol.iterator] = function () { throw new Error('iterated'); }",
+    );
+    let errors = vec![context.construct_type_error("first")];
+    let error = context.construct_aggregate_error(errors, "failed");
+    context.register_global_property("error", error, Attribute::all());
+
+    assert_eq!(
+        forward(&mut context, "error.errors[0].message"),
+        "\"first\""
+    );
+    assert_eq!(forward(&mut context, "error.message"), "\"failed\"");
+}
@@ -16,7 +16,7 @@
 //! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/TypeError
 
 use crate::{
-    builtins::BuiltIn,
+    builtins::{error::Error, BuiltIn, JsArgs},
     context::StandardObjects,
     object::{
         internal_methods::get_prototype_from_constructor, ConstructorBuilder, JsObject, ObjectData,
@@ -72,9 +72,10 @@ impl TypeError {
         let obj = JsObject::from_proto_and_data(prototype, ObjectData::error());
         if let Some(message) = args.get(0) {
             if !message.is_undefined() {
-                obj.set("message", message.to_string(context)?, false, context)?;
+                Error::install_error_message(&obj, message, context)?;
             }
         }
+        Error::install_error_cause(&obj, args.get_or_undefined(1), context)?;
         Ok(obj.into())
     }
 }
@@ -30,6 +30,7 @@ use crate::{
     object::{internal_methods::get_prototype_from_constructor, NativeObject, ObjectData},
     property::Attribute,
     property::PropertyDescriptor,
+    realm::Realm,
     syntax::ast::node::{FormalParameter, RcStatementList},
     BoaProfiler, Context, JsResult, JsValue,
 };
@@ -47,9 +48,12 @@ use crate::{
 use super::JsArgs;
 
 pub(crate) mod arguments;
+mod native;
 #[cfg(test)]
 mod tests;
 
+pub use native::{IntoNativeFunction, NativeFunction};
+
 /// Type representing a native built-in function a.k.a. function pointer.
 ///
 /// Native functions need to have this signature in order to
@@ -165,6 +169,14 @@ impl Captures {
     pub fn as_mut_any(&self) -> gc::GcCellRefMut<'_, Box<dyn NativeObject>, dyn Any> {
         RefMut::map(self.0.borrow_mut(), |data| data.deref_mut().as_mut_any())
     }
+
+    /// Mutably casts `Captures` to `Any`, or returns `None` if it's already borrowed.
+    pub(crate) fn try_as_mut_any(
+        &self,
+    ) -> Option<gc::GcCellRefMut<'_, Box<dyn NativeObject>, dyn Any>> {
+        let data = self.0.try_borrow_mut().ok()?;
+        Some(RefMut::map(data, |data| data.deref_mut().as_mut_any()))
+    }
 }
 
 /// Boa representation of a Function Object.
@@ -178,12 +190,14 @@ pub enum Function {
         #[unsafe_ignore_trace]
         function: NativeFunctionSignature,
         constructor: bool,
+        realm: Realm,
     },
     Closure {
         #[unsafe_ignore_trace]
         function: Box<dyn ClosureFunctionSignature>,
         constructor: bool,
         captures: Captures,
+        realm: Realm,
     },
     Ordinary {
         constructor: bool,
@@ -191,11 +205,13 @@ pub enum Function {
         body: RcStatementList,
         params: Box<[FormalParameter]>,
         environment: Environment,
+        realm: Realm,
     },
     #[cfg(feature = "vm")]
     VmOrdinary {
         code: Gc<crate::vm::CodeBlock>,
         environment: Environment,
+        realm: Realm,
     },
 }
 
@@ -260,6 +276,17 @@ impl Function {
             Self::VmOrdinary { code, .. } => code.constructor,
         }
     }
+
+    /// Returns the `[[Realm]]` of the function object, the realm in which it was created.
+    pub(crate) fn realm(&self) -> &Realm {
+        match self {
+            Self::Native { realm, .. }
+            | Self::Closure { realm, .. }
+            | Self::Ordinary { realm, .. } => realm,
+            #[cfg(feature = "vm")]
+            Self::VmOrdinary { realm, .. } => realm,
+        }
+    }
 }
 
 /// Creates a new member function of a `Object` or `prototype`.
@@ -298,6 +325,7 @@ pub(crate) fn make_builtin_fn<N>(
         ObjectData::function(Function::Native {
             function,
             constructor: false,
+            realm: interpreter.realm().clone(),
         }),
     );
     let attribute = PropertyDescriptor::builder()
@@ -325,9 +353,16 @@ impl BuiltInFunctionObject {
 
     fn constructor(
         new_target: &JsValue,
-        _: &[JsValue],
+        args: &[JsValue],
         context: &mut Context,
     ) -> JsResult<JsValue> {
+        // Creating functions from source text can be disabled by the embedder, see
+        // `ContextBuilder::allow_code_generation` and `HostHooks::ensure_can_compile_strings`.
+        if !args.is_empty() {
+            let realm = context.realm().clone();
+            context.ensure_can_compile_strings(&realm, &realm)?;
+        }
+
         let prototype =
             get_prototype_from_constructor(new_target, StandardObjects::function_object, context)?;
 
@@ -336,6 +371,7 @@ impl BuiltInFunctionObject {
             ObjectData::function(Function::Native {
                 function: |_, _, _| Ok(JsValue::undefined()),
                 constructor: true,
+                realm: context.realm().clone(),
             }),
         );
 
@@ -491,6 +527,14 @@ impl BuiltInFunctionObject {
 
     #[allow(clippy::wrong_self_convention)]
     fn to_string(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // The host can hide the source text of functions, see `HostHasSourceTextAvailable`.
+        let source_text_available = match this.as_object() {
+            Some(object) if object.is_function() => context
+                .host_hooks()
+                .has_source_text_available(object, context),
+            _ => true,
+        };
+
         let object = this.as_object().map(JsObject::borrow);
         let function = object
             .as_deref()
@@ -512,13 +556,12 @@ impl BuiltInFunctionObject {
         };
 
         match (function, name) {
-            (
-                Function::Native {
-                    function: _,
-                    constructor: _,
-                },
-                Some(name),
-            ) => Ok(format!("function {}() {{\n  [native Code]\n}}", &name).into()),
+            (Function::Native { .. }, Some(name)) => {
+                Ok(format!("function {}() {{\n  [native Code]\n}}", &name).into())
+            }
+            (_, Some(name)) if !source_text_available => {
+                Ok(format!("function {}() {{\n  [native Code]\n}}", &name).into())
+            }
             (Function::Ordinary { body, params, .. }, Some(name)) => {
                 let arguments: String = params
                     .iter()
@@ -0,0 +1,285 @@
+//! Rust functions that can be called from JavaScript.
+//!
+//! Besides the raw [`NativeFunctionSignature`](super::NativeFunctionSignature), native functions
+//! can be plain Rust functions whose parameters implement [`TryFromJs`] and whose return value
+//! implements [`IntoJsResult`]. The arguments of a call are converted into the parameters, with
+//! missing arguments converted from `undefined`.
+//!
+//! A [`NativeFunction`] owns one of these functions, together with any state it captures.
+
+use std::fmt;
+
+use crate::{
+    gc::{Finalize, Trace},
+    realm::Realm,
+    value::{IntoJsResult, TryFromJs},
+    Context, JsResult, JsValue,
+};
+
+use super::{Captures, ClosureFunctionSignature, Function, NativeFunctionSignature};
+
+/// A native function, together with the state that it captures.
+///
+/// Closures called from JavaScript must be `Copy`, since the garbage collector cannot trace the
+/// values that a closure captures: a captured `JsObject` could be collected while the closure is
+/// still alive (see <https://github.com/boa-dev/boa/issues/1515>). State that is not `Copy` is
+/// instead moved into the `NativeFunction` as a capture, which is traced with the function object,
+/// and is passed by mutable reference to the closure on every call.
+///
+/// # Examples
+/// ```
+///# use boa::{builtins::function::NativeFunction, Context, JsValue};
+/// let mut context = Context::new();
+///
+/// let counter = NativeFunction::from_copy_closure_with_captures(
+///     |_, _, count: &mut u32, _| {
+///         *count += 1;
+///         Ok(JsValue::new(*count))
+///     },
+///     0,
+/// );
+/// context
+///     .register_global_native_function("next", 0, counter)
+///     .unwrap();
+///
+/// context.eval("next(); next();").unwrap();
+/// assert_eq!(context.eval("next()").unwrap().as_number(), Some(3.0));
+/// ```
+#[derive(Clone, Trace, Finalize)]
+pub struct NativeFunction {
+    inner: Inner,
+}
+
+#[derive(Clone, Trace, Finalize)]
+enum Inner {
+    Pointer(#[unsafe_ignore_trace] NativeFunctionSignature),
+    Closure {
+        #[unsafe_ignore_trace]
+        function: Box<dyn ClosureFunctionSignature>,
+        captures: Captures,
+    },
+}
+
+impl fmt::Debug for NativeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NativeFunction { ... }")
+    }
+}
+
+/// Wraps the captures of a `NativeFunction`, so they only need to implement `Trace`.
+#[derive(Trace, Finalize)]
+struct NativeCaptures<T: Trace + 'static>(T);
+
+impl<T: Trace + 'static> fmt::Debug for NativeCaptures<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NativeCaptures { ... }")
+    }
+}
+
+impl NativeFunction {
+    /// Creates a `NativeFunction` from a function pointer.
+    #[inline]
+    pub fn from_fn_ptr(function: NativeFunctionSignature) -> Self {
+        Self {
+            inner: Inner::Pointer(function),
+        }
+    }
+
+    /// Creates a `NativeFunction` from a `Copy` closure.
+    #[inline]
+    pub fn from_copy_closure<F>(closure: F) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+    {
+        Self {
+            inner: Inner::Closure {
+                function: Box::new(move |this, args, _, context| closure(this, args, context)),
+                captures: Captures::new(()),
+            },
+        }
+    }
+
+    /// Creates a `NativeFunction` from a `Copy` closure and the state it captures.
+    ///
+    /// The captures can be any type that implements [`Trace`], and are traced together with the
+    /// function object. They are mutably borrowed during a call, so calling the function again
+    /// before the call returns throws a `TypeError`.
+    #[inline]
+    pub fn from_copy_closure_with_captures<F, T>(closure: F, captures: T) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut T, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        T: Trace + 'static,
+    {
+        Self {
+            inner: Inner::Closure {
+                function: Box::new(move |this, args, captures: Captures, context| {
+                    let mut captures = captures.try_as_mut_any().ok_or_else(|| {
+                        context.construct_type_error("native function called recursively")
+                    })?;
+                    let captures =
+                        captures
+                            .downcast_mut::<NativeCaptures<T>>()
+                            .ok_or_else(|| {
+                                context.construct_type_error(
+                                    "cannot downcast `Captures` to given type",
+                                )
+                            })?;
+                    closure(this, args, &mut captures.0, context)
+                }),
+                captures: Captures::new(NativeCaptures(captures)),
+            },
+        }
+    }
+
+    /// Creates a `NativeFunction` from a `Copy` closure and state that it only reads.
+    ///
+    /// Unlike [`NativeFunction::from_copy_closure_with_captures`], the captures are shared, so the
+    /// function can be called again before a call returns.
+    #[inline]
+    pub(crate) fn from_copy_closure_with_shared_captures<F, T>(closure: F, captures: T) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &T, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        T: Trace + 'static,
+    {
+        Self {
+            inner: Inner::Closure {
+                function: Box::new(move |this, args, captures: Captures, context| {
+                    let captures = captures.as_any();
+                    let captures =
+                        captures
+                            .downcast_ref::<NativeCaptures<T>>()
+                            .ok_or_else(|| {
+                                context.construct_type_error(
+                                    "cannot downcast `Captures` to given type",
+                                )
+                            })?;
+                    closure(this, args, &captures.0, context)
+                }),
+                captures: Captures::new(NativeCaptures(captures)),
+            },
+        }
+    }
+
+    /// Creates a `NativeFunction` from any [`IntoNativeFunction`].
+    #[inline]
+    pub fn from_typed<F, Args>(function: F) -> Self
+    where
+        F: IntoNativeFunction<Args>,
+    {
+        Self::from_copy_closure(move |this, args, context| {
+            function.call_native(this, args, context)
+        })
+    }
+
+    /// Calls the function with the given `this` value and arguments.
+    #[inline]
+    pub fn call(
+        &self,
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        match &self.inner {
+            Inner::Pointer(function) => function(this, args, context),
+            Inner::Closure { function, captures } => {
+                function(this, args, captures.clone(), context)
+            }
+        }
+    }
+
+    /// Converts the `NativeFunction` into the function data of a function object.
+    #[inline]
+    pub(crate) fn into_function(self, constructor: bool, realm: Realm) -> Function {
+        match &self.inner {
+            Inner::Pointer(function) => Function::Native {
+                function: *function,
+                constructor,
+                realm,
+            },
+            Inner::Closure { function, captures } => Function::Closure {
+                function: function.clone(),
+                constructor,
+                captures: captures.clone(),
+                realm,
+            },
+        }
+    }
+}
+
+/// A Rust function that can be called from JavaScript.
+///
+/// It is implemented for functions with the [`NativeFunctionSignature`](super::NativeFunctionSignature),
+/// and for functions with up to 8 parameters that implement [`TryFromJs`] returning a type that
+/// implements [`IntoJsResult`]. The `Args` parameter only distinguishes these implementations.
+///
+/// An argument that cannot be converted throws a `TypeError` with the position of the argument.
+///
+/// # Examples
+/// ```
+///# use boa::Context;
+/// fn add(a: f64, b: f64) -> f64 {
+///     a + b
+/// }
+///
+/// let mut context = Context::new();
+/// context.register_global_function("add", 2, add).unwrap();
+///
+/// assert_eq!(context.eval("add(1, 2)").unwrap().as_number(), Some(3.0));
+/// assert!(context.eval("add(1, '2')").is_err());
+/// ```
+pub trait IntoNativeFunction<Args>: Copy + 'static {
+    /// Calls the function with the arguments of a call from JavaScript.
+    fn call_native(
+        self,
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue>;
+}
+
+impl<F> IntoNativeFunction<fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue>> for F
+where
+    F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+{
+    #[inline]
+    fn call_native(
+        self,
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        self(this, args, context)
+    }
+}
+
+macro_rules! impl_into_native_function {
+    ($($arg:ident $value:ident $index:literal),*) => {
+        impl<F, R, $($arg),*> IntoNativeFunction<fn($($arg),*) -> R> for F
+        where
+            F: Fn($($arg),*) -> R + Copy + 'static,
+            R: IntoJsResult,
+            $($arg: TryFromJs,)*
+        {
+            #[allow(unused_variables)]
+            fn call_native(
+                self,
+                _: &JsValue,
+                args: &[JsValue],
+                context: &mut Context,
+            ) -> JsResult<JsValue> {
+                $(let $value = $arg::try_from_js_argument(args, $index, context)?;)*
+                self($($value),*).into_js_result(context)
+            }
+        }
+    };
+}
+
+impl_into_native_function!();
+impl_into_native_function!(A a 0);
+impl_into_native_function!(A a 0, B b 1);
+impl_into_native_function!(A a 0, B b 1, C c 2);
+impl_into_native_function!(A a 0, B b 1, C c 2, D d 3);
+impl_into_native_function!(A a 0, B b 1, C c 2, D d 3, E e 4);
+impl_into_native_function!(A a 0, B b 1, C c 2, D d 3, E e 4, G g 5);
+impl_into_native_function!(A a 0, B b 1, C c 2, D d 3, E e 4, G g 5, H h 6);
+impl_into_native_function!(A a 0, B b 1, C c 2, D d 3, E e 4, G g 5, H h 6, I i 7);
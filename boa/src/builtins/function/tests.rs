@@ -1,8 +1,8 @@
 use crate::{
     forward, forward_val,
-    object::FunctionBuilder,
+    object::{FunctionBuilder, JsObject},
     property::{Attribute, PropertyDescriptor},
-    Context, JsString,
+    Context, JsString, JsValue,
 };
 
 #[allow(clippy::float_cmp)]
@@ -260,3 +260,182 @@ fn closure_capture_clone() {
 
     assert_eq!(forward(&mut context, "closure()"), "\"Hello world!\"");
 }
+
+#[test]
+fn typed_native_functions() {
+    fn add(a: f64, b: f64) -> f64 {
+        a + b
+    }
+
+    fn repeat(text: String, times: Option<usize>) -> String {
+        text.repeat(times.unwrap_or(2))
+    }
+
+    fn checked_sqrt(x: f64) -> Result<f64, JsValue> {
+        if x < 0.0 {
+            Err("negative number".into())
+        } else {
+            Ok(x.sqrt())
+        }
+    }
+
+    let mut context = Context::new();
+    context.register_global_function("add", 2, add).unwrap();
+    context
+        .register_global_function("repeat", 2, repeat)
+        .unwrap();
+    context
+        .register_global_function("checkedSqrt", 1, checked_sqrt)
+        .unwrap();
+    context
+        .register_global_function("sum", 1, |numbers: Vec<i32>| numbers.iter().sum::<i32>())
+        .unwrap();
+
+    assert_eq!(forward(&mut context, "add(1, 2.5)"), "3.5");
+    assert_eq!(forward(&mut context, "repeat('ab')"), "\"abab\"");
+    assert_eq!(forward(&mut context, "repeat('ab', 3)"), "\"ababab\"");
+    assert_eq!(forward(&mut context, "checkedSqrt(9)"), "3");
+    assert_eq!(
+        forward(&mut context, "try { checkedSqrt(-1) } catch (e) { e }"),
+        "\"negative number\""
+    );
+    assert_eq!(forward(&mut context, "sum([1, 2, 3])"), "6");
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { add(1, '2') } catch (e) { e.toString() }"
+        ),
+        "\"TypeError: argument 2: expected a number, got string\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { sum([1, 'a']) } catch (e) { e.toString() }"
+        ),
+        "\"TypeError: argument 1: expected a number, got string\""
+    );
+}
+
+#[test]
+fn native_function_captures() {
+    use crate::{
+        builtins::{function::NativeFunction, JsArgs},
+        object::ObjectInitializer,
+    };
+
+    let mut context = Context::new();
+
+    let store = context.construct_object();
+    let counter = NativeFunction::from_copy_closure_with_captures(
+        |_, args, (store, count): &mut (JsObject, u32), context| {
+            *count += 1;
+            let value = args.get_or_undefined(0).clone();
+            store.set(count.to_string(), value, true, context)?;
+            Ok(store.clone().into())
+        },
+        (store, 0),
+    );
+    context
+        .register_global_native_function("record", 1, counter)
+        .unwrap();
+
+    crate::gc::force_collect();
+
+    assert_eq!(
+        forward(&mut context, "record('a'); record('b')[2]"),
+        "\"b\""
+    );
+    assert_eq!(forward(&mut context, "record('c')[1]"), "\"a\"");
+
+    let greeting = JsString::new("hello, ");
+    let object = ObjectInitializer::new(&mut context)
+        .native_function(
+            NativeFunction::from_copy_closure_with_captures(
+                |_, args, greeting: &mut JsString, context| {
+                    let name = args.get_or_undefined(0);
+                    let name = name.to_string(context)?;
+                    Ok(JsString::concat(greeting, &name).into())
+                },
+                greeting,
+            ),
+            "greet",
+            1,
+        )
+        .build();
+    context.register_global_property("greeter", object, Attribute::all());
+
+    assert_eq!(
+        forward(&mut context, "greeter.greet('world')"),
+        "\"hello, world\""
+    );
+
+    let reentrant = NativeFunction::from_copy_closure_with_captures(
+        |_, args, _: &mut (), context| {
+            let callback = args.get_or_undefined(0);
+            context.call(callback, &JsValue::undefined(), &[])
+        },
+        (),
+    );
+    context
+        .register_global_native_function("reenter", 1, reentrant)
+        .unwrap();
+
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { reenter(() => reenter(() => 1)) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+    assert_eq!(forward(&mut context, "reenter(() => 2)"), "2");
+}
+
+#[test]
+fn host_hooks() {
+    use crate::{host::HostHooks, realm::Realm, JsResult};
+
+    struct Hooks;
+
+    impl HostHooks for Hooks {
+        fn ensure_can_compile_strings(
+            &self,
+            _caller_realm: &Realm,
+            _callee_realm: &Realm,
+            context: &mut Context,
+        ) -> JsResult<()> {
+            Err(context.construct_eval_error("no eval"))
+        }
+
+        fn has_source_text_available(&self, function: &JsObject, context: &mut Context) -> bool {
+            function.get("name", context).map_or(true, |name| {
+                name.as_string().map(JsString::as_str) != Some("secret")
+            })
+        }
+    }
+
+    let mut context = Context::new();
+    context.set_host_hooks(Hooks);
+
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { new Function('return 1') } catch (e) { e.toString() }"
+        ),
+        "\"EvalError: no eval\""
+    );
+    assert_eq!(forward(&mut context, "typeof Function()"), "\"function\"");
+    assert_eq!(
+        forward(
+            &mut context,
+            "function secret() { return 1 }; secret.toString()"
+        ),
+        "\"function secret() {\n  [native Code]\n}\""
+    );
+    assert_ne!(
+        forward(
+            &mut context,
+            "function open() { return 1 }; open.toString()"
+        ),
+        "\"function open() {\n  [native Code]\n}\""
+    );
+}
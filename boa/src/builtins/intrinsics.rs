@@ -4,8 +4,9 @@ use crate::{
     property::PropertyDescriptor,
     Context, JsResult, JsValue,
 };
+use gc::{Finalize, Trace};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, Trace, Finalize)]
 pub struct IntrinsicObjects {
     throw_type_error: JsObject,
 }
@@ -32,6 +33,7 @@ fn create_throw_type_error(context: &mut Context) -> JsObject {
         ObjectData::function(Function::Native {
             function: throw_type_error,
             constructor: false,
+            realm: context.realm().clone(),
         }),
     );
 
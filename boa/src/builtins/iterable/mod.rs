@@ -8,8 +8,9 @@ use crate::{
     symbol::WellKnownSymbols,
     BoaProfiler, Context, JsResult, JsValue,
 };
+use gc::{Finalize, Trace};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, Trace, Finalize)]
 pub struct IteratorPrototypes {
     iterator_prototype: JsObject,
     array_iterator: JsObject,
@@ -195,6 +196,18 @@ impl IteratorRecord {
         }
     }
 
+    /// Gets the `[[Iterator]]` field of the record.
+    #[inline]
+    pub fn iterator_object(&self) -> &JsValue {
+        &self.iterator_object
+    }
+
+    /// Gets the `[[NextMethod]]` field of the record.
+    #[inline]
+    pub fn next_function(&self) -> &JsValue {
+        &self.next_function
+    }
+
     /// Get the next value in the iterator
     ///
     /// More information:
@@ -24,10 +24,11 @@ use crate::{
     value::IntegerOrInfinity,
     BoaProfiler, Context, JsResult, JsString, JsValue,
 };
-use serde_json::{self, Value as JSONValue};
 
+use self::parser::{JsonParser, ParseRecord};
 use super::JsArgs;
 
+mod parser;
 #[cfg(test)]
 mod tests;
 
@@ -79,15 +80,11 @@ impl Json {
             .unwrap_or_default()
             .to_string(context)?;
 
+        let reviver = args.get_or_undefined(1).as_callable();
+
         // 2. Parse ! StringToCodePoints(jsonString) as a JSON text as specified in ECMA-404.
         //    Throw a SyntaxError exception if it is not a valid JSON text as defined in that specification.
-        if let Err(e) = serde_json::from_str::<JSONValue>(&json_string) {
-            return context.throw_syntax_error(e.to_string());
-        }
-
         // 3. Let scriptString be the string-concatenation of "(", jsonString, and ");".
-        let script_string = JsString::concat_array(&["(", json_string.as_str(), ");"]);
-
         // 4. Let script be ParseText(! StringToCodePoints(scriptString), Script).
         // 5. NOTE: The early error rules defined in 13.2.5.1 have special handling for the above invocation of ParseText.
         // 6. Assert: script is a Parse Node.
@@ -95,67 +92,102 @@ impl Json {
         // 8. NOTE: The PropertyDefinitionEvaluation semantics defined in 13.2.5.5 have special handling for the above evaluation.
         // 9. Let unfiltered be completion.[[Value]].
         // 10. Assert: unfiltered is either a String, Number, Boolean, Null, or an Object that is defined by either an ArrayLiteral or an ObjectLiteral.
-        let unfiltered = context.eval(script_string.as_bytes())?;
+        //
+        // NOTE: Instead of evaluating the JSON text as a script, it is parsed directly into values
+        // by a dedicated JSON parser, which has the same observable behaviour. The source text of
+        // the values is only recorded if it can be observed by a reviver.
+        let record = JsonParser::new(json_string.as_str(), reviver.is_some()).parse(context)?;
 
         // 11. If IsCallable(reviver) is true, then
-        if let Some(obj) = args.get_or_undefined(1).as_callable() {
+        if let Some(obj) = reviver {
             // a. Let root be ! OrdinaryObjectCreate(%Object.prototype%).
             let root = context.construct_object();
 
             // b. Let rootName be the empty String.
             // c. Perform ! CreateDataPropertyOrThrow(root, rootName, unfiltered).
-            root.create_data_property_or_throw("", unfiltered, context)
+            root.create_data_property_or_throw("", record.value.clone(), context)
                 .expect("CreateDataPropertyOrThrow should never throw here");
 
-            // d. Return ? InternalizeJSONProperty(root, rootName, reviver).
-            Self::internalize_json_property(&root, "".into(), obj, context)
+            // d. Let snapshot be CreateJSONParseRecord(script, rootName, unfiltered).
+            // e. Return ? InternalizeJSONProperty(root, rootName, reviver, snapshot).
+            Self::internalize_json_property(&root, "".into(), obj, Some(&record), context)
         } else {
             // 12. Else,
             // a. Return unfiltered.
-            Ok(unfiltered)
+            Ok(record.value)
         }
     }
 
-    /// `25.5.1.1 InternalizeJSONProperty ( holder, name, reviver )`
+    /// `25.5.1.1 InternalizeJSONProperty ( holder, name, reviver, parseRecord )`
+    ///
+    /// The `parseRecord` argument and the `context` argument passed to the reviver are defined by
+    /// the `JSON.parse` source text access proposal.
     ///
     /// More information:
     ///  - [ECMAScript reference][spec]
+    ///  - [Proposal reference][proposal]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-internalizejsonproperty
+    /// [proposal]: https://tc39.es/proposal-json-parse-with-source/#sec-internalizejsonproperty
     fn internalize_json_property(
         holder: &JsObject,
         name: JsString,
         reviver: &JsObject,
+        parse_record: Option<&ParseRecord>,
         context: &mut Context,
     ) -> JsResult<JsValue> {
         // 1. Let val be ? Get(holder, name).
         let val = holder.get(name.clone(), context)?;
 
-        // 2. If Type(val) is Object, then
+        // 2. Let context be OrdinaryObjectCreate(%Object.prototype%).
+        let reviver_context = context.construct_object();
+
+        // 3. If parseRecord is a JSON Parse Record and SameValue(parseRecord.[[Value]], val) is true, then
+        //     a. If val is not an Object, then
+        //         i. Perform ! CreateDataPropertyOrThrow(context, "source", CreateSourceText(parseRecord)).
+        //     b. Let elementRecords be parseRecord.[[Elements]].
+        //     c. Let entryRecords be parseRecord.[[Entries]].
+        // 4. Else,
+        //     a. Let elementRecords be a new empty List.
+        //     b. Let entryRecords be a new empty List.
+        let parse_record = parse_record.filter(|record| JsValue::same_value(&record.value, &val));
+        if let Some(source) = parse_record.and_then(|record| record.source.clone()) {
+            reviver_context
+                .create_data_property_or_throw("source", source, context)
+                .expect("CreateDataPropertyOrThrow should never throw here");
+        }
+
+        // 5. If Type(val) is Object, then
         if let Some(obj) = val.as_object() {
             // a. Let isArray be ? IsArray(val).
             // b. If isArray is true, then
             if obj.is_array() {
-                // i. Let I be 0.
-                // ii. Let len be ? LengthOfArrayLike(val).
-                // iii. Repeat, while I < len,
+                // i. Let elementRecordsLen be the number of elements in elementRecords.
+                // ii. Let I be 0.
+                // iii. Let len be ? LengthOfArrayLike(val).
+                // iv. Repeat, while I < len,
                 let len = obj.length_of_array_like(context)? as i64;
                 for i in 0..len {
                     // 1. Let prop be ! ToString(𝔽(I)).
-                    // 2. Let newElement be ? InternalizeJSONProperty(val, prop, reviver).
+                    // 2. If I < elementRecordsLen, let elementRecord be elementRecords[I]. Otherwise, let elementRecord be empty.
+                    let element_record =
+                        parse_record.and_then(|record| record.elements.get(i as usize));
+
+                    // 3. Let newElement be ? InternalizeJSONProperty(val, prop, reviver, elementRecord).
                     let new_element = Self::internalize_json_property(
                         obj,
                         i.to_string().into(),
                         reviver,
+                        element_record,
                         context,
                     )?;
 
-                    // 3. If newElement is undefined, then
+                    // 4. If newElement is undefined, then
                     if new_element.is_undefined() {
                         // a. Perform ? val.[[Delete]](prop).
                         obj.__delete__(&i.into(), context)?;
                     }
-                    // 4. Else,
+                    // 5. Else,
                     else {
                         // a. Perform ? CreateDataProperty(val, prop, newElement).
                         obj.create_data_property(i, new_element, context)?;
@@ -172,16 +204,24 @@ impl Json {
                     // This is safe, because EnumerableOwnPropertyNames with 'key' type only returns strings.
                     let p = p.as_string().unwrap();
 
-                    // 1. Let newElement be ? InternalizeJSONProperty(val, P, reviver).
-                    let new_element =
-                        Self::internalize_json_property(obj, p.clone(), reviver, context)?;
+                    // 1. Let entryRecord be the element of entryRecords whose [[Key]] field is P. If there is no such element, let entryRecord be empty.
+                    let entry_record = parse_record.and_then(|record| record.entries.get(p));
+
+                    // 2. Let newElement be ? InternalizeJSONProperty(val, P, reviver, entryRecord).
+                    let new_element = Self::internalize_json_property(
+                        obj,
+                        p.clone(),
+                        reviver,
+                        entry_record,
+                        context,
+                    )?;
 
-                    // 2. If newElement is undefined, then
+                    // 3. If newElement is undefined, then
                     if new_element.is_undefined() {
                         // a. Perform ? val.[[Delete]](P).
                         obj.__delete__(&p.clone().into(), context)?;
                     }
-                    // 3. Else,
+                    // 4. Else,
                     else {
                         // a. Perform ? CreateDataProperty(val, P, newElement).
                         obj.create_data_property(p.as_str(), new_element, context)?;
@@ -190,8 +230,12 @@ impl Json {
             }
         }
 
-        // 3. Return ? Call(reviver, holder, « name, val »).
-        reviver.call(&holder.clone().into(), &[name.into(), val], context)
+        // 6. Return ? Call(reviver, holder, « name, val, context »).
+        reviver.call(
+            &holder.clone().into(),
+            &[name.into(), val, reviver_context.into()],
+            context,
+        )
     }
 
     /// `JSON.stringify( value[, replacer[, space]] )`
@@ -0,0 +1,444 @@
+//! This module implements the parser used by `JSON.parse`.
+//!
+//! The parser builds the resulting `JsValue` directly from the JSON text, instead of evaluating it
+//! as an ECMAScript script. It can also record the source text of every primitive value, so that
+//! revivers can access it through the `context` argument of the
+//! [`JSON.parse` source text access][proposal] proposal.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [JSON specification][json]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-json.parse
+//! [json]: https://www.ecma-international.org/publications-and-standards/standards/ecma-404/
+//! [proposal]: https://tc39.es/proposal-json-parse-with-source/
+
+use crate::{
+    builtins::Array, object::JsObject, syntax::lexer::UTF16CodeUnitsBuffer, Context, JsResult,
+    JsString, JsValue,
+};
+use rustc_hash::FxHashMap;
+
+/// The maximum nesting depth of arrays and objects in a JSON text.
+///
+/// The parser itself doesn't recurse, but the values it produces are traversed recursively by
+/// revivers and by the garbage collector.
+const MAX_DEPTH: usize = 10_000;
+
+/// A JSON Parse Record, which associates a parsed value with its source text.
+///
+/// More information:
+///  - [Proposal reference][spec]
+///
+/// [spec]: https://tc39.es/proposal-json-parse-with-source/#sec-json-parse-record
+#[derive(Debug, Default)]
+pub(super) struct ParseRecord {
+    /// The parsed value.
+    pub(super) value: JsValue,
+
+    /// The source text of the value, if it is a primitive.
+    pub(super) source: Option<JsString>,
+
+    /// The records of the elements, if the value is an array.
+    pub(super) elements: Vec<ParseRecord>,
+
+    /// The records of the properties, if the value is an object.
+    ///
+    /// If a key is duplicated, only the record of its last value is kept.
+    pub(super) entries: FxHashMap<JsString, ParseRecord>,
+}
+
+impl ParseRecord {
+    /// Creates a record for a primitive value.
+    fn primitive(value: JsValue, source: Option<&str>) -> Self {
+        Self {
+            value,
+            source: source.map(JsString::from),
+            ..Self::default()
+        }
+    }
+}
+
+/// An array or object that is currently being parsed.
+#[derive(Debug)]
+enum Container {
+    Array {
+        values: Vec<JsValue>,
+        elements: Vec<ParseRecord>,
+    },
+    Object {
+        object: JsObject,
+        key: JsString,
+        entries: FxHashMap<JsString, ParseRecord>,
+    },
+}
+
+/// A parser for JSON texts, as defined in [ECMA-404][json].
+///
+/// [json]: https://www.ecma-international.org/publications-and-standards/standards/ecma-404/
+#[derive(Debug)]
+pub(super) struct JsonParser<'a> {
+    source: &'a str,
+    position: usize,
+    track_source: bool,
+}
+
+impl<'a> JsonParser<'a> {
+    /// Creates a new parser for the given JSON text.
+    ///
+    /// If `track_source` is `true`, the returned record contains the source text of every
+    /// primitive value and the records of all nested values.
+    pub(super) fn new(source: &'a str, track_source: bool) -> Self {
+        Self {
+            source,
+            position: 0,
+            track_source,
+        }
+    }
+
+    /// Parses the whole JSON text, throwing a `SyntaxError` if it is not valid.
+    pub(super) fn parse(mut self, context: &mut Context) -> JsResult<ParseRecord> {
+        let mut stack: Vec<Container> = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+            let start = self.position;
+
+            // Parse the next value. Arrays and objects that aren't empty push a new container and
+            // continue with their first element instead.
+            let mut record = match self.peek() {
+                Some(b'[') => {
+                    self.position += 1;
+                    self.skip_whitespace();
+                    if self.eat(b']') {
+                        ParseRecord {
+                            value: Array::create_array_from_list(Vec::new(), context).into(),
+                            ..ParseRecord::default()
+                        }
+                    } else {
+                        self.check_depth(&stack, context)?;
+                        stack.push(Container::Array {
+                            values: Vec::new(),
+                            elements: Vec::new(),
+                        });
+                        continue;
+                    }
+                }
+                Some(b'{') => {
+                    self.position += 1;
+                    self.skip_whitespace();
+                    if self.eat(b'}') {
+                        ParseRecord {
+                            value: context.construct_object().into(),
+                            ..ParseRecord::default()
+                        }
+                    } else {
+                        self.check_depth(&stack, context)?;
+                        let key = self.parse_key(context)?;
+                        stack.push(Container::Object {
+                            object: context.construct_object(),
+                            key,
+                            entries: FxHashMap::default(),
+                        });
+                        continue;
+                    }
+                }
+                Some(b'"') => {
+                    let string = self.parse_string(context)?;
+                    ParseRecord::primitive(string.into(), self.source_text(start))
+                }
+                Some(b'-' | b'0'..=b'9') => {
+                    let number = self.parse_number(context)?;
+                    ParseRecord::primitive(number, self.source_text(start))
+                }
+                Some(b't') => {
+                    self.expect_literal("true", context)?;
+                    ParseRecord::primitive(true.into(), self.source_text(start))
+                }
+                Some(b'f') => {
+                    self.expect_literal("false", context)?;
+                    ParseRecord::primitive(false.into(), self.source_text(start))
+                }
+                Some(b'n') => {
+                    self.expect_literal("null", context)?;
+                    ParseRecord::primitive(JsValue::null(), self.source_text(start))
+                }
+                _ => return Err(self.unexpected(context)),
+            };
+
+            // Add the value to the enclosing containers, completing every container that ends
+            // after it.
+            loop {
+                let container = if let Some(container) = stack.last_mut() {
+                    container
+                } else {
+                    self.skip_whitespace();
+                    if self.position != self.source.len() {
+                        return Err(self.unexpected(context));
+                    }
+                    return Ok(record);
+                };
+
+                match container {
+                    Container::Array { values, elements } => {
+                        values.push(record.value.clone());
+                        if self.track_source {
+                            elements.push(record);
+                        }
+
+                        self.skip_whitespace();
+                        if self.eat(b',') {
+                            break;
+                        }
+                        if !self.eat(b']') {
+                            return Err(self.unexpected(context));
+                        }
+                    }
+                    Container::Object {
+                        object,
+                        key,
+                        entries,
+                    } => {
+                        // NOTE: `__proto__` is defined as an own property, and duplicate keys
+                        // overwrite the previous value while keeping its position.
+                        object
+                            .create_data_property_or_throw(
+                                key.clone(),
+                                record.value.clone(),
+                                context,
+                            )
+                            .expect("CreateDataPropertyOrThrow should never throw here");
+                        if self.track_source {
+                            entries.insert(key.clone(), record);
+                        }
+
+                        self.skip_whitespace();
+                        if self.eat(b',') {
+                            self.skip_whitespace();
+                            *key = self.parse_key(context)?;
+                            break;
+                        }
+                        if !self.eat(b'}') {
+                            return Err(self.unexpected(context));
+                        }
+                    }
+                }
+
+                record = match stack.pop().expect("the container was just accessed") {
+                    Container::Array { values, elements } => ParseRecord {
+                        value: Array::create_array_from_list(values, context).into(),
+                        elements,
+                        ..ParseRecord::default()
+                    },
+                    Container::Object {
+                        object, entries, ..
+                    } => ParseRecord {
+                        value: object.into(),
+                        entries,
+                        ..ParseRecord::default()
+                    },
+                };
+            }
+        }
+    }
+
+    /// Gets the next byte of the JSON text without consuming it.
+    fn peek(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.position).copied()
+    }
+
+    /// Consumes the next byte if it is `byte`, returning whether it was consumed.
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skips the JSON whitespace: tabs, line feeds, carriage returns and spaces.
+    fn skip_whitespace(&mut self) {
+        while let Some(b'\t' | b'\n' | b'\r' | b' ') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    /// Gets the source text from `start` to the current position, if the source is tracked.
+    fn source_text(&self, start: usize) -> Option<&'a str> {
+        self.track_source
+            .then(|| &self.source[start..self.position])
+    }
+
+    /// Throws a `SyntaxError` if another array or object can't be nested in `stack`.
+    fn check_depth(&self, stack: &[Container], context: &mut Context) -> JsResult<()> {
+        if stack.len() >= MAX_DEPTH {
+            return Err(context.construct_syntax_error(format!(
+                "JSON.parse: too many nested arrays and objects at position {}",
+                self.position
+            )));
+        }
+        Ok(())
+    }
+
+    /// Creates a `SyntaxError` for an unexpected character or end of input at the current
+    /// position.
+    fn unexpected(&self, context: &mut Context) -> JsValue {
+        match self.source[self.position..].chars().next() {
+            Some(ch) => context.construct_syntax_error(format!(
+                "JSON.parse: unexpected character {:?} at position {}",
+                ch, self.position
+            )),
+            None => context.construct_syntax_error("JSON.parse: unexpected end of JSON input"),
+        }
+    }
+
+    /// Consumes `literal`, which starts at the current position.
+    fn expect_literal(&mut self, literal: &str, context: &mut Context) -> JsResult<()> {
+        for &byte in literal.as_bytes() {
+            if !self.eat(byte) {
+                return Err(self.unexpected(context));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the key of an object member and the colon that follows it.
+    fn parse_key(&mut self, context: &mut Context) -> JsResult<JsString> {
+        if self.peek() != Some(b'"') {
+            return Err(self.unexpected(context));
+        }
+        let key = self.parse_string(context)?;
+
+        self.skip_whitespace();
+        if !self.eat(b':') {
+            return Err(self.unexpected(context));
+        }
+
+        Ok(key)
+    }
+
+    /// Parses a JSON string, starting at its opening quote.
+    fn parse_string(&mut self, context: &mut Context) -> JsResult<JsString> {
+        // Skip the opening quote.
+        self.position += 1;
+        let start = self.position;
+
+        // Fast path for strings without escape sequences.
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    let string = JsString::from(&self.source[start..self.position]);
+                    self.position += 1;
+                    return Ok(string);
+                }
+                Some(b'\\') => break,
+                Some(0x00..=0x1F) | None => return Err(self.unexpected(context)),
+                Some(_) => self.position += 1,
+            }
+        }
+
+        let mut buf: Vec<u16> = self.source[start..self.position].encode_utf16().collect();
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    self.position += 1;
+                    return Ok(buf.to_string_lossy().into());
+                }
+                Some(b'\\') => {
+                    self.position += 1;
+                    let code_unit = match self.peek() {
+                        Some(b'"') => u16::from(b'"'),
+                        Some(b'\\') => u16::from(b'\\'),
+                        Some(b'/') => u16::from(b'/'),
+                        Some(b'b') => 0x0008,
+                        Some(b'f') => 0x000C,
+                        Some(b'n') => u16::from(b'\n'),
+                        Some(b'r') => u16::from(b'\r'),
+                        Some(b't') => u16::from(b'\t'),
+                        Some(b'u') => {
+                            self.position += 1;
+                            let digits = self
+                                .source
+                                .get(self.position..self.position + 4)
+                                .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+                                .ok_or_else(|| {
+                                    context.construct_syntax_error(format!(
+                                        "JSON.parse: invalid unicode escape at position {}",
+                                        self.position
+                                    ))
+                                })?;
+                            self.position += 3;
+                            u16::from_str_radix(digits, 16).expect("checked hex digits")
+                        }
+                        _ => return Err(self.unexpected(context)),
+                    };
+                    self.position += 1;
+                    buf.push(code_unit);
+                }
+                Some(0x00..=0x1F) | None => return Err(self.unexpected(context)),
+                Some(_) => {
+                    let ch = self.source[self.position..]
+                        .chars()
+                        .next()
+                        .expect("checked that there is a character");
+                    self.position += ch.len_utf8();
+                    buf.push_code_point(u32::from(ch));
+                }
+            }
+        }
+    }
+
+    /// Parses a JSON number.
+    fn parse_number(&mut self, context: &mut Context) -> JsResult<JsValue> {
+        let start = self.position;
+        let mut is_integer = true;
+
+        self.eat(b'-');
+
+        // The integer part can't have leading zeros.
+        match self.peek() {
+            Some(b'0') => self.position += 1,
+            Some(b'1'..=b'9') => self.skip_digits(),
+            _ => return Err(self.unexpected(context)),
+        }
+
+        if self.eat(b'.') {
+            is_integer = false;
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.unexpected(context));
+            }
+            self.skip_digits();
+        }
+
+        if let Some(b'e' | b'E') = self.peek() {
+            is_integer = false;
+            self.position += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.position += 1;
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.unexpected(context));
+            }
+            self.skip_digits();
+        }
+
+        let text = &self.source[start..self.position];
+        if is_integer && text != "-0" {
+            if let Ok(integer) = text.parse::<i32>() {
+                return Ok(integer.into());
+            }
+        }
+
+        Ok(fast_float::parse::<f64, _>(text)
+            .expect("checked that the text is a valid number")
+            .into())
+    }
+
+    /// Skips a sequence of decimal digits.
+    fn skip_digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+    }
+}
@@ -460,3 +460,128 @@ fn json_parse_with_no_args_throws_syntax_error() {
     let result = forward(&mut context, "JSON.parse();");
     assert!(result.contains("SyntaxError"));
 }
+
+#[test]
+fn json_parse_proto_and_duplicate_keys() {
+    let mut context = Context::new();
+
+    forward(
+        &mut context,
+        r#"var obj = JSON.parse('{"__proto__": 1, "a": 1, "a": 2}');"#,
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "Object.getPrototypeOf(obj) === Object.prototype"
+        ),
+        "true"
+    );
+    assert_eq!(forward(&mut context, "obj.__proto__"), "1");
+    assert_eq!(forward(&mut context, "obj.a"), "2");
+    assert_eq!(forward(&mut context, "Object.keys(obj).length"), "2");
+}
+
+#[test]
+fn json_parse_strings_and_numbers() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(&mut context, r#"JSON.parse('"\\ud83d\\ude00\\n\\u0041"')"#),
+        "\"😀\nA\""
+    );
+    assert_eq!(
+        forward(&mut context, r#"1 / JSON.parse("-0")"#),
+        "-Infinity"
+    );
+    assert_eq!(forward(&mut context, r#"JSON.parse("1e3")"#), "1000");
+    assert_eq!(forward(&mut context, r#"JSON.parse("-1.5E-1")"#), "-0.15");
+    assert_eq!(
+        forward(&mut context, r#"JSON.parse("12345678901234567890")"#),
+        "12345678901234567000"
+    );
+}
+
+#[test]
+fn json_parse_invalid_text() {
+    let mut context = Context::new();
+
+    for text in [
+        r#"'[1, 2,]'"#,
+        r#"'{"a": 1,}'"#,
+        r#"'01'"#,
+        r#"'1.'"#,
+        r#"'.5'"#,
+        r#"'+1'"#,
+        r#"'"\t"'"#,
+        r#"'"\\x41"'"#,
+        r#"'{a: 1}'"#,
+        r#""'a'""#,
+        r#"'[1] 2'"#,
+        r#"'undefined'"#,
+        r#"'"abc'"#,
+        r#"''"#,
+    ] {
+        assert_eq!(
+            forward(
+                &mut context,
+                &format!("try {{ JSON.parse({}) }} catch (e) {{ e.name }}", text)
+            ),
+            "\"SyntaxError\"",
+            "JSON.parse({}) should throw",
+            text
+        );
+    }
+}
+
+#[test]
+fn json_parse_deep_nesting() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(
+            &mut context,
+            r#"JSON.parse("[".repeat(5000) + "]".repeat(5000)).length"#
+        ),
+        "1"
+    );
+}
+
+#[test]
+fn json_parse_reviver_source_text() {
+    let mut context = Context::new();
+
+    let init = r#"
+        var sources = [];
+        var result = JSON.parse('{"a": [1.0, "x\\u0041", true], "b": -0e5, "c": {}}', function (key, value, context) {
+            sources.push(key + "=" + context.source);
+            return value;
+        });
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(
+        forward(&mut context, "sources.join(', ')"),
+        r#""0=1.0, 1="x\u0041", 2=true, a=undefined, b=-0e5, c=undefined, =undefined""#
+    );
+
+    let init = r#"
+        var big = JSON.parse('[12345678901234567890]', function (key, value, context) {
+            return typeof value === "number" ? context.source : value;
+        });
+        "#;
+    forward(&mut context, init);
+    assert_eq!(forward(&mut context, "big[0]"), "\"12345678901234567890\"");
+
+    let init = r#"
+        var modified = [];
+        JSON.parse('[1, 2]', function (key, value, context) {
+            if (key === "0") {
+                this[1] = 3;
+            }
+            modified.push(context.source);
+            return value;
+        });
+        "#;
+    forward(&mut context, init);
+    assert_eq!(forward(&mut context, "modified.join()"), "\"1,,\"");
+}
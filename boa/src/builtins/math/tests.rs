@@ -727,3 +727,18 @@ fn trunc() {
     assert_eq!(a.to_number(&mut context).unwrap(), 13_f64);
     assert_eq!(b.to_number(&mut context).unwrap(), 0_f64);
 }
+
+#[test]
+fn random_seeded() {
+    let mut first = Context::new();
+    first.seed_random(7);
+    let mut second = Context::new();
+    second.seed_random(7);
+
+    let sequence = "[Math.random(), Math.random(), Math.random()].join()";
+    let expected = forward(&mut first, sequence);
+    assert_eq!(forward(&mut second, sequence), expected);
+
+    second.seed_random(8);
+    assert_ne!(forward(&mut second, sequence), expected);
+}
@@ -2,6 +2,7 @@
 
 pub mod array;
 pub mod array_buffer;
+pub mod atomics;
 pub mod bigint;
 pub mod boolean;
 #[cfg(feature = "console")]
@@ -25,6 +26,8 @@ pub mod proxy;
 pub mod reflect;
 pub mod regexp;
 pub mod set;
+pub mod shadow_realm;
+pub mod shared_array_buffer;
 pub mod string;
 pub mod symbol;
 pub mod typed_array;
@@ -32,11 +35,15 @@ pub mod undefined;
 
 pub(crate) use self::{
     array::{array_iterator::ArrayIterator, Array},
+    atomics::Atomics,
     bigint::BigInt,
     boolean::Boolean,
     dataview::DataView,
     date::Date,
-    error::{Error, EvalError, RangeError, ReferenceError, SyntaxError, TypeError, UriError},
+    error::{
+        AggregateError, Error, EvalError, RangeError, ReferenceError, SyntaxError, TypeError,
+        UriError,
+    },
     function::BuiltInFunctionObject,
     global_this::GlobalThis,
     infinity::Infinity,
@@ -54,6 +61,8 @@ pub(crate) use self::{
     regexp::RegExp,
     set::set_iterator::SetIterator,
     set::Set,
+    shadow_realm::ShadowRealm,
+    shared_array_buffer::SharedArrayBuffer,
     string::String,
     symbol::Symbol,
     typed_array::{
@@ -131,6 +140,8 @@ pub fn init(context: &mut Context) {
         Array,
         Proxy,
         ArrayBuffer,
+        SharedArrayBuffer,
+        Atomics,
         BigInt,
         Boolean,
         Date,
@@ -159,7 +170,9 @@ pub fn init(context: &mut Context) {
         SyntaxError,
         EvalError,
         UriError,
-        Reflect
+        AggregateError,
+        Reflect,
+        ShadowRealm
     };
 
     #[cfg(feature = "console")]
@@ -0,0 +1,422 @@
+//! This module implements the global `ShadowRealm` object.
+//!
+//! A `ShadowRealm` evaluates code in a new realm, with its own global object and intrinsic
+//! objects. Only primitive values and callable objects cross the boundary between the realms,
+//! and callable objects are wrapped in a function of the realm that receives them.
+//!
+//! More information:
+//!  - [TC39 proposal][spec]
+//!
+//! [spec]: https://tc39.es/proposal-shadowrealm/
+
+use crate::{
+    builtins::{function::NativeFunction, BuiltIn, JsArgs},
+    context::StandardObjects,
+    object::{
+        internal_methods::get_prototype_from_constructor, ConstructorBuilder, FunctionBuilder,
+        JsObject, ObjectData,
+    },
+    property::{Attribute, PropertyDescriptor},
+    realm::Realm,
+    symbol::WellKnownSymbols,
+    syntax::Parser,
+    value::IntegerOrInfinity,
+    BoaProfiler, Context, JsResult, JsValue,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The `ShadowRealm` builtin.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ShadowRealm;
+
+impl BuiltIn for ShadowRealm {
+    const NAME: &'static str = "ShadowRealm";
+
+    const ATTRIBUTE: Attribute = Attribute::WRITABLE
+        .union(Attribute::NON_ENUMERABLE)
+        .union(Attribute::CONFIGURABLE);
+
+    fn init(context: &mut Context) -> JsValue {
+        let _timer = BoaProfiler::global().start_event(Self::NAME, "init");
+
+        ConstructorBuilder::with_standard_object(
+            context,
+            Self::constructor,
+            context.standard_objects().shadow_realm_object().clone(),
+        )
+        .name(Self::NAME)
+        .length(Self::LENGTH)
+        .method(Self::evaluate, "evaluate", 1)
+        .method(Self::import_value, "importValue", 2)
+        .property(
+            WellKnownSymbols::to_string_tag(),
+            Self::NAME,
+            Attribute::READONLY | Attribute::NON_ENUMERABLE | Attribute::CONFIGURABLE,
+        )
+        .build()
+        .into()
+    }
+}
+
+impl ShadowRealm {
+    pub(crate) const LENGTH: usize = 0;
+
+    /// `ShadowRealm ( )`
+    ///
+    /// More information:
+    ///  - [TC39 proposal][spec]
+    ///
+    /// [spec]: https://tc39.es/proposal-shadowrealm/#sec-shadowrealm
+    fn constructor(
+        new_target: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. If NewTarget is undefined, throw a TypeError exception.
+        if new_target.is_undefined() {
+            return context.throw_type_error("ShadowRealm constructor requires 'new'");
+        }
+
+        // 2. Let O be ? OrdinaryCreateFromConstructor(NewTarget, "%ShadowRealm.prototype%", « [[ShadowRealm]], [[ExecutionContext]] »).
+        let prototype = get_prototype_from_constructor(
+            new_target,
+            StandardObjects::shadow_realm_object,
+            context,
+        )?;
+
+        // 3. Let realmRec be CreateRealm().
+        // 4. Set O.[[ShadowRealm]] to realmRec.
+        // 5-10. Create the execution context of the realm.
+        // 11. Perform ? SetDefaultGlobalBindings(O.[[ShadowRealm]]).
+        let realm = context.create_realm();
+
+        // 12. Perform ? HostInitializeShadowRealm(O.[[ShadowRealm]]).
+        // 13. Return O.
+        Ok(JsObject::from_proto_and_data(prototype, ObjectData::shadow_realm(realm)).into())
+    }
+
+    /// `ShadowRealm.prototype.evaluate ( sourceText )`
+    ///
+    /// Evaluates a script in the realm of the `ShadowRealm`.
+    ///
+    /// More information:
+    ///  - [TC39 proposal][spec]
+    ///
+    /// [spec]: https://tc39.es/proposal-shadowrealm/#sec-shadowrealm.prototype.evaluate
+    fn evaluate(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let O be this value.
+        // 2. Perform ? ValidateShadowRealmObject(O).
+        let eval_realm = Self::validate(this, context)?;
+
+        // 3. If Type(sourceText) is not String, throw a TypeError exception.
+        let source_text = match args.get_or_undefined(0) {
+            JsValue::String(source_text) => source_text.clone(),
+            _ => {
+                return context.throw_type_error(
+                    "ShadowRealm.prototype.evaluate: source text must be a string",
+                )
+            }
+        };
+
+        // 4. Let callerRealm be the current Realm Record.
+        let caller_realm = context.realm().clone();
+
+        // 5. Let evalRealm be O.[[ShadowRealm]].
+        // 6. Return ? PerformShadowRealmEval(sourceText, callerRealm, evalRealm).
+        perform_shadow_realm_eval(&source_text, &caller_realm, eval_realm, context)
+    }
+
+    /// `ShadowRealm.prototype.importValue ( specifier, exportName )`
+    ///
+    /// Modules are not supported, so the arguments are validated and a `TypeError` is thrown.
+    ///
+    /// More information:
+    ///  - [TC39 proposal][spec]
+    ///
+    /// [spec]: https://tc39.es/proposal-shadowrealm/#sec-shadowrealm.prototype.importvalue
+    fn import_value(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let O be this value.
+        // 2. Perform ? ValidateShadowRealmObject(O).
+        Self::validate(this, context)?;
+
+        // 3. Let specifierString be ? ToString(specifier).
+        args.get_or_undefined(0).to_string(context)?;
+
+        // 4. If Type(exportName) is not String, throw a TypeError exception.
+        if !args.get_or_undefined(1).is_string() {
+            return context.throw_type_error(
+                "ShadowRealm.prototype.importValue: export name must be a string",
+            );
+        }
+
+        // 5-8. Return ShadowRealmImportValue(specifierString, exportName, callerRealm, evalRealm, evalContext).
+        context.throw_type_error("ShadowRealm.prototype.importValue: modules are not supported")
+    }
+
+    /// Abstract operation `ValidateShadowRealmObject ( O )`
+    ///
+    /// Returns the realm of a `ShadowRealm` object.
+    ///
+    /// More information:
+    ///  - [TC39 proposal][spec]
+    ///
+    /// [spec]: https://tc39.es/proposal-shadowrealm/#sec-validateshadowrealmobject
+    fn validate(this: &JsValue, context: &mut Context) -> JsResult<Realm> {
+        // 1. Perform ? RequireInternalSlot(O, [[ShadowRealm]]).
+        // 2. Perform ? RequireInternalSlot(O, [[ExecutionContext]]).
+        this.as_object()
+            .and_then(|object| object.borrow().as_shadow_realm().cloned())
+            .ok_or_else(|| context.construct_type_error("`this` is not a ShadowRealm object"))
+    }
+}
+
+/// Abstract operation `PerformShadowRealmEval ( sourceText, callerRealm, evalRealm )`
+///
+/// More information:
+///  - [TC39 proposal][spec]
+///
+/// [spec]: https://tc39.es/proposal-shadowrealm/#sec-performshadowrealmeval
+fn perform_shadow_realm_eval(
+    source_text: &str,
+    caller_realm: &Realm,
+    eval_realm: Realm,
+    context: &mut Context,
+) -> JsResult<JsValue> {
+    // 1. Perform ? HostEnsureCanCompileStrings(callerRealm, evalRealm).
+    context.ensure_can_compile_strings(caller_realm, &eval_realm)?;
+
+    // 2-7. Let script be ParseText(sourceText, Script).
+    let script = Parser::new(source_text.as_bytes(), context.strict_scripts())
+        .parse_all()
+        // 8. If script is a List of errors, throw a SyntaxError exception.
+        .map_err(|e| context.construct_syntax_error(e.to_string()))?;
+
+    // 9-17. Let evalContext be a new ECMAScript code execution context, whose Realm is
+    // evalRealm, and push it onto the execution context stack.
+    context.enter_realm(eval_realm);
+    // The script runs in the global environment of the realm, even if code of the realm is
+    // running further down the stack.
+    let global_environment = context.environment.global_only();
+    let environment = std::mem::replace(&mut context.environment, global_environment);
+
+    // 18. Let result be Completion(EvalDeclarationInstantiation(...)), followed by the evaluation
+    // of the script body.
+    let result = context.execute(&script);
+
+    // 19. Suspend evalContext and remove it from the execution context stack.
+    // 20. Resume the context that is now on the top of the execution context stack as the
+    // running execution context.
+    context.environment = environment;
+    context.enter_realm(caller_realm.clone());
+
+    match result {
+        // 22. Return ? GetWrappedValue(callerRealm, result.[[Value]]).
+        Ok(value) => get_wrapped_value(caller_realm, &value, context),
+        // Terminations of the script are not catchable, and are passed through unchanged.
+        Err(error) if error.as_termination().is_some() => Err(error),
+        // 21. If result.[[Type]] is not normal, throw a TypeError exception.
+        Err(error) => context.throw_type_error(format!(
+            "ShadowRealm.prototype.evaluate: the evaluated code threw {}",
+            error.display()
+        )),
+    }
+}
+
+/// Abstract operation `GetWrappedValue ( callerRealm, value )`
+///
+/// Primitive values are returned as they are, and callable objects are wrapped in a function of
+/// `caller_realm`.
+///
+/// More information:
+///  - [TC39 proposal][spec]
+///
+/// [spec]: https://tc39.es/proposal-shadowrealm/#sec-getwrappedvalue
+fn get_wrapped_value(
+    caller_realm: &Realm,
+    value: &JsValue,
+    context: &mut Context,
+) -> JsResult<JsValue> {
+    // 1. If Type(value) is Object, then
+    if let Some(object) = value.as_object() {
+        // a. If IsCallable(value) is false, throw a TypeError exception.
+        if !object.is_callable() {
+            return context
+                .throw_type_error("only primitives and callable objects can cross a ShadowRealm");
+        }
+
+        // b. Return ? WrappedFunctionCreate(callerRealm, value).
+        return wrapped_function_create(caller_realm, object.clone(), context).map(Into::into);
+    }
+
+    // 2. Return value.
+    Ok(value.clone())
+}
+
+/// Abstract operation `WrappedFunctionCreate ( callerRealm, Target )`
+///
+/// More information:
+///  - [TC39 proposal][spec]
+///
+/// [spec]: https://tc39.es/proposal-shadowrealm/#sec-wrappedfunctioncreate
+fn wrapped_function_create(
+    caller_realm: &Realm,
+    target: JsObject,
+    context: &mut Context,
+) -> JsResult<JsObject> {
+    // 1. Let internalSlotsList be the internal slots listed in Table 2, plus [[Prototype]] and [[Extensible]].
+    // 2. Let wrapped be MakeBasicObject(internalSlotsList).
+    // 3. Set wrapped.[[Prototype]] to callerRealm.[[Intrinsics]].[[%Function.prototype%]].
+    // 4. Set wrapped.[[Call]] as described in 2.1.
+    // 5. Set wrapped.[[WrappedTargetFunction]] to Target.
+    // 6. Set wrapped.[[Realm]] to callerRealm.
+    let previous_realm = context.enter_realm(caller_realm.clone());
+    let wrapped = FunctionBuilder::native_function(
+        context,
+        NativeFunction::from_copy_closure_with_shared_captures(
+            wrapped_function_call,
+            target.clone(),
+        ),
+    )
+    .build();
+
+    // 7. Let result be CopyNameAndLength(wrapped, Target).
+    let result = copy_name_and_length(&wrapped, &target, context);
+    context.enter_realm(previous_realm);
+
+    match result {
+        Ok(()) => Ok(wrapped),
+        Err(error) if error.as_termination().is_some() => Err(error),
+        // 8. If result is an Abrupt Completion, throw a TypeError exception.
+        Err(_) => {
+            Err(context
+                .construct_type_error("cannot copy the name and length of a wrapped function"))
+        }
+    }
+}
+
+/// The `[[Call]]` internal method of a wrapped function exotic object.
+///
+/// The wrapped function runs in its own realm, the realm it was created for.
+///
+/// More information:
+///  - [TC39 proposal][spec]
+///
+/// [spec]: https://tc39.es/proposal-shadowrealm/#sec-wrapped-function-exotic-objects-call-thisargument-argumentslist
+fn wrapped_function_call(
+    this: &JsValue,
+    args: &[JsValue],
+    target: &JsObject,
+    context: &mut Context,
+) -> JsResult<JsValue> {
+    // OrdinaryWrappedFunctionCall ( F, thisArgument, argumentsList )
+    // 1. Let target be F.[[WrappedTargetFunction]].
+    // 2. Assert: IsCallable(target) is true.
+    // 3. Let callerRealm be F.[[Realm]].
+    let caller_realm = context.realm().clone();
+
+    // 4. NOTE: Any exception objects produced after this point are associated with callerRealm.
+    // 5. Let targetRealm be ? GetFunctionRealm(target).
+    let target_realm = target.get_function_realm(context)?;
+
+    // 6. Let wrappedArgs be a new empty List.
+    // 7. For each element arg of argumentsList, do
+    //     a. Let wrappedValue be ? GetWrappedValue(targetRealm, arg).
+    //     b. Append wrappedValue to wrappedArgs.
+    let wrapped_args = args
+        .iter()
+        .map(|arg| get_wrapped_value(&target_realm, arg, context))
+        .collect::<JsResult<Vec<_>>>()?;
+
+    // 8. Let wrappedThisArgument to ? GetWrappedValue(targetRealm, thisArgument).
+    // Calls that are not method calls pass the global object as the `this` value instead of
+    // `undefined`, it is not wrapped.
+    let wrapped_this = match this.as_object() {
+        Some(this) if JsObject::equals(this, &caller_realm.global_object()) => JsValue::undefined(),
+        _ => get_wrapped_value(&target_realm, this, context)?,
+    };
+
+    // 9. Let result be the Completion Record of Call(target, wrappedThisArgument, wrappedArgs).
+    match target.call(&wrapped_this, &wrapped_args, context) {
+        // 10. If result.[[Type]] is normal or result.[[Type]] is return, then
+        //     a. Return ? GetWrappedValue(callerRealm, result.[[Value]]).
+        Ok(result) => get_wrapped_value(&caller_realm, &result, context),
+        Err(error) if error.as_termination().is_some() => Err(error),
+        // 11. Else,
+        //     a. Throw a TypeError exception.
+        Err(error) => {
+            context.throw_type_error(format!("a wrapped function threw {}", error.display()))
+        }
+    }
+}
+
+/// Abstract operation `CopyNameAndLength ( F, Target )`
+///
+/// More information:
+///  - [TC39 proposal][spec]
+///
+/// [spec]: https://tc39.es/proposal-shadowrealm/#sec-copynameandlength
+fn copy_name_and_length(
+    function: &JsObject,
+    target: &JsObject,
+    context: &mut Context,
+) -> JsResult<()> {
+    // 1. If argCount is undefined, then set argCount to 0.
+    // 2. Let L be 0.
+    let mut length = JsValue::new(0);
+
+    // 3. Let targetHasLength be ? HasOwnProperty(Target, "length").
+    // 4. If targetHasLength is true, then
+    if target.has_own_property("length", context)? {
+        // a. Let targetLen be ? Get(Target, "length").
+        let target_len = target.get("length", context)?;
+        // b. If Type(targetLen) is Number, then
+        if target_len.is_number() {
+            match target_len
+                .to_integer_or_infinity(context)
+                .expect("to_integer_or_infinity cannot fail for a number")
+            {
+                // i. If targetLen is +∞𝔽, set L to +∞.
+                IntegerOrInfinity::PositiveInfinity => length = f64::INFINITY.into(),
+                // ii. Else if targetLen is -∞𝔽, set L to 0.
+                IntegerOrInfinity::NegativeInfinity => {}
+                // iii. Else,
+                //     1. Let targetLenAsInt be ! ToIntegerOrInfinity(targetLen).
+                //     2. Assert: targetLenAsInt is finite.
+                //     3. Set L to max(targetLenAsInt - argCount, 0).
+                IntegerOrInfinity::Integer(target_len) => length = target_len.max(0).into(),
+            }
+        }
+    }
+
+    // 5. Perform ! SetFunctionLength(F, L).
+    function.define_property_or_throw(
+        "length",
+        PropertyDescriptor::builder()
+            .value(length)
+            .writable(false)
+            .enumerable(false)
+            .configurable(true),
+        context,
+    )?;
+
+    // 6. Let targetName be ? Get(Target, "name").
+    let target_name = target.get("name", context)?;
+
+    // 7. If Type(targetName) is not String, set targetName to the empty String.
+    let target_name = target_name.as_string().cloned().unwrap_or_default();
+
+    // 8. Perform SetFunctionName(F, targetName, prefix).
+    function.define_property_or_throw(
+        "name",
+        PropertyDescriptor::builder()
+            .value(target_name)
+            .writable(false)
+            .enumerable(false)
+            .configurable(true),
+        context,
+    )?;
+
+    Ok(())
+}
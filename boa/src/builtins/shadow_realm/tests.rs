@@ -0,0 +1,152 @@
+use crate::{forward, Context};
+
+#[test]
+fn evaluate() {
+    let mut context = Context::new();
+    let init = r#"
+        var realm = new ShadowRealm();
+        var x = "outer";
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "realm.evaluate('1 + 2')"), "3");
+    assert_eq!(
+        forward(&mut context, "realm.evaluate('var x = \"inner\"; x')"),
+        "\"inner\""
+    );
+    assert_eq!(forward(&mut context, "x"), "\"outer\"");
+    assert_eq!(forward(&mut context, "realm.evaluate('x')"), "\"inner\"");
+    assert_eq!(
+        forward(&mut context, "realm.evaluate('Array') === undefined"),
+        "false"
+    );
+    assert_eq!(
+        forward(&mut context, r#"realm.evaluate("'realm' in globalThis")"#),
+        "false"
+    );
+}
+
+#[test]
+fn evaluate_errors() {
+    let mut context = Context::new();
+    forward(&mut context, "var realm = new ShadowRealm();");
+
+    let error_name = |source: &str| format!("try {{ {} }} catch (e) {{ e.name }}", source);
+
+    assert_eq!(
+        forward(&mut context, &error_name("realm.evaluate('({})')")),
+        "\"TypeError\""
+    );
+    assert_eq!(
+        forward(&mut context, &error_name("realm.evaluate('throw 1')")),
+        "\"TypeError\""
+    );
+    assert_eq!(
+        forward(&mut context, &error_name("realm.evaluate('1 +')")),
+        "\"SyntaxError\""
+    );
+    assert_eq!(
+        forward(&mut context, &error_name("realm.evaluate(1)")),
+        "\"TypeError\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            &error_name("ShadowRealm.prototype.evaluate.call({}, '1')")
+        ),
+        "\"TypeError\""
+    );
+    assert_eq!(
+        forward(&mut context, &error_name("ShadowRealm()")),
+        "\"TypeError\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            &error_name("realm.importValue('./a.js', 'x')")
+        ),
+        "\"TypeError\""
+    );
+}
+
+#[test]
+fn wrapped_functions() {
+    let mut context = Context::new();
+    let init = r#"
+        var realm = new ShadowRealm();
+        var double = realm.evaluate('function double(x) { return x * 2; } double');
+        var apply = realm.evaluate('(f, x) => f(x)');
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "double(21)"), "42");
+    assert_eq!(forward(&mut context, "double.name"), "\"double\"");
+    assert_eq!(forward(&mut context, "double.length"), "1");
+    assert_eq!(
+        forward(
+            &mut context,
+            "Object.getPrototypeOf(double) === Function.prototype"
+        ),
+        "true"
+    );
+    assert_eq!(forward(&mut context, "apply(x => x + 1, 1)"), "2");
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { apply(x => x, {}) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { new double(1) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn to_string_tag() {
+    let mut context = Context::new();
+    assert_eq!(
+        forward(
+            &mut context,
+            "Object.prototype.toString.call(new ShadowRealm())"
+        ),
+        "\"[object ShadowRealm]\""
+    );
+}
+
+#[test]
+fn host_hooks() {
+    use crate::{host::HostHooks, realm::Realm, JsResult};
+
+    struct Hooks;
+
+    impl HostHooks for Hooks {
+        fn ensure_can_compile_strings(
+            &self,
+            caller_realm: &Realm,
+            callee_realm: &Realm,
+            context: &mut Context,
+        ) -> JsResult<()> {
+            if Realm::ptr_eq(caller_realm, callee_realm) {
+                Ok(())
+            } else {
+                Err(context.construct_eval_error("no shadow eval"))
+            }
+        }
+    }
+
+    let mut context = Context::new();
+    context.set_host_hooks(Hooks);
+
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { new ShadowRealm().evaluate('1') } catch (e) { e.toString() }"
+        ),
+        "\"EvalError: no shadow eval\""
+    );
+}
@@ -0,0 +1,770 @@
+//! This module implements the global `SharedArrayBuffer` object.
+//!
+//! A `SharedArrayBuffer` is a fixed-length raw binary data buffer whose memory can be shared
+//! between multiple agents. In Boa every agent is a [`Context`], so the same memory can be
+//! accessed by contexts living on different threads through a [`SharedDataBlock`].
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-sharedarraybuffer-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/SharedArrayBuffer
+
+use crate::{
+    builtins::{
+        array_buffer::{ArrayBuffer, ArrayBufferData, SharedMemoryOrder},
+        BuiltIn, JsArgs,
+    },
+    context::StandardObjects,
+    gc::{empty_trace, Finalize, Trace},
+    object::{
+        internal_methods::get_prototype_from_constructor, ConstructorBuilder, FunctionBuilder,
+        JsObject, ObjectData,
+    },
+    profiler::BoaProfiler,
+    property::Attribute,
+    symbol::WellKnownSymbols,
+    value::IntegerOrInfinity,
+    Context, JsResult, JsValue,
+};
+use std::{
+    mem,
+    ops::Range,
+    sync::{
+        atomic::{self, AtomicU16, AtomicU32, AtomicU64, AtomicU8},
+        Arc, Condvar, Mutex,
+    },
+    time::Duration,
+};
+
+/// A Shared Data Block.
+///
+/// The bytes of a shared data block can be read and written by several agents at the same time,
+/// so every access goes through atomic operations.
+///
+/// Cloning a `SharedDataBlock` creates a new handle to the same memory. The handle can be sent
+/// to another thread and exposed to the scripts of a different [`Context`] with
+/// [`SharedArrayBuffer::from_data_block`].
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-shared-data-blocks
+#[derive(Debug, Clone)]
+pub struct SharedDataBlock {
+    inner: Arc<SharedDataBlockInner>,
+}
+
+#[derive(Debug)]
+struct SharedDataBlockInner {
+    /// The memory of the block.
+    ///
+    /// It is allocated as a sequence of `AtomicU64` so that it is aligned for every element type;
+    /// every naturally aligned element can then be accessed with a single atomic instruction.
+    words: Box<[AtomicU64]>,
+
+    /// The number of bytes of the block.
+    byte_length: usize,
+
+    /// The `WaiterList`s of the block, stored as a list of `(byte index, waiter)` pairs in
+    /// insertion order.
+    waiters: Mutex<Vec<(usize, Arc<Waiter>)>>,
+}
+
+/// A single agent blocked in `Atomics.wait`.
+#[derive(Debug, Default)]
+struct Waiter {
+    notified: Mutex<bool>,
+    condvar: Condvar,
+}
+
+/// The result of a call to [`SharedDataBlock::wait`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WaitResult {
+    Ok,
+    NotEqual,
+    TimedOut,
+}
+
+impl WaitResult {
+    /// Gets the string returned by `Atomics.wait` for this result.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::NotEqual => "not-equal",
+            Self::TimedOut => "timed-out",
+        }
+    }
+}
+
+impl Finalize for SharedDataBlock {}
+
+// Safety: `SharedDataBlock` does not contain any objects which needs to be traced,
+// so this is safe.
+unsafe impl Trace for SharedDataBlock {
+    empty_trace!();
+}
+
+impl PartialEq for SharedDataBlock {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for SharedDataBlock {}
+
+impl SharedDataBlock {
+    /// `CreateSharedByteDataBlock ( size )`
+    ///
+    /// Creates a new shared data block of `byte_length` bytes, all initialized to zero.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-createsharedbytedatablock
+    pub fn new(byte_length: usize) -> Self {
+        let words = (0..(byte_length + mem::size_of::<u64>() - 1) / mem::size_of::<u64>())
+            .map(|_| AtomicU64::new(0))
+            .collect();
+
+        Self {
+            inner: Arc::new(SharedDataBlockInner {
+                words,
+                byte_length,
+                waiters: Mutex::default(),
+            }),
+        }
+    }
+
+    /// Creates a new shared data block with a copy of `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let block = Self::new(bytes.len());
+        for (dest, byte) in block.bytes().iter().zip(bytes) {
+            dest.store(*byte, atomic::Ordering::Relaxed);
+        }
+        block
+    }
+
+    /// Gets the number of bytes of the block.
+    #[inline]
+    pub fn byte_length(&self) -> usize {
+        self.inner.byte_length
+    }
+
+    /// Copies the current contents of the block into a `Vec`.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.bytes()
+            .iter()
+            .map(|byte| byte.load(atomic::Ordering::Relaxed))
+            .collect()
+    }
+
+    /// Copies the current contents of a range of the block into a `Vec`, or returns `None` if
+    /// the range is out of the bounds of the block.
+    pub fn range_to_vec(&self, range: Range<usize>) -> Option<Vec<u8>> {
+        Some(
+            self.bytes()
+                .get(range)?
+                .iter()
+                .map(|byte| byte.load(atomic::Ordering::Relaxed))
+                .collect(),
+        )
+    }
+
+    /// Gets the bytes of the block.
+    fn bytes(&self) -> &[AtomicU8] {
+        // Safety: `AtomicU8` has the same size and alignment as `u8`, and the words of the block
+        // hold at least `byte_length` bytes.
+        unsafe {
+            std::slice::from_raw_parts(
+                self.inner.words.as_ptr().cast::<AtomicU8>(),
+                self.inner.byte_length,
+            )
+        }
+    }
+
+    /// Gets a reference to the element of type `T` starting at `byte_index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is not aligned or out of bounds.
+    fn element<T>(&self, byte_index: usize) -> &T {
+        assert!(
+            byte_index % mem::size_of::<T>() == 0,
+            "unaligned atomic access"
+        );
+        assert!(byte_index + mem::size_of::<T>() <= self.inner.byte_length);
+
+        // Safety: the memory of the block is aligned to 8 bytes, so an aligned index inside the
+        // block points to a valid and aligned atomic integer of type `T`.
+        unsafe {
+            &*self
+                .inner
+                .words
+                .as_ptr()
+                .cast::<u8>()
+                .add(byte_index)
+                .cast::<T>()
+        }
+    }
+
+    /// Reads `bytes.len()` bytes of the block starting at `byte_index`.
+    ///
+    /// With a `SeqCst` order the read is performed as a single atomic operation, which requires
+    /// an element of 1, 2, 4 or 8 bytes aligned to its size.
+    pub(crate) fn load(&self, byte_index: usize, bytes: &mut [u8], order: &SharedMemoryOrder) {
+        if *order == SharedMemoryOrder::SeqCst {
+            let ordering = atomic::Ordering::SeqCst;
+            match bytes.len() {
+                1 => bytes[0] = self.element::<AtomicU8>(byte_index).load(ordering),
+                2 => bytes.copy_from_slice(
+                    &self
+                        .element::<AtomicU16>(byte_index)
+                        .load(ordering)
+                        .to_ne_bytes(),
+                ),
+                4 => bytes.copy_from_slice(
+                    &self
+                        .element::<AtomicU32>(byte_index)
+                        .load(ordering)
+                        .to_ne_bytes(),
+                ),
+                8 => bytes.copy_from_slice(
+                    &self
+                        .element::<AtomicU64>(byte_index)
+                        .load(ordering)
+                        .to_ne_bytes(),
+                ),
+                _ => unreachable!("invalid element size for an atomic access"),
+            }
+        } else {
+            for (byte, src) in bytes.iter_mut().zip(&self.bytes()[byte_index..]) {
+                *byte = src.load(atomic::Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Writes `bytes` into the block starting at `byte_index`.
+    ///
+    /// With a `SeqCst` order the write is performed as a single atomic operation, which requires
+    /// an element of 1, 2, 4 or 8 bytes aligned to its size.
+    pub(crate) fn store(&self, byte_index: usize, bytes: &[u8], order: &SharedMemoryOrder) {
+        if *order == SharedMemoryOrder::SeqCst {
+            let ordering = atomic::Ordering::SeqCst;
+            match bytes.len() {
+                1 => self
+                    .element::<AtomicU8>(byte_index)
+                    .store(bytes[0], ordering),
+                2 => self.element::<AtomicU16>(byte_index).store(
+                    u16::from_ne_bytes(bytes.try_into().expect("slice with incorrect length")),
+                    ordering,
+                ),
+                4 => self.element::<AtomicU32>(byte_index).store(
+                    u32::from_ne_bytes(bytes.try_into().expect("slice with incorrect length")),
+                    ordering,
+                ),
+                8 => self.element::<AtomicU64>(byte_index).store(
+                    u64::from_ne_bytes(bytes.try_into().expect("slice with incorrect length")),
+                    ordering,
+                ),
+                _ => unreachable!("invalid element size for an atomic access"),
+            }
+        } else {
+            for (dest, byte) in self.bytes()[byte_index..].iter().zip(bytes) {
+                dest.store(*byte, atomic::Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Atomically replaces the little-endian integer of `size` bytes at `byte_index` with the
+    /// result of `op`, returning the previous value.
+    ///
+    /// Both the argument and the result of `op` are zero-extended to 64 bits; the result is
+    /// truncated back to `size` bytes.
+    pub(crate) fn fetch_update<F>(&self, byte_index: usize, size: usize, op: F) -> u64
+    where
+        F: Fn(u64) -> u64,
+    {
+        macro_rules! fetch_update {
+            ($atomic:ty, $int:ty) => {{
+                let element = self.element::<$atomic>(byte_index);
+                let mut current = element.load(atomic::Ordering::SeqCst);
+                loop {
+                    let new = op(u64::from(<$int>::from_le(current))).to_le_bytes();
+                    let new = <$int>::from_le_bytes(
+                        new[..mem::size_of::<$int>()]
+                            .try_into()
+                            .expect("slice with incorrect length"),
+                    )
+                    .to_le();
+                    match element.compare_exchange_weak(
+                        current,
+                        new,
+                        atomic::Ordering::SeqCst,
+                        atomic::Ordering::SeqCst,
+                    ) {
+                        Ok(previous) => break u64::from(<$int>::from_le(previous)),
+                        Err(previous) => current = previous,
+                    }
+                }
+            }};
+        }
+
+        match size {
+            1 => fetch_update!(AtomicU8, u8),
+            2 => fetch_update!(AtomicU16, u16),
+            4 => fetch_update!(AtomicU32, u32),
+            8 => fetch_update!(AtomicU64, u64),
+            _ => unreachable!("invalid element size for an atomic access"),
+        }
+    }
+
+    /// Suspends the current thread while the little-endian integer of `size` bytes at
+    /// `byte_index` is equal to `expected`, until it is woken up by [`Self::notify`] or the
+    /// `timeout` expires.
+    ///
+    /// This implements the blocking part of `DoWait`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-dowait
+    pub(crate) fn wait(
+        &self,
+        byte_index: usize,
+        size: usize,
+        expected: u64,
+        timeout: Option<Duration>,
+    ) -> WaitResult {
+        let waiter = {
+            // 11. Perform EnterCriticalSection(WL).
+            let mut waiters = self
+                .inner
+                .waiters
+                .lock()
+                .expect("waiter list lock should not be poisoned");
+
+            // 12. Let elementType be the Element Type value in Table 70 for arrayTypeName.
+            // 13. Let w be ! GetValueFromBuffer(buffer, indexedPosition, elementType, true, SeqCst).
+            let mut bytes = [0; 8];
+            self.load(byte_index, &mut bytes[..size], &SharedMemoryOrder::SeqCst);
+
+            // 14. If v ≠ w, then
+            if u64::from_le_bytes(bytes) != expected {
+                // a. Perform LeaveCriticalSection(WL).
+                // b. Return the String "not-equal".
+                return WaitResult::NotEqual;
+            }
+
+            // 15. Let W be AgentSignifier().
+            // 16. Perform AddWaiter(WL, W).
+            let waiter = Arc::new(Waiter::default());
+            waiters.push((byte_index, waiter.clone()));
+            waiter
+        };
+
+        // 17. Let notified be SuspendAgent(WL, W, t).
+        let notified = waiter
+            .notified
+            .lock()
+            .expect("waiter lock should not be poisoned");
+        let notified = if let Some(timeout) = timeout {
+            *waiter
+                .condvar
+                .wait_timeout_while(notified, timeout, |notified| !*notified)
+                .expect("waiter lock should not be poisoned")
+                .0
+        } else {
+            *waiter
+                .condvar
+                .wait_while(notified, |notified| !*notified)
+                .expect("waiter lock should not be poisoned")
+        };
+
+        let mut waiters = self
+            .inner
+            .waiters
+            .lock()
+            .expect("waiter list lock should not be poisoned");
+
+        // 18. If notified is true, then
+        //     a. Assert: W is not on the list of waiters in WL.
+        // 19. Else,
+        //     a. Perform RemoveWaiter(WL, W).
+        // A notification could arrive between the timeout and the re-entry to the critical
+        // section, so the waiter list is the source of truth here.
+        let position = waiters.iter().position(|(_, w)| Arc::ptr_eq(w, &waiter));
+        match position {
+            Some(position) if !notified => {
+                waiters.remove(position);
+                // 21. If notified is true, return "ok".
+                // 22. Return "timed-out".
+                WaitResult::TimedOut
+            }
+            _ => WaitResult::Ok,
+        }
+    }
+
+    /// Wakes up to `count` agents waiting on `byte_index`, in FIFO order, returning the number
+    /// of woken agents.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-atomics.notify
+    pub(crate) fn notify(&self, byte_index: usize, count: Option<usize>) -> usize {
+        // 9. Let WL be GetWaiterList(block, indexedPosition).
+        // 11. Perform EnterCriticalSection(WL).
+        let mut waiters = self
+            .inner
+            .waiters
+            .lock()
+            .expect("waiter list lock should not be poisoned");
+
+        // 12. Let S be RemoveWaiters(WL, c).
+        let count = count.unwrap_or(usize::MAX);
+        let mut removed = Vec::new();
+        let mut i = 0;
+        while i < waiters.len() && removed.len() < count {
+            if waiters[i].0 == byte_index {
+                removed.push(waiters.remove(i).1);
+            } else {
+                i += 1;
+            }
+        }
+
+        // 13. For each element W of S, do
+        for waiter in &removed {
+            // a. Perform NotifyWaiter(WL, W).
+            *waiter
+                .notified
+                .lock()
+                .expect("waiter lock should not be poisoned") = true;
+            waiter.condvar.notify_one();
+        }
+
+        // 14. Perform LeaveCriticalSection(WL).
+        // 15. Let n be the number of elements in S.
+        // 16. Return 𝔽(n).
+        removed.len()
+    }
+}
+
+/// JavaScript `SharedArrayBuffer` built-in implementation.
+///
+/// `SharedArrayBuffer` objects are represented by an [`ArrayBuffer`] whose data is a
+/// [`SharedDataBlock`], matching the spec, where both kinds of objects share the
+/// `[[ArrayBufferData]]` internal slot.
+#[derive(Debug, Clone, Copy)]
+pub struct SharedArrayBuffer;
+
+impl BuiltIn for SharedArrayBuffer {
+    const NAME: &'static str = "SharedArrayBuffer";
+
+    const ATTRIBUTE: Attribute = Attribute::WRITABLE
+        .union(Attribute::NON_ENUMERABLE)
+        .union(Attribute::CONFIGURABLE);
+
+    fn init(context: &mut Context) -> JsValue {
+        let _timer = BoaProfiler::global().start_event(Self::NAME, "init");
+
+        let get_species = FunctionBuilder::native(context, Self::get_species)
+            .name("get [Symbol.species]")
+            .constructor(false)
+            .build();
+
+        let get_byte_length = FunctionBuilder::native(context, Self::byte_length)
+            .name("get byteLength")
+            .constructor(false)
+            .build();
+
+        ConstructorBuilder::with_standard_object(
+            context,
+            Self::constructor,
+            context
+                .standard_objects()
+                .shared_array_buffer_object()
+                .clone(),
+        )
+        .name(Self::NAME)
+        .length(Self::LENGTH)
+        .static_accessor(
+            WellKnownSymbols::species(),
+            Some(get_species),
+            None,
+            Attribute::CONFIGURABLE,
+        )
+        .accessor(
+            "byteLength",
+            Some(get_byte_length),
+            None,
+            Attribute::CONFIGURABLE | Attribute::NON_ENUMERABLE,
+        )
+        .method(Self::slice, "slice", 2)
+        .property(
+            WellKnownSymbols::to_string_tag(),
+            Self::NAME,
+            Attribute::READONLY | Attribute::NON_ENUMERABLE | Attribute::CONFIGURABLE,
+        )
+        .build()
+        .into()
+    }
+}
+
+impl SharedArrayBuffer {
+    const LENGTH: usize = 1;
+
+    /// Creates a new `SharedArrayBuffer` object that exposes the memory of `block`.
+    ///
+    /// This is the way to share memory between [`Context`]s: create the block once, send a clone
+    /// of it to every thread and wrap it with this function in each `Context`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use boa::{builtins::shared_array_buffer::{SharedArrayBuffer, SharedDataBlock}, Context};
+    ///
+    /// let block = SharedDataBlock::new(16);
+    ///
+    /// let mut context = Context::new();
+    /// let buffer = SharedArrayBuffer::from_data_block(block.clone(), &mut context);
+    /// context.register_global_property("buffer", buffer, Default::default());
+    /// context.eval("new Uint8Array(buffer)[3] = 42").unwrap();
+    ///
+    /// assert_eq!(block.to_vec()[3], 42);
+    /// ```
+    pub fn from_data_block(block: SharedDataBlock, context: &mut Context) -> JsObject {
+        let byte_length = block.byte_length();
+        JsObject::from_proto_and_data(
+            context
+                .standard_objects()
+                .shared_array_buffer_object()
+                .prototype(),
+            ObjectData::array_buffer(ArrayBuffer {
+                array_buffer_data: Some(ArrayBufferData::Shared(block)),
+                array_buffer_byte_length: byte_length,
+                array_buffer_detach_key: JsValue::Undefined,
+                array_buffer_max_byte_length: None,
+            }),
+        )
+    }
+
+    /// `25.2.3.1 SharedArrayBuffer ( length )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-sharedarraybuffer-length
+    fn constructor(
+        new_target: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. If NewTarget is undefined, throw a TypeError exception.
+        if new_target.is_undefined() {
+            return context.throw_type_error(
+                "SharedArrayBuffer.constructor called with undefined new target",
+            );
+        }
+
+        // 2. Let byteLength be ? ToIndex(length).
+        let byte_length = args.get_or_undefined(0).to_index(context)?;
+
+        // 3. Return ? AllocateSharedArrayBuffer(NewTarget, byteLength).
+        Ok(Self::allocate(new_target, byte_length, context)?.into())
+    }
+
+    /// `25.2.4.2 get SharedArrayBuffer [ @@species ]`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-sharedarraybuffer-@@species
+    fn get_species(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        // 1. Return the this value.
+        Ok(this.clone())
+    }
+
+    /// `25.2.5.1 get SharedArrayBuffer.prototype.byteLength`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-get-sharedarraybuffer.prototype.bytelength
+    fn byte_length(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[ArrayBufferData]]).
+        let obj = if let Some(obj) = this.as_object() {
+            obj
+        } else {
+            return context
+                .throw_type_error("SharedArrayBuffer.byteLength called with non-object value");
+        };
+        let obj = obj.borrow();
+        let o = match obj.as_array_buffer() {
+            // 3. If IsSharedArrayBuffer(O) is false, throw a TypeError exception.
+            Some(o) if o.is_shared_array_buffer() => o,
+            _ => {
+                return context
+                    .throw_type_error("SharedArrayBuffer.byteLength called with invalid object")
+            }
+        };
+
+        // 4. Let length be O.[[ArrayBufferByteLength]].
+        // 5. Return 𝔽(length).
+        Ok(o.array_buffer_byte_length.into())
+    }
+
+    /// `25.2.5.3 SharedArrayBuffer.prototype.slice ( start, end )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-sharedarraybuffer.prototype.slice
+    fn slice(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[ArrayBufferData]]).
+        let obj = if let Some(obj) = this.as_object() {
+            obj
+        } else {
+            return context
+                .throw_type_error("SharedArrayBuffer.slice called with non-object value");
+        };
+
+        // 3. If IsSharedArrayBuffer(O) is false, throw a TypeError exception.
+        let (from_block, len) = match obj.borrow().as_array_buffer() {
+            Some(ArrayBuffer {
+                array_buffer_data: Some(ArrayBufferData::Shared(block)),
+                array_buffer_byte_length,
+                ..
+            }) => (block.clone(), *array_buffer_byte_length as i64),
+            _ => {
+                return context
+                    .throw_type_error("SharedArrayBuffer.slice called with invalid object")
+            }
+        };
+
+        // 4. Let len be O.[[ArrayBufferByteLength]].
+        // 5. Let relativeStart be ? ToIntegerOrInfinity(start).
+        let relative_start = args.get_or_undefined(0).to_integer_or_infinity(context)?;
+
+        let first = match relative_start {
+            // 6. If relativeStart is -∞, let first be 0.
+            IntegerOrInfinity::NegativeInfinity => 0,
+            // 7. Else if relativeStart < 0, let first be max(len + relativeStart, 0).
+            IntegerOrInfinity::Integer(i) if i < 0 => std::cmp::max(len + i, 0),
+            // 8. Else, let first be min(relativeStart, len).
+            IntegerOrInfinity::Integer(i) => std::cmp::min(i, len),
+            IntegerOrInfinity::PositiveInfinity => len,
+        };
+
+        // 9. If end is undefined, let relativeEnd be len; else let relativeEnd be ? ToIntegerOrInfinity(end).
+        let end = args.get_or_undefined(1);
+        let relative_end = if end.is_undefined() {
+            IntegerOrInfinity::Integer(len)
+        } else {
+            end.to_integer_or_infinity(context)?
+        };
+
+        let r#final = match relative_end {
+            // 10. If relativeEnd is -∞, let final be 0.
+            IntegerOrInfinity::NegativeInfinity => 0,
+            // 11. Else if relativeEnd < 0, let final be max(len + relativeEnd, 0).
+            IntegerOrInfinity::Integer(i) if i < 0 => std::cmp::max(len + i, 0),
+            // 12. Else, let final be min(relativeEnd, len).
+            IntegerOrInfinity::Integer(i) => std::cmp::min(i, len),
+            IntegerOrInfinity::PositiveInfinity => len,
+        };
+
+        // 13. Let newLen be max(final - first, 0).
+        let new_len = std::cmp::max(r#final - first, 0) as usize;
+
+        // 14. Let ctor be ? SpeciesConstructor(O, %SharedArrayBuffer%).
+        let ctor = obj.species_constructor(StandardObjects::shared_array_buffer_object, context)?;
+
+        // 15. Let new be ? Construct(ctor, « 𝔽(newLen) »).
+        let new = ctor.construct(&[new_len.into()], &ctor.clone().into(), context)?;
+
+        // 16. Perform ? RequireInternalSlot(new, [[ArrayBufferData]]).
+        // 17. If IsSharedArrayBuffer(new) is false, throw a TypeError exception.
+        let (to_block, new_byte_length) =
+            match new
+                .as_object()
+                .and_then(|new| match new.borrow().as_array_buffer() {
+                    Some(ArrayBuffer {
+                        array_buffer_data: Some(ArrayBufferData::Shared(block)),
+                        array_buffer_byte_length,
+                        ..
+                    }) => Some((block.clone(), *array_buffer_byte_length)),
+                    _ => None,
+                }) {
+                Some(new) => new,
+                None => {
+                    return context
+                        .throw_type_error("SharedArrayBuffer constructor returned invalid object")
+                }
+            };
+
+        // 18. If new.[[ArrayBufferData]] and O.[[ArrayBufferData]] are the same Shared Data Block values, throw a TypeError exception.
+        if to_block == from_block {
+            return context
+                .throw_type_error("New SharedArrayBuffer is the same as this SharedArrayBuffer");
+        }
+
+        // 19. If new.[[ArrayBufferByteLength]] < newLen, throw a TypeError exception.
+        if new_byte_length < new_len {
+            return context.throw_type_error("New SharedArrayBuffer length too small");
+        }
+
+        // 20. Let fromBuf be O.[[ArrayBufferData]].
+        // 21. Let toBuf be new.[[ArrayBufferData]].
+        // 22. Perform CopyDataBlockBytes(toBuf, 0, fromBuf, first, newLen).
+        let mut bytes = vec![0; new_len];
+        from_block.load(first as usize, &mut bytes, &SharedMemoryOrder::Unordered);
+        to_block.store(0, &bytes, &SharedMemoryOrder::Unordered);
+
+        // 23. Return new.
+        Ok(new)
+    }
+
+    /// `25.2.1.1 AllocateSharedArrayBuffer ( constructor, byteLength )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-allocatesharedarraybuffer
+    pub(crate) fn allocate(
+        constructor: &JsValue,
+        byte_length: usize,
+        context: &mut Context,
+    ) -> JsResult<JsObject> {
+        // 1. Let obj be ? OrdinaryCreateFromConstructor(constructor, "%SharedArrayBuffer.prototype%", « [[ArrayBufferData]], [[ArrayBufferByteLength]] »).
+        let prototype = get_prototype_from_constructor(
+            constructor,
+            StandardObjects::shared_array_buffer_object,
+            context,
+        )?;
+        let obj = context.construct_object();
+        obj.set_prototype(prototype.into());
+
+        // 2. Let block be ? CreateSharedByteDataBlock(byteLength).
+        // TODO: for now just a arbitrary limit to not OOM.
+        if byte_length > 8589934592 {
+            return Err(context.construct_range_error("SharedArrayBuffer allocation failed"));
+        }
+        let block = SharedDataBlock::new(byte_length);
+
+        // 3. Set obj.[[ArrayBufferData]] to block.
+        // 4. Set obj.[[ArrayBufferByteLength]] to byteLength.
+        obj.borrow_mut().data = ObjectData::array_buffer(ArrayBuffer {
+            array_buffer_data: Some(ArrayBufferData::Shared(block)),
+            array_buffer_byte_length: byte_length,
+            array_buffer_detach_key: JsValue::Undefined,
+            array_buffer_max_byte_length: None,
+        });
+
+        // 5. Return obj.
+        Ok(obj)
+    }
+}
@@ -35,6 +35,7 @@ pub struct IntegerIndexed {
     byte_offset: usize,
     byte_length: usize,
     array_length: usize,
+    length_tracking: bool,
 }
 
 impl IntegerIndexed {
@@ -51,6 +52,7 @@ impl IntegerIndexed {
             byte_offset,
             byte_length,
             array_length,
+            length_tracking: false,
         }
     }
 
@@ -83,25 +85,6 @@ impl IntegerIndexed {
         a
     }
 
-    /// Abstract operation `IsDetachedBuffer ( arrayBuffer )`.
-    ///
-    /// Check if `[[ArrayBufferData]]` is null.
-    ///
-    /// More information:
-    ///  - [ECMAScript reference][spec]
-    ///
-    /// [spec]: https://tc39.es/ecma262/#sec-isdetachedbuffer
-    pub(crate) fn is_detached(&self) -> bool {
-        if let Some(obj) = &self.viewed_array_buffer {
-            obj.borrow()
-                .as_array_buffer()
-                .expect("Typed array must have internal array buffer object")
-                .is_detached_buffer()
-        } else {
-            false
-        }
-    }
-
     /// Get the integer indexed object's byte offset.
     pub(crate) fn byte_offset(&self) -> usize {
         self.byte_offset
@@ -128,8 +111,31 @@ impl IntegerIndexed {
     }
 
     /// Get the integer indexed object's byte length.
+    ///
+    /// This is the `TypedArrayByteLength` abstract operation, so it is `0` if the typed array is
+    /// out of bounds and follows the size of the buffer if the typed array is length-tracking.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-typedarraybytelength
     pub fn byte_length(&self) -> usize {
-        self.byte_length
+        // 1. If IsTypedArrayOutOfBounds(taRecord) is true, return 0.
+        if self.is_out_of_bounds() {
+            return 0;
+        }
+
+        // 2. Let length be TypedArrayLength(taRecord).
+        // 3. If length = 0, return 0.
+        // 4. Let O be taRecord.[[Object]].
+        // 5. If O.[[ByteLength]] is not auto, return O.[[ByteLength]].
+        if !self.length_tracking {
+            return self.byte_length;
+        }
+
+        // 6. Let elementSize be TypedArrayElementSize(O).
+        // 7. Return length × elementSize.
+        self.array_length() * self.typed_array_name.element_size()
     }
 
     /// Set the integer indexed object's byte length.
@@ -138,14 +144,109 @@ impl IntegerIndexed {
     }
 
     /// Get the integer indexed object's array length.
+    ///
+    /// This is the `TypedArrayLength` abstract operation, so it is `0` if the typed array is
+    /// out of bounds and follows the size of the buffer if the typed array is length-tracking.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-typedarraylength
     pub fn array_length(&self) -> usize {
-        self.array_length
+        // 1. Assert: IsTypedArrayOutOfBounds(taRecord) is false.
+        if self.is_out_of_bounds() {
+            return 0;
+        }
+
+        // 2. Let O be taRecord.[[Object]].
+        // 3. If O.[[ArrayLength]] is not auto, return O.[[ArrayLength]].
+        if !self.length_tracking {
+            return self.array_length;
+        }
+
+        // 4. Assert: IsFixedLengthArrayBuffer(O.[[ViewedArrayBuffer]]) is false.
+        // 5. Let byteOffset be O.[[ByteOffset]].
+        // 6. Let elementSize be TypedArrayElementSize(O).
+        // 7. Let byteLength be taRecord.[[CachedBufferByteLength]].
+        // 8. Assert: byteLength is not detached.
+        // 9. Return floor((byteLength - byteOffset) / elementSize).
+        (self.buffer_byte_length() - self.byte_offset) / self.typed_array_name.element_size()
     }
 
     /// Set the integer indexed object's array length.
     pub(crate) fn set_array_length(&mut self, array_length: usize) {
         self.array_length = array_length;
     }
+
+    /// Checks if the array length of the integer indexed object is `auto`, which means it tracks
+    /// the length of a resizable buffer.
+    pub(crate) fn is_length_tracking(&self) -> bool {
+        self.length_tracking
+    }
+
+    /// Set whether the integer indexed object tracks the length of its buffer.
+    pub(crate) fn set_length_tracking(&mut self, length_tracking: bool) {
+        self.length_tracking = length_tracking;
+    }
+
+    /// Abstract operation `IsTypedArrayOutOfBounds ( taRecord )`.
+    ///
+    /// Check if the typed array is detached or doesn't fit in its (possibly resized) buffer.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-istypedarrayoutofbounds
+    pub(crate) fn is_out_of_bounds(&self) -> bool {
+        // 1. Let O be taRecord.[[Object]].
+        // 2. Let bufferByteLength be taRecord.[[CachedBufferByteLength]].
+        // 3. Assert: IsDetachedBuffer(O.[[ViewedArrayBuffer]]) is true if and only if bufferByteLength is detached.
+        // 4. If bufferByteLength is detached, return true.
+        let buffer_byte_length = if let Some(obj) = &self.viewed_array_buffer {
+            let buffer = obj.borrow();
+            let buffer = buffer
+                .as_array_buffer()
+                .expect("Typed array must have internal array buffer object");
+            if buffer.is_detached_buffer() {
+                return true;
+            }
+            buffer.array_buffer_byte_length()
+        } else {
+            return false;
+        };
+
+        // 5. Let byteOffsetStart be O.[[ByteOffset]].
+        let byte_offset_start = self.byte_offset;
+
+        // 6. If O.[[ArrayLength]] is auto, then
+        let byte_offset_end = if self.length_tracking {
+            // a. Let byteOffsetEnd be bufferByteLength.
+            buffer_byte_length
+        } else {
+            // 7. Else,
+            // a. Let elementSize be TypedArrayElementSize(O).
+            // b. Let byteOffsetEnd be byteOffsetStart + O.[[ArrayLength]] × elementSize.
+            byte_offset_start + self.array_length * self.typed_array_name.element_size()
+        };
+
+        // 8. If byteOffsetStart > bufferByteLength or byteOffsetEnd > bufferByteLength, return true.
+        // 9. NOTE: 0-length TypedArrays are not considered out-of-bounds.
+        // 10. Return false.
+        byte_offset_start > buffer_byte_length || byte_offset_end > buffer_byte_length
+    }
+
+    /// Gets the current byte length of the viewed array buffer, or `0` if there is no buffer.
+    fn buffer_byte_length(&self) -> usize {
+        self.viewed_array_buffer
+            .as_ref()
+            .map(|obj| {
+                obj.borrow()
+                    .as_array_buffer()
+                    .expect("Typed array must have internal array buffer object")
+                    .array_buffer_byte_length()
+            })
+            .unwrap_or_default()
+    }
 }
 
 /// A Data Block
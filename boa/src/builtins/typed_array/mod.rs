@@ -536,7 +536,7 @@ impl TypedArray {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-%typedarray%.prototype.at
-    fn at(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    pub(crate) fn at(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         // 1. Let O be the this value.
         // 2. Perform ? ValidateTypedArray(O).
         let obj = this
@@ -546,7 +546,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -622,15 +622,10 @@ impl TypedArray {
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
 
-        // 4. Let buffer be O.[[ViewedArrayBuffer]].
-        // 5. If IsDetachedBuffer(buffer) is true, return +0𝔽.
-        // 6. Let size be O.[[ByteLength]].
-        // 7. Return 𝔽(size).
-        if typed_array.is_detached() {
-            Ok(0.into())
-        } else {
-            Ok(typed_array.byte_length().into())
-        }
+        // 4. Let taRecord be MakeTypedArrayWithBufferWitnessRecord(O, seq-cst).
+        // 5. Let size be TypedArrayByteLength(taRecord).
+        // 6. Return 𝔽(size).
+        Ok(typed_array.byte_length().into())
     }
 
     /// `23.2.3.4 get %TypedArray%.prototype.byteOffset`
@@ -651,11 +646,11 @@ impl TypedArray {
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
 
-        // 4. Let buffer be O.[[ViewedArrayBuffer]].
-        // 5. If IsDetachedBuffer(buffer) is true, return +0𝔽.
+        // 4. Let taRecord be MakeTypedArrayWithBufferWitnessRecord(O, seq-cst).
+        // 5. If IsTypedArrayOutOfBounds(taRecord) is true, return +0𝔽.
         // 6. Let offset be O.[[ByteOffset]].
         // 7. Return 𝔽(offset).
-        if typed_array.is_detached() {
+        if typed_array.is_out_of_bounds() {
             Ok(0.into())
         } else {
             Ok(typed_array.byte_offset().into())
@@ -681,7 +676,7 @@ impl TypedArray {
                 .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
 
             // 2. Perform ? ValidateTypedArray(O).
-            if o.is_detached() {
+            if o.is_out_of_bounds() {
                 return Err(context.construct_type_error("Buffer of the typed array is detached"));
             }
 
@@ -746,7 +741,7 @@ impl TypedArray {
             // a. NOTE: The copying must be performed in a manner that preserves the bit-level encoding of the source data.
             // b. Let buffer be O.[[ViewedArrayBuffer]].
             // c. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
-            if o.is_detached() {
+            if o.is_out_of_bounds() {
                 return Err(context.construct_type_error("Buffer of the typed array is detached"));
             }
 
@@ -759,6 +754,11 @@ impl TypedArray {
             // f. Let byteOffset be O.[[ByteOffset]].
             let byte_offset = o.byte_offset() as i64;
 
+            // NOTE: A resizable buffer may have shrunk while the arguments were converted, so
+            // bytes past the current end of the typed array must not be copied.
+            // Let bufferByteLimit be TypedArrayLength(taRecord) × elementSize + byteOffset.
+            let buffer_byte_limit = o.array_length() as i64 * element_size + byte_offset;
+
             // g. Let toByteIndex be to × elementSize + byteOffset.
             let mut to_byte_index = to * element_size + byte_offset;
 
@@ -795,8 +795,11 @@ impl TypedArray {
                 .as_array_buffer_mut()
                 .expect("Already checked for detached buffer");
 
-            // l. Repeat, while countBytes > 0,
-            while count_bytes > 0 {
+            // l. Repeat, while countBytes > 0 and both indices are below bufferByteLimit,
+            while count_bytes > 0
+                && from_byte_index < buffer_byte_limit
+                && to_byte_index < buffer_byte_limit
+            {
                 // i. Let value be GetValueFromBuffer(buffer, fromByteIndex, Uint8, true, Unordered).
                 let value = buffer.get_value_from_buffer(
                     from_byte_index as usize,
@@ -846,7 +849,7 @@ impl TypedArray {
         if o.borrow()
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?
-            .is_detached()
+            .is_out_of_bounds()
         {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
@@ -875,7 +878,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -924,7 +927,11 @@ impl TypedArray {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-%typedarray%.prototype.fill
-    fn fill(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    pub(crate) fn fill(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
         // 1. Let O be the this value.
         // 2. Perform ? ValidateTypedArray(O).
         let obj = this
@@ -934,7 +941,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -979,7 +986,7 @@ impl TypedArray {
         };
 
         // 14. If IsDetachedBuffer(O.[[ViewedArrayBuffer]]) is true, throw a TypeError exception.
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1014,7 +1021,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1095,7 +1102,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1153,7 +1160,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1210,7 +1217,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1262,7 +1269,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1335,7 +1342,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1407,7 +1414,11 @@ impl TypedArray {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-%typedarray%.prototype.join
-    fn join(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    pub(crate) fn join(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
         // 1. Let O be the this value.
         // 2. Perform ? ValidateTypedArray(O).
         let obj = this
@@ -1417,7 +1428,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1473,7 +1484,7 @@ impl TypedArray {
         if o.borrow()
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?
-            .is_detached()
+            .is_out_of_bounds()
         {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
@@ -1502,7 +1513,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1578,11 +1589,11 @@ impl TypedArray {
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
 
-        // 4. Let buffer be O.[[ViewedArrayBuffer]].
-        // 5. If IsDetachedBuffer(buffer) is true, return +0𝔽.
-        // 6. Let length be O.[[ArrayLength]].
+        // 4. Let taRecord be MakeTypedArrayWithBufferWitnessRecord(O, seq-cst).
+        // 5. If IsTypedArrayOutOfBounds(taRecord) is true, return +0𝔽.
+        // 6. Let length be TypedArrayLength(taRecord).
         // 7. Return 𝔽(length).
-        if typed_array.is_detached() {
+        if typed_array.is_out_of_bounds() {
             Ok(0.into())
         } else {
             Ok(typed_array.array_length().into())
@@ -1605,7 +1616,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1663,7 +1674,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1740,7 +1751,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1820,7 +1831,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -1932,7 +1943,7 @@ impl TypedArray {
 
         // 1. Let targetBuffer be target.[[ViewedArrayBuffer]].
         // 2. If IsDetachedBuffer(targetBuffer) is true, throw a TypeError exception.
-        if target_array.is_detached() {
+        if target_array.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
         let target_buffer_obj = target_array
@@ -1944,7 +1955,7 @@ impl TypedArray {
 
         // 4. Let srcBuffer be source.[[ViewedArrayBuffer]].
         // 5. If IsDetachedBuffer(srcBuffer) is true, throw a TypeError exception.
-        if source_array.is_detached() {
+        if source_array.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
         let mut src_buffer_obj = source_array
@@ -1998,13 +2009,22 @@ impl TypedArray {
             ));
         }
 
-        // TODO: Shared Array Buffer
-        // 18. If both IsSharedArrayBuffer(srcBuffer) and IsSharedArrayBuffer(targetBuffer) are true, then
-
-        // a. If srcBuffer.[[ArrayBufferData]] and targetBuffer.[[ArrayBufferData]] are the same Shared Data Block values, let same be true; else let same be false.
-
-        // 19. Else, let same be SameValue(srcBuffer, targetBuffer).
-        let same = JsObject::equals(&src_buffer_obj, target_buffer_obj);
+        let same = match (
+            src_buffer_obj
+                .borrow()
+                .as_array_buffer()
+                .and_then(ArrayBuffer::shared_data_block),
+            target_buffer_obj
+                .borrow()
+                .as_array_buffer()
+                .and_then(ArrayBuffer::shared_data_block),
+        ) {
+            // 18. If both IsSharedArrayBuffer(srcBuffer) and IsSharedArrayBuffer(targetBuffer) are true, then
+            // a. If srcBuffer.[[ArrayBufferData]] and targetBuffer.[[ArrayBufferData]] are the same Shared Data Block values, let same be true; else let same be false.
+            (Some(src_block), Some(target_block)) => src_block == target_block,
+            // 19. Else, let same be SameValue(srcBuffer, targetBuffer).
+            _ => JsObject::equals(&src_buffer_obj, target_buffer_obj),
+        };
 
         // 20. If same is true, then
         let mut src_byte_index = if same {
@@ -2141,7 +2161,7 @@ impl TypedArray {
 
         // 1. Let targetBuffer be target.[[ViewedArrayBuffer]].
         // 2. If IsDetachedBuffer(targetBuffer) is true, throw a TypeError exception.
-        if target_array.is_detached() {
+        if target_array.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -2203,6 +2223,10 @@ impl TypedArray {
                 value.to_number(context)?.into()
             };
 
+            // NOTE: A resizable buffer may have shrunk while the values were converted, in which
+            // case writes to indices that are no longer valid are ignored.
+            let is_valid_integer_index = target_offset + k < target_array.array_length();
+
             let target_buffer_obj = target_array
                 .viewed_array_buffer()
                 .expect("Already checked for detached buffer");
@@ -2219,14 +2243,16 @@ impl TypedArray {
             }
 
             // f. Perform SetValueInBuffer(targetBuffer, targetByteIndex, targetType, value, true, Unordered).
-            target_buffer.set_value_in_buffer(
-                target_byte_index,
-                target_name,
-                value,
-                SharedMemoryOrder::Unordered,
-                None,
-                context,
-            )?;
+            if is_valid_integer_index {
+                target_buffer.set_value_in_buffer(
+                    target_byte_index,
+                    target_name,
+                    value,
+                    SharedMemoryOrder::Unordered,
+                    None,
+                    context,
+                )?;
+            }
 
             // g. Set k to k + 1.
             k += 1;
@@ -2254,7 +2280,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -2303,10 +2329,15 @@ impl TypedArray {
         // 14. If count > 0, then
         if count > 0 {
             // a. If IsDetachedBuffer(O.[[ViewedArrayBuffer]]) is true, throw a TypeError exception.
-            if o.is_detached() {
+            if o.is_out_of_bounds() {
                 return Err(context.construct_type_error("Buffer of the typed array is detached"));
             }
 
+            // NOTE: A resizable buffer may have shrunk while creating A, so only copy the elements
+            // that are still in bounds.
+            let r#final = std::cmp::min(r#final, o.array_length() as i64);
+            let count = std::cmp::max(r#final - k, 0) as usize;
+
             // b. Let srcName be the String value of O.[[TypedArrayName]].
             // c. Let srcType be the Element Type value in Table 73 for srcName.
             // d. Let targetName be the String value of A.[[TypedArrayName]].
@@ -2418,7 +2449,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -2490,7 +2521,7 @@ impl TypedArray {
             let o = obj_borrow.as_typed_array().ok_or_else(|| {
                 context.construct_type_error("TypedArray.sort must be called on typed array object")
             })?;
-            if o.is_detached() {
+            if o.is_out_of_bounds() {
                 return context.throw_type_error(
                     "TypedArray.sort called on typed array object with detached array buffer",
                 );
@@ -2736,19 +2767,22 @@ impl TypedArray {
         // 18. Let beginByteOffset be srcByteOffset + beginIndex × elementSize.
         let begin_byte_offset = src_byte_offset + begin_index as usize * element_size;
 
-        // 19. Let argumentsList be « buffer, 𝔽(beginByteOffset), 𝔽(newLength) ».
-        // 20. Return ? TypedArraySpeciesCreate(O, argumentsList).
-        Ok(Self::species_create(
-            obj,
-            o.typed_array_name(),
-            &[
+        // 19. If O.[[ArrayLength]] is auto and end is undefined, then
+        let arguments_list = if o.is_length_tracking() && end.is_undefined() {
+            // a. Let argumentsList be « buffer, 𝔽(beginByteOffset) ».
+            vec![buffer.clone().into(), begin_byte_offset.into()]
+        } else {
+            // 20. Else,
+            // a. Let argumentsList be « buffer, 𝔽(beginByteOffset), 𝔽(newLength) ».
+            vec![
                 buffer.clone().into(),
                 begin_byte_offset.into(),
                 new_length.into(),
-            ],
-            context,
-        )?
-        .into())
+            ]
+        };
+
+        // 21. Return ? TypedArraySpeciesCreate(O, argumentsList).
+        Ok(Self::species_create(obj, o.typed_array_name(), &arguments_list, context)?.into())
     }
 
     // TODO: 23.2.3.29 %TypedArray%.prototype.toLocaleString ( [ reserved1 [ , reserved2 ] ] )
@@ -2768,7 +2802,7 @@ impl TypedArray {
         if o.borrow()
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?
-            .is_detached()
+            .is_out_of_bounds()
         {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
@@ -2818,19 +2852,7 @@ impl TypedArray {
         context: &mut Context,
     ) -> JsResult<JsObject> {
         // 1. Let defaultConstructor be the intrinsic object listed in column one of Table 73 for exemplar.[[TypedArrayName]].
-        let default_constructor = match typed_array_name {
-            TypedArrayName::Int8Array => StandardObjects::typed_int8_array_object,
-            TypedArrayName::Uint8Array => StandardObjects::typed_uint8_array_object,
-            TypedArrayName::Uint8ClampedArray => StandardObjects::typed_uint8clamped_array_object,
-            TypedArrayName::Int16Array => StandardObjects::typed_int16_array_object,
-            TypedArrayName::Uint16Array => StandardObjects::typed_uint16_array_object,
-            TypedArrayName::Int32Array => StandardObjects::typed_int32_array_object,
-            TypedArrayName::Uint32Array => StandardObjects::typed_uint32_array_object,
-            TypedArrayName::BigInt64Array => StandardObjects::typed_bigint64_array_object,
-            TypedArrayName::BigUint64Array => StandardObjects::typed_biguint64_array_object,
-            TypedArrayName::Float32Array => StandardObjects::typed_float32_array_object,
-            TypedArrayName::Float64Array => StandardObjects::typed_float64_array_object,
-        };
+        let default_constructor = typed_array_name.standard_constructor();
 
         // 2. Let constructor be ? SpeciesConstructor(exemplar, defaultConstructor).
         let constructor = exemplar.species_constructor(default_constructor, context)?;
@@ -2862,7 +2884,7 @@ impl TypedArray {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#typedarray-create
-    fn create(
+    pub(crate) fn create(
         constructor: &JsObject,
         args: &[JsValue],
         context: &mut Context,
@@ -2878,7 +2900,7 @@ impl TypedArray {
         let o = obj_borrow
             .as_typed_array()
             .ok_or_else(|| context.construct_type_error("Value is not a typed array object"))?;
-        if o.is_detached() {
+        if o.is_out_of_bounds() {
             return Err(context.construct_type_error("Buffer of the typed array is detached"));
         }
 
@@ -2921,6 +2943,7 @@ impl TypedArray {
                 .constructor()
                 .into(),
             byte_length,
+            None,
             context,
         )?;
 
@@ -3034,7 +3057,7 @@ impl TypedArray {
 
         // 1. Let srcData be srcArray.[[ViewedArrayBuffer]].
         // 2. If IsDetachedBuffer(srcData) is true, throw a TypeError exception.
-        if src_array.is_detached() {
+        if src_array.is_out_of_bounds() {
             return Err(
                 context.construct_type_error("Cannot initialize typed array from detached buffer")
             );
@@ -3087,7 +3110,8 @@ impl TypedArray {
         // 15. Else,
         } else {
             // a. Let data be ? AllocateArrayBuffer(bufferConstructor, byteLength).
-            let data_obj = ArrayBuffer::allocate(&buffer_constructor.into(), byte_length, context)?;
+            let data_obj =
+                ArrayBuffer::allocate(&buffer_constructor.into(), byte_length, None, context)?;
             let mut data_obj_b = data_obj.borrow_mut();
             let data = data_obj_b
                 .as_array_buffer_mut()
@@ -3191,52 +3215,74 @@ impl TypedArray {
             return Err(context.construct_range_error("Invalid length for typed array"));
         }
 
-        let buffer_byte_length = {
+        // 6. If length is not undefined, then
+        let new_length = if length.is_undefined() {
+            None
+        } else {
+            // a. Let newLength be ? ToIndex(length).
+            Some(length.to_index(context)?)
+        };
+
+        let (buffer_is_fixed_length, buffer_byte_length) = {
             let buffer_obj_b = buffer.borrow();
             let buffer_array = buffer_obj_b
                 .as_array_buffer()
                 .expect("This must be an ArrayBuffer");
 
-            // 6. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
+            // 7. If IsDetachedBuffer(buffer) is true, throw a TypeError exception.
             if buffer_array.is_detached_buffer() {
                 return Err(context
                     .construct_type_error("Cannot construct typed array from detached buffer"));
             }
 
-            // 7. Let bufferByteLength be buffer.[[ArrayBufferByteLength]].
-            buffer_array.array_buffer_byte_length()
+            // 5. Let bufferIsFixedLength be IsFixedLengthArrayBuffer(buffer).
+            // 8. Let bufferByteLength be ArrayBufferByteLength(buffer, seq-cst).
+            (
+                buffer_array.is_fixed_length(),
+                buffer_array.array_buffer_byte_length(),
+            )
         };
 
-        // 8. If length is undefined, then
-        let new_byte_length = if length.is_undefined() {
-            // a. If bufferByteLength modulo elementSize ≠ 0, throw a RangeError exception.
-            if buffer_byte_length % constructor_name.element_size() != 0 {
-                return Err(context.construct_range_error("Invalid length for typed array"));
-            }
-
-            // b. Let newByteLength be bufferByteLength - offset.
-            let new_byte_length = buffer_byte_length as isize - offset as isize;
+        let (new_byte_length, length_tracking) = match new_length {
+            // 9. If length is undefined and bufferIsFixedLength is false, then
+            None if !buffer_is_fixed_length => {
+                // a. If offset > bufferByteLength, throw a RangeError exception.
+                if offset > buffer_byte_length {
+                    return Err(context.construct_range_error("Invalid offset for typed array"));
+                }
 
-            // c. If newByteLength < 0, throw a RangeError exception.
-            if new_byte_length < 0 {
-                return Err(context.construct_range_error("Invalid length for typed array"));
+                // b. Set O.[[ByteLength]] to auto.
+                // c. Set O.[[ArrayLength]] to auto.
+                (0, true)
             }
+            // 10. Else,
+            // a. If length is undefined, then
+            None => {
+                // i. If bufferByteLength modulo elementSize ≠ 0, throw a RangeError exception.
+                if buffer_byte_length % constructor_name.element_size() != 0 {
+                    return Err(context.construct_range_error("Invalid length for typed array"));
+                }
 
-            new_byte_length as usize
-        // 9. Else,
-        } else {
-            // 5. If length is not undefined, then
-            // a. Let newLength be ? ToIndex(length).
-
-            // a. Let newByteLength be newLength × elementSize.
-            let new_byte_length = length.to_index(context)? * constructor_name.element_size();
+                // ii. Let newByteLength be bufferByteLength - offset.
+                // iii. If newByteLength < 0, throw a RangeError exception.
+                let new_byte_length = buffer_byte_length.checked_sub(offset).ok_or_else(|| {
+                    context.construct_range_error("Invalid length for typed array")
+                })?;
 
-            // b. If offset + newByteLength > bufferByteLength, throw a RangeError exception.
-            if offset + new_byte_length > buffer_byte_length {
-                return Err(context.construct_range_error("Invalid length for typed array"));
+                (new_byte_length, false)
             }
+            // b. Else,
+            Some(new_length) => {
+                // i. Let newByteLength be newLength × elementSize.
+                let new_byte_length = new_length * constructor_name.element_size();
+
+                // ii. If offset + newByteLength > bufferByteLength, throw a RangeError exception.
+                if offset + new_byte_length > buffer_byte_length {
+                    return Err(context.construct_range_error("Invalid length for typed array"));
+                }
 
-            new_byte_length
+                (new_byte_length, false)
+            }
         };
 
         let mut o_obj_borrow = o.borrow_mut();
@@ -3244,15 +3290,17 @@ impl TypedArray {
             .as_typed_array_mut()
             .expect("This must be an ArrayBuffer");
 
-        // 10. Set O.[[ViewedArrayBuffer]] to buffer.
-        o.set_viewed_array_buffer(Some(buffer));
-        // 11. Set O.[[ByteLength]] to newByteLength.
+        // 10.c. Set O.[[ByteLength]] to newByteLength.
         o.set_byte_length(new_byte_length);
+        // 10.d. Set O.[[ArrayLength]] to newByteLength / elementSize.
+        o.set_array_length(new_byte_length / constructor_name.element_size());
+        o.set_length_tracking(length_tracking);
+        // 11. Set O.[[ViewedArrayBuffer]] to buffer.
+        o.set_viewed_array_buffer(Some(buffer));
         // 12. Set O.[[ByteOffset]] to offset.
         o.set_byte_offset(offset);
-        // 13. Set O.[[ArrayLength]] to newByteLength / elementSize.
-        o.set_array_length(new_byte_length / constructor_name.element_size());
 
+        // 13. Return unused.
         Ok(())
     }
 
@@ -3294,7 +3342,7 @@ impl TypedArray {
 
 /// Names of all the typed arrays.
 #[derive(Debug, Clone, Copy, Finalize, PartialEq)]
-pub(crate) enum TypedArrayName {
+pub enum TypedArrayName {
     Int8Array,
     Uint8Array,
     Uint8ClampedArray,
@@ -3318,7 +3366,7 @@ impl TypedArrayName {
     ///
     /// [spec]: https://tc39.es/ecma262/#table-the-typedarray-constructors
     #[inline]
-    pub(crate) const fn element_size(self) -> usize {
+    pub const fn element_size(self) -> usize {
         match self {
             Self::Int8Array | Self::Uint8Array | Self::Uint8ClampedArray => 1,
             Self::Int16Array | Self::Uint16Array => 2,
@@ -3338,7 +3386,7 @@ impl TypedArrayName {
 
     /// Gets the name of this typed array name.
     #[inline]
-    pub(crate) const fn name(&self) -> &str {
+    pub const fn name(&self) -> &str {
         match self {
             TypedArrayName::Int8Array => "Int8Array",
             TypedArrayName::Uint8Array => "Uint8Array",
@@ -3354,6 +3402,24 @@ impl TypedArrayName {
         }
     }
 
+    /// Gets the accessor of the standard constructor of this typed array name.
+    #[inline]
+    pub(crate) fn standard_constructor(self) -> fn(&StandardObjects) -> &StandardConstructor {
+        match self {
+            Self::Int8Array => StandardObjects::typed_int8_array_object,
+            Self::Uint8Array => StandardObjects::typed_uint8_array_object,
+            Self::Uint8ClampedArray => StandardObjects::typed_uint8clamped_array_object,
+            Self::Int16Array => StandardObjects::typed_int16_array_object,
+            Self::Uint16Array => StandardObjects::typed_uint16_array_object,
+            Self::Int32Array => StandardObjects::typed_int32_array_object,
+            Self::Uint32Array => StandardObjects::typed_uint32_array_object,
+            Self::BigInt64Array => StandardObjects::typed_bigint64_array_object,
+            Self::BigUint64Array => StandardObjects::typed_biguint64_array_object,
+            Self::Float32Array => StandardObjects::typed_float32_array_object,
+            Self::Float64Array => StandardObjects::typed_float64_array_object,
+        }
+    }
+
     pub(crate) fn is_big_int_element_type(&self) -> bool {
         matches!(
             self,
@@ -2,15 +2,21 @@ use gc::Gc;
 
 use crate::{
     builtins::function::ThisMode,
+    environment::compile_time_environment::{
+        BindingKind, BindingLocator, CompileTimeEnvironmentStack, ScopeLayout,
+    },
     syntax::ast::{
         node::{
-            Declaration, GetConstField, GetField, MethodDefinitionKind, PropertyDefinition,
-            PropertyName, StatementList,
+            declaration::{BindingPatternTypeArray, BindingPatternTypeObject, DeclarationPattern},
+            iteration::{ForInLoop, ForLoop, ForOfLoop},
+            template::TemplateElement,
+            Block, Declaration, DeclarationList, FormalParameter, GetConstField, GetField,
+            MethodDefinitionKind, PropertyDefinition, PropertyName, StatementList,
         },
         op::{AssignOp, BinOp, BitOp, CompOp, LogOp, NumOp, UnaryOp},
-        Const, Node,
+        Const, Node, Position,
     },
-    vm::{CodeBlock, Opcode},
+    vm::{CodeBlock, FunctionIndices, Opcode, RegisterCodeBlock, TemplateSite},
     JsBigInt, JsString, JsValue,
 };
 use std::collections::HashMap;
@@ -27,17 +33,58 @@ struct Label {
     index: u32,
 }
 
+/// The kind of statement that a `break`, `continue` or `return` may have to leave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JumpControlKind {
+    Loop,
+    /// A `for..in` or `for..of` loop, its iterator has to be closed when it is left early.
+    IteratorLoop,
+    Switch,
+    /// The `try` block of a `try` statement, its exception handler is active.
+    Try {
+        has_finally: bool,
+    },
+    /// The `catch` block of a `try` statement, its exception handler is only active if the
+    /// statement has a `finally` block.
+    Catch {
+        has_finally: bool,
+    },
+    /// The `finally` block of a `try` statement, its completion has to be discarded.
+    Finally,
+    /// A labelled statement that is not a loop, only a `break` with its label targets it.
+    Labelled,
+}
+
 #[derive(Debug, Clone)]
 struct JumpControlInfo {
     label: Option<Box<str>>,
     start_address: u32,
-    is_loop: bool,
+    kind: JumpControlKind,
     breaks: Vec<Label>,
+    /// Jumps to the `finally` block, for `try` statements.
+    finally_jumps: Vec<Label>,
+    /// The number of environments pushed in the current function when the statement was entered.
+    env_depth: u32,
+}
+
+impl JumpControlKind {
+    #[inline]
+    fn is_loop(self) -> bool {
+        matches!(self, Self::Loop | Self::IteratorLoop)
+    }
+}
+
+/// The opcode used to declare a binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BindingOpcode {
+    Var,
+    Let,
+    Const,
 }
 
 #[derive(Debug, Clone, Copy)]
 enum Access<'a> {
-    Variable { index: u32 },
+    Variable { name: &'a str },
     ByName { node: &'a GetConstField },
     ByValue { node: &'a GetField },
     This,
@@ -50,7 +97,18 @@ pub struct ByteCompiler {
     names_map: HashMap<JsString, u32>,
     functions_map: HashMap<JsString, u32>,
     jump_info: Vec<JumpControlInfo>,
+    env_depth: u32,
     top_level: bool,
+    /// The indices of the functions defined in the code block, by their syntax node.
+    function_indices: FunctionIndices,
+    /// Whether register code is generated for the code block and the functions defined in it.
+    register_code: bool,
+    /// The declarative environments that enclose the code, to resolve bindings.
+    environments: CompileTimeEnvironmentStack,
+    /// Whether the code calls `eval` directly.
+    has_direct_eval: bool,
+    /// Whether the code contains syntax that can not be compiled to bytecode yet.
+    unsupported: bool,
 }
 
 impl ByteCompiler {
@@ -65,10 +123,35 @@ impl ByteCompiler {
             names_map: HashMap::new(),
             functions_map: HashMap::new(),
             jump_info: Vec::new(),
+            env_depth: 0,
             top_level: true,
+            function_indices: FunctionIndices::new(),
+            register_code: false,
+            environments: CompileTimeEnvironmentStack::default(),
+            has_direct_eval: false,
+            unsupported: false,
         }
     }
 
+    /// Enables the generation of register code for the functions that are compiled, in
+    /// addition to bytecode.
+    ///
+    /// The register code binds its variables by name, so bindings are not resolved at compile
+    /// time when it is enabled.
+    #[inline]
+    pub(crate) fn set_register_code(&mut self, register_code: bool) {
+        self.register_code = register_code;
+    }
+
+    /// Compiles a script to register code, after it has been compiled to bytecode.
+    ///
+    /// The script keeps running on the stack machine if it can not be compiled to register
+    /// code.
+    pub(crate) fn compile_register_code(&mut self, list: &StatementList) {
+        self.code_block.register_code =
+            RegisterCodeBlock::compile_script(list, &self.function_indices);
+    }
+
     #[inline]
     fn get_or_insert_literal(&mut self, literal: Literal) -> u32 {
         if let Some(index) = self.literals_map.get(&literal) {
@@ -208,16 +291,20 @@ impl ByteCompiler {
     }
 
     #[inline]
-    fn patch_jump_with_target(&mut self, label: Label, target: u32) {
-        let Label { index } = label;
-
+    fn patch_u32(&mut self, index: u32, value: u32) {
         let index = index as usize;
 
-        let bytes = target.to_ne_bytes();
-        self.code_block.code[index + 1] = bytes[0];
-        self.code_block.code[index + 2] = bytes[1];
-        self.code_block.code[index + 3] = bytes[2];
-        self.code_block.code[index + 4] = bytes[3];
+        let bytes = value.to_ne_bytes();
+        self.code_block.code[index] = bytes[0];
+        self.code_block.code[index + 1] = bytes[1];
+        self.code_block.code[index + 2] = bytes[2];
+        self.code_block.code[index + 3] = bytes[3];
+    }
+
+    #[inline]
+    fn patch_jump_with_target(&mut self, label: Label, target: u32) {
+        let Label { index } = label;
+        self.patch_u32(index + 1, target);
     }
 
     #[inline]
@@ -227,20 +314,32 @@ impl ByteCompiler {
     }
 
     #[inline]
-    fn push_loop_control_info(&mut self, label: Option<Box<str>>, start_address: u32) {
+    fn push_jump_control_info(
+        &mut self,
+        label: Option<Box<str>>,
+        start_address: u32,
+        kind: JumpControlKind,
+    ) {
         self.jump_info.push(JumpControlInfo {
             label,
             start_address,
-            is_loop: true,
+            kind,
             breaks: Vec::new(),
+            finally_jumps: Vec::new(),
+            env_depth: self.env_depth,
         })
     }
 
+    #[inline]
+    fn push_loop_control_info(&mut self, label: Option<Box<str>>, start_address: u32) {
+        self.push_jump_control_info(label, start_address, JumpControlKind::Loop)
+    }
+
     #[inline]
     fn pop_loop_control_info(&mut self) {
         let loop_info = self.jump_info.pop().unwrap();
 
-        assert!(loop_info.is_loop);
+        assert!(loop_info.kind.is_loop());
 
         for label in loop_info.breaks {
             self.patch_jump(label);
@@ -249,45 +348,208 @@ impl ByteCompiler {
 
     #[inline]
     fn push_switch_control_info(&mut self, label: Option<Box<str>>, start_address: u32) {
-        self.jump_info.push(JumpControlInfo {
-            label,
-            start_address,
-            is_loop: false,
-            breaks: Vec::new(),
-        })
+        self.push_jump_control_info(label, start_address, JumpControlKind::Switch)
     }
 
     #[inline]
     fn pop_switch_control_info(&mut self) {
         let info = self.jump_info.pop().unwrap();
 
-        assert!(!info.is_loop);
+        assert_eq!(info.kind, JumpControlKind::Switch);
+
+        for label in info.breaks {
+            self.patch_jump(label);
+        }
+    }
+
+    /// Compiles a labelled block or `if` statement, which is the target of the `break`
+    /// statements with its label.
+    fn compile_labelled<F>(&mut self, label: Option<&str>, compile: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let label = match label {
+            Some(label) => label,
+            None => return compile(self),
+        };
+
+        let start_address = self.next_opcode_location();
+        self.push_jump_control_info(Some(label.into()), start_address, JumpControlKind::Labelled);
+        compile(self);
 
+        let info = self.jump_info.pop().unwrap();
+        assert_eq!(info.kind, JumpControlKind::Labelled);
         for label in info.breaks {
             self.patch_jump(label);
         }
     }
 
+    /// Gets the layout of a scope, which is empty if bindings are not resolved at compile time.
+    #[inline]
+    fn scope_layout(&self, layout: ScopeLayout) -> Gc<ScopeLayout> {
+        if self.register_code {
+            Gc::new(ScopeLayout::default())
+        } else {
+            Gc::new(layout)
+        }
+    }
+
+    /// Pushes a declarative environment with the bindings of `layout`, returns the index of the
+    /// scope.
+    #[inline]
+    fn push_declarative_environment(&mut self, layout: ScopeLayout) -> u32 {
+        let layout = self.scope_layout(layout);
+        let index = self.code_block.scopes.len() as u32;
+        self.code_block.scopes.push(layout.clone());
+        self.environments.push(layout, false);
+        self.emit(Opcode::PushDeclarativeEnvironment, &[index]);
+        self.env_depth += 1;
+        index
+    }
+
+    #[inline]
+    fn pop_environment(&mut self) {
+        self.emit_opcode(Opcode::PopEnvironment);
+        self.environments.pop();
+        self.env_depth -= 1;
+    }
+
+    /// Pushes the value of the binding `name`.
+    #[inline]
+    fn emit_get_name(&mut self, name: &str) {
+        match self.environments.resolve(name) {
+            BindingLocator::Declarative { depth, slot } => {
+                self.emit(Opcode::GetBinding, &[depth, slot])
+            }
+            BindingLocator::Dynamic => {
+                let index = self.get_or_insert_name(name);
+                self.emit(Opcode::GetName, &[index]);
+            }
+        }
+    }
+
+    /// Assigns the value on the top of the stack to the binding `name`.
+    #[inline]
+    fn emit_set_name(&mut self, name: &str) {
+        match self.environments.resolve(name) {
+            BindingLocator::Declarative { depth, slot } => {
+                self.emit(Opcode::SetBinding, &[depth, slot])
+            }
+            BindingLocator::Dynamic => {
+                let index = self.get_or_insert_name(name);
+                self.emit(Opcode::SetName, &[index]);
+            }
+        }
+    }
+
+    /// Emits the code that leaves the statements in `jump_info[target..]`, running their
+    /// `finally` blocks, closing their iterators and popping their environments, before a
+    /// `break`, `continue` or `return`.
+    ///
+    /// `has_value` is `true` if the value on the top of the stack has to be kept.
+    ///
+    /// Returns the depth of the environment stack once the statements are left.
+    fn leave_jump_controls(&mut self, target: usize, has_value: bool) -> u32 {
+        let mut env_depth = self.env_depth;
+        for index in (target..self.jump_info.len()).rev() {
+            let JumpControlInfo {
+                kind,
+                env_depth: info_env_depth,
+                ..
+            } = self.jump_info[index];
+            for _ in info_env_depth..env_depth {
+                self.emit_opcode(Opcode::PopEnvironment);
+            }
+            env_depth = info_env_depth;
+
+            let has_finally = match kind {
+                JumpControlKind::Try { has_finally } => {
+                    self.emit_opcode(Opcode::TryEnd);
+                    has_finally
+                }
+                JumpControlKind::Catch { has_finally } => {
+                    if has_finally {
+                        self.emit_opcode(Opcode::TryEnd);
+                    }
+                    has_finally
+                }
+                JumpControlKind::Finally => {
+                    self.emit_opcode(Opcode::FinallyDiscard);
+                    false
+                }
+                JumpControlKind::IteratorLoop => {
+                    self.emit_opcode(Opcode::TryEnd);
+                    if has_value {
+                        self.emit(Opcode::RotateDown, &[3]);
+                    }
+                    self.emit_opcode(Opcode::IteratorClose);
+                    false
+                }
+                JumpControlKind::Loop | JumpControlKind::Switch | JumpControlKind::Labelled => {
+                    false
+                }
+            };
+
+            // Run the `finally` block, which continues with the code after the jump to it.
+            if has_finally {
+                let resume = self.jump_with_custom_opcode(Opcode::FinallySetJump);
+                let finally = self.jump();
+                self.jump_info[index].finally_jumps.push(finally);
+                self.patch_jump(resume);
+            }
+        }
+
+        env_depth
+    }
+
+    /// Emits the code that leaves the statements up to the target of a `break` or `continue`.
+    fn leave_to_jump_target(&mut self, target: usize) {
+        let env_depth = self.leave_jump_controls(target + 1, false);
+        for _ in self.jump_info[target].env_depth..env_depth {
+            self.emit_opcode(Opcode::PopEnvironment);
+        }
+
+        // The exception handler of an iteration of a `for..in` or `for..of` loop.
+        if self.jump_info[target].kind == JumpControlKind::IteratorLoop {
+            self.emit_opcode(Opcode::TryEnd);
+        }
+    }
+
+    /// Finds the statement targeted by a `break` or `continue` with the given label.
+    ///
+    /// Returns `None` for the labels of statements that can not be targeted yet.
+    fn find_jump_target(&self, label: Option<&str>, is_continue: bool) -> Option<usize> {
+        self.jump_info.iter().rposition(|info| match label {
+            None if is_continue => info.kind.is_loop(),
+            None => info.kind.is_loop() || info.kind == JumpControlKind::Switch,
+            Some(label) => {
+                info.label.as_deref() == Some(label) && (!is_continue || info.kind.is_loop())
+            }
+        })
+    }
+
     #[inline]
     fn compile_access<'a>(&mut self, node: &'a Node) -> Access<'a> {
         match node {
-            Node::Identifier(name) => {
-                let index = self.get_or_insert_name(name.as_ref());
-                Access::Variable { index }
-            }
+            Node::Identifier(name) => Access::Variable {
+                name: name.as_ref(),
+            },
             Node::GetConstField(node) => Access::ByName { node },
             Node::GetField(node) => Access::ByValue { node },
             Node::This => Access::This,
-            _ => unreachable!(),
+            // Destructuring assignment targets are not compiled yet; setting `this` only pops
+            // the value, so the stack stays balanced until the code block is discarded.
+            _ => {
+                self.unsupported = true;
+                Access::This
+            }
         }
     }
 
     #[inline]
     fn access_get(&mut self, access: Access<'_>, use_expr: bool) {
         match access {
-            Access::Variable { index: name } => {
-                self.emit(Opcode::GetName, &[name]);
-            }
+            Access::Variable { name } => self.emit_get_name(name),
             Access::ByName { node } => {
                 let index = self.get_or_insert_name(node.field());
                 self.compile_expr(node.obj(), true);
@@ -319,9 +581,7 @@ impl ByteCompiler {
         }
 
         match access {
-            Access::Variable { index } => {
-                self.emit(Opcode::SetName, &[index]);
-            }
+            Access::Variable { name } => self.emit_set_name(name),
             Access::ByName { node } => {
                 self.compile_expr(node.obj(), true);
                 let index = self.get_or_insert_name(node.field());
@@ -332,19 +592,33 @@ impl ByteCompiler {
                 self.compile_expr(node.obj(), true);
                 self.emit(Opcode::SetPropertyByValue, &[]);
             }
-            Access::This => todo!("access_set 'this'"),
+            // Assignments to `this` are ignored.
+            Access::This => self.emit_opcode(Opcode::Pop),
         }
     }
 
+    /// Compiles the statements of a script or function body.
+    ///
+    /// If `use_expr` is set the completion value of the statements is kept in the call frame, to
+    /// be returned once the end of the code block is reached.
     #[inline]
     pub fn compile_statement_list(&mut self, list: &StatementList, use_expr: bool) {
         for (i, node) in list.items().iter().enumerate() {
-            if i + 1 == list.items().len() {
-                self.compile_stmt(node, use_expr);
-                break;
+            if let Some(position) = list.position(i) {
+                self.add_source_position(position);
             }
+            self.compile_stmt(node, use_expr);
+        }
+    }
 
-            self.compile_stmt(node, false);
+    /// Maps the next instruction to a statement starting at `position` in the source code.
+    #[inline]
+    fn add_source_position(&mut self, position: Position) {
+        let location = self.next_opcode_location();
+        match self.code_block.source_map.last_mut() {
+            // The previous statement emitted no instructions.
+            Some((last, last_position)) if *last == location => *last_position = position,
+            _ => self.code_block.source_map.push((location, position)),
         }
     }
 
@@ -374,7 +648,7 @@ impl ByteCompiler {
                         self.emit(Opcode::Inc, &[]);
 
                         let access = self.compile_access(unary.target());
-                        self.access_set(access, None, use_expr);
+                        self.access_set(access, None, true);
                         None
                     }
                     UnaryOp::DecrementPre => {
@@ -382,7 +656,7 @@ impl ByteCompiler {
                         self.emit(Opcode::Dec, &[]);
 
                         let access = self.compile_access(unary.target());
-                        self.access_set(access, None, use_expr);
+                        self.access_set(access, None, true);
                         None
                     }
                     UnaryOp::IncrementPost => {
@@ -392,10 +666,6 @@ impl ByteCompiler {
                         let access = self.compile_access(unary.target());
                         self.access_set(access, None, false);
 
-                        if !use_expr {
-                            self.emit(Opcode::Pop, &[]);
-                        }
-
                         None
                     }
                     UnaryOp::DecrementPost => {
@@ -405,10 +675,6 @@ impl ByteCompiler {
                         let access = self.compile_access(unary.target());
                         self.access_set(access, None, false);
 
-                        if !use_expr {
-                            self.emit(Opcode::Pop, &[]);
-                        }
-
                         None
                     }
                     UnaryOp::Delete => match unary.target() {
@@ -424,9 +690,9 @@ impl ByteCompiler {
                             self.emit(Opcode::DeletePropertyByValue, &[]);
                             None
                         }
-                        // TODO: implement delete on references.
-                        Node::Identifier(_) => {
-                            self.emit(Opcode::PushFalse, &[]);
+                        Node::Identifier(name) => {
+                            let index = self.get_or_insert_name(name.as_ref());
+                            self.emit(Opcode::DeleteName, &[index]);
                             None
                         }
                         _ => {
@@ -508,13 +774,11 @@ impl ByteCompiler {
                             LogOp::And => {
                                 let exit = self.jump_with_custom_opcode(Opcode::LogicalAnd);
                                 self.compile_expr(binary.rhs(), true);
-                                self.emit(Opcode::ToBoolean, &[]);
                                 self.patch_jump(exit);
                             }
                             LogOp::Or => {
                                 let exit = self.jump_with_custom_opcode(Opcode::LogicalOr);
                                 self.compile_expr(binary.rhs(), true);
-                                self.emit(Opcode::ToBoolean, &[]);
                                 self.patch_jump(exit);
                             }
                             LogOp::Coalesce => {
@@ -545,7 +809,6 @@ impl ByteCompiler {
                             AssignOp::BoolAnd => {
                                 let exit = self.jump_with_custom_opcode(Opcode::LogicalAnd);
                                 self.compile_expr(binary.rhs(), true);
-                                self.emit(Opcode::ToBoolean, &[]);
                                 self.patch_jump(exit);
 
                                 None
@@ -553,7 +816,6 @@ impl ByteCompiler {
                             AssignOp::BoolOr => {
                                 let exit = self.jump_with_custom_opcode(Opcode::LogicalOr);
                                 self.compile_expr(binary.rhs(), true);
-                                self.emit(Opcode::ToBoolean, &[]);
                                 self.patch_jump(exit);
 
                                 None
@@ -595,7 +857,7 @@ impl ByteCompiler {
                             self.emit(Opcode::SetPropertyByName, &[index]);
                         }
                         PropertyDefinition::Property(name, node) => {
-                            self.compile_stmt(node, true);
+                            self.compile_expr(node, true);
                             self.emit_opcode(Opcode::Swap);
                             match name {
                                 PropertyName::Literal(name) => {
@@ -603,7 +865,7 @@ impl ByteCompiler {
                                     self.emit(Opcode::SetPropertyByName, &[index]);
                                 }
                                 PropertyName::Computed(name_node) => {
-                                    self.compile_stmt(name_node, true);
+                                    self.compile_expr(name_node, true);
                                     self.emit_opcode(Opcode::Swap);
                                     self.emit_opcode(Opcode::SetPropertyByValue);
                                 }
@@ -612,7 +874,7 @@ impl ByteCompiler {
                         PropertyDefinition::MethodDefinition(kind, name, func) => {
                             match kind {
                                 MethodDefinitionKind::Get => {
-                                    self.compile_stmt(&func.clone().into(), true);
+                                    self.compile_expr(&func.clone().into(), true);
                                     self.emit_opcode(Opcode::Swap);
                                     match name {
                                         PropertyName::Literal(name) => {
@@ -620,14 +882,14 @@ impl ByteCompiler {
                                             self.emit(Opcode::SetPropertyGetterByName, &[index]);
                                         }
                                         PropertyName::Computed(name_node) => {
-                                            self.compile_stmt(name_node, true);
+                                            self.compile_expr(name_node, true);
                                             self.emit_opcode(Opcode::Swap);
                                             self.emit_opcode(Opcode::SetPropertyGetterByValue);
                                         }
                                     }
                                 }
                                 MethodDefinitionKind::Set => {
-                                    self.compile_stmt(&func.clone().into(), true);
+                                    self.compile_expr(&func.clone().into(), true);
                                     self.emit_opcode(Opcode::Swap);
                                     match name {
                                         PropertyName::Literal(name) => {
@@ -635,14 +897,14 @@ impl ByteCompiler {
                                             self.emit(Opcode::SetPropertySetterByName, &[index]);
                                         }
                                         PropertyName::Computed(name_node) => {
-                                            self.compile_stmt(name_node, true);
+                                            self.compile_expr(name_node, true);
                                             self.emit_opcode(Opcode::Swap);
                                             self.emit_opcode(Opcode::SetPropertySetterByValue);
                                         }
                                     }
                                 }
                                 MethodDefinitionKind::Ordinary => {
-                                    self.compile_stmt(&func.clone().into(), true);
+                                    self.compile_expr(&func.clone().into(), true);
                                     self.emit_opcode(Opcode::Swap);
                                     match name {
                                         PropertyName::Literal(name) => {
@@ -650,7 +912,7 @@ impl ByteCompiler {
                                             self.emit(Opcode::SetPropertyByName, &[index]);
                                         }
                                         PropertyName::Computed(name_node) => {
-                                            self.compile_stmt(name_node, true);
+                                            self.compile_expr(name_node, true);
                                             self.emit_opcode(Opcode::Swap);
                                             self.emit_opcode(Opcode::SetPropertyByValue);
                                         }
@@ -666,7 +928,7 @@ impl ByteCompiler {
                                             self.emit(Opcode::SetPropertyByName, &[index]);
                                         }
                                         PropertyName::Computed(name_node) => {
-                                            self.compile_stmt(name_node, true);
+                                            self.compile_expr(name_node, true);
                                             self.emit_opcode(Opcode::Swap);
                                             self.emit_opcode(Opcode::SetPropertyByValue);
                                         }
@@ -682,7 +944,7 @@ impl ByteCompiler {
                                             self.emit(Opcode::SetPropertyByName, &[index])
                                         }
                                         PropertyName::Computed(name_node) => {
-                                            self.compile_stmt(name_node, true);
+                                            self.compile_expr(name_node, true);
                                             self.emit_opcode(Opcode::Swap);
                                             self.emit_opcode(Opcode::SetPropertyByValue);
                                         }
@@ -698,7 +960,7 @@ impl ByteCompiler {
                                             self.emit(Opcode::SetPropertyByName, &[index])
                                         }
                                         PropertyName::Computed(name_node) => {
-                                            self.compile_stmt(name_node, true);
+                                            self.compile_expr(name_node, true);
                                             self.emit_opcode(Opcode::Swap);
                                             self.emit_opcode(Opcode::SetPropertyByValue);
                                         }
@@ -706,8 +968,11 @@ impl ByteCompiler {
                                 }
                             }
                         }
-                        // TODO: Spread Object
-                        PropertyDefinition::SpreadObject(_) => todo!(),
+                        PropertyDefinition::SpreadObject(source) => {
+                            self.compile_expr(source, true);
+                            self.emit(Opcode::CopyDataProperties, &[0]);
+                            self.emit_opcode(Opcode::Pop);
+                        }
                     }
                 }
 
@@ -716,8 +981,9 @@ impl ByteCompiler {
                 }
             }
             Node::Identifier(name) => {
-                let index = self.get_or_insert_name(name.as_ref());
-                let access = Access::Variable { index };
+                let access = Access::Variable {
+                    name: name.as_ref(),
+                };
                 self.access_get(access, use_expr);
             }
             Node::Assign(assign) => {
@@ -746,16 +1012,7 @@ impl ByteCompiler {
                 }
             }
             Node::ArrayDecl(array) => {
-                let mut count = 0;
-                for element in array.as_ref().iter().rev() {
-                    if let Node::Spread(_) = element {
-                        todo!("array with spread element");
-                    } else {
-                        self.compile_expr(element, true);
-                    }
-                    count += 1;
-                }
-                self.emit(Opcode::PushNewArray, &[count]);
+                self.compile_array(array.as_ref());
 
                 if !use_expr {
                     self.emit(Opcode::Pop, &[]);
@@ -767,154 +1024,126 @@ impl ByteCompiler {
             Node::FunctionExpr(_function) => self.function(expr, use_expr),
             Node::ArrowFunctionDecl(_function) => self.function(expr, use_expr),
             Node::Call(call) => {
-                for arg in call.args().iter().rev() {
-                    self.compile_expr(arg, true);
+                if matches!(call.expr(), Node::Identifier(name) if name.as_ref() == "eval") {
+                    self.has_direct_eval = true;
                 }
-                match call.expr() {
-                    Node::GetConstField(field) => {
-                        self.compile_expr(field.obj(), true);
-                        self.emit(Opcode::Dup, &[]);
-                        let index = self.get_or_insert_name(field.field());
-                        self.emit(Opcode::GetPropertyByName, &[index]);
-                    }
-                    Node::GetField(field) => {
-                        self.compile_expr(field.obj(), true);
-                        self.emit(Opcode::Dup, &[]);
-                        self.compile_expr(field.field(), true);
-                        self.emit(Opcode::Swap, &[]);
-                        self.emit(Opcode::GetPropertyByValue, &[]);
-                    }
-                    expr => {
-                        self.emit(Opcode::This, &[]);
-                        self.compile_expr(expr, true);
-                    }
+                self.compile_callee(call.expr());
+                match self.compile_arguments(call.args()) {
+                    Some(argc) => self.emit(Opcode::Call, &[argc]),
+                    None => self.emit_opcode(Opcode::CallSpread),
                 }
-                self.emit(Opcode::Call, &[call.args().len() as u32]);
 
                 if !use_expr {
                     self.emit(Opcode::Pop, &[]);
                 }
             }
-            expr => todo!("TODO compile: {}", expr),
-        }
-    }
-
-    #[inline]
-    pub fn compile_stmt(&mut self, node: &Node, use_expr: bool) {
-        match node {
-            Node::VarDeclList(list) => {
-                for decl in list.as_ref() {
-                    match decl {
-                        Declaration::Identifier { ident, .. } => {
-                            let index = self.get_or_insert_name(ident.as_ref());
-                            self.emit(Opcode::DefVar, &[index]);
+            Node::New(new) => {
+                self.compile_expr(new.expr(), true);
+                let index = self.get_or_insert_name(&new.expr().to_string());
+                match self.compile_arguments(new.args()) {
+                    Some(argc) => self.emit(Opcode::New, &[argc, index]),
+                    None => self.emit(Opcode::NewSpread, &[index]),
+                }
 
-                            if let Some(expr) = decl.init() {
-                                self.compile_expr(expr, true);
-                                self.emit(Opcode::InitLexical, &[index]);
-                            };
-                        }
-                        Declaration::Pattern(pattern) => {
-                            for ident in pattern.idents() {
-                                let index = self.get_or_insert_name(ident);
-                                self.emit(Opcode::DefVar, &[index]);
-
-                                if let Some(expr) = decl.init() {
-                                    self.compile_expr(expr, true);
-                                    self.emit(Opcode::InitLexical, &[index]);
-                                };
-                            }
-                        }
-                    }
+                if !use_expr {
+                    self.emit(Opcode::Pop, &[]);
                 }
             }
-            Node::LetDeclList(list) => {
-                for decl in list.as_ref() {
-                    match decl {
-                        Declaration::Identifier { ident, .. } => {
-                            let index = self.get_or_insert_name(ident.as_ref());
-                            self.emit(Opcode::DefLet, &[index]);
-
-                            if let Some(expr) = decl.init() {
-                                self.compile_expr(expr, true);
-                                self.emit(Opcode::InitLexical, &[index]);
-                            };
-                        }
-                        Declaration::Pattern(pattern) => {
-                            for ident in pattern.idents() {
-                                let index = self.get_or_insert_name(ident);
-                                self.emit(Opcode::DefLet, &[index]);
-
-                                if let Some(expr) = decl.init() {
-                                    self.compile_expr(expr, true);
-                                    self.emit(Opcode::InitLexical, &[index]);
-                                };
-                            }
+            Node::TemplateLit(template) => {
+                for element in template.elements() {
+                    match element {
+                        TemplateElement::String(s) => {
+                            self.emit_push_literal(Literal::String(s.as_ref().into()))
                         }
+                        TemplateElement::Expr(expr) => self.compile_expr(expr, true),
                     }
                 }
+                self.emit(Opcode::ConcatToString, &[template.elements().len() as u32]);
+
+                if !use_expr {
+                    self.emit(Opcode::Pop, &[]);
+                }
             }
-            Node::ConstDeclList(list) => {
-                for decl in list.as_ref() {
-                    match decl {
-                        Declaration::Identifier { ident, .. } => {
-                            let index = self.get_or_insert_name(ident.as_ref());
-                            self.emit(Opcode::DefConst, &[index]);
+            Node::TaggedTemplate(template) => {
+                self.compile_callee(template.tag());
+
+                // The template object is created once per tagged template, see
+                // `TemplateSite::template_object`.
+                let index = self.code_block.templates.len() as u32;
+                self.code_block.templates.push(TemplateSite::new(
+                    template
+                        .cookeds()
+                        .iter()
+                        .map(|cooked| cooked.as_deref().map(JsString::new))
+                        .collect(),
+                    template.raws().iter().map(JsString::new).collect(),
+                ));
+                self.emit(Opcode::GetTemplateObject, &[index]);
+
+                for expr in template.exprs() {
+                    self.compile_expr(expr, true);
+                }
+                self.emit(Opcode::Call, &[template.exprs().len() as u32 + 1]);
 
-                            if let Some(expr) = decl.init() {
-                                self.compile_expr(expr, true);
-                                self.emit(Opcode::InitLexical, &[index]);
-                            };
-                        }
-                        Declaration::Pattern(pattern) => {
-                            for ident in pattern.idents() {
-                                let index = self.get_or_insert_name(ident);
-                                self.emit(Opcode::DefConst, &[index]);
-
-                                if let Some(expr) = decl.init() {
-                                    self.compile_expr(expr, true);
-                                    self.emit(Opcode::InitLexical, &[index]);
-                                };
-                            }
-                        }
-                    }
+                if !use_expr {
+                    self.emit(Opcode::Pop, &[]);
                 }
             }
-            Node::If(node) => {
-                self.compile_expr(node.cond(), true);
-                let jelse = self.jump_if_false();
+            Node::Spread(spread) => self.compile_expr(spread.val(), use_expr),
+            // TODO: Implement async functions, generators and the remaining expressions
+            _ => {
+                self.unsupported = true;
+                if use_expr {
+                    self.emit_opcode(Opcode::PushUndefined);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub fn compile_stmt(&mut self, node: &Node, use_expr: bool) {
+        match node {
+            Node::VarDeclList(list) => self.compile_declaration_list(list),
+            Node::LetDeclList(list) => self.compile_declaration_list(list),
+            Node::ConstDeclList(list) => self.compile_declaration_list(list),
+            Node::If(node) => self.compile_labelled(node.label(), |compiler| {
+                compiler.compile_expr(node.cond(), true);
+                let jelse = compiler.jump_if_false();
 
-                self.compile_stmt(node.body(), false);
+                compiler.compile_stmt(node.body(), use_expr);
 
                 match node.else_node() {
                     None => {
-                        self.patch_jump(jelse);
+                        compiler.patch_jump(jelse);
                     }
                     Some(else_body) => {
-                        let exit = self.jump();
-                        self.patch_jump(jelse);
-                        self.compile_stmt(else_body, false);
-                        self.patch_jump(exit);
+                        let exit = compiler.jump();
+                        compiler.patch_jump(jelse);
+                        compiler.compile_stmt(else_body, use_expr);
+                        compiler.patch_jump(exit);
                     }
                 }
-            }
+            }),
             Node::WhileLoop(while_) => {
                 let start_address = self.next_opcode_location();
                 self.push_loop_control_info(while_.label().map(Into::into), start_address);
 
                 self.compile_expr(while_.cond(), true);
                 let exit = self.jump_if_false();
-                self.compile_stmt(while_.body(), false);
+                self.compile_stmt(while_.body(), use_expr);
                 self.emit(Opcode::Jump, &[start_address]);
                 self.patch_jump(exit);
 
                 self.pop_loop_control_info();
             }
+            Node::ForLoop(for_loop) => self.compile_for_loop(for_loop, use_expr),
+            Node::ForInLoop(for_in_loop) => self.compile_for_in_loop(for_in_loop, use_expr),
+            Node::ForOfLoop(for_of_loop) => self.compile_for_of_loop(for_of_loop, use_expr),
             Node::DoWhileLoop(do_while) => {
                 let start_address = self.next_opcode_location();
                 self.push_loop_control_info(do_while.label().map(Into::into), start_address);
 
-                self.compile_stmt(do_while.body(), false);
+                self.compile_stmt(do_while.body(), use_expr);
 
                 self.compile_expr(do_while.cond(), true);
                 self.emit(Opcode::JumpIfTrue, &[start_address]);
@@ -922,34 +1151,93 @@ impl ByteCompiler {
                 self.pop_loop_control_info();
             }
             Node::Continue(node) => {
-                let label = self.jump();
-                let mut items = self.jump_info.iter_mut().rev().filter(|info| info.is_loop);
-                let target = if node.label().is_none() {
-                    items.next()
-                } else {
-                    items.find(|info| info.label.as_deref() == node.label())
-                }
-                .expect("continue target")
-                .start_address;
+                let Some(target) = self.find_jump_target(node.label(), true) else {
+                    self.unsupported = true;
+                    return;
+                };
+                self.leave_to_jump_target(target);
 
-                self.patch_jump_with_target(label, target);
+                let label = self.jump();
+                let start_address = self.jump_info[target].start_address;
+                self.patch_jump_with_target(label, start_address);
             }
             Node::Break(node) => {
+                // TODO: Implement the labels of the other statements
+                let Some(target) = self.find_jump_target(node.label(), false) else {
+                    self.unsupported = true;
+                    return;
+                };
+                self.leave_to_jump_target(target);
+
                 let label = self.jump();
-                if node.label().is_none() {
-                    self.jump_info.last_mut().unwrap().breaks.push(label);
-                } else {
-                    for info in self.jump_info.iter_mut().rev() {
-                        if info.label.as_deref() == node.label() {
-                            info.breaks.push(label);
-                            break;
+                self.jump_info[target].breaks.push(label);
+            }
+            Node::Block(block) => self.compile_labelled(block.label(), |compiler| {
+                compiler.compile_block(block, use_expr)
+            }),
+            Node::Try(t) => {
+                let start_address = self.next_opcode_location();
+                let has_finally = t.finally().is_some();
+
+                self.emit(
+                    Opcode::TryStart,
+                    &[Self::DUMMY_ADDRESS, Self::DUMMY_ADDRESS],
+                );
+                self.push_jump_control_info(
+                    None,
+                    start_address,
+                    JumpControlKind::Try { has_finally },
+                );
+
+                self.compile_block(t.block(), use_expr);
+                self.emit_opcode(Opcode::TryEnd);
+
+                if let Some(catch) = t.catch() {
+                    let exit = self.jump();
+
+                    let catch_address = self.next_opcode_location();
+                    self.patch_u32(start_address + 1, catch_address);
+                    self.jump_info.last_mut().unwrap().kind =
+                        JumpControlKind::Catch { has_finally };
+
+                    // The exception is on the stack.
+                    self.push_declarative_environment(ScopeLayout::catch_parameter(
+                        catch.parameter(),
+                    ));
+                    match catch.parameter() {
+                        Some(Declaration::Identifier { ident, .. }) => {
+                            self.emit_binding(BindingOpcode::Let, ident.as_ref());
                         }
+                        Some(Declaration::Pattern(pattern)) => {
+                            self.compile_declaration_pattern(pattern, BindingOpcode::Let);
+                        }
+                        None => self.emit_opcode(Opcode::Pop),
+                    }
+                    self.compile_block(catch.block(), use_expr);
+                    self.pop_environment();
+
+                    if has_finally {
+                        self.emit_opcode(Opcode::TryEnd);
                     }
+                    self.patch_jump(exit);
                 }
-            }
-            Node::Block(block) => {
-                for node in block.items() {
-                    self.compile_stmt(node, false);
+
+                let info = self.jump_info.pop().unwrap();
+
+                if let Some(finally) = t.finally() {
+                    self.emit_opcode(Opcode::FinallyStart);
+
+                    let finally_address = self.next_opcode_location();
+                    self.patch_u32(start_address + 5, finally_address);
+                    for label in info.finally_jumps {
+                        self.patch_jump_with_target(label, finally_address);
+                    }
+
+                    self.push_jump_control_info(None, start_address, JumpControlKind::Finally);
+                    self.compile_block(finally, false);
+                    self.jump_info.pop();
+
+                    self.emit_opcode(Opcode::FinallyEnd);
                 }
             }
             Node::Throw(throw) => {
@@ -961,6 +1249,14 @@ impl ByteCompiler {
                 self.push_switch_control_info(None, start_address);
 
                 self.compile_expr(switch.val(), true);
+
+                // The `let` and `const` declarations of the cases are scoped to the `switch`.
+                let layout = ScopeLayout::switch(switch);
+                let has_environment = !layout.is_empty();
+                if has_environment {
+                    self.push_declarative_environment(layout);
+                }
+
                 let mut labels = Vec::with_capacity(switch.cases().len());
                 for case in switch.cases() {
                     self.compile_expr(case.condition(), true);
@@ -971,29 +1267,482 @@ impl ByteCompiler {
 
                 for (label, case) in labels.into_iter().zip(switch.cases()) {
                     self.patch_jump(label);
-                    self.compile_statement_list(case.body(), false);
+                    self.compile_statement_list(case.body(), use_expr);
                 }
 
                 self.patch_jump(exit);
                 if let Some(body) = switch.default() {
                     for node in body {
-                        self.compile_stmt(node, false);
+                        self.compile_stmt(node, use_expr);
                     }
                 }
 
+                if has_environment {
+                    self.pop_environment();
+                }
                 self.pop_switch_control_info();
             }
             Node::FunctionDecl(_function) => self.function(node, false),
+            // TODO: Implement async functions and generators
+            Node::AsyncFunctionDecl(_) | Node::AsyncGeneratorDecl(_) | Node::GeneratorDecl(_) => {
+                self.unsupported = true;
+            }
             Node::Return(ret) => {
                 if let Some(expr) = ret.expr() {
                     self.compile_expr(expr, true);
                 } else {
                     self.emit(Opcode::PushUndefined, &[]);
                 }
+                let _ = self.leave_jump_controls(0, true);
                 self.emit(Opcode::Return, &[]);
             }
             Node::Empty => {}
-            expr => self.compile_expr(expr, use_expr),
+            expr => {
+                self.compile_expr(expr, use_expr);
+                if use_expr {
+                    self.emit_opcode(Opcode::SetCompletionValue);
+                }
+            }
+        }
+    }
+
+    /// Pushes the `this` value and the function of a call expression.
+    fn compile_callee(&mut self, callee: &Node) {
+        match callee {
+            Node::GetConstField(field) => {
+                self.compile_expr(field.obj(), true);
+                self.emit(Opcode::Dup, &[]);
+                let index = self.get_or_insert_name(field.field());
+                self.emit(Opcode::GetPropertyByName, &[index]);
+            }
+            Node::GetField(field) => {
+                self.compile_expr(field.obj(), true);
+                self.emit(Opcode::Dup, &[]);
+                self.compile_expr(field.field(), true);
+                self.emit(Opcode::Swap, &[]);
+                self.emit(Opcode::GetPropertyByValue, &[]);
+            }
+            expr => {
+                self.emit(Opcode::This, &[]);
+                self.compile_expr(expr, true);
+            }
+        }
+    }
+
+    /// Pushes the arguments of a call, returns their count.
+    ///
+    /// If there are spread arguments, a single array of all arguments is pushed instead and
+    /// `None` is returned.
+    fn compile_arguments(&mut self, args: &[Node]) -> Option<u32> {
+        if args.iter().any(|arg| matches!(arg, Node::Spread(_))) {
+            self.compile_array(args);
+            return None;
+        }
+
+        for arg in args {
+            self.compile_expr(arg, true);
+        }
+        Some(args.len() as u32)
+    }
+
+    /// Pushes a new array with the given elements, expanding spread elements.
+    fn compile_array(&mut self, elements: &[Node]) {
+        if !elements
+            .iter()
+            .any(|element| matches!(element, Node::Spread(_)))
+        {
+            for element in elements {
+                self.compile_expr(element, true);
+            }
+            self.emit(Opcode::PushNewArray, &[elements.len() as u32]);
+            return;
+        }
+
+        self.emit(Opcode::PushNewArray, &[0]);
+        for element in elements {
+            if let Node::Spread(spread) = element {
+                self.compile_expr(spread.val(), true);
+                self.emit_opcode(Opcode::GetIterator);
+                self.emit_opcode(Opcode::PushIteratorToArray);
+            } else {
+                self.compile_expr(element, true);
+                self.emit_opcode(Opcode::PushValueToArray);
+            }
+        }
+    }
+
+    #[inline]
+    fn compile_block(&mut self, block: &Block, use_expr: bool) {
+        let has_lexical_declarations = block
+            .items()
+            .iter()
+            .any(|node| matches!(node, Node::LetDeclList(_) | Node::ConstDeclList(_)));
+
+        if has_lexical_declarations {
+            self.push_declarative_environment(ScopeLayout::lexical(block.items()));
+        }
+
+        self.compile_statement_list(block.statements(), use_expr);
+
+        if has_lexical_declarations {
+            self.pop_environment();
+        }
+    }
+
+    fn compile_for_loop(&mut self, for_loop: &ForLoop, use_expr: bool) {
+        // The bindings of a `let` declaration are copied to a new environment for each
+        // iteration, so that closures capture the value of the current iteration.
+        let mut per_iteration_bindings = Vec::new();
+        let layout = match for_loop.init() {
+            Some(Node::LetDeclList(list)) => {
+                for decl in list.as_ref() {
+                    match decl {
+                        Declaration::Identifier { ident, .. } => {
+                            per_iteration_bindings.push(ident.as_ref())
+                        }
+                        Declaration::Pattern(pattern) => {
+                            per_iteration_bindings.extend(pattern.idents())
+                        }
+                    }
+                }
+                Some(ScopeLayout::declaration_list(list, BindingKind::Let))
+            }
+            Some(Node::ConstDeclList(list)) => {
+                Some(ScopeLayout::declaration_list(list, BindingKind::Const))
+            }
+            _ => None,
+        };
+
+        let has_environment = layout.is_some();
+        let scope_index = layout.map(|layout| self.push_declarative_environment(layout));
+        if let Some(init) = for_loop.init() {
+            self.compile_stmt(init, false);
+        }
+        self.emit_per_iteration_environment(&per_iteration_bindings, scope_index);
+
+        let condition = self.jump();
+        let start_address = self.next_opcode_location();
+        self.push_loop_control_info(for_loop.label().map(Into::into), start_address);
+
+        self.emit_per_iteration_environment(&per_iteration_bindings, scope_index);
+        if let Some(final_expr) = for_loop.final_expr() {
+            self.compile_expr(final_expr, false);
+        }
+
+        self.patch_jump(condition);
+        let exit = if let Some(condition) = for_loop.condition() {
+            self.compile_expr(condition, true);
+            Some(self.jump_if_false())
+        } else {
+            None
+        };
+
+        self.compile_stmt(for_loop.body(), use_expr);
+        self.emit(Opcode::Jump, &[start_address]);
+
+        if let Some(exit) = exit {
+            self.patch_jump(exit);
+        }
+        self.pop_loop_control_info();
+
+        if has_environment {
+            self.pop_environment();
+        }
+    }
+
+    /// Replaces the current environment, of the scope `scope_index`, with a new one that
+    /// contains copies of `bindings`.
+    fn emit_per_iteration_environment(&mut self, bindings: &[&str], scope_index: Option<u32>) {
+        let scope_index = match scope_index {
+            Some(scope_index) if !bindings.is_empty() => scope_index,
+            _ => return,
+        };
+
+        for name in bindings {
+            self.emit_get_name(name);
+        }
+        self.emit_opcode(Opcode::PopEnvironment);
+        self.emit(Opcode::PushDeclarativeEnvironment, &[scope_index]);
+        for name in bindings.iter().rev() {
+            self.emit_binding(BindingOpcode::Let, name);
+        }
+    }
+
+    #[inline]
+    fn compile_for_in_loop(&mut self, for_in_loop: &ForInLoop, use_expr: bool) {
+        self.compile_expr(for_in_loop.expr(), true);
+        self.emit_opcode(Opcode::ForInLoopInitIterator);
+        self.compile_iterator_loop(
+            for_in_loop.variable(),
+            for_in_loop.body(),
+            for_in_loop.label(),
+            use_expr,
+        );
+    }
+
+    #[inline]
+    fn compile_for_of_loop(&mut self, for_of_loop: &ForOfLoop, use_expr: bool) {
+        self.compile_expr(for_of_loop.iterable(), true);
+        self.emit_opcode(Opcode::GetIterator);
+        self.compile_iterator_loop(
+            for_of_loop.variable(),
+            for_of_loop.body(),
+            for_of_loop.label(),
+            use_expr,
+        );
+    }
+
+    /// Compiles the iterations of a `for..in` or `for..of` loop, over the iterator on the stack.
+    ///
+    /// Each iteration runs inside of an exception handler that closes the iterator.
+    fn compile_iterator_loop(
+        &mut self,
+        variable: &Node,
+        body: &Node,
+        label: Option<&str>,
+        use_expr: bool,
+    ) {
+        let start_address = self.next_opcode_location();
+        self.push_jump_control_info(
+            label.map(Into::into),
+            start_address,
+            JumpControlKind::IteratorLoop,
+        );
+
+        self.emit(
+            Opcode::TryStart,
+            &[Self::DUMMY_ADDRESS, Self::DUMMY_ADDRESS],
+        );
+        let done = self.jump_with_custom_opcode(Opcode::IteratorStep);
+
+        let (def, layout) = match variable {
+            Node::LetDeclList(list) => (
+                BindingOpcode::Let,
+                Some(ScopeLayout::declaration_list(list, BindingKind::Let)),
+            ),
+            Node::ConstDeclList(list) => (
+                BindingOpcode::Const,
+                Some(ScopeLayout::declaration_list(list, BindingKind::Const)),
+            ),
+            _ => (BindingOpcode::Var, None),
+        };
+        let has_environment = layout.is_some();
+        if let Some(layout) = layout {
+            self.push_declarative_environment(layout);
+        }
+
+        match variable {
+            Node::VarDeclList(list) | Node::LetDeclList(list) | Node::ConstDeclList(list) => {
+                match list.as_ref() {
+                    [Declaration::Identifier { ident, .. }] => {
+                        self.emit_binding(def, ident.as_ref())
+                    }
+                    [Declaration::Pattern(pattern)] => {
+                        self.compile_declaration_pattern(pattern, def)
+                    }
+                    _ => unreachable!("the head of a for..in/of loop declares a single binding"),
+                }
+            }
+            node => {
+                let access = self.compile_access(node);
+                self.access_set(access, None, false);
+            }
+        }
+
+        self.compile_stmt(body, use_expr);
+
+        if has_environment {
+            self.pop_environment();
+        }
+        self.emit_opcode(Opcode::TryEnd);
+        self.emit(Opcode::Jump, &[start_address]);
+
+        self.patch_jump(done);
+        self.emit_opcode(Opcode::TryEnd);
+        let exit = self.jump();
+
+        let catch_address = self.next_opcode_location();
+        self.patch_u32(start_address + 1, catch_address);
+        self.emit_opcode(Opcode::IteratorCloseWithException);
+
+        self.patch_jump(exit);
+        self.pop_loop_control_info();
+        self.emit_opcode(Opcode::IteratorClose);
+    }
+
+    fn compile_declaration_list(&mut self, list: &DeclarationList) {
+        let def = match list {
+            DeclarationList::Var(_) => BindingOpcode::Var,
+            DeclarationList::Let(_) => BindingOpcode::Let,
+            DeclarationList::Const(_) => BindingOpcode::Const,
+        };
+
+        for decl in list.as_ref() {
+            match decl {
+                Declaration::Identifier { ident, .. } => {
+                    if let Some(expr) = decl.init() {
+                        self.compile_expr(expr, true);
+                    } else if def == BindingOpcode::Var {
+                        // `var` declarations without an initializer keep the current value,
+                        // resolved bindings are declared when their environment is created.
+                        if self.environments.resolve(ident.as_ref()) == BindingLocator::Dynamic {
+                            let index = self.get_or_insert_name(ident.as_ref());
+                            self.emit(Opcode::DefVar, &[index]);
+                        }
+                        continue;
+                    } else {
+                        self.emit_opcode(Opcode::PushUndefined);
+                    }
+                    self.emit_binding(def, ident.as_ref());
+                }
+                Declaration::Pattern(pattern) => {
+                    if let Some(expr) = decl.init() {
+                        self.compile_expr(expr, true);
+                    } else {
+                        self.emit_opcode(Opcode::PushUndefined);
+                    }
+                    self.compile_declaration_pattern(pattern, def);
+                }
+            }
+        }
+    }
+
+    /// Declares the binding `name` and initializes it with the value on the top of the stack.
+    ///
+    /// Bindings that were resolved at compile time already exist in their environment, and
+    /// only have to be assigned or initialized.
+    #[inline]
+    fn emit_binding(&mut self, def: BindingOpcode, name: &str) {
+        match (def, self.environments.resolve(name)) {
+            (BindingOpcode::Var, BindingLocator::Declarative { depth, slot }) => {
+                self.emit(Opcode::SetBinding, &[depth, slot]);
+                return;
+            }
+            (_, BindingLocator::Declarative { depth: 0, slot }) => {
+                self.emit(Opcode::InitBinding, &[0, slot]);
+                return;
+            }
+            _ => {}
+        }
+
+        let index = self.get_or_insert_name(name);
+        match def {
+            BindingOpcode::Var => {
+                self.emit(Opcode::DefVar, &[index]);
+                self.emit(Opcode::SetName, &[index]);
+            }
+            BindingOpcode::Let => {
+                self.emit(Opcode::DefLet, &[index]);
+                self.emit(Opcode::InitLexical, &[index]);
+            }
+            BindingOpcode::Const => {
+                self.emit(Opcode::DefConst, &[index]);
+                self.emit(Opcode::InitLexical, &[index]);
+            }
+        }
+    }
+
+    /// Replaces the value on the top of the stack with the value of `init` if it is `undefined`.
+    #[inline]
+    fn emit_default_value(&mut self, init: &Node) {
+        let skip = self.jump_with_custom_opcode(Opcode::JumpIfNotUndefined);
+        self.compile_expr(init, true);
+        self.patch_jump(skip);
+    }
+
+    /// Binds the value on the top of the stack to the identifiers of a destructuring pattern.
+    fn compile_declaration_pattern(&mut self, pattern: &DeclarationPattern, def: BindingOpcode) {
+        match pattern {
+            DeclarationPattern::Object(pattern) => {
+                self.emit_opcode(Opcode::ValueNotNullOrUndefined);
+
+                for binding in pattern.bindings() {
+                    match binding {
+                        BindingPatternTypeObject::Empty => {}
+                        BindingPatternTypeObject::SingleName {
+                            ident,
+                            property_name,
+                            default_init,
+                        } => {
+                            self.emit_opcode(Opcode::Dup);
+                            let index = self.get_or_insert_name(property_name);
+                            self.emit(Opcode::GetPropertyByName, &[index]);
+                            if let Some(init) = default_init {
+                                self.emit_default_value(init);
+                            }
+                            self.emit_binding(def, ident);
+                        }
+                        BindingPatternTypeObject::RestProperty {
+                            ident,
+                            excluded_keys,
+                        } => {
+                            self.emit_opcode(Opcode::Dup);
+                            self.emit_opcode(Opcode::PushEmptyObject);
+                            self.emit_opcode(Opcode::Swap);
+                            for key in excluded_keys {
+                                self.emit_push_literal(Literal::String(key.as_ref().into()));
+                            }
+                            self.emit(Opcode::CopyDataProperties, &[excluded_keys.len() as u32]);
+                            self.emit_binding(def, ident);
+                        }
+                        BindingPatternTypeObject::BindingPattern {
+                            ident,
+                            pattern,
+                            default_init,
+                        } => {
+                            self.emit_opcode(Opcode::Dup);
+                            let index = self.get_or_insert_name(ident);
+                            self.emit(Opcode::GetPropertyByName, &[index]);
+                            if let Some(init) = default_init {
+                                self.emit_default_value(init);
+                            }
+                            self.compile_declaration_pattern(pattern, def);
+                        }
+                    }
+                }
+
+                self.emit_opcode(Opcode::Pop);
+            }
+            DeclarationPattern::Array(pattern) => {
+                self.emit_opcode(Opcode::GetIterator);
+
+                for binding in pattern.bindings() {
+                    match binding {
+                        BindingPatternTypeArray::Empty => {}
+                        BindingPatternTypeArray::Elision => {
+                            self.emit_opcode(Opcode::IteratorNext);
+                            self.emit_opcode(Opcode::Pop);
+                        }
+                        BindingPatternTypeArray::SingleName {
+                            ident,
+                            default_init,
+                        } => {
+                            self.emit_opcode(Opcode::IteratorNext);
+                            if let Some(init) = default_init {
+                                self.emit_default_value(init);
+                            }
+                            self.emit_binding(def, ident);
+                        }
+                        BindingPatternTypeArray::BindingPattern { pattern } => {
+                            self.emit_opcode(Opcode::IteratorNext);
+                            if let Some(init) = pattern.init() {
+                                self.emit_default_value(init);
+                            }
+                            self.compile_declaration_pattern(pattern, def);
+                        }
+                        BindingPatternTypeArray::SingleNameRest { ident } => {
+                            self.emit_opcode(Opcode::IteratorToArray);
+                            self.emit_binding(def, ident);
+                        }
+                        BindingPatternTypeArray::BindingPatternRest { pattern } => {
+                            self.emit_opcode(Opcode::IteratorToArray);
+                            self.compile_declaration_pattern(pattern, def);
+                        }
+                    }
+                }
+
+                self.emit_opcode(Opcode::IteratorClose);
+            }
         }
     }
 
@@ -1028,43 +1777,70 @@ impl ByteCompiler {
         };
 
         let length = parameters.len() as u32;
-        let mut code = CodeBlock::new(name.unwrap_or("").into(), length, false, true);
+        let strict = self.code_block.strict || body.strict();
+        let mut code = CodeBlock::new(name.unwrap_or("").into(), length, strict, true);
 
         if let FunctionKind::Arrow = kind {
             code.constructor = false;
             code.this_mode = ThisMode::Lexical;
         }
 
-        let mut compiler = ByteCompiler {
-            code_block: code,
-            literals_map: HashMap::new(),
-            names_map: HashMap::new(),
-            functions_map: HashMap::new(),
-            jump_info: Vec::new(),
-            top_level: false,
-        };
-
-        for node in body.items() {
-            compiler.compile_stmt(node, false);
+        // An arguments object is created if the function is not an arrow function and its
+        // parameters or declarations do not shadow `arguments`.
+        // <https://tc39.es/ecma262/#sec-functiondeclarationinstantiation>
+        let has_parameter_expressions = parameters.iter().any(|param| param.init().is_some());
+        code.arguments_binding = code.this_mode != ThisMode::Lexical
+            && !parameters.iter().any(|param| param.name() == "arguments")
+            && (has_parameter_expressions
+                || (!body.lexically_declared_names().contains("arguments")
+                    && !body.function_declared_names().contains("arguments")));
+
+        code.params = parameters.to_owned().into_boxed_slice();
+        code.function_layout = self.scope_layout(ScopeLayout::function(
+            parameters,
+            body,
+            code.arguments_binding,
+        ));
+
+        let mut compiler = self.function_compiler(code.clone(), false);
+        compiler.compile_function_body(parameters, body);
+
+        // A direct `eval` in sloppy mode can declare variables in the function environment,
+        // which may shadow the bindings of the enclosing environments.
+        if compiler.has_direct_eval && !strict {
+            compiler = self.function_compiler(code.clone(), true);
+            compiler.compile_function_body(parameters, body);
         }
 
-        compiler.code_block.params = parameters.to_owned().into_boxed_slice();
-
-        // TODO These are redundant if a function returns so may need to check if a function returns and adding these if it doesn't
-        compiler.emit(Opcode::PushUndefined, &[]);
-        compiler.emit(Opcode::Return, &[]);
-
-        let code = Gc::new(compiler.finish());
+        // A function that uses syntax the compiler does not support yet is run by the
+        // interpreter, so that the rest of the script still runs in the VM.
+        let code = if compiler.unsupported {
+            let mut body = body.clone();
+            body.set_strict(strict);
+            code.interpreted_body = Some(body.into());
+            Gc::new(code)
+        } else {
+            if compiler.register_code {
+                compiler.code_block.register_code = RegisterCodeBlock::compile_function(
+                    parameters,
+                    body,
+                    &compiler.function_indices,
+                );
+            }
+            Gc::new(compiler.finish())
+        };
 
         let index = self.code_block.functions.len() as u32;
         self.code_block.functions.push(code);
+        self.function_indices.insert(function as *const Node, index);
 
         self.emit(Opcode::GetFunction, &[index]);
 
         match kind {
             FunctionKind::Declaration => {
-                let index = self.get_or_insert_name(name.unwrap());
-                let access = Access::Variable { index };
+                let access = Access::Variable {
+                    name: name.unwrap(),
+                };
                 self.access_set(access, None, false);
             }
             FunctionKind::Expression | FunctionKind::Arrow => {
@@ -1075,6 +1851,56 @@ impl ByteCompiler {
         }
     }
 
+    /// Creates the compiler of a function defined in the code, whose function environment has
+    /// the layout of `code`.
+    ///
+    /// If `dynamic` is set, the bindings that are not declared in the function environment are
+    /// looked up by name.
+    fn function_compiler(&self, code: CodeBlock, dynamic: bool) -> Self {
+        let mut environments = self.environments.clone();
+        environments.push(code.function_layout.clone(), dynamic);
+
+        ByteCompiler {
+            code_block: code,
+            literals_map: HashMap::new(),
+            names_map: HashMap::new(),
+            functions_map: HashMap::new(),
+            jump_info: Vec::new(),
+            env_depth: 0,
+            top_level: false,
+            function_indices: FunctionIndices::new(),
+            register_code: self.register_code,
+            environments,
+            has_direct_eval: false,
+            unsupported: false,
+        }
+    }
+
+    /// Compiles the default parameter values and the body of a function.
+    fn compile_function_body(&mut self, parameters: &[FormalParameter], body: &StatementList) {
+        // Default parameter values replace `undefined` arguments.
+        for parameter in parameters {
+            if let Some(init) = parameter.init() {
+                self.emit_get_name(parameter.name());
+                self.emit_default_value(init);
+                self.emit_set_name(parameter.name());
+            }
+        }
+
+        self.compile_statement_list(body, false);
+
+        // TODO These are redundant if a function returns so may need to check if a function returns and adding these if it doesn't
+        self.emit(Opcode::PushUndefined, &[]);
+        self.emit(Opcode::Return, &[]);
+    }
+
+    /// Returns `true` if the compiled code contains syntax that the VM can not execute yet, in
+    /// which case the code must be run by the interpreter instead.
+    #[inline]
+    pub fn is_unsupported(&self) -> bool {
+        self.unsupported
+    }
+
     #[inline]
     pub fn finish(self) -> CodeBlock {
         self.code_block
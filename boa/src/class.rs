@@ -41,7 +41,7 @@
 //!
 //!     /// This is where the object is intitialized.
 //!     fn init(class: &mut ClassBuilder) -> JsResult<()> {
-//!         class.method("speak", 0, |this, _args, _ctx| {
+//!         class.method("speak", 0, |this: &JsValue, _args: &[JsValue], _ctx: &mut Context| {
 //!             if let Some(object) = this.as_object() {
 //!                 if let Some(animal) = object.downcast_ref::<Animal>() {
 //!                     match &*animal {
@@ -59,15 +59,63 @@
 //! }
 //! ```
 //!
+//! The [`Class`][class-trait] trait can also be implemented with `#[derive(Class)]`, which
+//! declares the name of the class and the accessors of its fields, and
+//! [`#[boa::class]`](crate::class()) on an `impl` block, which declares its constructor and methods.
+//! The arguments are converted with [`TryFromJs`](crate::value::TryFromJs), and the return values
+//! with [`IntoJs`](crate::value::IntoJs).
+//! ```
+//!# use boa::{
+//!#    class::Class,
+//!#    gc::{Finalize, Trace},
+//!#    Context,
+//!# };
+//!#
+//! #[derive(Debug, Trace, Finalize, Class)]
+//! struct Counter {
+//!     // `counter.count` gets and sets this field.
+//!     #[class(get, set)]
+//!     count: u32,
+//! }
+//!
+//! #[boa::class]
+//! impl Counter {
+//!     // This is what is called when we do `new Counter(start)`.
+//!     #[class(constructor)]
+//!     fn new(start: Option<u32>) -> Self {
+//!         Self {
+//!             count: start.unwrap_or(0),
+//!         }
+//!     }
+//!
+//!     // This is added to the prototype as `counter.increment()`.
+//!     fn increment(&mut self) -> u32 {
+//!         self.count += 1;
+//!         self.count
+//!     }
+//! }
+//!
+//! let mut context = Context::new();
+//! context.register_global_class::<Counter>().unwrap();
+//!
+//! let count = context.eval("let c = new Counter(1); c.increment(); c.count").unwrap();
+//! assert_eq!(count.as_number(), Some(2.0));
+//! ```
+//!
 //! [class-trait]: ./trait.Class.html
 
 use crate::{
-    builtins::function::NativeFunctionSignature,
-    object::{ConstructorBuilder, JsObject, NativeObject, ObjectData, PROTOTYPE},
+    builtins::function::{IntoNativeFunction, NativeFunction},
+    gc::{Ref, RefMut},
+    object::{
+        ConstructorBuilder, FunctionBuilder, JsObject, NativeObject, Object, ObjectData, PROTOTYPE,
+    },
     property::{Attribute, PropertyDescriptor, PropertyKey},
     Context, JsResult, JsValue,
 };
 
+pub use boa_macros::Class;
+
 /// Native class.
 pub trait Class: NativeObject + Sized {
     /// The binding name of the object.
@@ -84,6 +132,45 @@ pub trait Class: NativeObject + Sized {
     fn init(class: &mut ClassBuilder<'_>) -> JsResult<()>;
 }
 
+/// The name and the field accessors of a native class.
+///
+/// This is implemented with `#[derive(Class)]`, and used by [`#[boa::class]`](crate::class()) to
+/// implement [`Class`].
+pub trait ClassFields {
+    /// The binding name of the object.
+    const NAME: &'static str;
+
+    /// Adds the accessors of the fields to the class.
+    fn init_fields(class: &mut ClassBuilder<'_>) -> JsResult<()>;
+}
+
+/// Borrows the instance of the class `T` of the `this` value of a method, throwing a `TypeError`
+/// if it is not an instance of `T`.
+///
+/// # Panics
+///
+/// Panics if the instance is currently mutably borrowed.
+pub fn this_ref<'a, T: Class>(this: &'a JsValue, context: &mut Context) -> JsResult<Ref<'a, T>> {
+    this.as_object()
+        .and_then(JsObject::downcast_ref)
+        .ok_or_else(|| context.construct_type_error(format!("`this` is not a {}", T::NAME)))
+}
+
+/// Mutably borrows the instance of the class `T` of the `this` value of a method, throwing a
+/// `TypeError` if it is not an instance of `T`.
+///
+/// # Panics
+///
+/// Panics if the instance is currently borrowed.
+pub fn this_mut<'a, T: Class>(
+    this: &'a JsValue,
+    context: &mut Context,
+) -> JsResult<RefMut<'a, Object, T>> {
+    this.as_object()
+        .and_then(JsObject::downcast_mut)
+        .ok_or_else(|| context.construct_type_error(format!("`this` is not a {}", T::NAME)))
+}
+
 /// This is a wrapper around `Class::constructor` that sets the internal data of a class.
 ///
 /// This is automatically implemented, when a type implements `Class`.
@@ -174,35 +261,77 @@ impl<'context> ClassBuilder<'context> {
 
     /// Add a method to the class.
     ///
+    /// It is added to `prototype`. The method can be any [`IntoNativeFunction`].
+    #[inline]
+    pub fn method<N, F, Args>(&mut self, name: N, length: usize, function: F) -> &mut Self
+    where
+        N: AsRef<str>,
+        F: IntoNativeFunction<Args>,
+    {
+        self.native_method(name, length, NativeFunction::from_typed(function))
+    }
+
+    /// Add a static method to the class.
+    ///
+    /// It is added to class object itself. The method can be any [`IntoNativeFunction`].
+    #[inline]
+    pub fn static_method<N, F, Args>(&mut self, name: N, length: usize, function: F) -> &mut Self
+    where
+        N: AsRef<str>,
+        F: IntoNativeFunction<Args>,
+    {
+        self.static_native_method(name, length, NativeFunction::from_typed(function))
+    }
+
+    /// Add a [`NativeFunction`] method to the class.
+    ///
     /// It is added to `prototype`.
     #[inline]
-    pub fn method<N>(
+    pub fn native_method<N>(
         &mut self,
         name: N,
         length: usize,
-        function: NativeFunctionSignature,
+        function: NativeFunction,
     ) -> &mut Self
     where
         N: AsRef<str>,
     {
-        self.builder.method(function, name.as_ref(), length);
+        let function = FunctionBuilder::native_function(self.builder.context(), function)
+            .name(name.as_ref())
+            .length(length)
+            .constructor(false)
+            .build();
+        self.builder.property(
+            name.as_ref(),
+            function,
+            Attribute::WRITABLE | Attribute::NON_ENUMERABLE | Attribute::CONFIGURABLE,
+        );
         self
     }
 
-    /// Add a static method to the class.
+    /// Add a static [`NativeFunction`] method to the class.
     ///
     /// It is added to class object itself.
     #[inline]
-    pub fn static_method<N>(
+    pub fn static_native_method<N>(
         &mut self,
         name: N,
         length: usize,
-        function: NativeFunctionSignature,
+        function: NativeFunction,
     ) -> &mut Self
     where
         N: AsRef<str>,
     {
-        self.builder.static_method(function, name.as_ref(), length);
+        let function = FunctionBuilder::native_function(self.builder.context(), function)
+            .name(name.as_ref())
+            .length(length)
+            .constructor(false)
+            .build();
+        self.builder.static_property(
+            name.as_ref(),
+            function,
+            Attribute::WRITABLE | Attribute::NON_ENUMERABLE | Attribute::CONFIGURABLE,
+        );
         self
     }
 
@@ -300,3 +429,97 @@ impl<'context> ClassBuilder<'context> {
         self.builder.context()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        class::Class,
+        forward,
+        gc::{Finalize, Trace},
+        Context, JsResult, JsValue,
+    };
+
+    #[derive(Debug, Trace, Finalize, Class)]
+    #[class(rename = "Point")]
+    struct Vector {
+        #[class(get, set)]
+        x: f64,
+        #[class(get, rename = "ordinate")]
+        y: f64,
+    }
+
+    #[crate::class]
+    impl Vector {
+        #[class(constructor)]
+        fn new(x: f64, y: f64) -> JsResult<Self> {
+            if x.is_finite() && y.is_finite() {
+                Ok(Self { x, y })
+            } else {
+                Err("coordinates must be finite".into())
+            }
+        }
+
+        fn squared_length(&self) -> f64 {
+            self.x * self.x + self.y * self.y
+        }
+
+        fn scale(&mut self, factor: f64) {
+            self.x *= factor;
+            self.y *= factor;
+        }
+
+        #[class(rename = "isPoint")]
+        fn is(value: JsValue) -> bool {
+            value
+                .as_object()
+                .map_or(false, |object| object.is::<Self>())
+        }
+
+        #[class(skip)]
+        #[allow(dead_code)]
+        fn helper(&self) -> String {
+            String::new()
+        }
+    }
+
+    #[test]
+    fn derived_class() {
+        let mut context = Context::new();
+        context.register_global_class::<Vector>().unwrap();
+        forward(&mut context, "var p = new Point(3, 4);");
+
+        assert_eq!(forward(&mut context, "Point.length"), "2");
+        assert_eq!(forward(&mut context, "p.squaredLength()"), "25");
+        assert_eq!(forward(&mut context, "p.scale(2); p.x"), "6");
+        assert_eq!(forward(&mut context, "p.ordinate"), "8");
+        assert_eq!(forward(&mut context, "p.x = 1; p.x"), "1");
+        assert_eq!(forward(&mut context, "Point.isPoint(p)"), "true");
+        assert_eq!(forward(&mut context, "Point.isPoint({})"), "false");
+        assert_eq!(forward(&mut context, "typeof p.helper"), "\"undefined\"");
+    }
+
+    #[test]
+    fn derived_class_errors() {
+        let mut context = Context::new();
+        context.register_global_class::<Vector>().unwrap();
+
+        assert_eq!(
+            forward(&mut context, "try { new Point(1, NaN) } catch (e) { e }"),
+            "\"coordinates must be finite\""
+        );
+        assert_eq!(
+            forward(
+                &mut context,
+                "try { new Point(1, '2') } catch (e) { e.message }"
+            ),
+            "\"argument 2: expected a number, got string\""
+        );
+        assert_eq!(
+            forward(
+                &mut context,
+                "try { Point.prototype.squaredLength.call({}) } catch (e) { e.message }"
+            ),
+            "\"`this` is not a Point\""
+        );
+    }
+}
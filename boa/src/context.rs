@@ -3,17 +3,22 @@
 use crate::{
     builtins::{
         self,
-        function::{Function, NativeFunctionSignature, ThisMode},
+        array::Array,
+        function::{Function, IntoNativeFunction, NativeFunction, ThisMode},
         intrinsics::IntrinsicObjects,
         iterable::IteratorPrototypes,
         typed_array::TypedArray,
     },
     class::{Class, ClassBuilder},
+    environment::lexical_environment::LexicalEnvironment,
     exec::Interpreter,
+    gc::{Finalize, Trace},
+    host::Host,
+    limits::ExecutionLimits,
     object::PROTOTYPE,
-    object::{FunctionBuilder, JsObject, ObjectData},
+    object::{FunctionBuilder, IntegrityLevel, JsObject, ObjectData},
     property::{Attribute, PropertyDescriptor, PropertyKey},
-    realm::Realm,
+    realm::{Intrinsics, Realm},
     syntax::{
         ast::{
             node::{
@@ -26,29 +31,48 @@ use crate::{
     },
     BoaProfiler, Executable, JsResult, JsString, JsValue,
 };
+use rustc_hash::FxHashSet;
+use std::time::Duration;
 
 #[cfg(feature = "console")]
-use crate::builtins::console::Console;
+use crate::builtins::console::{Console, ConsoleLogger};
 
 #[cfg(feature = "vm")]
-use crate::vm::Vm;
+use crate::vm::{CodeBlock, OptimizationLevel, Vm};
 
-/// Store a builtin constructor (such as `Object`) and its corresponding prototype.
-#[derive(Debug, Clone)]
-pub struct StandardConstructor {
-    pub(crate) constructor: JsObject,
-    pub(crate) prototype: JsObject,
+/// The execution backend used by [`Context::eval`] to run scripts.
+///
+/// The bytecode VM is the default backend, the tree-walking interpreter is kept as a fallback.
+#[cfg(feature = "vm")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Compiles the script to bytecode and runs it in the virtual machine.
+    Vm,
+
+    /// Evaluates the syntax tree of the script directly.
+    Interpreter,
+
+    /// Compiles the script to bytecode and to the experimental register-based instruction
+    /// set, and runs the register code in the virtual machine.
+    ///
+    /// The scripts and functions that can not be compiled to register code run as bytecode.
+    Register,
 }
 
-impl Default for StandardConstructor {
+#[cfg(feature = "vm")]
+impl Default for Backend {
     fn default() -> Self {
-        Self {
-            constructor: JsObject::empty(),
-            prototype: JsObject::empty(),
-        }
+        Self::Vm
     }
 }
 
+/// Store a builtin constructor (such as `Object`) and its corresponding prototype.
+#[derive(Debug, Clone, Default, Trace, Finalize)]
+pub struct StandardConstructor {
+    pub(crate) constructor: JsObject,
+    pub(crate) prototype: JsObject,
+}
+
 impl StandardConstructor {
     /// Build a constructor with a defined prototype.
     fn with_prototype(prototype: JsObject) -> Self {
@@ -76,7 +100,7 @@ impl StandardConstructor {
 }
 
 /// Cached core standard objects.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Trace, Finalize)]
 pub struct StandardObjects {
     object: StandardConstructor,
     proxy: StandardConstructor,
@@ -95,8 +119,11 @@ pub struct StandardObjects {
     syntax_error: StandardConstructor,
     eval_error: StandardConstructor,
     uri_error: StandardConstructor,
+    aggregate_error: StandardConstructor,
     map: StandardConstructor,
     set: StandardConstructor,
+    date: StandardConstructor,
+    shadow_realm: StandardConstructor,
     typed_array: StandardConstructor,
     typed_int8_array: StandardConstructor,
     typed_uint8_array: StandardConstructor,
@@ -110,6 +137,7 @@ pub struct StandardObjects {
     typed_float32_array: StandardConstructor,
     typed_float64_array: StandardConstructor,
     array_buffer: StandardConstructor,
+    shared_array_buffer: StandardConstructor,
     data_view: StandardConstructor,
 }
 
@@ -142,8 +170,11 @@ impl Default for StandardObjects {
             syntax_error: StandardConstructor::default(),
             eval_error: StandardConstructor::default(),
             uri_error: StandardConstructor::default(),
+            aggregate_error: StandardConstructor::default(),
             map: StandardConstructor::default(),
             set: StandardConstructor::default(),
+            date: StandardConstructor::default(),
+            shadow_realm: StandardConstructor::default(),
             typed_array: StandardConstructor::default(),
             typed_int8_array: StandardConstructor::default(),
             typed_uint8_array: StandardConstructor::default(),
@@ -157,6 +188,7 @@ impl Default for StandardObjects {
             typed_float32_array: StandardConstructor::default(),
             typed_float64_array: StandardConstructor::default(),
             array_buffer: StandardConstructor::default(),
+            shared_array_buffer: StandardConstructor::default(),
             data_view: StandardConstructor::default(),
         }
     }
@@ -248,6 +280,11 @@ impl StandardObjects {
         &self.uri_error
     }
 
+    #[inline]
+    pub fn aggregate_error_object(&self) -> &StandardConstructor {
+        &self.aggregate_error
+    }
+
     #[inline]
     pub fn map_object(&self) -> &StandardConstructor {
         &self.map
@@ -258,6 +295,16 @@ impl StandardObjects {
         &self.set
     }
 
+    #[inline]
+    pub fn date_object(&self) -> &StandardConstructor {
+        &self.date
+    }
+
+    #[inline]
+    pub fn shadow_realm_object(&self) -> &StandardConstructor {
+        &self.shadow_realm
+    }
+
     #[inline]
     pub fn typed_array_object(&self) -> &StandardConstructor {
         &self.typed_array
@@ -323,6 +370,11 @@ impl StandardObjects {
         &self.array_buffer
     }
 
+    #[inline]
+    pub fn shared_array_buffer_object(&self) -> &StandardConstructor {
+        &self.shared_array_buffer
+    }
+
     #[inline]
     pub fn data_view_object(&self) -> &StandardConstructor {
         &self.data_view
@@ -380,9 +432,12 @@ pub(crate) enum StrictType {
 /// ```
 #[derive(Debug)]
 pub struct Context {
-    /// realm holds both the global object and the environment
+    /// The current realm, which holds the global object.
     pub(crate) realm: Realm,
 
+    /// The environment stack of the current realm.
+    pub(crate) environment: LexicalEnvironment,
+
     /// The current executor.
     executor: Interpreter,
 
@@ -390,39 +445,62 @@ pub struct Context {
     #[cfg(feature = "console")]
     console: Console,
 
-    /// Cached iterator prototypes.
-    iterator_prototypes: IteratorPrototypes,
+    /// Cached intrinsic objects of the current realm.
+    intrinsics: Intrinsics,
 
-    /// Cached TypedArray constructor.
-    typed_array_constructor: StandardConstructor,
+    /// Whether or not strict mode is active.
+    strict: StrictType,
 
-    /// Cached standard objects and their prototypes.
-    standard_objects: StandardObjects,
+    /// Whether scripts are strict mode code, even without a `"use strict"` directive.
+    strict_scripts: bool,
 
-    /// Cached intrinsic objects
-    intrinsic_objects: IntrinsicObjects,
+    /// Whether the `Function` constructor can create functions from source code strings.
+    allow_code_generation: bool,
 
-    /// Whether or not strict mode is active.
-    strict: StrictType,
+    /// The paths of the builtins that are removed from the global object of every realm.
+    removed_builtins: Vec<String>,
+
+    /// Whether the intrinsic objects of every realm are frozen.
+    freeze_intrinsics: bool,
+
+    /// The limits on the execution of scripts.
+    pub(crate) limits: ExecutionLimits,
+
+    /// The host-defined sources of time and randomness.
+    pub(crate) host: Host,
 
     #[cfg(feature = "vm")]
     pub(crate) vm: Vm,
+
+    /// The backend that executes the scripts passed to `eval`.
+    #[cfg(feature = "vm")]
+    backend: Backend,
+
+    /// The optimizations applied to the bytecode of the compiled scripts.
+    #[cfg(feature = "vm")]
+    optimization_level: OptimizationLevel,
 }
 
 impl Default for Context {
     fn default() -> Self {
         let realm = Realm::create();
+        let mut environment = LexicalEnvironment::default();
+        realm.swap_environment(&mut environment);
         let executor = Interpreter::new();
         let mut context = Self {
+            intrinsics: realm.intrinsics(),
             realm,
+            environment,
             executor,
             #[cfg(feature = "console")]
             console: Console::default(),
-            iterator_prototypes: IteratorPrototypes::default(),
-            typed_array_constructor: StandardConstructor::default(),
-            standard_objects: Default::default(),
-            intrinsic_objects: IntrinsicObjects::default(),
             strict: StrictType::Off,
+            strict_scripts: false,
+            allow_code_generation: true,
+            removed_builtins: Vec::new(),
+            freeze_intrinsics: false,
+            limits: ExecutionLimits::default(),
+            host: Host::default(),
             #[cfg(feature = "vm")]
             vm: Vm {
                 frame: None,
@@ -430,23 +508,16 @@ impl Default for Context {
                 trace: false,
                 stack_size_limit: 1024,
             },
+            #[cfg(feature = "vm")]
+            backend: Backend::default(),
+            #[cfg(feature = "vm")]
+            optimization_level: OptimizationLevel::default(),
         };
 
         // Add new builtIns to Context Realm
         // At a later date this can be removed from here and called explicitly,
         // but for now we almost always want these default builtins
-        let typed_array_constructor_constructor = TypedArray::init(&mut context);
-        let typed_array_constructor_prototype = typed_array_constructor_constructor
-            .get("prototype", &mut context)
-            .expect("prototype must exist")
-            .as_object()
-            .expect("prototype must be object")
-            .clone();
-        context.typed_array_constructor.constructor = typed_array_constructor_constructor;
-        context.typed_array_constructor.prototype = typed_array_constructor_prototype;
-        context.create_intrinsics();
-        context.iterator_prototypes = IteratorPrototypes::init(&mut context);
-        context.intrinsic_objects = IntrinsicObjects::init(&mut context);
+        context.initialize_realm();
         context
     }
 }
@@ -476,6 +547,19 @@ impl Context {
         &mut self.console
     }
 
+    /// Sets the backend that receives the messages logged with the `console` object.
+    ///
+    /// By default, they are printed to the standard output and standard error, see
+    /// [`StdoutLogger`](crate::builtins::console::StdoutLogger).
+    #[cfg(feature = "console")]
+    #[inline]
+    pub fn set_console_logger<L>(&mut self, logger: L)
+    where
+        L: ConsoleLogger + 'static,
+    {
+        self.console.set_logger(Box::new(logger));
+    }
+
     /// Returns if strict mode is currently active.
     #[inline]
     pub fn strict(&self) -> bool {
@@ -506,6 +590,103 @@ impl Context {
         self.strict = StrictType::Global;
     }
 
+    /// Returns whether scripts are strict mode code, even without a `"use strict"` directive.
+    #[inline]
+    pub(crate) fn strict_scripts(&self) -> bool {
+        self.strict_scripts
+    }
+
+    /// Returns whether the `Function` constructor can create functions from source code strings.
+    #[inline]
+    pub(crate) fn allow_code_generation(&self) -> bool {
+        self.allow_code_generation
+    }
+
+    /// Creates the intrinsic objects of the current realm, and adds the builtins to its global
+    /// object.
+    fn initialize_realm(&mut self) {
+        let typed_array_constructor_constructor = TypedArray::init(self);
+        let typed_array_constructor_prototype = typed_array_constructor_constructor
+            .get("prototype", self)
+            .expect("prototype must exist")
+            .as_object()
+            .expect("prototype must be object")
+            .clone();
+        self.intrinsics.typed_array_constructor.constructor = typed_array_constructor_constructor;
+        self.intrinsics.typed_array_constructor.prototype = typed_array_constructor_prototype;
+        self.create_intrinsics();
+        self.intrinsics.iterator_prototypes = IteratorPrototypes::init(self);
+        self.intrinsics.intrinsic_objects = IntrinsicObjects::init(self);
+        self.realm.set_intrinsics(self.intrinsics.clone());
+    }
+
+    /// Removes the builtins configured with [`ContextBuilder::without_builtin`] from the current
+    /// realm, and freezes its intrinsic objects if [`ContextBuilder::freeze_intrinsics`] is set.
+    fn restrict_realm(&mut self) -> JsResult<()> {
+        for name in self.removed_builtins.clone() {
+            ContextBuilder::remove_builtin(self, &name)?;
+        }
+        if self.freeze_intrinsics {
+            ContextBuilder::freeze(self)?;
+        }
+        Ok(())
+    }
+
+    /// Creates a new realm, with its own global object and intrinsic objects.
+    ///
+    /// The realm shares the heap of the context, so objects can be passed between realms. Use
+    /// [`Context::enter_realm`] to run scripts in it.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    /// let realm = context.create_realm();
+    ///
+    /// let previous = context.enter_realm(realm);
+    /// context.eval("var x = []").unwrap();
+    /// let array = context.global_object().get("x", &mut context).unwrap();
+    /// context.enter_realm(previous);
+    ///
+    /// context.register_global_property("y", array, Default::default());
+    /// let value = context.eval("y instanceof Array").unwrap();
+    /// assert_eq!(value.as_boolean(), Some(false));
+    /// ```
+    pub fn create_realm(&mut self) -> Realm {
+        let _timer = BoaProfiler::global().start_event("create_realm", "interpreter");
+        let realm = Realm::create();
+        let previous = self.enter_realm(realm.clone());
+        self.initialize_realm();
+        self.restrict_realm()
+            .expect("restricting a new realm should not throw");
+        self.enter_realm(previous);
+        realm
+    }
+
+    /// Returns the current realm.
+    #[inline]
+    pub fn realm(&self) -> &Realm {
+        &self.realm
+    }
+
+    /// Makes `realm` the current realm, and returns the previous current realm.
+    ///
+    /// Scripts passed to [`Context::eval`] run in the current realm, and objects created by the
+    /// engine use its intrinsic objects. Calling a function temporarily makes the realm of the
+    /// function the current realm.
+    pub fn enter_realm(&mut self, realm: Realm) -> Realm {
+        if Realm::ptr_eq(&self.realm, &realm) {
+            return realm;
+        }
+
+        // The environment stack of the realm being left is saved in it, and restored when it is
+        // entered again.
+        self.realm.swap_environment(&mut self.environment);
+        realm.swap_environment(&mut self.environment);
+        self.intrinsics = realm.intrinsics();
+        std::mem::replace(&mut self.realm, realm)
+    }
+
     /// Sets up the default global objects within Global
     #[inline]
     fn create_intrinsics(&mut self) {
@@ -539,7 +720,7 @@ impl Context {
     /// Return the global object.
     #[inline]
     pub fn global_object(&self) -> JsObject {
-        self.realm.global_object.clone()
+        self.realm.global_object()
     }
 
     /// Constructs a `Error` with the specified message.
@@ -702,6 +883,40 @@ impl Context {
         Err(self.construct_uri_error(message))
     }
 
+    /// Constructs an `AggregateError` with the specified errors and message.
+    pub fn construct_aggregate_error<E, M>(&mut self, errors: E, message: M) -> JsValue
+    where
+        E: IntoIterator<Item = JsValue>,
+        M: Into<Box<str>>,
+    {
+        // The object is created directly, instead of calling the `AggregateError` constructor,
+        // which iterates `errors` with the user-overridable `Array.prototype[@@iterator]`.
+        let prototype = self.standard_objects().aggregate_error_object().prototype();
+        let error = JsObject::from_proto_and_data(prototype, ObjectData::error());
+        let attribute = PropertyDescriptor::builder()
+            .writable(true)
+            .enumerable(false)
+            .configurable(true);
+        error.insert_property(
+            "message",
+            attribute.clone().value(JsString::from(message.into())),
+        );
+        error.insert_property(
+            "errors",
+            attribute.value(Array::create_array_from_list(errors, self)),
+        );
+        error.into()
+    }
+
+    /// Throws an `AggregateError` with the specified errors and message.
+    pub fn throw_aggregate_error<E, M>(&mut self, errors: E, message: M) -> JsResult<JsValue>
+    where
+        E: IntoIterator<Item = JsValue>,
+        M: Into<Box<str>>,
+    {
+        Err(self.construct_aggregate_error(errors, message))
+    }
+
     /// Utility to create a function Value for Function Declarations, Arrow Functions or Function Expressions
     pub(crate) fn create_function<N, P>(
         &mut self,
@@ -734,6 +949,7 @@ impl Context {
             body: RcStatementList::from(body),
             params,
             environment: self.get_current_environment().clone(),
+            realm: self.realm.clone(),
         };
 
         let function =
@@ -773,10 +989,12 @@ impl Context {
 
     /// Register a global native function.
     ///
-    /// This is more efficient that creating a closure function, since this does not allocate,
-    /// it is just a function pointer.
+    /// The function can have the
+    /// [`NativeFunctionSignature`](crate::builtins::function::NativeFunctionSignature), or be a
+    /// typed Rust function whose arguments are converted from JavaScript values, see
+    /// [`IntoNativeFunction`].
     ///
-    /// The function will be both `constructable` (call with `new`).
+    /// The function will be both `callable` and `constructable` (call with `new`).
     ///
     /// The function will be bound to the global object with `writable`, `non-enumerable`
     /// and `configurable` attributes. The same as when you create a function in JavaScript.
@@ -787,13 +1005,16 @@ impl Context {
     /// to the global object, you can create the function object with [`FunctionBuilder`](crate::object::FunctionBuilder::native).
     /// And bind it to the global object with [`Context::register_global_property`](Context::register_global_property) method.
     #[inline]
-    pub fn register_global_function(
+    pub fn register_global_function<F, Args>(
         &mut self,
         name: &str,
         length: usize,
-        body: NativeFunctionSignature,
-    ) -> JsResult<()> {
-        let function = FunctionBuilder::native(self, body)
+        body: F,
+    ) -> JsResult<()>
+    where
+        F: IntoNativeFunction<Args>,
+    {
+        let function = FunctionBuilder::typed(self, body)
             .name(name)
             .length(length)
             .constructor(true)
@@ -812,7 +1033,7 @@ impl Context {
 
     /// Register a global closure function.
     ///
-    /// The function will be both `constructable` (call with `new`).
+    /// The function will be both `callable` and `constructable` (call with `new`).
     ///
     /// The function will be bound to the global object with `writable`, `non-enumerable`
     /// and `configurable` attributes. The same as when you create a function in JavaScript.
@@ -827,7 +1048,8 @@ impl Context {
     ///
     /// This function will only accept `Copy` closures, meaning you cannot
     /// move `Clone` types, just `Copy` types. If you need to move `Clone` types
-    /// as captures, see [`FunctionBuilder::closure_with_captures`].
+    /// as captures, see [`NativeFunction::from_copy_closure_with_captures`] and
+    /// [`Context::register_global_native_function`].
     ///
     /// See <https://github.com/boa-dev/boa/issues/1515> for an explanation on
     /// why we need to restrict the set of accepted closures.
@@ -853,6 +1075,36 @@ impl Context {
         Ok(())
     }
 
+    /// Register a global [`NativeFunction`].
+    ///
+    /// The function will be both `callable` and `constructable` (call with `new`).
+    ///
+    /// The function will be bound to the global object with `writable`, `non-enumerable`
+    /// and `configurable` attributes. The same as when you create a function in JavaScript.
+    #[inline]
+    pub fn register_global_native_function(
+        &mut self,
+        name: &str,
+        length: usize,
+        function: NativeFunction,
+    ) -> JsResult<()> {
+        let function = FunctionBuilder::native_function(self, function)
+            .name(name)
+            .length(length)
+            .constructor(true)
+            .build();
+
+        self.global_object().insert_property(
+            name,
+            PropertyDescriptor::builder()
+                .value(function)
+                .writable(true)
+                .enumerable(false)
+                .configurable(true),
+        );
+        Ok(())
+    }
+
     /// <https://tc39.es/ecma262/#sec-hasproperty>
     #[inline]
     pub(crate) fn has_property(&mut self, obj: &JsValue, key: &PropertyKey) -> JsResult<bool> {
@@ -967,6 +1219,9 @@ impl Context {
 
     /// Evaluates the given code.
     ///
+    /// With the `vm` feature the code is compiled to bytecode and run in the virtual machine,
+    /// unless the tree-walking interpreter was selected with [`Context::set_backend`].
+    ///
     /// # Examples
     /// ```
     ///# use boa::Context;
@@ -977,14 +1232,13 @@ impl Context {
     /// assert!(value.is_number());
     /// assert_eq!(value.as_number().unwrap(), 4.0);
     /// ```
-    #[cfg(not(feature = "vm"))]
     #[allow(clippy::unit_arg, clippy::drop_copy)]
     #[inline]
     pub fn eval<T: AsRef<[u8]>>(&mut self, src: T) -> JsResult<JsValue> {
         let main_timer = BoaProfiler::global().start_event("Main", "Main");
         let src_bytes: &[u8] = src.as_ref();
 
-        let parsing_result = Parser::new(src_bytes, false)
+        let parsing_result = Parser::new(src_bytes, self.strict_scripts)
             .parse_all()
             .map_err(|e| e.to_string());
 
@@ -993,7 +1247,7 @@ impl Context {
                 if statement_list.strict() {
                     self.set_strict_mode_global();
                 }
-                statement_list.run(self)
+                self.execute(&statement_list)
             }
             Err(e) => self.throw_syntax_error(e),
         };
@@ -1005,85 +1259,146 @@ impl Context {
         execution_result
     }
 
-    /// Evaluates the given code by compiling down to bytecode, then interpreting the bytecode into a value
+    /// Executes a parsed script with the tree-walking interpreter.
+    #[cfg(not(feature = "vm"))]
+    #[inline]
+    pub(crate) fn execute(&mut self, statement_list: &StatementList) -> JsResult<JsValue> {
+        self.enter_execution();
+        let result = statement_list.run(self);
+        self.exit_execution();
+        result
+    }
+
+    /// Executes a parsed script with the selected backend.
+    ///
+    /// The bytecode VM compiles the script to bytecode, then interprets the bytecode into a value.
+    /// Functions that use syntax the compiler does not support yet are run by the tree-walking
+    /// interpreter instead, as is the whole script if its top level uses such syntax.
+    #[cfg(feature = "vm")]
+    pub(crate) fn execute(&mut self, statement_list: &StatementList) -> JsResult<JsValue> {
+        if self.backend != Backend::Interpreter {
+            if let Some(code_block) = self.compile_statement_list(statement_list) {
+                return self.execute_code_block(code_block);
+            }
+        }
+
+        self.enter_execution();
+        let result = statement_list.run(self);
+        self.exit_execution();
+        result
+    }
+
+    /// Compiles a script to bytecode, without executing it.
+    ///
+    /// The bytecode can be serialized with [`CodeBlock::to_bytes`] and executed later with
+    /// [`Context::execute_bytecode`], to skip parsing and compiling the script again. A
+    /// `SyntaxError` is thrown if the script uses syntax that can not be compiled to bytecode yet.
+    #[cfg(feature = "vm")]
+    pub fn compile<T: AsRef<[u8]>>(&mut self, src: T) -> JsResult<CodeBlock> {
+        let statement_list = match Parser::new(src.as_ref(), self.strict_scripts).parse_all() {
+            Ok(statement_list) => statement_list,
+            Err(e) => return Err(self.construct_syntax_error(e.to_string())),
+        };
+
+        match self.compile_statement_list(&statement_list) {
+            Some(code_block) if !code_block.uses_interpreter() => Ok(code_block),
+            _ => Err(self.construct_syntax_error(
+                "the script uses syntax that can not be compiled to bytecode yet",
+            )),
+        }
+    }
+
+    /// Compiles a parsed script for the selected backend.
+    ///
+    /// Returns `None` if the script uses syntax that the compiler does not support yet.
+    #[cfg(feature = "vm")]
+    fn compile_statement_list(&mut self, statement_list: &StatementList) -> Option<CodeBlock> {
+        use crate::bytecompiler::ByteCompiler;
+
+        let register_code = self.backend == Backend::Register;
+
+        let mut compiler = ByteCompiler::new(JsString::new("<main>"), statement_list.strict());
+        compiler.set_register_code(register_code);
+        compiler.compile_statement_list(statement_list, true);
+        if compiler.is_unsupported() {
+            return None;
+        }
+        if register_code {
+            compiler.compile_register_code(statement_list);
+        }
+        let mut code_block = compiler.finish();
+        self.optimize(&mut code_block, self.optimization_level);
+        Some(code_block)
+    }
+
+    /// Executes a script compiled to bytecode by [`Context::compile`], or loaded from a bytecode
+    /// cache.
     ///
     /// # Examples
     /// ```
-    ///# use boa::Context;
+    ///# use boa::{Context, vm::CodeBlock};
     /// let mut context = Context::new();
     ///
-    /// let value = context.eval("1 + 3").unwrap();
+    /// let bytes = context.compile("1 + 2").unwrap().to_bytes();
     ///
-    /// assert!(value.is_number());
-    /// assert_eq!(value.as_number().unwrap(), 4.0);
+    /// let code_block = CodeBlock::from_bytes(&bytes).unwrap();
+    /// let value = context.execute_bytecode(code_block).unwrap();
+    /// assert_eq!(value.as_number(), Some(3.0));
     /// ```
     #[cfg(feature = "vm")]
-    #[allow(clippy::unit_arg, clippy::drop_copy)]
-    pub fn eval<T: AsRef<[u8]>>(&mut self, src: T) -> JsResult<JsValue> {
-        use gc::Gc;
+    pub fn execute_bytecode(&mut self, code_block: CodeBlock) -> JsResult<JsValue> {
+        if code_block.strict {
+            self.set_strict_mode_global();
+        }
+        self.execute_code_block(code_block)
+    }
 
+    /// Executes a compiled script in the global scope.
+    #[cfg(feature = "vm")]
+    fn execute_code_block(&mut self, code_block: CodeBlock) -> JsResult<JsValue> {
         use crate::vm::CallFrame;
+        use gc::Gc;
 
-        let main_timer = BoaProfiler::global().start_event("Main", "Main");
-        let src_bytes: &[u8] = src.as_ref();
-
-        let parsing_result = Parser::new(src_bytes, false)
-            .parse_all()
-            .map_err(|e| e.to_string());
-
-        let statement_list = match parsing_result {
-            Ok(statement_list) => statement_list,
-            Err(e) => return self.throw_syntax_error(e),
-        };
-
-        let mut compiler = crate::bytecompiler::ByteCompiler::new(JsString::new("<main>"), false);
-        compiler.compile_statement_list(&statement_list, true);
-        let code_block = compiler.finish();
-
-        let environment = self.get_current_environment().clone();
         let fp = self.vm.stack.len();
         let global_object = self.global_object().into();
 
-        self.vm.push_frame(CallFrame {
-            prev: None,
-            code: Gc::new(code_block),
-            this: global_object,
-            pc: 0,
+        let env_depth = self.environment_stack_len();
+        self.vm.push_frame(CallFrame::new(
+            Gc::new(code_block),
+            global_object,
             fp,
-            exit_on_return: true,
-            environment,
-        });
-        let result = self.run();
-
-        // The main_timer needs to be dropped before the BoaProfiler is.
-        drop(main_timer);
-        BoaProfiler::global().drop();
+            true,
+            env_depth,
+        ));
 
+        self.enter_execution();
+        let result = self.run();
+        self.exit_execution();
         result
     }
 
     /// Return the cached iterator prototypes.
     #[inline]
     pub fn iterator_prototypes(&self) -> &IteratorPrototypes {
-        &self.iterator_prototypes
+        &self.intrinsics.iterator_prototypes
     }
 
     /// Return the cached TypedArray constructor.
     #[inline]
     pub(crate) fn typed_array_constructor(&self) -> &StandardConstructor {
-        &self.typed_array_constructor
+        &self.intrinsics.typed_array_constructor
     }
 
     /// Return the core standard objects.
     #[inline]
     pub fn standard_objects(&self) -> &StandardObjects {
-        &self.standard_objects
+        &self.intrinsics.standard_objects
     }
 
     /// Return the intrinsic objects.
     #[inline]
     pub fn intrinsics(&self) -> &IntrinsicObjects {
-        &self.intrinsic_objects
+        &self.intrinsics.intrinsic_objects
     }
 
     /// Set the value of trace on the context
@@ -1091,4 +1406,478 @@ impl Context {
     pub fn set_trace(&mut self, trace: bool) {
         self.vm.trace = trace;
     }
+
+    /// Returns the backend that executes the scripts passed to [`Context::eval`].
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Selects the backend that executes the scripts passed to [`Context::eval`].
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::{context::Backend, Context};
+    /// let mut context = Context::new();
+    /// context.set_backend(Backend::Interpreter);
+    ///
+    /// let value = context.eval("1 + 3").unwrap();
+    /// assert_eq!(value.as_number().unwrap(), 4.0);
+    /// ```
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.backend = backend;
+    }
+
+    /// Returns the optimizations applied to the bytecode of the compiled scripts.
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub fn optimization_level(&self) -> OptimizationLevel {
+        self.optimization_level
+    }
+
+    /// Selects the optimizations applied to the bytecode of the compiled scripts.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::{vm::OptimizationLevel, Context};
+    /// let mut context = Context::new();
+    /// context.set_optimization_level(OptimizationLevel::Full);
+    ///
+    /// let value = context.eval("let x = 2 * 3 + 1; x").unwrap();
+    /// assert_eq!(value.as_number().unwrap(), 7.0);
+    /// ```
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub fn set_optimization_level(&mut self, level: OptimizationLevel) {
+        self.optimization_level = level;
+    }
+}
+
+/// Builder for a [`Context`] whose global object and behaviour are set up front.
+///
+/// It can leave builtins out of the global object, freeze the intrinsic objects, forbid creating
+/// functions from strings, and set the strictness and the execution limits of the scripts.
+/// [`ContextBuilder::sandbox`] is a starting point for running untrusted scripts.
+///
+/// # Examples
+/// ```
+///# use boa::context::ContextBuilder;
+/// let mut context = ContextBuilder::sandbox()
+///     .without_builtin("Date")
+///     .without_builtin("Math.random")
+///     .loop_iteration_limit(Some(10_000))
+///     .build();
+///
+/// assert_eq!(context.eval("'Date' in globalThis").unwrap().as_boolean(), Some(false));
+/// assert!(context.eval("Array.prototype.push = null").is_err());
+/// assert!(context.eval("Function('return 1')").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContextBuilder {
+    removed_builtins: Vec<String>,
+    freeze_intrinsics: bool,
+    allow_code_generation: bool,
+    strict: bool,
+    loop_iteration_limit: Option<u64>,
+    recursion_limit: Option<usize>,
+    time_limit: Option<Duration>,
+    soft_heap_limit: Option<usize>,
+    hard_heap_limit: Option<usize>,
+}
+
+impl Default for ContextBuilder {
+    fn default() -> Self {
+        Self {
+            removed_builtins: Vec::new(),
+            freeze_intrinsics: false,
+            allow_code_generation: true,
+            strict: false,
+            loop_iteration_limit: None,
+            recursion_limit: None,
+            time_limit: None,
+            soft_heap_limit: None,
+            hard_heap_limit: None,
+        }
+    }
+}
+
+impl ContextBuilder {
+    /// Create a new `ContextBuilder`, for a context like the one of [`Context::new`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new `ContextBuilder` for running untrusted scripts.
+    ///
+    /// The intrinsic objects are frozen, functions cannot be created from strings, and scripts
+    /// are strict mode code. Builtins that should not be reachable and execution limits can be
+    /// set on the returned builder.
+    #[inline]
+    pub fn sandbox() -> Self {
+        Self {
+            freeze_intrinsics: true,
+            allow_code_generation: false,
+            strict: true,
+            ..Self::default()
+        }
+    }
+
+    /// Leave a builtin out of the global object.
+    ///
+    /// The builtin is named by its path from the global object, like `"Date"` or `"Math.random"`.
+    /// Builtins that don't exist, like `"console"` without the `console` feature, are ignored.
+    /// Leaving a builtin out doesn't remove the intrinsic objects that the engine uses internally.
+    #[inline]
+    pub fn without_builtin<N>(&mut self, name: N) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        self.removed_builtins.push(name.into());
+        self
+    }
+
+    /// Specify whether the intrinsic objects are frozen once the context is set up.
+    ///
+    /// The objects reachable from the builtins of the global object, like constructors,
+    /// prototypes and their methods, are frozen, and the builtins become read-only properties of
+    /// the global object. The global object itself is not frozen, so scripts can still declare
+    /// global variables.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn freeze_intrinsics(&mut self, freeze: bool) -> &mut Self {
+        self.freeze_intrinsics = freeze;
+        self
+    }
+
+    /// Specify whether the `Function` constructor can create functions from source code strings.
+    ///
+    /// When it can't, calling it with arguments throws an `EvalError`.
+    ///
+    /// The default is `true`.
+    #[inline]
+    pub fn allow_code_generation(&mut self, allow: bool) -> &mut Self {
+        self.allow_code_generation = allow;
+        self
+    }
+
+    /// Specify whether scripts are strict mode code, even without a `"use strict"` directive.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn strict(&mut self, strict: bool) -> &mut Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Specify the maximum number of loop iterations of a script.
+    ///
+    /// See [`Context::set_loop_iteration_limit`].
+    #[inline]
+    pub fn loop_iteration_limit(&mut self, limit: Option<u64>) -> &mut Self {
+        self.loop_iteration_limit = limit;
+        self
+    }
+
+    /// Specify the maximum depth of nested calls.
+    ///
+    /// See [`Context::set_recursion_limit`].
+    #[inline]
+    pub fn recursion_limit(&mut self, limit: Option<usize>) -> &mut Self {
+        self.recursion_limit = limit;
+        self
+    }
+
+    /// Specify the maximum wall-clock time a script can run for.
+    ///
+    /// See [`Context::set_time_limit`].
+    #[inline]
+    pub fn time_limit(&mut self, limit: Option<Duration>) -> &mut Self {
+        self.time_limit = limit;
+        self
+    }
+
+    /// Specify the size of the heap past which scripts throw a `RangeError`, in bytes.
+    ///
+    /// See [`Context::set_soft_heap_limit`].
+    #[inline]
+    pub fn soft_heap_limit(&mut self, limit: Option<usize>) -> &mut Self {
+        self.soft_heap_limit = limit;
+        self
+    }
+
+    /// Specify the size of the heap past which scripts are terminated, in bytes.
+    ///
+    /// See [`Context::set_hard_heap_limit`].
+    #[inline]
+    pub fn hard_heap_limit(&mut self, limit: Option<usize>) -> &mut Self {
+        self.hard_heap_limit = limit;
+        self
+    }
+
+    /// Build the context.
+    pub fn build(&mut self) -> Context {
+        let mut context = Context::new();
+
+        context.removed_builtins = self.removed_builtins.clone();
+        context.freeze_intrinsics = self.freeze_intrinsics;
+        context
+            .restrict_realm()
+            .expect("restricting the realm should not throw");
+
+        context.allow_code_generation = self.allow_code_generation;
+        context.strict_scripts = self.strict;
+        context.set_loop_iteration_limit(self.loop_iteration_limit);
+        context.set_recursion_limit(self.recursion_limit);
+        context.set_time_limit(self.time_limit);
+        context.set_soft_heap_limit(self.soft_heap_limit);
+        context.set_hard_heap_limit(self.hard_heap_limit);
+        context
+    }
+
+    /// Removes the builtin at the given path from the global object.
+    fn remove_builtin(context: &mut Context, name: &str) -> JsResult<()> {
+        let mut object = context.global_object();
+        let mut path = name.split('.').peekable();
+        while let Some(key) = path.next() {
+            if path.peek().is_none() {
+                object.__delete__(&key.into(), context)?;
+                break;
+            }
+            object = match object.get(key, context)?.as_object() {
+                Some(next) => next.clone(),
+                None => break,
+            };
+        }
+        Ok(())
+    }
+
+    /// Freezes the objects reachable from the builtins of the global object, and makes the
+    /// builtins read-only.
+    fn freeze(context: &mut Context) -> JsResult<()> {
+        let global = context.global_object();
+        let mut pending = vec![
+            context.intrinsics().throw_type_error(),
+            context.iterator_prototypes().iterator_prototype(),
+            context.iterator_prototypes().array_iterator(),
+            context.iterator_prototypes().set_iterator(),
+            context.iterator_prototypes().string_iterator(),
+            context.iterator_prototypes().regexp_string_iterator(),
+            context.iterator_prototypes().map_iterator(),
+            context.iterator_prototypes().for_in_iterator(),
+        ];
+
+        for key in global.__own_property_keys__(context)? {
+            if let Some(desc) = global.__get_own_property__(&key, context)? {
+                if let Some(value) = desc.value().and_then(JsValue::as_object) {
+                    pending.push(value.clone());
+                }
+                if desc.is_data_descriptor() {
+                    global.define_property_or_throw(
+                        key,
+                        PropertyDescriptor::builder()
+                            .writable(false)
+                            .configurable(false),
+                        context,
+                    )?;
+                }
+            }
+        }
+
+        let mut visited = FxHashSet::default();
+        while let Some(object) = pending.pop() {
+            if JsObject::equals(&object, &global)
+                || !visited.insert(object.as_ref() as *const _ as usize)
+            {
+                continue;
+            }
+
+            object.set_integrity_level(IntegrityLevel::Frozen, context)?;
+
+            if let Some(prototype) = object.__get_prototype_of__(context)? {
+                pending.push(prototype);
+            }
+            for key in object.__own_property_keys__(context)? {
+                if let Some(desc) = object.__get_own_property__(&key, context)? {
+                    pending.extend(
+                        [desc.value(), desc.get(), desc.set()]
+                            .into_iter()
+                            .flatten()
+                            .filter_map(JsValue::as_object)
+                            .cloned(),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Context, ContextBuilder};
+    use crate::{forward, property::Attribute};
+
+    #[test]
+    fn without_builtin() {
+        let mut context = ContextBuilder::new()
+            .without_builtin("Date")
+            .without_builtin("Math.random")
+            .without_builtin("console")
+            .without_builtin("Missing.builtin")
+            .build();
+
+        assert_eq!(forward(&mut context, "'Date' in globalThis"), "false");
+        assert_eq!(forward(&mut context, "'random' in Math"), "false");
+        assert_eq!(forward(&mut context, "typeof Math.floor"), "\"function\"");
+    }
+
+    #[test]
+    fn freeze_intrinsics() {
+        let mut context = ContextBuilder::new().freeze_intrinsics(true).build();
+
+        assert_eq!(
+            forward(&mut context, "Object.isFrozen(Array.prototype)"),
+            "true"
+        );
+        assert_eq!(
+            forward(
+                &mut context,
+                "Object.isFrozen(Object.getPrototypeOf([][Symbol.iterator]()))"
+            ),
+            "true"
+        );
+        assert_eq!(
+            forward(
+                &mut context,
+                "try { Array.prototype.push = null; } catch (e) {} typeof Array.prototype.push"
+            ),
+            "\"function\""
+        );
+        assert_eq!(
+            forward(
+                &mut context,
+                "try { Array = null; } catch (e) {} typeof Array"
+            ),
+            "\"function\""
+        );
+
+        // The global object can still be extended.
+        assert_eq!(forward(&mut context, "var x = 1; x"), "1");
+
+        // Errors still get their own message, despite the frozen `Error.prototype.message`.
+        assert_eq!(
+            forward(&mut context, "new TypeError('frozen').message"),
+            "\"frozen\""
+        );
+    }
+
+    #[test]
+    fn restrictions_apply_to_new_realms() {
+        let mut context = ContextBuilder::new()
+            .without_builtin("Date")
+            .without_builtin("Math.random")
+            .freeze_intrinsics(true)
+            .build();
+
+        assert_eq!(
+            forward(
+                &mut context,
+                r#"
+                const realm = new ShadowRealm();
+                [
+                    realm.evaluate("'Date' in globalThis"),
+                    realm.evaluate("typeof Math.random"),
+                    realm.evaluate("typeof Math.floor"),
+                    realm.evaluate("Object.isFrozen(Object.prototype)"),
+                ].join()
+                "#
+            ),
+            "\"false,undefined,function,true\""
+        );
+
+        let realm = context.create_realm();
+        context.enter_realm(realm);
+        assert_eq!(forward(&mut context, "'Date' in globalThis"), "false");
+        assert_eq!(
+            forward(&mut context, "Object.isFrozen(Array.prototype)"),
+            "true"
+        );
+    }
+
+    #[test]
+    fn disallow_code_generation() {
+        let mut context = ContextBuilder::new().allow_code_generation(false).build();
+
+        assert_eq!(
+            forward(
+                &mut context,
+                "try { Function('return 1'); } catch (e) { e.name }"
+            ),
+            "\"EvalError\""
+        );
+        assert_eq!(forward(&mut context, "typeof Function()"), "\"function\"");
+    }
+
+    #[test]
+    fn strict_scripts() {
+        // Deleting an unqualified identifier is a syntax error in strict mode code.
+        let mut context = ContextBuilder::new().strict(true).build();
+        assert!(context.eval("var x = 1; delete x").is_err());
+
+        let mut context = ContextBuilder::new().build();
+        assert!(context.eval("var x = 1; delete x").is_ok());
+    }
+
+    #[test]
+    fn sandbox_limits() {
+        let mut context = ContextBuilder::sandbox()
+            .loop_iteration_limit(Some(100))
+            .build();
+
+        assert!(context.eval("while (true) {}").is_err());
+        assert_eq!(forward(&mut context, "Object.isFrozen(Object)"), "true");
+    }
+
+    #[test]
+    fn realms() {
+        let mut context = Context::new();
+        let realm = context.create_realm();
+
+        let main_realm = context.enter_realm(realm);
+        let other_array = context.eval("Array").unwrap();
+        let f = context
+            .eval("function F() {} F.prototype = null; F")
+            .unwrap();
+        let make_array = context
+            .eval("function makeArray() { return []; } makeArray")
+            .unwrap();
+        context.enter_realm(main_realm);
+
+        context.register_global_property("OtherArray", other_array, Attribute::all());
+        context.register_global_property("F", f, Attribute::all());
+        context.register_global_property("makeArray", make_array, Attribute::all());
+
+        assert_eq!(forward(&mut context, "OtherArray === Array"), "false");
+        assert_eq!(
+            forward(
+                &mut context,
+                "Object.getPrototypeOf(Reflect.construct(Array, [], F)) === OtherArray.prototype"
+            ),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut context, "makeArray() instanceof Array"),
+            "false"
+        );
+        assert_eq!(
+            forward(&mut context, "makeArray() instanceof OtherArray"),
+            "true"
+        );
+        assert_eq!(forward(&mut context, "[] instanceof Array"), "true");
+    }
 }
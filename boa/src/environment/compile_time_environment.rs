@@ -0,0 +1,345 @@
+//! # Compile-time environments
+//!
+//! The scope analysis of the bytecode compiler. While a script or function is compiled, the
+//! compiler keeps a stack of the declarative environments that will exist at runtime, with
+//! the bindings that are declared in each of them. An identifier that is declared in one of
+//! these environments is resolved to its location, the number of environments to walk up the
+//! environment chain and the index of the binding in that environment, so that it can be
+//! accessed at runtime without looking it up by name.
+//!
+//! The bindings of a declarative environment are stored in a flat array, where the bindings
+//! declared at compile time occupy the first slots in the order of its [`ScopeLayout`].
+//! Bindings created at runtime, for example by assignments to undeclared variables in
+//! sloppy mode, are added after them and are only found by name.
+//!
+//! Identifiers fall back to a lookup by name when they are not declared in any of the
+//! environments, which is the case for the bindings of the global environment, or when the
+//! lookup passes through the environment of a function that calls `eval`, which may declare
+//! new variables in it.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-static-semantics-vardeclarednames
+
+use crate::{
+    gc::{empty_trace, Finalize, Trace},
+    syntax::ast::node::{
+        Declaration, DeclarationList, FormalParameter, Node, StatementList, Switch,
+    },
+};
+use gc::Gc;
+use rustc_hash::FxHashMap;
+use std::rc::Rc;
+
+/// The kind of a binding that is declared when its environment is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BindingKind {
+    /// A `var` binding, a parameter or a function declaration, initialized to `undefined`.
+    Var,
+    /// A `let` binding, uninitialized until its declaration is evaluated.
+    Let,
+    /// A `const` binding, uninitialized until its declaration is evaluated.
+    Const,
+}
+
+/// A binding that is declared when its environment is created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DeclaredBinding {
+    pub(crate) name: Box<str>,
+    pub(crate) kind: BindingKind,
+}
+
+/// The bindings that are declared in a declarative environment when it is created, with their
+/// slots.
+///
+/// A layout is shared by all the environments of a scope.
+#[derive(Debug, Clone, Default, Finalize)]
+pub struct ScopeLayout {
+    bindings: Box<[DeclaredBinding]>,
+    slots: FxHashMap<Box<str>, usize>,
+}
+
+// Safety: `ScopeLayout` does not contain any garbage collected values.
+unsafe impl Trace for ScopeLayout {
+    empty_trace!();
+}
+
+impl ScopeLayout {
+    /// Creates a layout with the given bindings, the first declaration of a name wins.
+    pub(crate) fn new(declarations: Vec<DeclaredBinding>) -> Self {
+        let mut bindings = Vec::with_capacity(declarations.len());
+        let mut slots = FxHashMap::default();
+        for declaration in declarations {
+            if !slots.contains_key(&declaration.name) {
+                slots.insert(declaration.name.clone(), bindings.len());
+                bindings.push(declaration);
+            }
+        }
+        Self {
+            bindings: bindings.into_boxed_slice(),
+            slots,
+        }
+    }
+
+    /// Gets the declared bindings, by slot.
+    #[inline]
+    pub(crate) fn bindings(&self) -> &[DeclaredBinding] {
+        &self.bindings
+    }
+
+    /// Gets the slot of the binding `name`, if it is declared.
+    #[inline]
+    pub(crate) fn slot(&self, name: &str) -> Option<usize> {
+        self.slots.get(name).copied()
+    }
+
+    /// Checks if the layout does not declare any binding.
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// The layout of the environment of a function.
+    ///
+    /// It declares the `arguments` object, the parameters, the `var` and function
+    /// declarations, and the `let` and `const` declarations at the top level of the body.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-functiondeclarationinstantiation
+    pub(crate) fn function(
+        parameters: &[FormalParameter],
+        body: &StatementList,
+        arguments_binding: bool,
+    ) -> Self {
+        let mut names = Vec::new();
+        if arguments_binding {
+            names.push("arguments");
+        }
+        names.extend(parameters.iter().map(FormalParameter::name));
+        for node in body.items() {
+            var_declared_names(node, &mut names);
+        }
+
+        let mut declarations: Vec<_> = names
+            .into_iter()
+            .map(|name| DeclaredBinding {
+                name: name.into(),
+                kind: BindingKind::Var,
+            })
+            .collect();
+        lexical_declarations(body.items(), &mut declarations);
+        Self::new(declarations)
+    }
+
+    /// The layout of the environment of a block, that declares the `let` and `const`
+    /// declarations of the statements.
+    pub(crate) fn lexical(items: &[Node]) -> Self {
+        let mut declarations = Vec::new();
+        lexical_declarations(items, &mut declarations);
+        Self::new(declarations)
+    }
+
+    /// The layout of the environment of a `switch` statement, that declares the `let` and
+    /// `const` declarations of its cases.
+    pub(crate) fn switch(switch: &Switch) -> Self {
+        let mut declarations = Vec::new();
+        for case in switch.cases() {
+            lexical_declarations(case.body().items(), &mut declarations);
+        }
+        lexical_declarations(switch.default().unwrap_or_default(), &mut declarations);
+        Self::new(declarations)
+    }
+
+    /// The layout of an environment that declares the bindings of a declaration list.
+    pub(crate) fn declaration_list(list: &DeclarationList, kind: BindingKind) -> Self {
+        let mut declarations = Vec::new();
+        for declaration in list.as_ref() {
+            declared_bindings(declaration, kind, &mut declarations);
+        }
+        Self::new(declarations)
+    }
+
+    /// The layout of the environment of a `catch` block, that declares its parameter.
+    pub(crate) fn catch_parameter(parameter: Option<&Declaration>) -> Self {
+        let mut declarations = Vec::new();
+        if let Some(parameter) = parameter {
+            declared_bindings(parameter, BindingKind::Let, &mut declarations);
+        }
+        Self::new(declarations)
+    }
+}
+
+/// Pushes the bindings declared by a declaration.
+fn declared_bindings(
+    declaration: &Declaration,
+    kind: BindingKind,
+    declarations: &mut Vec<DeclaredBinding>,
+) {
+    let mut push = |name: &str| {
+        declarations.push(DeclaredBinding {
+            name: name.into(),
+            kind,
+        })
+    };
+    match declaration {
+        Declaration::Identifier { ident, .. } => push(ident.as_ref()),
+        Declaration::Pattern(pattern) => pattern.idents().into_iter().for_each(push),
+    }
+}
+
+/// Pushes the bindings declared by the `let` and `const` declarations of a statement list.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-static-semantics-lexicallydeclarednames
+fn lexical_declarations(items: &[Node], declarations: &mut Vec<DeclaredBinding>) {
+    for node in items {
+        let (list, kind) = match node {
+            Node::LetDeclList(list) => (list, BindingKind::Let),
+            Node::ConstDeclList(list) => (list, BindingKind::Const),
+            _ => continue,
+        };
+        for declaration in list.as_ref() {
+            declared_bindings(declaration, kind, declarations);
+        }
+    }
+}
+
+/// Collects the names declared by the `var` and function declarations of a statement,
+/// including the ones in nested statements, but not in nested functions.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-static-semantics-vardeclarednames
+fn var_declared_names<'n>(node: &'n Node, names: &mut Vec<&'n str>) {
+    match node {
+        Node::VarDeclList(list) => {
+            for declaration in list.as_ref() {
+                match declaration {
+                    Declaration::Identifier { ident, .. } => names.push(ident.as_ref()),
+                    Declaration::Pattern(pattern) => names.extend(pattern.idents()),
+                }
+            }
+        }
+        // Function declarations in blocks are bound in the function environment, like `var`
+        // declarations.
+        Node::FunctionDecl(function) => names.push(function.name()),
+        Node::Block(block) => {
+            for node in block.items() {
+                var_declared_names(node, names);
+            }
+        }
+        Node::If(node) => {
+            var_declared_names(node.body(), names);
+            if let Some(else_node) = node.else_node() {
+                var_declared_names(else_node, names);
+            }
+        }
+        Node::WhileLoop(while_) => var_declared_names(while_.body(), names),
+        Node::DoWhileLoop(do_while) => var_declared_names(do_while.body(), names),
+        Node::ForLoop(for_loop) => {
+            if let Some(init) = for_loop.init() {
+                var_declared_names(init, names);
+            }
+            var_declared_names(for_loop.body(), names);
+        }
+        Node::ForInLoop(for_in_loop) => {
+            var_declared_names(for_in_loop.variable(), names);
+            var_declared_names(for_in_loop.body(), names);
+        }
+        Node::ForOfLoop(for_of_loop) => {
+            var_declared_names(for_of_loop.variable(), names);
+            var_declared_names(for_of_loop.body(), names);
+        }
+        Node::Try(try_node) => {
+            for node in try_node.block().items() {
+                var_declared_names(node, names);
+            }
+            if let Some(catch) = try_node.catch() {
+                for node in catch.block().items() {
+                    var_declared_names(node, names);
+                }
+            }
+            if let Some(finally) = try_node.finally() {
+                for node in finally.items() {
+                    var_declared_names(node, names);
+                }
+            }
+        }
+        Node::Switch(switch) => {
+            for case in switch.cases() {
+                for node in case.body().items() {
+                    var_declared_names(node, names);
+                }
+            }
+            for node in switch.default().unwrap_or_default() {
+                var_declared_names(node, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The location of a binding, as resolved at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BindingLocator {
+    /// The binding is in the slot `slot` of the environment that is `depth` environments up
+    /// the environment chain.
+    Declarative { depth: u32, slot: u32 },
+    /// The binding has to be looked up by name at runtime.
+    Dynamic,
+}
+
+/// A declarative environment, as known at compile time.
+#[derive(Debug)]
+struct CompileTimeEnvironment {
+    layout: Gc<ScopeLayout>,
+    /// Whether bindings that are not in the layout may be created in this environment by a
+    /// direct `eval`, so that the identifiers that are not found in it can not be resolved.
+    dynamic: bool,
+}
+
+/// The declarative environments that enclose the code being compiled, innermost last.
+///
+/// The environments of the enclosing functions are shared with the compilers of the
+/// functions defined in them.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CompileTimeEnvironmentStack {
+    environments: Vec<Rc<CompileTimeEnvironment>>,
+}
+
+impl CompileTimeEnvironmentStack {
+    /// Pushes an environment with the given layout.
+    pub(crate) fn push(&mut self, layout: Gc<ScopeLayout>, dynamic: bool) {
+        self.environments
+            .push(Rc::new(CompileTimeEnvironment { layout, dynamic }));
+    }
+
+    /// Pops the innermost environment.
+    pub(crate) fn pop(&mut self) {
+        self.environments
+            .pop()
+            .expect("environment stack underflow");
+    }
+
+    /// Resolves the binding `name` from the innermost environment.
+    pub(crate) fn resolve(&self, name: &str) -> BindingLocator {
+        for (depth, environment) in self.environments.iter().rev().enumerate() {
+            if let Some(slot) = environment.layout.slot(name) {
+                return BindingLocator::Declarative {
+                    depth: depth as u32,
+                    slot: slot as u32,
+                };
+            }
+            if environment.dynamic {
+                break;
+            }
+        }
+        BindingLocator::Dynamic
+    }
+}
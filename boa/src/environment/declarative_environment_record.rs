@@ -3,10 +3,14 @@
 //! Each declarative Environment Record is associated with an ECMAScript program scope containing variable,
 //! `constant`, `let`, `class`, `module`, `import`, and/or function declarations.
 //! A declarative Environment Record binds the set of identifiers defined by the declarations contained within its scope.
+//!
+//! The bindings are stored in a flat array. The bindings declared at compile time occupy the
+//! first slots, as described by the [`ScopeLayout`] of the scope, and can be accessed by slot.
 //! More info:  [ECMA-262 sec-declarative-environment-records](https://tc39.es/ecma262/#sec-declarative-environment-records)
 
 use crate::{
     environment::{
+        compile_time_environment::{BindingKind, ScopeLayout},
         environment_record_trait::EnvironmentRecordTrait,
         lexical_environment::{Environment, EnvironmentType},
     },
@@ -34,7 +38,12 @@ pub struct DeclarativeEnvironmentRecordBinding {
 /// declarations contained within its scope.
 #[derive(Debug, Trace, Finalize, Clone)]
 pub struct DeclarativeEnvironmentRecord {
-    pub env_rec: GcCell<FxHashMap<Box<str>, DeclarativeEnvironmentRecordBinding>>,
+    /// The bindings, by slot.
+    pub bindings: GcCell<Vec<DeclarativeEnvironmentRecordBinding>>,
+    /// The bindings declared at compile time, which occupy the first slots.
+    pub layout: Option<Gc<ScopeLayout>>,
+    /// The slots of the bindings created at runtime, by name.
+    pub slots: GcCell<FxHashMap<Box<str>, usize>>,
     pub outer_env: Option<Environment>,
 }
 
@@ -42,10 +51,136 @@ impl DeclarativeEnvironmentRecord {
     pub fn new(env: Option<Environment>) -> DeclarativeEnvironmentRecord {
         let _timer = BoaProfiler::global().start_event("new_declarative_environment", "env");
         DeclarativeEnvironmentRecord {
-            env_rec: GcCell::new(FxHashMap::default()),
+            bindings: GcCell::new(Vec::new()),
+            layout: None,
+            slots: GcCell::new(FxHashMap::default()),
             outer_env: env,
         }
     }
+
+    /// Creates a declarative environment with the bindings of a scope.
+    pub fn with_layout(env: Option<Environment>, layout: Gc<ScopeLayout>) -> Self {
+        let mut record = Self::new(env);
+        record.declare_layout(layout);
+        record
+    }
+
+    /// Declares the bindings of a scope, `var` bindings are initialized to `undefined`.
+    ///
+    /// The environment must not have any binding yet.
+    pub fn declare_layout(&mut self, layout: Gc<ScopeLayout>) {
+        debug_assert!(self.bindings.borrow().is_empty());
+        if layout.is_empty() {
+            return;
+        }
+
+        *self.bindings.borrow_mut() = layout
+            .bindings()
+            .iter()
+            .map(|binding| DeclarativeEnvironmentRecordBinding {
+                value: (binding.kind == BindingKind::Var).then(JsValue::undefined),
+                can_delete: false,
+                mutable: binding.kind != BindingKind::Const,
+                strict: false,
+            })
+            .collect();
+        self.layout = Some(layout);
+    }
+
+    /// Gets the slot of the binding `name`.
+    fn slot(&self, name: &str) -> Option<usize> {
+        self.layout
+            .as_ref()
+            .and_then(|layout| layout.slot(name))
+            .or_else(|| self.slots.borrow().get(name).copied())
+    }
+
+    /// Gets the name of the binding in `slot`.
+    fn slot_name(&self, slot: usize) -> Box<str> {
+        if let Some(binding) = self
+            .layout
+            .as_ref()
+            .and_then(|layout| layout.bindings().get(slot))
+        {
+            return binding.name.clone();
+        }
+        self.slots
+            .borrow()
+            .iter()
+            .find(|(_, binding_slot)| **binding_slot == slot)
+            .map(|(name, _)| name.clone())
+            .expect("slot has no binding")
+    }
+
+    /// Creates the binding `name`, or replaces it if it already exists.
+    fn insert_binding(&self, name: &str, binding: DeclarativeEnvironmentRecordBinding) {
+        if let Some(slot) = self.slot(name) {
+            self.bindings.borrow_mut()[slot] = binding;
+        } else {
+            let mut bindings = self.bindings.borrow_mut();
+            self.slots.borrow_mut().insert(name.into(), bindings.len());
+            bindings.push(binding);
+        }
+    }
+
+    /// Gets the value of the binding in `slot`.
+    ///
+    /// See [`EnvironmentRecordTrait::get_binding_value`].
+    pub fn get_binding_value_by_slot(
+        &self,
+        slot: usize,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        if let Some(ref value) = self.bindings.borrow()[slot].value {
+            return Ok(value.clone());
+        }
+        context.throw_reference_error(format!(
+            "{} is an uninitialized binding",
+            self.slot_name(slot)
+        ))
+    }
+
+    /// Initializes the binding in `slot`.
+    ///
+    /// See [`EnvironmentRecordTrait::initialize_binding`].
+    pub fn initialize_binding_by_slot(&self, slot: usize, value: JsValue) {
+        let mut bindings = self.bindings.borrow_mut();
+        let binding = &mut bindings[slot];
+        debug_assert!(binding.value.is_none(), "binding is already initialized");
+        binding.value = Some(value);
+    }
+
+    /// Assigns the binding in `slot`.
+    ///
+    /// See [`EnvironmentRecordTrait::set_mutable_binding`].
+    pub fn set_mutable_binding_by_slot(
+        &self,
+        slot: usize,
+        value: JsValue,
+        strict: bool,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let (initialized, mutable, binding_strict) = {
+            let binding = &self.bindings.borrow()[slot];
+            (binding.value.is_some(), binding.mutable, binding.strict)
+        };
+
+        if !initialized {
+            return Err(context.construct_reference_error(format!(
+                "{} has not been initialized",
+                self.slot_name(slot)
+            )));
+        }
+        if mutable {
+            self.bindings.borrow_mut()[slot].value = Some(value);
+        } else if strict || binding_strict {
+            return Err(context.construct_type_error(format!(
+                "Cannot mutate an immutable binding {}",
+                self.slot_name(slot)
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
@@ -58,7 +193,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     fn has_binding(&self, name: &str, _context: &mut Context) -> JsResult<bool> {
         // 1. If envRec has a binding for the name that is the value of N, return true.
         // 2. Return false.
-        Ok(self.env_rec.borrow().contains_key(name))
+        Ok(self.slot(name).is_some())
     }
 
     /// `9.1.1.1.2 CreateMutableBinding ( N, D )`
@@ -77,7 +212,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         // 1. Assert: envRec does not already have a binding for N.
         if !allow_name_reuse {
             assert!(
-                !self.env_rec.borrow().contains_key(name),
+                self.slot(name).is_none(),
                 "Identifier {} has already been declared",
                 name
             );
@@ -85,8 +220,8 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
 
         // 2. Create a mutable binding in envRec for N and record that it is uninitialized.
         //    If D is true, record that the newly created binding may be deleted by a subsequent DeleteBinding call.
-        self.env_rec.borrow_mut().insert(
-            name.into(),
+        self.insert_binding(
+            name,
             DeclarativeEnvironmentRecordBinding {
                 value: None,
                 can_delete: deletion,
@@ -113,18 +248,18 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     ) -> JsResult<()> {
         // 1. Assert: envRec does not already have a binding for N.
         assert!(
-            !self.env_rec.borrow().contains_key(name),
+            self.slot(name).is_none(),
             "Identifier {} has already been declared",
             name
         );
 
         // 2. Create an immutable binding in envRec for N and record that it is uninitialized.
         //    If S is true, record that the newly created binding is a strict binding.
-        self.env_rec.borrow_mut().insert(
-            name.into(),
+        self.insert_binding(
+            name,
             DeclarativeEnvironmentRecordBinding {
                 value: None,
-                can_delete: true,
+                can_delete: false,
                 mutable: false,
                 strict,
             },
@@ -146,7 +281,9 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         value: JsValue,
         _context: &mut Context,
     ) -> JsResult<()> {
-        if let Some(ref mut record) = self.env_rec.borrow_mut().get_mut(name) {
+        if let Some(slot) = self.slot(name) {
+            let mut bindings = self.bindings.borrow_mut();
+            let record = &mut bindings[slot];
             if record.value.is_none() {
                 // 2. Set the bound value for N in envRec to V.
                 // 3. Record that the binding for N in envRec has been initialized.
@@ -176,7 +313,9 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         context: &mut Context,
     ) -> JsResult<()> {
         // 1. If envRec does not have a binding for N, then
-        if self.env_rec.borrow().get(name).is_none() {
+        let slot = if let Some(slot) = self.slot(name) {
+            slot
+        } else {
             // a. If S is true, throw a ReferenceError exception.
             if strict {
                 return Err(context.construct_reference_error(format!("{} not found", name)));
@@ -189,11 +328,10 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
 
             // d. Return NormalCompletion(empty).
             return Ok(());
-        }
+        };
 
         let (binding_strict, binding_value_is_none, binding_mutable) = {
-            let env_rec = self.env_rec.borrow();
-            let binding = env_rec.get(name).unwrap();
+            let binding = &self.bindings.borrow()[slot];
             (binding.strict, binding.value.is_none(), binding.mutable)
         };
 
@@ -209,9 +347,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
             );
         // 4. Else if the binding for N in envRec is a mutable binding, change its bound value to V.
         } else if binding_mutable {
-            let mut env_rec = self.env_rec.borrow_mut();
-            let binding = env_rec.get_mut(name).unwrap();
-            binding.value = Some(value);
+            self.bindings.borrow_mut()[slot].value = Some(value);
         // 5. Else,
         // a. Assert: This is an attempt to change the value of an immutable binding.
         // b. If S is true, throw a TypeError exception.
@@ -239,8 +375,8 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         // 1. Assert: envRec has a binding for N.
         // 2. If the binding for N in envRec is an uninitialized binding, throw a ReferenceError exception.
         // 3. Return the value currently bound to N in envRec.
-        if let Some(binding) = self.env_rec.borrow().get(name) {
-            if let Some(ref val) = binding.value {
+        if let Some(slot) = self.slot(name) {
+            if let Some(ref val) = self.bindings.borrow()[slot].value {
                 Ok(val.clone())
             } else {
                 context.throw_reference_error(format!("{} is an uninitialized binding", name))
@@ -261,17 +397,15 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         // 2. If the binding for N in envRec cannot be deleted, return false.
         // 3. Remove the binding for N from envRec.
         // 4. Return true.
-        match self.env_rec.borrow().get(name) {
-            Some(binding) => {
-                if binding.can_delete {
-                    self.env_rec.borrow_mut().remove(name);
-                    Ok(true)
-                } else {
-                    Ok(false)
-                }
-            }
+        let can_delete = match self.slot(name) {
+            Some(slot) => self.bindings.borrow()[slot].can_delete,
             None => panic!("env_rec has no binding for {}", name),
+        };
+        if can_delete {
+            // Only bindings created at runtime can be deleted, their slot is left unused.
+            self.slots.borrow_mut().remove(name);
         }
+        Ok(can_delete)
     }
 
     /// `9.1.1.1.8 HasThisBinding ( )`
@@ -321,6 +455,10 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     fn get_environment_type(&self) -> EnvironmentType {
         EnvironmentType::Declarative
     }
+
+    fn declarative_record(&self) -> Option<&DeclarativeEnvironmentRecord> {
+        Some(self)
+    }
 }
 
 impl From<DeclarativeEnvironmentRecord> for Environment {
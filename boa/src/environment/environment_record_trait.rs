@@ -9,12 +9,18 @@
 //! There are 5 Environment record kinds. They all have methods in common, these are implemented as a the `EnvironmentRecordTrait`
 //!
 
-use crate::{environment::lexical_environment::VariableScope, object::JsObject};
 use crate::{
     environment::lexical_environment::{Environment, EnvironmentType},
     gc::{Finalize, Trace},
     Context, JsResult, JsValue,
 };
+use crate::{
+    environment::{
+        declarative_environment_record::DeclarativeEnvironmentRecord,
+        lexical_environment::VariableScope,
+    },
+    object::JsObject,
+};
 use std::fmt::Debug;
 
 /// <https://tc39.es/ecma262/#sec-environment-records>
@@ -114,6 +120,12 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Get the type of environment this is
     fn get_environment_type(&self) -> EnvironmentType;
 
+    /// Gets the declarative record of the environment, which stores the bindings that are
+    /// resolved at compile time.
+    fn declarative_record(&self) -> Option<&DeclarativeEnvironmentRecord> {
+        None
+    }
+
     /// Return the `this` binding from the environment or try to get it from outer environments
     fn recursive_get_this_binding(&self, context: &mut Context) -> JsResult<JsValue> {
         if self.has_this_binding() {
@@ -202,6 +214,20 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
             })
     }
 
+    /// Delete binding from current or any outer environment
+    ///
+    /// Returns `true` if the binding does not exist.
+    fn recursive_delete_binding(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+        if self.has_binding(name, context)? {
+            self.delete_binding(name, context)
+        } else {
+            match self.get_outer_environment_ref() {
+                Some(outer) => outer.recursive_delete_binding(name, context),
+                None => Ok(true),
+            }
+        }
+    }
+
     /// Retrieve binding from current or any outer environment
     fn recursive_get_binding_value(&self, name: &str, context: &mut Context) -> JsResult<JsValue> {
         if self.has_binding(name, context)? {
@@ -260,6 +260,10 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         EnvironmentType::Function
     }
 
+    fn declarative_record(&self) -> Option<&DeclarativeEnvironmentRecord> {
+        Some(&self.declarative_record)
+    }
+
     fn recursive_create_mutable_binding(
         &self,
         name: &str,
@@ -5,12 +5,14 @@
 //! The following operations are used to operate upon lexical environments
 //! This is the entrypoint to lexical environments.
 
+#[cfg(feature = "vm")]
+use super::declarative_environment_record::DeclarativeEnvironmentRecord;
 use super::global_environment_record::GlobalEnvironmentRecord;
 use crate::{
     environment::environment_record_trait::EnvironmentRecordTrait, object::JsObject, BoaProfiler,
     Context, JsResult, JsValue,
 };
-use gc::Gc;
+use gc::{Finalize, Gc, Trace};
 use std::{collections::VecDeque, error, fmt};
 
 /// Environments are wrapped in a Box and then in a GC wrapper
@@ -35,7 +37,7 @@ pub enum VariableScope {
     Function,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Trace, Finalize)]
 pub struct LexicalEnvironment {
     environment_stack: VecDeque<Environment>,
 }
@@ -76,16 +78,28 @@ impl LexicalEnvironment {
     }
 }
 
+impl LexicalEnvironment {
+    /// Returns an environment stack that only holds the global environment of this one.
+    pub(crate) fn global_only(&self) -> Self {
+        Self {
+            environment_stack: self.environment_stack.iter().take(1).cloned().collect(),
+        }
+    }
+}
+
 impl Context {
     pub(crate) fn push_environment<T: Into<Environment>>(&mut self, env: T) {
-        self.realm
-            .environment
-            .environment_stack
-            .push_back(env.into());
+        self.environment.environment_stack.push_back(env.into());
     }
 
     pub(crate) fn pop_environment(&mut self) -> Option<Environment> {
-        self.realm.environment.environment_stack.pop_back()
+        self.environment.environment_stack.pop_back()
+    }
+
+    /// Returns the number of environments on the environment stack.
+    #[cfg(feature = "vm")]
+    pub(crate) fn environment_stack_len(&self) -> usize {
+        self.environment.environment_stack.len()
     }
 
     pub(crate) fn get_this_binding(&mut self) -> JsResult<JsValue> {
@@ -131,8 +145,7 @@ impl Context {
     /// When neededing to clone an environment (linking it with another environnment)
     /// cloning is more suited. The GC will remove the env once nothing is linking to it anymore
     pub(crate) fn get_current_environment(&mut self) -> Environment {
-        self.realm
-            .environment
+        self.environment
             .environment_stack
             .back_mut()
             .expect("Could not get mutable reference to back object")
@@ -148,6 +161,62 @@ impl Context {
         self.get_current_environment()
             .recursive_get_binding_value(name, self)
     }
+
+    #[cfg(feature = "vm")]
+    pub(crate) fn delete_binding(&mut self, name: &str) -> JsResult<bool> {
+        self.get_current_environment()
+            .recursive_delete_binding(name, self)
+    }
+
+    /// Gets the value of the binding in `slot` of the environment that is `depth`
+    /// environments up the environment chain.
+    #[cfg(feature = "vm")]
+    pub(crate) fn get_binding_value_by_slot(&mut self, depth: u32, slot: u32) -> JsResult<JsValue> {
+        let environment = self.get_current_environment();
+        declarative_record_at(&environment, depth).get_binding_value_by_slot(slot as usize, self)
+    }
+
+    /// Assigns the binding in `slot` of the environment that is `depth` environments up the
+    /// environment chain.
+    #[cfg(feature = "vm")]
+    pub(crate) fn set_mutable_binding_by_slot(
+        &mut self,
+        depth: u32,
+        slot: u32,
+        value: JsValue,
+        strict: bool,
+    ) -> JsResult<()> {
+        let environment = self.get_current_environment();
+        declarative_record_at(&environment, depth).set_mutable_binding_by_slot(
+            slot as usize,
+            value,
+            strict,
+            self,
+        )
+    }
+
+    /// Initializes the binding in `slot` of the environment that is `depth` environments up
+    /// the environment chain.
+    #[cfg(feature = "vm")]
+    pub(crate) fn initialize_binding_by_slot(&mut self, depth: u32, slot: u32, value: JsValue) {
+        let environment = self.get_current_environment();
+        declarative_record_at(&environment, depth).initialize_binding_by_slot(slot as usize, value);
+    }
+}
+
+/// Gets the declarative record of the environment that is `depth` environments up the
+/// environment chain from `environment`.
+#[cfg(feature = "vm")]
+fn declarative_record_at(environment: &Environment, depth: u32) -> &DeclarativeEnvironmentRecord {
+    let mut environment: &dyn EnvironmentRecordTrait = &***environment;
+    for _ in 0..depth {
+        environment = &***environment
+            .get_outer_environment_ref()
+            .expect("binding resolved outside of the environment chain");
+    }
+    environment
+        .declarative_record()
+        .expect("binding resolved to a non declarative environment")
 }
 
 #[cfg(test)]
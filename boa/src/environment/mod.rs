@@ -1,5 +1,6 @@
 //! Environment handling, lexical, object, function and declaritive records
 
+pub mod compile_time_environment;
 pub mod declarative_environment_record;
 pub mod environment_record_trait;
 pub mod function_environment_record;
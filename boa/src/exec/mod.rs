@@ -5,6 +5,21 @@ mod tests;
 
 use crate::{Context, JsResult, JsValue};
 
+impl Context {
+    /// Completes a labelled statement that is not a loop, which stops a `break` with its label.
+    #[inline]
+    pub(crate) fn complete_labelled_statement(&mut self, label: Option<&str>) {
+        let is_target = matches!(
+            (label, self.executor().get_current_state()),
+            (Some(label), InterpreterState::Break(Some(target))) if label == target.as_ref()
+        );
+        if is_target {
+            self.executor()
+                .set_current_state(InterpreterState::Executing);
+        }
+    }
+}
+
 pub trait Executable {
     /// Runs this executable in the given context.
     fn run(&self, context: &mut Context) -> JsResult<JsValue>;
@@ -1,10 +1,342 @@
 //! This module represents the main way to interact with the garbage collector.
+//!
+//! The `Trace` and `Finalize` derives of this module generate paths into `boa::gc`, so types
+//! deriving them don't need to depend on the `gc` crate.
+//!
+//! The garbage collected heap is shared by all the [`Context`]s of a thread. A [`Context`] can
+//! force a collection and report statistics about the objects of the heap:
+//!
+//! ```
+//!# use boa::Context;
+//! let mut context = Context::new();
+//! context.set_heap_object_tracking(true);
+//! context
+//!     .eval("var map = new Map(); var cycle = {}; cycle.self = cycle; cycle = undefined;")
+//!     .unwrap();
+//! context.collect_garbage();
+//!
+//! let statistics = context.heap_statistics();
+//! assert!(statistics.objects > 0);
+//! assert_eq!(statistics.objects_by_kind["Map"], 1);
+//! assert!(statistics.collections > 0);
+//! ```
 
 // This is because `rust-gc` unsafe_empty_trace has a `unsafe_`
 // when it should be `empty_trace`.
 #![allow(clippy::unsafe_removed_from_name)]
 
+pub use boa_macros::{Finalize, Trace};
 pub use gc::{
-    custom_trace, force_collect, unsafe_empty_trace as empty_trace, Finalize, GcCellRef as Ref,
-    GcCellRefMut as RefMut, Trace,
+    custom_trace, finalizer_safe, force_collect, unsafe_empty_trace as empty_trace, Gc, GcCell,
+    GcCellRef as Ref, GcCellRefMut as RefMut,
 };
+pub use traits::*;
+
+use crate::{object::Object, Context};
+use rustc_hash::FxHashMap;
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    mem,
+};
+
+/// The traits of the garbage collector, whose derives are shadowed by the ones of `boa_macros`.
+mod traits {
+    pub use gc::{Finalize, Trace};
+}
+
+/// Statistics about the objects of the garbage collected heap of a thread.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeapStatistics {
+    /// The number of live objects.
+    ///
+    /// Objects that are unreachable but were not collected yet are counted as live.
+    pub objects: usize,
+
+    /// The number of live objects of each kind, indexed by the name of the kind.
+    ///
+    /// Only the objects allocated while [`Context::set_heap_object_tracking`] is enabled are
+    /// counted. Objects that are mutably borrowed while the statistics are computed are only
+    /// counted in [`HeapStatistics::objects`].
+    pub objects_by_kind: BTreeMap<String, usize>,
+
+    /// The number of bytes allocated for the live objects, the strings and the data blocks of
+    /// the `ArrayBuffer`s of the thread.
+    ///
+    /// Other memory owned by the objects, like the storage of their properties, and the data
+    /// blocks of `SharedArrayBuffer`s, which are shared with other threads, are not counted.
+    pub bytes_allocated: usize,
+
+    /// The number of garbage collections that ran on the thread.
+    ///
+    /// Collections that run without any object being allocated since the previous collection
+    /// are counted once.
+    pub collections: usize,
+}
+
+/// The objects of the garbage collected heap of a thread.
+#[derive(Debug, Default)]
+struct Heap {
+    /// The live objects allocated while the objects are tracked, indexed by the address of
+    /// their [`HeapEntry`].
+    tracked_objects: FxHashMap<usize, *const GcCell<Object>>,
+
+    /// Whether the objects that are allocated are added to `tracked_objects`.
+    track_objects: bool,
+
+    /// The number of live objects.
+    objects: usize,
+
+    /// The number of collections that finalized a [`Sentinel`].
+    collections: usize,
+
+    /// Whether a [`Sentinel`] is waiting to be collected.
+    sentinel_armed: bool,
+}
+
+/// The number of bytes allocated on the heap for an object.
+const OBJECT_SIZE: usize = mem::size_of::<GcCell<Object>>();
+
+thread_local! {
+    static HEAP: RefCell<Heap> = RefCell::default();
+
+    /// The number of bytes allocated for the live objects, strings and data blocks.
+    static BYTES_ALLOCATED: Cell<usize> = Cell::new(0);
+}
+
+/// The entry of an object in the heap statistics, removed when the object is dropped.
+#[derive(Debug, Default)]
+pub(crate) struct HeapEntry {
+    /// Whether the object is counted in the heap statistics.
+    registered: Cell<bool>,
+    /// Whether the object is in the tracked objects of the heap.
+    tracked: Cell<bool>,
+}
+
+impl Drop for HeapEntry {
+    fn drop(&mut self) {
+        if !self.registered.get() {
+            return;
+        }
+        track_deallocation(OBJECT_SIZE);
+
+        let key = self as *const Self as usize;
+        let tracked = self.tracked.get();
+        // The heap can already be destroyed if the object is dropped when the thread exits.
+        let _ = HEAP.try_with(|heap| {
+            let mut heap = heap.borrow_mut();
+            heap.objects -= 1;
+            if tracked {
+                heap.tracked_objects.remove(&key);
+            }
+        });
+    }
+}
+
+/// An unreachable allocation that counts the collections that run.
+///
+/// It is finalized by the first collection after it is allocated, and a new one is allocated
+/// with the next object.
+#[derive(Debug)]
+struct Sentinel;
+
+impl Finalize for Sentinel {
+    fn finalize(&self) {
+        let _ = HEAP.try_with(|heap| {
+            let mut heap = heap.borrow_mut();
+            heap.collections += 1;
+            heap.sentinel_armed = false;
+        });
+    }
+}
+
+unsafe impl Trace for Sentinel {
+    empty_trace!();
+}
+
+/// Allocates a [`Sentinel`] if none is waiting to be collected.
+///
+/// The heap must not be borrowed, because allocating can run a collection.
+fn arm_sentinel() {
+    let arm = HEAP.with(|heap| !mem::replace(&mut heap.borrow_mut().sentinel_armed, true));
+    if arm {
+        drop(Gc::new(Sentinel));
+    }
+}
+
+/// Adds a newly allocated object to the heap statistics.
+pub(crate) fn register_object(object: &Gc<GcCell<Object>>) {
+    let cell: &GcCell<Object> = object;
+    let entry = &cell.borrow().heap_entry;
+    entry.registered.set(true);
+    track_allocation(OBJECT_SIZE);
+    HEAP.with(|heap| {
+        let mut heap = heap.borrow_mut();
+        heap.objects += 1;
+        if heap.track_objects {
+            entry.tracked.set(true);
+            heap.tracked_objects
+                .insert(entry as *const HeapEntry as usize, cell as *const _);
+        }
+    });
+    arm_sentinel();
+}
+
+/// Counts memory allocated for a string or a data block in the heap statistics.
+#[inline]
+pub(crate) fn track_allocation(bytes: usize) {
+    let _ = BYTES_ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + bytes));
+}
+
+/// Removes memory counted by [`track_allocation`] from the heap statistics, when it is freed.
+#[inline]
+pub(crate) fn track_deallocation(bytes: usize) {
+    // The counter can already be destroyed if the memory is freed when the thread exits.
+    let _ =
+        BYTES_ALLOCATED.try_with(|allocated| allocated.set(allocated.get().saturating_sub(bytes)));
+}
+
+/// Returns the number of bytes allocated for the live objects, strings and data blocks.
+#[inline]
+pub(crate) fn bytes_allocated() -> usize {
+    BYTES_ALLOCATED.with(Cell::get)
+}
+
+impl Context {
+    /// Runs a garbage collection, freeing the objects of the thread that are not reachable.
+    ///
+    /// Collections also run automatically when objects are allocated.
+    #[inline]
+    pub fn collect_garbage(&mut self) {
+        collect_garbage();
+    }
+
+    /// Enables or disables the tracking of the objects allocated on the thread, to count them by
+    /// kind in [`HeapStatistics::objects_by_kind`].
+    ///
+    /// Tracking the objects has a cost on every allocation, so it is disabled by default. It is
+    /// shared by all the contexts of the thread.
+    #[inline]
+    pub fn set_heap_object_tracking(&mut self, enabled: bool) {
+        HEAP.with(|heap| heap.borrow_mut().track_objects = enabled);
+    }
+
+    /// Returns statistics about the objects of the garbage collected heap.
+    ///
+    /// The heap is shared by all the contexts of the thread, so the statistics include the
+    /// objects of the other contexts.
+    pub fn heap_statistics(&self) -> HeapStatistics {
+        HEAP.with(|heap| {
+            let heap = heap.borrow();
+            let mut objects_by_kind = BTreeMap::new();
+            for &object in heap.tracked_objects.values() {
+                // SAFETY: objects are removed from the heap when they are dropped, and they are
+                // not moved while they are alive.
+                let object = unsafe { &*object };
+                if let Ok(object) = object.try_borrow() {
+                    *objects_by_kind
+                        .entry(object.kind().to_string())
+                        .or_default() += 1;
+                }
+            }
+
+            HeapStatistics {
+                objects: heap.objects,
+                objects_by_kind,
+                bytes_allocated: bytes_allocated(),
+                collections: heap.collections,
+            }
+        })
+    }
+
+    /// Drops the context and runs a garbage collection, to free all of its objects.
+    ///
+    /// The objects of the context often reference each other, so they are only freed by a
+    /// collection after the context is dropped. Objects that are still referenced by values
+    /// held outside of the context are not freed.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    /// context.collect_garbage();
+    /// let objects = context.heap_statistics().objects;
+    ///
+    /// let mut other = Context::new();
+    /// other.eval("var cycle = {}; cycle.self = cycle;").unwrap();
+    /// other.dispose();
+    ///
+    /// assert_eq!(context.heap_statistics().objects, objects);
+    /// ```
+    #[inline]
+    pub fn dispose(self) {
+        drop(self);
+        collect_garbage();
+    }
+}
+
+/// Runs a garbage collection, and counts it in the heap statistics.
+fn collect_garbage() {
+    arm_sentinel();
+    force_collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Context;
+
+    #[test]
+    fn heap_statistics() {
+        let mut context = Context::new();
+        context.set_heap_object_tracking(true);
+        context.collect_garbage();
+        let before = context.heap_statistics();
+
+        context
+            .eval("var maps = []; for (let i = 0; i < 10; i++) { maps.push(new Map()); }")
+            .unwrap();
+        let after = context.heap_statistics();
+        assert!(after.objects >= before.objects + 10);
+        assert!(after.bytes_allocated > before.bytes_allocated);
+        assert_eq!(
+            after
+                .objects_by_kind
+                .get("Map")
+                .copied()
+                .unwrap_or_default(),
+            before
+                .objects_by_kind
+                .get("Map")
+                .copied()
+                .unwrap_or_default()
+                + 10
+        );
+
+        context.eval("maps = undefined;").unwrap();
+        context.collect_garbage();
+        let collected = context.heap_statistics();
+        assert_eq!(
+            collected.objects_by_kind.get("Map"),
+            before.objects_by_kind.get("Map")
+        );
+        assert_eq!(collected.collections, after.collections + 1);
+    }
+
+    #[test]
+    fn bytes_allocated_counts_strings_and_data_blocks() {
+        let mut context = Context::new();
+        context.eval("var string, buffer;").unwrap();
+        context.collect_garbage();
+        let before = context.heap_statistics().bytes_allocated;
+
+        context
+            .eval(r#"string = "a".repeat(100000); buffer = new ArrayBuffer(100000);"#)
+            .unwrap();
+        let after = context.heap_statistics().bytes_allocated;
+        assert!(after >= before + 200_000);
+
+        context.eval("string = buffer = undefined;").unwrap();
+        context.collect_garbage();
+        assert!(context.heap_statistics().bytes_allocated < after - 200_000);
+    }
+}
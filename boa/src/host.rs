@@ -0,0 +1,416 @@
+//! Host-defined sources of time and randomness, and the host hooks of the specification.
+//!
+//! By default, a [`Context`] reads the current time from the system clock, converts dates with
+//! the time zone of the system, and draws `Math.random` values from a thread-local random number
+//! generator. Any of these can be replaced, for example to make the execution of a script
+//! deterministic:
+//!
+//! ```
+//!# use boa::{host::{FixedClock, FixedTimeZone}, Context};
+//! let mut context = Context::new();
+//! context.set_clock(FixedClock::new(1_600_000_000_000));
+//! context.set_time_zone(FixedTimeZone::UTC);
+//! context.seed_random(42);
+//!
+//! let time = context.eval("Date.now()").unwrap();
+//! assert_eq!(time.as_number(), Some(1_600_000_000_000.0));
+//! ```
+//!
+//! The behaviour the specification leaves to the host is customised with [`HostHooks`]:
+//!
+//! ```
+//!# use boa::{host::HostHooks, realm::Realm, Context, JsResult};
+//! struct NoEval;
+//!
+//! impl HostHooks for NoEval {
+//!     fn ensure_can_compile_strings(
+//!         &self,
+//!         _caller_realm: &Realm,
+//!         _callee_realm: &Realm,
+//!         context: &mut Context,
+//!     ) -> JsResult<()> {
+//!         Err(context.construct_eval_error("eval is disabled"))
+//!     }
+//! }
+//!
+//! let mut context = Context::new();
+//! context.set_host_hooks(NoEval);
+//!
+//! assert!(context.eval("new Function('return 1')").is_err());
+//! ```
+
+use crate::{object::JsObject, property::PropertyKey, realm::Realm, Context, JsResult, JsValue};
+use chrono::{LocalResult, NaiveDateTime, Offset, TimeZone};
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+use std::{fmt, rc::Rc};
+
+/// A source of the current time.
+pub trait Clock {
+    /// Returns the current time, in milliseconds since the Unix epoch.
+    fn now(&self) -> i64;
+}
+
+/// The clock of the system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+}
+
+/// A clock that is stopped at a fixed time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock {
+    millis: i64,
+}
+
+impl FixedClock {
+    /// Creates a clock stopped at the given time, in milliseconds since the Unix epoch.
+    #[inline]
+    pub fn new(millis: i64) -> Self {
+        Self { millis }
+    }
+}
+
+impl Clock for FixedClock {
+    #[inline]
+    fn now(&self) -> i64 {
+        self.millis
+    }
+}
+
+/// The rules of the local time zone.
+///
+/// Times are given in milliseconds since the Unix epoch, and offsets are returned in seconds
+/// east of UTC.
+pub trait LocalTimeZone {
+    /// Returns the offset of the local time from UTC at the given UTC time.
+    fn offset_from_utc(&self, utc: i64) -> i32;
+
+    /// Returns the offset of the local time from UTC at the given local time.
+    ///
+    /// Returns `None` if the local time doesn't exist, for example because it is skipped by a
+    /// daylight saving time transition. If the local time is ambiguous, the earliest offset is
+    /// returned.
+    fn offset_from_local(&self, local: i64) -> Option<i32>;
+}
+
+/// Converts a time in milliseconds since the Unix epoch to a `NaiveDateTime`.
+pub(crate) fn from_millis(millis: i64) -> Option<NaiveDateTime> {
+    NaiveDateTime::from_timestamp_opt(
+        millis.div_euclid(1000),
+        (millis.rem_euclid(1000) * 1_000_000) as u32,
+    )
+}
+
+/// The time zone of the system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemTimeZone;
+
+impl LocalTimeZone for SystemTimeZone {
+    fn offset_from_utc(&self, utc: i64) -> i32 {
+        from_millis(utc).map_or(0, |utc| {
+            chrono::Local
+                .offset_from_utc_datetime(&utc)
+                .fix()
+                .local_minus_utc()
+        })
+    }
+
+    fn offset_from_local(&self, local: i64) -> Option<i32> {
+        match chrono::Local.offset_from_local_datetime(&from_millis(local)?) {
+            LocalResult::Single(offset) | LocalResult::Ambiguous(offset, _) => {
+                Some(offset.fix().local_minus_utc())
+            }
+            LocalResult::None => None,
+        }
+    }
+}
+
+/// A time zone with a fixed offset from UTC, without daylight saving time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedTimeZone {
+    offset: i32,
+}
+
+impl FixedTimeZone {
+    /// The UTC time zone.
+    pub const UTC: Self = Self { offset: 0 };
+
+    /// Creates a time zone with the given offset, in seconds east of UTC.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset is not less than a day.
+    #[inline]
+    pub fn new(offset: i32) -> Self {
+        assert!(
+            offset.abs() < 86_400,
+            "the offset of a time zone must be less than a day"
+        );
+        Self { offset }
+    }
+}
+
+impl LocalTimeZone for FixedTimeZone {
+    #[inline]
+    fn offset_from_utc(&self, _: i64) -> i32 {
+        self.offset
+    }
+
+    #[inline]
+    fn offset_from_local(&self, _: i64) -> Option<i32> {
+        Some(self.offset)
+    }
+}
+
+/// A source of the random numbers returned by `Math.random`.
+///
+/// It is implemented for all the random number generators of the `rand` crate.
+pub trait RandomSource {
+    /// Returns a random number in the range `[0, 1)`.
+    fn next_random(&mut self) -> f64;
+}
+
+impl<R: RngCore> RandomSource for R {
+    #[inline]
+    fn next_random(&mut self) -> f64 {
+        self.gen()
+    }
+}
+
+/// The operation reported to [`HostHooks::promise_rejection_tracker`].
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-host-promise-rejection-tracker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationType {
+    /// A promise was rejected without any handlers.
+    Reject,
+
+    /// A handler was added to an already rejected promise.
+    Handle,
+}
+
+/// The host-defined hooks of the specification.
+///
+/// Every method has a default implementation, which is the behaviour of a `Context` without
+/// custom hooks.
+pub trait HostHooks {
+    /// `HostEnsureCanCompileStrings ( callerRealm, calleeRealm )`
+    ///
+    /// Called before source text is compiled at runtime, by the `Function` constructor and
+    /// `ShadowRealm.prototype.evaluate`. Returning an error forbids the compilation.
+    ///
+    /// The default implementation allows all compilations.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-hostensurecancompilestrings
+    #[inline]
+    fn ensure_can_compile_strings(
+        &self,
+        _caller_realm: &Realm,
+        _callee_realm: &Realm,
+        _context: &mut Context,
+    ) -> JsResult<()> {
+        Ok(())
+    }
+
+    /// `HostPromiseRejectionTracker ( promise, operation )`
+    ///
+    /// Called when a promise is rejected without handlers, and when a handler is added to a
+    /// rejected promise. Boa doesn't implement promises yet, so this hook is never called.
+    ///
+    /// The default implementation does nothing.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-host-promise-rejection-tracker
+    #[inline]
+    fn promise_rejection_tracker(
+        &self,
+        _promise: &JsObject,
+        _operation: OperationType,
+        _context: &mut Context,
+    ) {
+    }
+
+    /// `HostCallJobCallback ( jobCallback, V, argumentsList )`
+    ///
+    /// Called to run the callbacks of jobs. Boa doesn't have a job queue yet, so this hook is
+    /// never called.
+    ///
+    /// The default implementation calls `callback` with `this` and `args`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-hostcalljobcallback
+    #[inline]
+    fn call_job_callback(
+        &self,
+        callback: &JsObject,
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        callback.call(this, args, context)
+    }
+
+    /// `HostHasSourceTextAvailable ( func )`
+    ///
+    /// Called by `Function.prototype.toString`. If it returns `false`, the source text of
+    /// `function` is hidden and the function is printed like a native function.
+    ///
+    /// The default implementation returns `true`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-hosthassourcetextavailable
+    #[inline]
+    fn has_source_text_available(&self, _function: &JsObject, _context: &mut Context) -> bool {
+        true
+    }
+
+    /// `HostGetImportMetaProperties ( moduleRecord )`
+    ///
+    /// Returns the properties of the `import.meta` object of a module. Boa doesn't support
+    /// modules yet, so this hook is never called.
+    ///
+    /// The default implementation returns no properties.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-hostgetimportmetaproperties
+    #[inline]
+    fn get_import_meta_properties(&self, _context: &mut Context) -> Vec<(PropertyKey, JsValue)> {
+        Vec::new()
+    }
+}
+
+/// The host hooks of a `Context` without custom hooks.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultHooks;
+
+impl HostHooks for DefaultHooks {}
+
+/// The host-defined sources of a `Context`.
+pub(crate) struct Host {
+    clock: Box<dyn Clock>,
+    time_zone: Box<dyn LocalTimeZone>,
+    random: Box<dyn RandomSource>,
+    hooks: Rc<dyn HostHooks>,
+}
+
+impl Default for Host {
+    fn default() -> Self {
+        Self {
+            clock: Box::new(SystemClock),
+            time_zone: Box::new(SystemTimeZone),
+            random: Box::new(rand::thread_rng()),
+            hooks: Rc::new(DefaultHooks),
+        }
+    }
+}
+
+impl fmt::Debug for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Host { .. }")
+    }
+}
+
+impl Context {
+    /// Sets the clock that `Date` and `console.time` read the current time from.
+    #[inline]
+    pub fn set_clock<C>(&mut self, clock: C)
+    where
+        C: Clock + 'static,
+    {
+        self.host.clock = Box::new(clock);
+    }
+
+    /// Sets the local time zone of `Date`.
+    #[inline]
+    pub fn set_time_zone<T>(&mut self, time_zone: T)
+    where
+        T: LocalTimeZone + 'static,
+    {
+        self.host.time_zone = Box::new(time_zone);
+    }
+
+    /// Sets the source of the random numbers of `Math.random`.
+    #[inline]
+    pub fn set_random_source<R>(&mut self, random: R)
+    where
+        R: RandomSource + 'static,
+    {
+        self.host.random = Box::new(random);
+    }
+
+    /// Makes `Math.random` return a reproducible sequence of numbers, generated from `seed`.
+    #[inline]
+    pub fn seed_random(&mut self, seed: u64) {
+        self.set_random_source(StdRng::seed_from_u64(seed));
+    }
+
+    /// Sets the host hooks of the context.
+    #[inline]
+    pub fn set_host_hooks<H>(&mut self, hooks: H)
+    where
+        H: HostHooks + 'static,
+    {
+        self.host.hooks = Rc::new(hooks);
+    }
+
+    /// Returns the host hooks of the context.
+    ///
+    /// The hooks are returned as a new handle, so that they can be called with the context.
+    #[inline]
+    pub(crate) fn host_hooks(&self) -> Rc<dyn HostHooks> {
+        self.host.hooks.clone()
+    }
+
+    /// Abstract operation `HostEnsureCanCompileStrings ( callerRealm, calleeRealm )`
+    ///
+    /// Fails if code generation from strings is disallowed, either by
+    /// `ContextBuilder::allow_code_generation` or by the host hooks.
+    pub(crate) fn ensure_can_compile_strings(
+        &mut self,
+        caller_realm: &Realm,
+        callee_realm: &Realm,
+    ) -> JsResult<()> {
+        if !self.allow_code_generation() {
+            return Err(self.construct_eval_error("code generation from strings is disallowed"));
+        }
+        self.host_hooks()
+            .ensure_can_compile_strings(caller_realm, callee_realm, self)
+    }
+
+    /// Returns the current time, in milliseconds since the Unix epoch.
+    #[inline]
+    pub(crate) fn now(&self) -> i64 {
+        self.host.clock.now()
+    }
+
+    /// Returns the local time zone.
+    #[inline]
+    pub(crate) fn time_zone(&self) -> &dyn LocalTimeZone {
+        self.host.time_zone.as_ref()
+    }
+
+    /// Returns the next random number of `Math.random`.
+    #[inline]
+    pub(crate) fn next_random(&mut self) -> f64 {
+        self.host.random.next_random()
+    }
+}
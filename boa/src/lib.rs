@@ -41,6 +41,9 @@ This is an experimental Javascript lexer, parser and compiler written in Rust. C
     rustdoc::missing_doc_code_examples
 )]
 
+// Allows the derives of `boa_macros` to refer to `::boa` from inside this crate.
+extern crate self as boa;
+
 pub mod bigint;
 pub mod builtins;
 pub mod class;
@@ -48,6 +51,8 @@ pub mod context;
 pub mod environment;
 pub mod exec;
 pub mod gc;
+pub mod host;
+pub mod limits;
 pub mod object;
 pub mod profiler;
 pub mod property;
@@ -77,6 +82,9 @@ pub use crate::{
     bigint::JsBigInt, context::Context, string::JsString, symbol::JsSymbol, value::JsValue,
 };
 
+#[doc(inline)]
+pub use boa_macros::class;
+
 use crate::syntax::{
     ast::node::StatementList,
     parser::{ParseError, Parser},
@@ -115,7 +123,7 @@ pub(crate) fn forward<T: AsRef<[u8]>>(context: &mut Context, src: T) -> String {
             );
         }
     };
-    expr.run(context).map_or_else(
+    context.execute(&expr).map_or_else(
         |e| format!("Uncaught {}", e.display()),
         |v| v.display().to_string(),
     )
@@ -138,7 +146,7 @@ pub(crate) fn forward_val<T: AsRef<[u8]>>(context: &mut Context, src: T) -> JsRe
                 .throw_syntax_error(e.to_string())
                 .expect_err("interpreter.throw_syntax_error() did not return an error")
         })
-        .and_then(|expr| expr.run(context));
+        .and_then(|expr| context.execute(&expr));
 
     // The main_timer needs to be dropped before the BoaProfiler is.
     drop(main_timer);
@@ -0,0 +1,686 @@
+//! Limits on the execution of scripts.
+//!
+//! A [`Context`] can be configured to stop the scripts that run for too long: after a number of
+//! loop iterations, when the calls are nested too deeply, after a wall-clock time limit, or when
+//! another thread requests it through an [`InterruptHandle`]. Scripts can also be stopped when
+//! the garbage collected heap grows past a hard limit, see [`Context::set_hard_heap_limit`].
+//!
+//! A script that is stopped is terminated with an error that can not be caught by the script:
+//! `catch` and `finally` blocks are skipped, and the error is returned to the caller of
+//! [`Context::eval`]. The reason of the termination is obtained with
+//! [`JsValue::as_termination`].
+//!
+//! The limits are checked when a loop starts a new iteration and when a function is called,
+//! with all the execution backends.
+//!
+//! Independently of these limits, calls throw a `RangeError` when the script uses more native
+//! stack than allowed by [`Context::set_native_stack_limit`], before the stack of the thread
+//! overflows and aborts the process.
+
+use crate::{
+    gc::{self, Finalize, Trace},
+    object::{JsObject, ObjectData},
+    property::PropertyDescriptor,
+    Context, JsResult, JsValue,
+};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// The reason why the execution of a script was terminated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminationReason {
+    /// The script executed more loop iterations than allowed by
+    /// [`Context::set_loop_iteration_limit`].
+    LoopIterationLimit,
+
+    /// The calls were nested deeper than allowed by [`Context::set_recursion_limit`].
+    RecursionLimit,
+
+    /// The script ran for longer than allowed by [`Context::set_time_limit`].
+    TimeLimit,
+
+    /// The execution was interrupted through an [`InterruptHandle`].
+    Interrupted,
+
+    /// The heap grew past the limit set by [`Context::set_hard_heap_limit`].
+    HeapLimit,
+}
+
+impl fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::LoopIterationLimit => "loop iteration limit exceeded",
+            Self::RecursionLimit => "recursion limit exceeded",
+            Self::TimeLimit => "time limit exceeded",
+            Self::Interrupted => "interrupted",
+            Self::HeapLimit => "heap limit exceeded",
+        })
+    }
+}
+
+/// The data of the error that terminates a script.
+#[derive(Debug, Trace, Finalize)]
+pub(crate) struct Termination {
+    #[unsafe_ignore_trace]
+    pub(crate) reason: TerminationReason,
+}
+
+/// A handle that interrupts the scripts executed by a [`Context`], from any thread.
+///
+/// # Examples
+/// ```
+///# use boa::{limits::TerminationReason, Context};
+/// let mut context = Context::new();
+/// let handle = context.interrupt_handle();
+///
+/// std::thread::spawn(move || handle.interrupt()).join().unwrap();
+///
+/// let error = context.eval("while (true) {}").unwrap_err();
+/// assert_eq!(error.as_termination(), Some(TerminationReason::Interrupted));
+/// ```
+#[derive(Debug, Clone)]
+pub struct InterruptHandle {
+    interrupted: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    /// Requests the termination of the script that is executing.
+    ///
+    /// If no script is executing, the next script is terminated as soon as it starts.
+    #[inline]
+    pub fn interrupt(&self) {
+        self.interrupted.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks if an interruption was requested and has not terminated a script yet.
+    #[inline]
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::Relaxed)
+    }
+}
+
+/// The default size of the native stack that scripts can use, in bytes.
+///
+/// It is half of the stack of the threads spawned by the standard library, to leave room for
+/// the frames of the host and for the work done between two calls.
+pub const DEFAULT_NATIVE_STACK_LIMIT: usize = 1024 * 1024;
+
+/// The execution limits of a [`Context`] and the state of the script that is executing.
+#[derive(Debug)]
+pub(crate) struct ExecutionLimits {
+    loop_iteration_limit: Option<u64>,
+    recursion_limit: Option<usize>,
+    native_stack_limit: Option<usize>,
+    time_limit: Option<Duration>,
+    soft_heap_limit: Option<usize>,
+    hard_heap_limit: Option<usize>,
+    interrupted: Arc<AtomicBool>,
+
+    /// The number of executions and calls that are in progress.
+    depth: usize,
+    /// The number of calls that are in progress.
+    call_depth: usize,
+    loop_iterations: u64,
+    deadline: Option<Instant>,
+    /// The address of the native stack when the outermost execution started.
+    stack_base: usize,
+    /// The size of the heap past which the heap limits collect the garbage again, if it is
+    /// larger than the limits.
+    heap_collection_threshold: usize,
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        Self {
+            loop_iteration_limit: None,
+            recursion_limit: None,
+            native_stack_limit: Some(DEFAULT_NATIVE_STACK_LIMIT),
+            time_limit: None,
+            soft_heap_limit: None,
+            hard_heap_limit: None,
+            interrupted: Arc::default(),
+            depth: 0,
+            call_depth: 0,
+            loop_iterations: 0,
+            deadline: None,
+            stack_base: 0,
+            heap_collection_threshold: 0,
+        }
+    }
+}
+
+/// Returns the address of a local variable of the caller, which is close to the top of the
+/// native stack.
+#[inline(always)]
+fn stack_address() -> usize {
+    let marker = 0u8;
+    std::hint::black_box(&marker) as *const u8 as usize
+}
+
+impl Context {
+    /// Returns the maximum number of loop iterations of a script.
+    #[inline]
+    pub fn loop_iteration_limit(&self) -> Option<u64> {
+        self.limits.loop_iteration_limit
+    }
+
+    /// Sets the maximum number of loop iterations of a script, or removes the limit.
+    ///
+    /// The iterations of all the loops executed by a script, and by the functions it calls,
+    /// are counted together.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::{limits::TerminationReason, Context};
+    /// let mut context = Context::new();
+    /// context.set_loop_iteration_limit(Some(1000));
+    ///
+    /// let error = context.eval("try { for (;;) {} } catch (e) {}").unwrap_err();
+    /// assert_eq!(error.as_termination(), Some(TerminationReason::LoopIterationLimit));
+    /// ```
+    #[inline]
+    pub fn set_loop_iteration_limit(&mut self, limit: Option<u64>) {
+        self.limits.loop_iteration_limit = limit;
+    }
+
+    /// Returns the maximum number of nested function calls.
+    #[inline]
+    pub fn recursion_limit(&self) -> Option<usize> {
+        self.limits.recursion_limit
+    }
+
+    /// Sets the maximum number of nested function calls, or removes the limit.
+    ///
+    /// Unlike the native stack limit of [`Context::set_native_stack_limit`], that throws a
+    /// `RangeError`, exceeding this limit terminates the script.
+    #[inline]
+    pub fn set_recursion_limit(&mut self, limit: Option<usize>) {
+        self.limits.recursion_limit = limit;
+    }
+
+    /// Returns the size of the native stack that scripts can use, in bytes.
+    #[inline]
+    pub fn native_stack_limit(&self) -> Option<usize> {
+        self.limits.native_stack_limit
+    }
+
+    /// Sets the size of the native stack that scripts can use, in bytes, or removes the limit.
+    ///
+    /// Calls throw a `RangeError` once the script uses more native stack than the limit, which
+    /// defaults to [`DEFAULT_NATIVE_STACK_LIMIT`]. It must be lower than the stack of the
+    /// thread, or deep recursion aborts the process.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    /// context.set_native_stack_limit(Some(64 * 1024));
+    ///
+    /// let value = context
+    ///     .eval("function f() { return f(); } try { f(); } catch (e) { e.name }")
+    ///     .unwrap();
+    /// assert_eq!(value.as_string().unwrap().as_str(), "RangeError");
+    /// ```
+    #[inline]
+    pub fn set_native_stack_limit(&mut self, limit: Option<usize>) {
+        self.limits.native_stack_limit = limit;
+    }
+
+    /// Returns the maximum wall-clock time that a script can run for.
+    #[inline]
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.limits.time_limit
+    }
+
+    /// Sets the maximum wall-clock time that a script can run for, or removes the limit.
+    ///
+    /// The time is measured from the start of the outermost script execution.
+    #[inline]
+    pub fn set_time_limit(&mut self, limit: Option<Duration>) {
+        self.limits.time_limit = limit;
+    }
+
+    /// Returns the size of the heap past which scripts throw a `RangeError`, in bytes.
+    #[inline]
+    pub fn soft_heap_limit(&self) -> Option<usize> {
+        self.limits.soft_heap_limit
+    }
+
+    /// Sets the size of the heap past which scripts throw a `RangeError`, in bytes, or removes
+    /// the limit.
+    ///
+    /// The size is the one reported by [`HeapStatistics::bytes_allocated`][stats]. It is checked,
+    /// after collecting the garbage, when the other limits are checked. Once the garbage was
+    /// collected, the heap can grow past the limit by a quarter of the limit, or by half of the
+    /// gap between the soft and the hard limits, before it is collected again, so that scripts
+    /// whose heap stays close to the limit do not collect the garbage at every check. The heap
+    /// is shared by all the contexts of the thread.
+    ///
+    /// [stats]: crate::gc::HeapStatistics::bytes_allocated
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    /// context.set_soft_heap_limit(Some(context.heap_statistics().bytes_allocated + 100_000));
+    ///
+    /// let value = context
+    ///     .eval("let a = []; try { for (;;) { a.push({}); } } catch (e) { a = []; e.name }")
+    ///     .unwrap();
+    /// assert_eq!(value.as_string().unwrap().as_str(), "RangeError");
+    /// ```
+    #[inline]
+    pub fn set_soft_heap_limit(&mut self, limit: Option<usize>) {
+        self.limits.soft_heap_limit = limit;
+        self.limits.heap_collection_threshold = 0;
+    }
+
+    /// Returns the size of the heap past which scripts are terminated, in bytes.
+    #[inline]
+    pub fn hard_heap_limit(&self) -> Option<usize> {
+        self.limits.hard_heap_limit
+    }
+
+    /// Sets the size of the heap past which scripts are terminated, in bytes, or removes the
+    /// limit.
+    ///
+    /// The limit is checked like the one of [`Context::set_soft_heap_limit`].
+    #[inline]
+    pub fn set_hard_heap_limit(&mut self, limit: Option<usize>) {
+        self.limits.hard_heap_limit = limit;
+        self.limits.heap_collection_threshold = 0;
+    }
+
+    /// Returns a handle that interrupts the scripts executed by this context.
+    #[inline]
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle {
+            interrupted: self.limits.interrupted.clone(),
+        }
+    }
+
+    /// Marks the start of the execution of a script, or of a call from native code.
+    ///
+    /// The loop iterations and the time limit are counted from the start of the outermost
+    /// execution.
+    pub(crate) fn enter_execution(&mut self) {
+        if self.limits.depth == 0 {
+            self.limits.stack_base = stack_address();
+            self.limits.loop_iterations = 0;
+            self.limits.deadline = self.limits.time_limit.map(|limit| Instant::now() + limit);
+        }
+        self.limits.depth += 1;
+    }
+
+    /// Marks the end of an execution started by [`Context::enter_execution`].
+    pub(crate) fn exit_execution(&mut self) {
+        self.limits.depth -= 1;
+    }
+
+    /// Checks the limits before a function is called.
+    ///
+    /// If the call is allowed, it must be followed by a call to [`Context::exit_call`].
+    pub(crate) fn enter_call(&mut self) -> JsResult<()> {
+        if matches!(self.limits.recursion_limit, Some(limit) if self.limits.call_depth >= limit) {
+            return Err(self.terminate(TerminationReason::RecursionLimit));
+        }
+        self.enter_execution();
+        if let Err(error) = self.check_native_stack().and_then(|_| self.check_limits()) {
+            self.exit_execution();
+            return Err(error);
+        }
+        self.limits.call_depth += 1;
+        Ok(())
+    }
+
+    /// Marks the end of a call allowed by [`Context::enter_call`].
+    pub(crate) fn exit_call(&mut self) {
+        self.limits.call_depth -= 1;
+        self.exit_execution();
+    }
+
+    /// Checks the limits before a loop starts a new iteration.
+    pub(crate) fn count_loop_iteration(&mut self) -> JsResult<()> {
+        self.limits.loop_iterations += 1;
+        if matches!(self.limits.loop_iteration_limit, Some(limit) if self.limits.loop_iterations > limit)
+        {
+            return Err(self.terminate(TerminationReason::LoopIterationLimit));
+        }
+        self.check_limits()
+    }
+
+    /// Checks if the script uses more native stack than allowed.
+    fn check_native_stack(&mut self) -> JsResult<()> {
+        let used = stack_address().abs_diff(self.limits.stack_base);
+        if matches!(self.limits.native_stack_limit, Some(limit) if used > limit) {
+            // Like the heap limit error, it is not created by calling the `RangeError`
+            // constructor, which would use more stack.
+            let prototype = self.standard_objects().range_error_object().prototype();
+            return Err(Self::create_limit_error(
+                prototype,
+                ObjectData::error(),
+                "Maximum call stack size exceeded",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks if the time limit or the heap limits were exceeded, or if an interruption was
+    /// requested.
+    fn check_limits(&mut self) -> JsResult<()> {
+        if self.limits.interrupted.load(Ordering::Relaxed) {
+            self.limits.interrupted.store(false, Ordering::Relaxed);
+            return Err(self.terminate(TerminationReason::Interrupted));
+        }
+        if matches!(self.limits.deadline, Some(deadline) if Instant::now() >= deadline) {
+            return Err(self.terminate(TerminationReason::TimeLimit));
+        }
+        self.check_heap_limits()
+    }
+
+    /// Checks if the heap grew past the heap limits.
+    fn check_heap_limits(&mut self) -> JsResult<()> {
+        let limit = match (self.limits.soft_heap_limit, self.limits.hard_heap_limit) {
+            (Some(soft), Some(hard)) => soft.min(hard),
+            (Some(limit), None) | (None, Some(limit)) => limit,
+            (None, None) => return Ok(()),
+        };
+        // The unreachable objects are counted until they are collected. After a collection,
+        // the heap must grow before it is collected again, so that the cost of the collections
+        // is amortized over the allocations. It grows by less than the gap between the limits,
+        // so that the soft limit is exceeded before the hard one.
+        if gc::bytes_allocated() <= limit.max(self.limits.heap_collection_threshold) {
+            return Ok(());
+        }
+        self.collect_garbage();
+
+        let bytes = gc::bytes_allocated();
+        let growth = match (self.limits.soft_heap_limit, self.limits.hard_heap_limit) {
+            (Some(soft), Some(hard)) if hard > soft => (limit / 4).min((hard - soft) / 2),
+            _ => limit / 4,
+        };
+        self.limits.heap_collection_threshold = bytes.max(limit).saturating_add(growth);
+        if matches!(self.limits.hard_heap_limit, Some(limit) if bytes > limit) {
+            return Err(self.terminate(TerminationReason::HeapLimit));
+        }
+        if matches!(self.limits.soft_heap_limit, Some(limit) if bytes > limit) {
+            // Like the termination error, it is not created by calling the `RangeError`
+            // constructor, which would check the heap limits again.
+            let prototype = self.standard_objects().range_error_object().prototype();
+            return Err(Self::create_limit_error(
+                prototype,
+                ObjectData::error(),
+                "heap limit exceeded",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Creates the error that terminates the script.
+    ///
+    /// It is an error object, so that it is displayed like the errors thrown by scripts, but it
+    /// is not created by calling the `Error` constructor, which could exceed the limits again.
+    fn terminate(&mut self, reason: TerminationReason) -> JsValue {
+        let prototype = self.standard_objects().error_object().prototype();
+        Self::create_limit_error(
+            prototype,
+            ObjectData::native_object(Box::new(Termination { reason })),
+            format!("execution terminated: {}", reason),
+        )
+    }
+
+    /// Creates an error object with the given message, without calling any constructor.
+    fn create_limit_error<M>(prototype: JsObject, data: ObjectData, message: M) -> JsValue
+    where
+        M: Into<JsValue>,
+    {
+        let error = JsObject::from_proto_and_data(prototype, data);
+        error.insert_property(
+            "message",
+            PropertyDescriptor::builder()
+                .value(message)
+                .writable(true)
+                .enumerable(false)
+                .configurable(true),
+        );
+        error.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TerminationReason;
+    use crate::Context;
+    use std::time::Duration;
+
+    /// Creates a context for each execution backend.
+    fn contexts() -> Vec<Context> {
+        #[cfg(feature = "vm")]
+        {
+            use crate::context::Backend;
+
+            [Backend::Vm, Backend::Interpreter, Backend::Register]
+                .into_iter()
+                .map(|backend| {
+                    let mut context = Context::new();
+                    context.set_backend(backend);
+                    context
+                })
+                .collect()
+        }
+        #[cfg(not(feature = "vm"))]
+        {
+            vec![Context::new()]
+        }
+    }
+
+    #[test]
+    fn loop_iteration_limit() {
+        for mut context in contexts() {
+            context.set_loop_iteration_limit(Some(100));
+
+            let value = context
+                .eval("let sum = 0; for (let i = 0; i < 50; i++) { sum += i; } sum")
+                .unwrap();
+            assert_eq!(value.as_number(), Some(1225.0));
+
+            // The iterations are counted again for every script.
+            let value = context
+                .eval("let n = 0; while (n < 50) { n++; } n")
+                .unwrap();
+            assert_eq!(value.as_number(), Some(50.0));
+
+            let error = context
+                .eval(
+                    r#"
+                    var finallyExecuted = false;
+                    function spin() {
+                        do {} while (true);
+                    }
+                    try {
+                        spin();
+                    } catch (e) {
+                        "caught";
+                    } finally {
+                        finallyExecuted = true;
+                    }
+                    "#,
+                )
+                .unwrap_err();
+            assert_eq!(
+                error.as_termination(),
+                Some(TerminationReason::LoopIterationLimit)
+            );
+            assert_eq!(
+                error.display().to_string(),
+                "\"Error\": \"execution terminated: loop iteration limit exceeded\""
+            );
+            assert_eq!(
+                context.eval("finallyExecuted").unwrap().as_boolean(),
+                Some(false)
+            );
+
+            // Errors thrown by scripts are not terminations.
+            let error = context.eval("throw new Error('error')").unwrap_err();
+            assert_eq!(error.as_termination(), None);
+        }
+    }
+
+    #[test]
+    fn recursion_limit() {
+        for mut context in contexts() {
+            context.set_recursion_limit(Some(64));
+
+            let value = context
+                .eval("function depth(n) { return n === 0 ? 0 : 1 + depth(n - 1); } depth(32)")
+                .unwrap();
+            assert_eq!(value.as_number(), Some(32.0));
+
+            let error = context
+                .eval("try { depth(1000); } catch (e) {}")
+                .unwrap_err();
+            assert_eq!(
+                error.as_termination(),
+                Some(TerminationReason::RecursionLimit)
+            );
+
+            // The calls that were in progress are unwound.
+            let value = context.eval("depth(32)").unwrap();
+            assert_eq!(value.as_number(), Some(32.0));
+        }
+    }
+
+    #[test]
+    fn native_stack_limit() {
+        for mut context in contexts() {
+            let value = context
+                .eval(
+                    r#"
+                    function recurse(n) { return recurse(n + 1) + 1; }
+                    try {
+                        recurse(0);
+                    } catch (e) {
+                        e instanceof RangeError && e.message
+                    }
+                    "#,
+                )
+                .unwrap();
+            assert_eq!(
+                value.display().to_string(),
+                "\"Maximum call stack size exceeded\""
+            );
+
+            // The stack is unwound, so calls can be nested again.
+            let value = context
+                .eval("function depth(n) { return n === 0 ? 0 : 1 + depth(n - 1); } depth(32)")
+                .unwrap();
+            assert_eq!(value.as_number(), Some(32.0));
+        }
+    }
+
+    #[test]
+    fn time_limit() {
+        for mut context in contexts() {
+            context.set_time_limit(Some(Duration::from_millis(20)));
+
+            let error = context
+                .eval("try { for (;;) {} } finally { 'unreachable' }")
+                .unwrap_err();
+            assert_eq!(error.as_termination(), Some(TerminationReason::TimeLimit));
+
+            // The time is measured again for every script.
+            assert_eq!(context.eval("1 + 1").unwrap().as_number(), Some(2.0));
+        }
+    }
+
+    #[test]
+    fn interrupt_handle() {
+        for mut context in contexts() {
+            let handle = context.interrupt_handle();
+
+            let interrupter = {
+                let handle = handle.clone();
+                std::thread::spawn(move || {
+                    std::thread::sleep(Duration::from_millis(20));
+                    handle.interrupt();
+                })
+            };
+            let error = context
+                .eval("function spin() { while (true) {} } spin()")
+                .unwrap_err();
+            interrupter.join().unwrap();
+            assert_eq!(error.as_termination(), Some(TerminationReason::Interrupted));
+
+            // The interruption only terminates one script.
+            assert!(!handle.is_interrupted());
+            assert_eq!(context.eval("1 + 1").unwrap().as_number(), Some(2.0));
+        }
+    }
+
+    #[test]
+    fn heap_limits() {
+        for mut context in contexts() {
+            context.collect_garbage();
+            let bytes = context.heap_statistics().bytes_allocated;
+            context.set_soft_heap_limit(Some(bytes + 100_000));
+            context.set_hard_heap_limit(Some(bytes + 200_000));
+
+            // Unreachable objects are collected instead of exceeding the limits.
+            let value = context
+                .eval("let n = 0; for (let i = 0; i < 10000; i++) { n += [i].length; } n")
+                .unwrap();
+            assert_eq!(value.as_number(), Some(10000.0));
+
+            let value = context
+                .eval(
+                    r#"
+                    var kept = [];
+                    try {
+                        for (;;) { kept.push({}); }
+                    } catch (e) {
+                        kept = [];
+                        e.name;
+                    }
+                    "#,
+                )
+                .unwrap();
+            assert_eq!(value.as_string().unwrap().as_str(), "RangeError");
+
+            // Exceeding the hard limit can not be caught.
+            context.set_soft_heap_limit(None);
+            let error = context
+                .eval("try { for (;;) { kept.push({}); } } catch (e) {}")
+                .unwrap_err();
+            assert_eq!(error.as_termination(), Some(TerminationReason::HeapLimit));
+
+            context.eval("kept = [];").unwrap();
+            context.collect_garbage();
+            assert_eq!(context.eval("1 + 1").unwrap().as_number(), Some(2.0));
+        }
+    }
+
+    #[test]
+    fn heap_limit_collections_are_amortized() {
+        let mut context = Context::new();
+        context
+            .eval("var kept = []; for (let i = 0; i < 1000; i++) { kept.push({}); }")
+            .unwrap();
+        context.collect_garbage();
+        let before = context.heap_statistics();
+        context.set_soft_heap_limit(Some(before.bytes_allocated + 1000));
+
+        // The heap stays just below the limit, so the garbage is only collected again once the
+        // heap grew.
+        context
+            .eval("for (let i = 0; i < 20000; i++) { [i]; }")
+            .unwrap();
+        let after = context.heap_statistics();
+        assert!(after.collections - before.collections < 100);
+    }
+}
@@ -0,0 +1,355 @@
+//! A typed wrapper around `Array` objects.
+
+use super::{impl_object_wrapper, JsFunction};
+use crate::{
+    builtins::Array,
+    gc::{Finalize, Trace},
+    object::JsObject,
+    Context, JsResult, JsString, JsValue,
+};
+
+/// An `Array` object.
+///
+/// # Examples
+/// ```
+///# use boa::{object::JsArray, Context, JsValue};
+/// let mut context = Context::new();
+///
+/// let array = JsArray::from_iter([JsValue::new(1), JsValue::new(2)], &mut context);
+/// array.push(3, &mut context).unwrap();
+///
+/// assert_eq!(array.length(&mut context).unwrap(), 3);
+/// assert_eq!(array.join(None, &mut context).unwrap().as_str(), "1,2,3");
+/// ```
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsArray {
+    inner: JsObject,
+}
+
+impl JsArray {
+    /// Creates a new empty array.
+    #[inline]
+    pub fn new(context: &mut Context) -> Self {
+        Self {
+            inner: Array::array_create(0, None, context)
+                .expect("creating an empty array with the default prototype must not fail"),
+        }
+    }
+
+    /// Creates an array with the given elements.
+    #[inline]
+    pub fn from_iter<I>(elements: I, context: &mut Context) -> Self
+    where
+        I: IntoIterator<Item = JsValue>,
+    {
+        Self {
+            inner: Array::create_array_from_list(elements, context),
+        }
+    }
+
+    /// Wraps an object, throwing a `TypeError` if it is not an `Array`.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_array() {
+            Ok(Self { inner: object })
+        } else {
+            Err(context.construct_type_error("object is not an Array"))
+        }
+    }
+
+    /// Gets the value of the `length` property of the array.
+    #[inline]
+    pub fn length(&self, context: &mut Context) -> JsResult<usize> {
+        self.inner.length_of_array_like(context)
+    }
+
+    /// Checks if the array has no elements.
+    #[inline]
+    pub fn is_empty(&self, context: &mut Context) -> JsResult<bool> {
+        self.length(context).map(|length| length == 0)
+    }
+
+    /// Returns an iterator over the elements of the array.
+    ///
+    /// The length of the array is read again before every element, like a `for` loop over the
+    /// array would do in JavaScript.
+    #[inline]
+    pub fn iter<'a>(&'a self, context: &'a mut Context) -> JsArrayIter<'a> {
+        JsArrayIter {
+            array: self,
+            context,
+            index: 0,
+        }
+    }
+
+    /// Appends an element to the end of the array, and returns the new length of the array.
+    #[inline]
+    pub fn push<T>(&self, value: T, context: &mut Context) -> JsResult<JsValue>
+    where
+        T: Into<JsValue>,
+    {
+        self.push_items(&[value.into()], context)
+    }
+
+    /// Appends elements to the end of the array, and returns the new length of the array.
+    #[inline]
+    pub fn push_items(&self, items: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Array::push(&self.inner.clone().into(), items, context)
+    }
+
+    /// Removes the last element of the array and returns it.
+    #[inline]
+    pub fn pop(&self, context: &mut Context) -> JsResult<JsValue> {
+        Array::pop(&self.inner.clone().into(), &[], context)
+    }
+
+    /// Removes the first element of the array and returns it.
+    #[inline]
+    pub fn shift(&self, context: &mut Context) -> JsResult<JsValue> {
+        Array::shift(&self.inner.clone().into(), &[], context)
+    }
+
+    /// Inserts elements at the start of the array, and returns the new length of the array.
+    #[inline]
+    pub fn unshift(&self, items: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Array::unshift(&self.inner.clone().into(), items, context)
+    }
+
+    /// Gets the element at the given index, counting from the end of the array if it is
+    /// negative.
+    #[inline]
+    pub fn at<T>(&self, index: T, context: &mut Context) -> JsResult<JsValue>
+    where
+        T: Into<i64>,
+    {
+        Array::at(&self.inner.clone().into(), &[index.into().into()], context)
+    }
+
+    /// Creates a new array with the elements of this array followed by the given items, or by
+    /// their elements if they are arrays.
+    #[inline]
+    pub fn concat(&self, items: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        let array = Array::concat(&self.inner.clone().into(), items, context)?;
+        Self::from_value(array, context)
+    }
+
+    /// Joins the string representations of the elements, separated by `separator` or by commas.
+    #[inline]
+    pub fn join(&self, separator: Option<JsString>, context: &mut Context) -> JsResult<JsString> {
+        let separator = separator.map_or_else(JsValue::undefined, JsValue::from);
+        Array::join(&self.inner.clone().into(), &[separator], context)?.to_string(context)
+    }
+
+    /// Reverses the array in place.
+    #[inline]
+    pub fn reverse(&self, context: &mut Context) -> JsResult<Self> {
+        Array::reverse(&self.inner.clone().into(), &[], context)?;
+        Ok(self.clone())
+    }
+
+    /// Fills the elements from `start` to `end` with `value`, in place.
+    #[inline]
+    pub fn fill<T>(
+        &self,
+        value: T,
+        start: Option<i64>,
+        end: Option<i64>,
+        context: &mut Context,
+    ) -> JsResult<Self>
+    where
+        T: Into<JsValue>,
+    {
+        Array::fill(
+            &self.inner.clone().into(),
+            &[value.into(), optional(start), optional(end)],
+            context,
+        )?;
+        Ok(self.clone())
+    }
+
+    /// Creates a new array with the elements from `start` to `end`.
+    #[inline]
+    pub fn slice(
+        &self,
+        start: Option<i64>,
+        end: Option<i64>,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let array = Array::slice(
+            &self.inner.clone().into(),
+            &[optional(start), optional(end)],
+            context,
+        )?;
+        Self::from_value(array, context)
+    }
+
+    /// Gets the index of the first element that is strictly equal to `search_element`.
+    #[inline]
+    pub fn index_of<T>(&self, search_element: T, context: &mut Context) -> JsResult<Option<u32>>
+    where
+        T: Into<JsValue>,
+    {
+        let index = Array::index_of(
+            &self.inner.clone().into(),
+            &[search_element.into()],
+            context,
+        )?
+        .as_number()
+        .expect("Array.prototype.indexOf must return a number");
+        Ok((index >= 0.0).then(|| index as u32))
+    }
+
+    /// Checks if the array has an element that is equal to `value`, with the `SameValueZero`
+    /// comparison.
+    #[inline]
+    pub fn includes<T>(&self, value: T, context: &mut Context) -> JsResult<bool>
+    where
+        T: Into<JsValue>,
+    {
+        Array::includes_value(&self.inner.clone().into(), &[value.into()], context)
+            .map(|result| result.to_boolean())
+    }
+
+    /// Gets the first element for which `predicate` returns a truthy value.
+    #[inline]
+    pub fn find(
+        &self,
+        predicate: JsFunction,
+        this_arg: JsValue,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        Array::find(
+            &self.inner.clone().into(),
+            &[predicate.into(), this_arg],
+            context,
+        )
+    }
+
+    /// Creates a new array with the elements for which `predicate` returns a truthy value.
+    #[inline]
+    pub fn filter(
+        &self,
+        predicate: JsFunction,
+        this_arg: JsValue,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let array = Array::filter(
+            &self.inner.clone().into(),
+            &[predicate.into(), this_arg],
+            context,
+        )?;
+        Self::from_value(array, context)
+    }
+
+    /// Creates a new array with the results of calling `callback` on every element.
+    #[inline]
+    pub fn map(
+        &self,
+        callback: JsFunction,
+        this_arg: JsValue,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let array = Array::map(
+            &self.inner.clone().into(),
+            &[callback.into(), this_arg],
+            context,
+        )?;
+        Self::from_value(array, context)
+    }
+
+    /// Checks if `predicate` returns a truthy value for every element.
+    #[inline]
+    pub fn every(
+        &self,
+        predicate: JsFunction,
+        this_arg: JsValue,
+        context: &mut Context,
+    ) -> JsResult<bool> {
+        Array::every(
+            &self.inner.clone().into(),
+            &[predicate.into(), this_arg],
+            context,
+        )
+        .map(|result| result.to_boolean())
+    }
+
+    /// Checks if `predicate` returns a truthy value for some element.
+    #[inline]
+    pub fn some(
+        &self,
+        predicate: JsFunction,
+        this_arg: JsValue,
+        context: &mut Context,
+    ) -> JsResult<bool> {
+        Array::some(
+            &self.inner.clone().into(),
+            &[predicate.into(), this_arg],
+            context,
+        )
+        .map(|result| result.to_boolean())
+    }
+
+    /// Sorts the array in place, with `comparator` or by the string representations of the
+    /// elements.
+    #[inline]
+    pub fn sort(&self, comparator: Option<JsFunction>, context: &mut Context) -> JsResult<Self> {
+        let comparator = comparator.map_or_else(JsValue::undefined, JsValue::from);
+        Array::sort(&self.inner.clone().into(), &[comparator], context)?;
+        Ok(self.clone())
+    }
+
+    /// Reduces the elements to a single value, from left to right.
+    #[inline]
+    pub fn reduce(
+        &self,
+        reducer: JsFunction,
+        initial_value: Option<JsValue>,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let mut args = vec![reducer.into()];
+        args.extend(initial_value);
+        Array::reduce(&self.inner.clone().into(), &args, context)
+    }
+
+    /// Wraps the array returned by a builtin function.
+    fn from_value(value: JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.as_object() {
+            Some(object) => Self::from_object(object.clone(), context),
+            None => Err(context.construct_type_error("value is not an Array")),
+        }
+    }
+}
+
+impl_object_wrapper!(JsArray);
+
+/// Converts an optional index to its argument value.
+fn optional(index: Option<i64>) -> JsValue {
+    index.map_or_else(JsValue::undefined, JsValue::from)
+}
+
+/// An iterator over the elements of a [`JsArray`].
+///
+/// This `struct` is created by [`JsArray::iter`].
+#[derive(Debug)]
+pub struct JsArrayIter<'a> {
+    array: &'a JsArray,
+    context: &'a mut Context,
+    index: usize,
+}
+
+impl Iterator for JsArrayIter<'_> {
+    type Item = JsResult<JsValue>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let length = match self.array.length(self.context) {
+            Ok(length) => length,
+            Err(error) => return Some(Err(error)),
+        };
+        if self.index >= length {
+            return None;
+        }
+        let element = self.array.get(self.index, self.context);
+        self.index += 1;
+        Some(element)
+    }
+}
@@ -0,0 +1,99 @@
+//! A typed wrapper around `Date` objects.
+
+use super::impl_object_wrapper;
+use crate::{
+    builtins::Date,
+    gc::{Finalize, Trace},
+    object::JsObject,
+    Context, JsResult, JsString,
+};
+
+/// A `Date` object.
+///
+/// # Examples
+/// ```
+///# use boa::{object::JsDate, Context};
+/// let mut context = Context::new();
+///
+/// let date = JsDate::from_time(0.0, &mut context);
+///
+/// assert_eq!(date.get_time(), 0.0);
+/// assert_eq!(
+///     date.to_iso_string(&mut context).unwrap().as_str(),
+///     "1970-01-01T00:00:00.000Z"
+/// );
+/// ```
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsDate {
+    inner: JsObject,
+}
+
+impl JsDate {
+    /// Creates a date with the current time.
+    #[inline]
+    pub fn new(context: &mut Context) -> Self {
+        let prototype = context.standard_objects().date_object().prototype();
+        Self {
+            inner: Date::make_date_now(prototype, context),
+        }
+    }
+
+    /// Creates a date with the given time value, in milliseconds since the epoch.
+    ///
+    /// The date is invalid if `time` is not finite.
+    #[inline]
+    pub fn from_time(time: f64, context: &mut Context) -> Self {
+        let prototype = context.standard_objects().date_object().prototype();
+        Self {
+            inner: Date::make_date_single(prototype, &[time.into()], context)
+                .expect("creating a date from a number must not fail"),
+        }
+    }
+
+    /// Wraps an object, throwing a `TypeError` if it is not a `Date`.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_date() {
+            Ok(Self { inner: object })
+        } else {
+            Err(context.construct_type_error("object is not a Date"))
+        }
+    }
+
+    /// Gets the time value of the date, in milliseconds since the epoch, or `NaN` if the date is
+    /// invalid.
+    #[inline]
+    pub fn get_time(&self) -> f64 {
+        self.inner
+            .borrow()
+            .as_date()
+            .expect("a JsDate must be a Date object")
+            .get_time()
+    }
+
+    /// Sets the time value of the date, and returns the time value that was stored.
+    #[inline]
+    pub fn set_time(&self, time: f64, context: &mut Context) -> JsResult<f64> {
+        Date::set_time(&self.inner.clone().into(), &[time.into()], context)?.to_number(context)
+    }
+
+    /// Gets the date in the ISO 8601 format, throwing a `RangeError` if the date is invalid.
+    #[inline]
+    pub fn to_iso_string(&self, context: &mut Context) -> JsResult<JsString> {
+        Date::to_iso_string(&self.inner.clone().into(), &[], context)?.to_string(context)
+    }
+
+    /// Gets the date part of the date in the local time zone, in a human readable format.
+    #[inline]
+    pub fn to_date_string(&self, context: &mut Context) -> JsResult<JsString> {
+        Date::to_date_string(&self.inner.clone().into(), &[], context)?.to_string(context)
+    }
+
+    /// Gets the date in the local time zone, in a human readable format.
+    #[inline]
+    pub fn to_string(&self, context: &mut Context) -> JsResult<JsString> {
+        Date::to_string(&self.inner.clone().into(), &[], context)?.to_string(context)
+    }
+}
+
+impl_object_wrapper!(JsDate);
@@ -0,0 +1,79 @@
+//! A typed wrapper around function objects.
+
+use super::impl_object_wrapper;
+use crate::{
+    gc::{Finalize, Trace},
+    object::JsObject,
+    Context, JsResult, JsValue,
+};
+
+/// A callable object.
+///
+/// # Examples
+/// ```
+///# use boa::{object::JsFunction, Context, JsValue};
+/// let mut context = Context::new();
+///
+/// let object = context.eval("(a, b) => a + b").unwrap();
+/// let function = JsFunction::from_object(object.as_object().unwrap().clone(), &mut context)
+///     .unwrap();
+///
+/// let result = function
+///     .call(&JsValue::undefined(), &[1.into(), 2.into()], &mut context)
+///     .unwrap();
+/// assert_eq!(result.as_number(), Some(3.0));
+/// ```
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsFunction {
+    inner: JsObject,
+}
+
+impl JsFunction {
+    /// Wraps an object, throwing a `TypeError` if it is not callable.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_callable() {
+            Ok(Self { inner: object })
+        } else {
+            Err(context.construct_type_error("object is not callable"))
+        }
+    }
+
+    /// Calls the function with the given `this` value and arguments.
+    #[inline]
+    pub fn call(
+        &self,
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        self.inner.call(this, args, context)
+    }
+
+    /// Calls the function as a constructor, like the `new` operator.
+    ///
+    /// The new object is created from `new_target`, or from the function itself if it is not
+    /// given. Throws a `TypeError` if the function is not a constructor.
+    #[inline]
+    pub fn construct(
+        &self,
+        args: &[JsValue],
+        new_target: Option<&JsObject>,
+        context: &mut Context,
+    ) -> JsResult<JsObject> {
+        if !self.inner.is_constructor() {
+            return Err(context.construct_type_error("function is not a constructor"));
+        }
+        let new_target = new_target.unwrap_or(&self.inner).clone().into();
+        match self
+            .inner
+            .construct(args, &new_target, context)?
+            .as_object()
+        {
+            Some(object) => Ok(object.clone()),
+            None => Err(context.construct_type_error("constructor did not return an object")),
+        }
+    }
+}
+
+impl_object_wrapper!(JsFunction);
@@ -0,0 +1,154 @@
+//! A typed wrapper around `Map` objects.
+
+use super::{impl_object_wrapper, JsFunction};
+use crate::{
+    builtins::{map::ordered_map::OrderedMap, Map},
+    gc::{Finalize, Trace},
+    object::{JsObject, ObjectData},
+    Context, JsResult, JsValue,
+};
+
+/// A `Map` object.
+///
+/// # Examples
+/// ```
+///# use boa::{object::JsMap, Context, JsValue};
+/// let mut context = Context::new();
+///
+/// let map = JsMap::new(&mut context);
+/// map.set("key", 42, &mut context).unwrap();
+///
+/// assert_eq!(map.get("key", &mut context).unwrap().as_number(), Some(42.0));
+/// assert_eq!(map.size(), 1);
+/// assert_eq!(map.entries(), vec![(JsValue::new("key"), JsValue::new(42))]);
+/// ```
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsMap {
+    inner: JsObject,
+}
+
+impl JsMap {
+    /// Creates a new empty map.
+    #[inline]
+    pub fn new(context: &mut Context) -> Self {
+        let prototype = context.standard_objects().map_object().prototype();
+        Self {
+            inner: JsObject::from_proto_and_data(prototype, ObjectData::map(OrderedMap::new())),
+        }
+    }
+
+    /// Wraps an object, throwing a `TypeError` if it is not a `Map`.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_map() {
+            Ok(Self { inner: object })
+        } else {
+            Err(context.construct_type_error("object is not a Map"))
+        }
+    }
+
+    /// Gets the number of entries of the map.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.inner
+            .borrow()
+            .as_map_ref()
+            .expect("a JsMap must be a Map object")
+            .len()
+    }
+
+    /// Gets the value of the entry with the given key, or `undefined`.
+    #[inline]
+    pub fn get<K>(&self, key: K, context: &mut Context) -> JsResult<JsValue>
+    where
+        K: Into<JsValue>,
+    {
+        Map::get(&self.inner.clone().into(), &[key.into()], context)
+    }
+
+    /// Adds an entry to the map, or replaces the value of the entry with the same key.
+    #[inline]
+    pub fn set<K, V>(&self, key: K, value: V, context: &mut Context) -> JsResult<()>
+    where
+        K: Into<JsValue>,
+        V: Into<JsValue>,
+    {
+        Map::set(
+            &self.inner.clone().into(),
+            &[key.into(), value.into()],
+            context,
+        )?;
+        Ok(())
+    }
+
+    /// Checks if the map has an entry with the given key.
+    #[inline]
+    pub fn has<K>(&self, key: K, context: &mut Context) -> JsResult<bool>
+    where
+        K: Into<JsValue>,
+    {
+        Map::has(&self.inner.clone().into(), &[key.into()], context)
+            .map(|result| result.to_boolean())
+    }
+
+    /// Removes the entry with the given key, and returns whether it existed.
+    #[inline]
+    pub fn delete<K>(&self, key: K, context: &mut Context) -> JsResult<bool>
+    where
+        K: Into<JsValue>,
+    {
+        Map::delete(&self.inner.clone().into(), &[key.into()], context)
+            .map(|result| result.to_boolean())
+    }
+
+    /// Removes all the entries of the map.
+    #[inline]
+    pub fn clear(&self, context: &mut Context) -> JsResult<()> {
+        Map::clear(&self.inner.clone().into(), &[], context)?;
+        Ok(())
+    }
+
+    /// Calls `callback` with the value and the key of every entry, in insertion order.
+    #[inline]
+    pub fn for_each(
+        &self,
+        callback: JsFunction,
+        this_arg: JsValue,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        Map::for_each(
+            &self.inner.clone().into(),
+            &[callback.into(), this_arg],
+            context,
+        )?;
+        Ok(())
+    }
+
+    /// Gets the keys and values of the entries of the map, in insertion order.
+    ///
+    /// The entries are copied, later changes to the map are not reflected in the list.
+    #[inline]
+    pub fn entries(&self) -> Vec<(JsValue, JsValue)> {
+        self.inner
+            .borrow()
+            .as_map_ref()
+            .expect("a JsMap must be a Map object")
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Gets the keys of the entries of the map, in insertion order.
+    #[inline]
+    pub fn keys(&self) -> Vec<JsValue> {
+        self.entries().into_iter().map(|(key, _)| key).collect()
+    }
+
+    /// Gets the values of the entries of the map, in insertion order.
+    #[inline]
+    pub fn values(&self) -> Vec<JsValue> {
+        self.entries().into_iter().map(|(_, value)| value).collect()
+    }
+}
+
+impl_object_wrapper!(JsMap);
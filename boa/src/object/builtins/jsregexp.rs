@@ -0,0 +1,94 @@
+//! A typed wrapper around `RegExp` objects.
+
+use super::impl_object_wrapper;
+use crate::{
+    builtins::RegExp,
+    gc::{Finalize, Trace},
+    object::JsObject,
+    Context, JsResult, JsString,
+};
+
+/// A `RegExp` object.
+///
+/// # Examples
+/// ```
+///# use boa::{object::JsRegExp, Context};
+/// let mut context = Context::new();
+///
+/// let regexp = JsRegExp::new("fo+", "g", &mut context).unwrap();
+///
+/// assert!(regexp.test("foo", &mut context).unwrap());
+/// assert_eq!(regexp.to_string(&mut context).unwrap().as_str(), "/fo+/g");
+/// ```
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsRegExp {
+    inner: JsObject,
+}
+
+impl JsRegExp {
+    /// Creates a regular expression from a pattern and flags, throwing a `SyntaxError` if they
+    /// are invalid.
+    #[inline]
+    pub fn new<P, F>(pattern: P, flags: F, context: &mut Context) -> JsResult<Self>
+    where
+        P: Into<JsString>,
+        F: Into<JsString>,
+    {
+        let regexp = RegExp::create(pattern.into().into(), flags.into().into(), context)?;
+        let inner = regexp
+            .as_object()
+            .expect("RegExpCreate must return an object")
+            .clone();
+        Ok(Self { inner })
+    }
+
+    /// Wraps an object, throwing a `TypeError` if it is not a `RegExp`.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_regexp() {
+            Ok(Self { inner: object })
+        } else {
+            Err(context.construct_type_error("object is not a RegExp"))
+        }
+    }
+
+    /// Gets the flags of the regular expression.
+    #[inline]
+    pub fn flags(&self, context: &mut Context) -> JsResult<JsString> {
+        RegExp::get_flags(&self.inner.clone().into(), &[], context)?.to_string(context)
+    }
+
+    /// Gets the source text of the pattern of the regular expression.
+    #[inline]
+    pub fn source(&self, context: &mut Context) -> JsResult<JsString> {
+        RegExp::get_source(&self.inner.clone().into(), &[], context)?.to_string(context)
+    }
+
+    /// Checks if the regular expression matches `input`.
+    #[inline]
+    pub fn test<S>(&self, input: S, context: &mut Context) -> JsResult<bool>
+    where
+        S: Into<JsString>,
+    {
+        RegExp::test(&self.inner.clone().into(), &[input.into().into()], context)
+            .map(|result| result.to_boolean())
+    }
+
+    /// Searches for a match in `input`, and returns the match array if there is one.
+    #[inline]
+    pub fn exec<S>(&self, input: S, context: &mut Context) -> JsResult<Option<JsObject>>
+    where
+        S: Into<JsString>,
+    {
+        let result = RegExp::exec(&self.inner.clone().into(), &[input.into().into()], context)?;
+        Ok(result.as_object().cloned())
+    }
+
+    /// Gets the string representation of the regular expression, like `/pattern/flags`.
+    #[inline]
+    pub fn to_string(&self, context: &mut Context) -> JsResult<JsString> {
+        RegExp::to_string(&self.inner.clone().into(), &[], context)?.to_string(context)
+    }
+}
+
+impl_object_wrapper!(JsRegExp);
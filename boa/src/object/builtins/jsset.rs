@@ -0,0 +1,128 @@
+//! A typed wrapper around `Set` objects.
+
+use super::{impl_object_wrapper, JsFunction};
+use crate::{
+    builtins::{set::ordered_set::OrderedSet, Set},
+    gc::{Finalize, Trace},
+    object::{JsObject, ObjectData},
+    Context, JsResult, JsValue,
+};
+
+/// A `Set` object.
+///
+/// # Examples
+/// ```
+///# use boa::{object::JsSet, Context, JsValue};
+/// let mut context = Context::new();
+///
+/// let set = JsSet::new(&mut context);
+/// set.add(1, &mut context).unwrap();
+/// set.add(1, &mut context).unwrap();
+///
+/// assert!(set.has(1, &mut context).unwrap());
+/// assert_eq!(set.values(), vec![JsValue::new(1)]);
+/// ```
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsSet {
+    inner: JsObject,
+}
+
+impl JsSet {
+    /// Creates a new empty set.
+    #[inline]
+    pub fn new(context: &mut Context) -> Self {
+        let prototype = context.standard_objects().set_object().prototype();
+        Self {
+            inner: JsObject::from_proto_and_data(prototype, ObjectData::set(OrderedSet::new())),
+        }
+    }
+
+    /// Wraps an object, throwing a `TypeError` if it is not a `Set`.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_set() {
+            Ok(Self { inner: object })
+        } else {
+            Err(context.construct_type_error("object is not a Set"))
+        }
+    }
+
+    /// Gets the number of values of the set.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.inner
+            .borrow()
+            .as_set_ref()
+            .expect("a JsSet must be a Set object")
+            .size()
+    }
+
+    /// Adds a value to the set, if it does not contain it already.
+    #[inline]
+    pub fn add<T>(&self, value: T, context: &mut Context) -> JsResult<()>
+    where
+        T: Into<JsValue>,
+    {
+        Set::add(&self.inner.clone().into(), &[value.into()], context)?;
+        Ok(())
+    }
+
+    /// Checks if the set contains the given value.
+    #[inline]
+    pub fn has<T>(&self, value: T, context: &mut Context) -> JsResult<bool>
+    where
+        T: Into<JsValue>,
+    {
+        Set::has(&self.inner.clone().into(), &[value.into()], context)
+            .map(|result| result.to_boolean())
+    }
+
+    /// Removes a value from the set, and returns whether it was in the set.
+    #[inline]
+    pub fn delete<T>(&self, value: T, context: &mut Context) -> JsResult<bool>
+    where
+        T: Into<JsValue>,
+    {
+        Set::delete(&self.inner.clone().into(), &[value.into()], context)
+            .map(|result| result.to_boolean())
+    }
+
+    /// Removes all the values of the set.
+    #[inline]
+    pub fn clear(&self, context: &mut Context) -> JsResult<()> {
+        Set::clear(&self.inner.clone().into(), &[], context)?;
+        Ok(())
+    }
+
+    /// Calls `callback` with every value of the set, in insertion order.
+    #[inline]
+    pub fn for_each(
+        &self,
+        callback: JsFunction,
+        this_arg: JsValue,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        Set::for_each(
+            &self.inner.clone().into(),
+            &[callback.into(), this_arg],
+            context,
+        )?;
+        Ok(())
+    }
+
+    /// Gets the values of the set, in insertion order.
+    ///
+    /// The values are copied, later changes to the set are not reflected in the list.
+    #[inline]
+    pub fn values(&self) -> Vec<JsValue> {
+        self.inner
+            .borrow()
+            .as_set_ref()
+            .expect("a JsSet must be a Set object")
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+impl_object_wrapper!(JsSet);
@@ -0,0 +1,171 @@
+//! A typed wrapper around `TypedArray` objects.
+
+use super::impl_object_wrapper;
+use crate::{
+    builtins::{
+        array_buffer::ArrayBufferData,
+        typed_array::{TypedArray, TypedArrayName},
+    },
+    gc::{Finalize, Trace},
+    object::JsObject,
+    Context, JsResult, JsString, JsValue,
+};
+
+/// A `TypedArray` object, like an `Int8Array` or a `Float64Array`.
+///
+/// # Examples
+/// ```
+///# use boa::{builtins::typed_array::TypedArrayName, object::JsTypedArray, Context};
+/// let mut context = Context::new();
+///
+/// let array = JsTypedArray::new(TypedArrayName::Uint16Array, 2, &mut context).unwrap();
+/// array.fill(258, None, None, &mut context).unwrap();
+///
+/// assert_eq!(array.length(), 2);
+/// assert_eq!(array.byte_length(), 4);
+/// assert_eq!(array.to_bytes(), Some(vec![2, 1, 2, 1]));
+/// ```
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsTypedArray {
+    inner: JsObject,
+}
+
+impl JsTypedArray {
+    /// Creates a typed array of the given kind, with `length` zeroed elements.
+    #[inline]
+    pub fn new(kind: TypedArrayName, length: usize, context: &mut Context) -> JsResult<Self> {
+        let constructor = kind.standard_constructor()(context.standard_objects()).constructor();
+        let inner = TypedArray::create(&constructor, &[length.into()], context)?;
+        Ok(Self { inner })
+    }
+
+    /// Wraps an object, throwing a `TypeError` if it is not a typed array.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_typed_array() {
+            Ok(Self { inner: object })
+        } else {
+            Err(context.construct_type_error("object is not a TypedArray"))
+        }
+    }
+
+    /// Gets the kind of the typed array.
+    #[inline]
+    pub fn kind(&self) -> TypedArrayName {
+        self.inner
+            .borrow()
+            .as_typed_array()
+            .expect("a JsTypedArray must be a typed array object")
+            .typed_array_name()
+    }
+
+    /// Gets the number of elements of the typed array, or `0` if its buffer is detached.
+    #[inline]
+    pub fn length(&self) -> usize {
+        self.inner
+            .borrow()
+            .as_typed_array()
+            .expect("a JsTypedArray must be a typed array object")
+            .array_length()
+    }
+
+    /// Checks if the typed array has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.length() == 0
+    }
+
+    /// Gets the number of bytes of the typed array, or `0` if its buffer is detached.
+    #[inline]
+    pub fn byte_length(&self) -> usize {
+        self.inner
+            .borrow()
+            .as_typed_array()
+            .expect("a JsTypedArray must be a typed array object")
+            .byte_length()
+    }
+
+    /// Gets the offset in bytes of the typed array into its buffer.
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.inner
+            .borrow()
+            .as_typed_array()
+            .expect("a JsTypedArray must be a typed array object")
+            .byte_offset()
+    }
+
+    /// Gets the `ArrayBuffer` or `SharedArrayBuffer` viewed by the typed array.
+    #[inline]
+    pub fn buffer(&self) -> JsObject {
+        self.inner
+            .borrow()
+            .as_typed_array()
+            .expect("a JsTypedArray must be a typed array object")
+            .viewed_array_buffer()
+            .expect("a typed array must have a viewed buffer")
+            .clone()
+    }
+
+    /// Gets the element at the given index, counting from the end of the typed array if it is
+    /// negative.
+    #[inline]
+    pub fn at<T>(&self, index: T, context: &mut Context) -> JsResult<JsValue>
+    where
+        T: Into<i64>,
+    {
+        TypedArray::at(&self.inner.clone().into(), &[index.into().into()], context)
+    }
+
+    /// Fills the elements from `start` to `end` with `value`, in place.
+    #[inline]
+    pub fn fill<T>(
+        &self,
+        value: T,
+        start: Option<i64>,
+        end: Option<i64>,
+        context: &mut Context,
+    ) -> JsResult<Self>
+    where
+        T: Into<JsValue>,
+    {
+        let start = start.map_or_else(JsValue::undefined, JsValue::from);
+        let end = end.map_or_else(JsValue::undefined, JsValue::from);
+        TypedArray::fill(
+            &self.inner.clone().into(),
+            &[value.into(), start, end],
+            context,
+        )?;
+        Ok(self.clone())
+    }
+
+    /// Joins the string representations of the elements, separated by `separator` or by commas.
+    #[inline]
+    pub fn join(&self, separator: Option<JsString>, context: &mut Context) -> JsResult<JsString> {
+        let separator = separator.map_or_else(JsValue::undefined, JsValue::from);
+        TypedArray::join(&self.inner.clone().into(), &[separator], context)?.to_string(context)
+    }
+
+    /// Copies the bytes viewed by the typed array, in the byte order of the platform.
+    ///
+    /// The bytes are copied because the buffer may be shared with other threads. Returns `None`
+    /// if the buffer is detached.
+    #[inline]
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        let (offset, length) = (self.byte_offset(), self.byte_length());
+        let buffer = self.buffer();
+        let buffer = buffer.borrow();
+        let data = buffer
+            .as_array_buffer()
+            .expect("the viewed buffer of a typed array must be an ArrayBuffer")
+            .array_buffer_data
+            .as_ref()?;
+        let bytes = match data {
+            ArrayBufferData::Owned(block) => block.get(offset..offset + length)?.to_vec(),
+            ArrayBufferData::Shared(block) => block.range_to_vec(offset..offset + length)?,
+        };
+        Some(bytes)
+    }
+}
+
+impl_object_wrapper!(JsTypedArray);
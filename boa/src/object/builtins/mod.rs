@@ -0,0 +1,55 @@
+//! Typed wrappers around the objects of the builtin kinds.
+//!
+//! Each wrapper is a [`JsObject`](crate::object::JsObject) that was checked to be of the kind
+//! of the wrapper. Its methods are implemented with the builtin functions of the kind, so they
+//! behave like the methods of the prototype called from JavaScript, without looking them up on
+//! the object.
+//!
+//! The wrappers dereference to the [`JsObject`](crate::object::JsObject) they wrap, and can be
+//! converted back into it or into a [`JsValue`](crate::JsValue).
+
+mod jsarray;
+mod jsdate;
+mod jsfunction;
+mod jsmap;
+mod jsregexp;
+mod jsset;
+mod jstypedarray;
+
+pub use jsarray::{JsArray, JsArrayIter};
+pub use jsdate::JsDate;
+pub use jsfunction::JsFunction;
+pub use jsmap::JsMap;
+pub use jsregexp::JsRegExp;
+pub use jsset::JsSet;
+pub use jstypedarray::JsTypedArray;
+
+/// Implements the conversions between a wrapper and `JsObject` and `JsValue`.
+macro_rules! impl_object_wrapper {
+    ($wrapper:ident) => {
+        impl std::ops::Deref for $wrapper {
+            type Target = crate::object::JsObject;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.inner
+            }
+        }
+
+        impl From<$wrapper> for crate::object::JsObject {
+            #[inline]
+            fn from(wrapper: $wrapper) -> Self {
+                wrapper.inner.clone()
+            }
+        }
+
+        impl From<$wrapper> for crate::JsValue {
+            #[inline]
+            fn from(wrapper: $wrapper) -> Self {
+                wrapper.inner.clone().into()
+            }
+        }
+    };
+}
+
+use impl_object_wrapper;
@@ -6,6 +6,7 @@ use crate::{
     },
     exec::{Executable, InterpreterState},
     object::{internal_methods::get_prototype_from_constructor, JsObject, ObjectData},
+    realm::Realm,
     syntax::ast::node::RcStatementList,
     Context, JsResult, JsValue,
 };
@@ -68,16 +69,46 @@ fn function_construct(
 
 /// Internal implementation of [`call`](#method.call) and [`construct`](#method.construct).
 ///
+/// The function runs in its own realm, which is the current realm of the context during the
+/// call.
+///
 /// # Panics
 ///
 /// Panics if the object is currently mutably borrowed.
 ///
 /// <https://tc39.es/ecma262/#sec-prepareforordinarycall>
+#[track_caller]
+pub(super) fn call_construct(
+    obj: &JsObject,
+    this_target: &JsValue,
+    args: &[JsValue],
+    context: &mut Context,
+    construct: bool,
+) -> JsResult<JsValue> {
+    // 4. Let calleeRealm be F.[[Realm]].
+    // 5. Set the Realm of calleeContext to calleeRealm.
+    let realm = obj
+        .borrow()
+        .as_function()
+        .map(|function| function.realm().clone());
+    match realm {
+        Some(realm) if !Realm::ptr_eq(&realm, context.realm()) => {
+            let caller_realm = context.enter_realm(realm);
+            let result = call_construct_in_realm(obj, this_target, args, context, construct);
+            context.enter_realm(caller_realm);
+            result
+        }
+        _ => call_construct_in_realm(obj, this_target, args, context, construct),
+    }
+}
+
+/// Calls or constructs the function object in the current realm.
+///
 /// <https://tc39.es/ecma262/#sec-ordinarycallbindthis>
 /// <https://tc39.es/ecma262/#sec-runtime-semantics-evaluatebody>
 /// <https://tc39.es/ecma262/#sec-ordinarycallevaluatebody>
 #[track_caller]
-pub(super) fn call_construct(
+fn call_construct_in_realm(
     obj: &JsObject,
     this_target: &JsValue,
     args: &[JsValue],
@@ -96,6 +127,8 @@ pub(super) fn call_construct(
             captures: Captures,
         },
         Ordinary(RcStatementList),
+        #[cfg(feature = "vm")]
+        VmOrdinary,
     }
 
     let this_function_object = obj.clone();
@@ -110,6 +143,7 @@ pub(super) fn call_construct(
                 Function::Native {
                     function,
                     constructor,
+                    ..
                 } => {
                     if *constructor || construct {
                         FunctionBody::BuiltInConstructor(*function)
@@ -124,11 +158,11 @@ pub(super) fn call_construct(
                     captures: captures.clone(),
                 },
                 Function::Ordinary {
-                    constructor: _,
                     this_mode,
                     body,
                     params,
                     environment,
+                    ..
                 } => {
                     let this = if construct {
                         // If the prototype of the constructor is not an object, then use the default object
@@ -257,9 +291,7 @@ pub(super) fn call_construct(
                     FunctionBody::Ordinary(body.clone())
                 }
                 #[cfg(feature = "vm")]
-                Function::VmOrdinary { .. } => {
-                    todo!("vm call")
-                }
+                Function::VmOrdinary { .. } => FunctionBody::VmOrdinary,
             }
         }
     } else {
@@ -277,6 +309,12 @@ pub(super) fn call_construct(
         FunctionBody::Closure { function, captures } => {
             (function)(this_target, args, captures, context)
         }
+        #[cfg(feature = "vm")]
+        FunctionBody::VmOrdinary if construct => {
+            obj.construct_internal(args, this_target, context, true)
+        }
+        #[cfg(feature = "vm")]
+        FunctionBody::VmOrdinary => obj.call_internal(this_target, args, context, true),
         FunctionBody::Ordinary(body) => {
             let result = body.run(context);
             let this = context.get_this_binding();
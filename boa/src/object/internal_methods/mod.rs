@@ -9,6 +9,7 @@ use crate::{
     context::{StandardConstructor, StandardObjects},
     object::JsObject,
     property::{DescriptorKind, PropertyDescriptor, PropertyKey},
+    realm::Realm,
     value::JsValue,
     BoaProfiler, Context, JsResult,
 };
@@ -230,9 +231,12 @@ impl JsObject {
         context: &mut Context,
     ) -> JsResult<JsValue> {
         let func = self.borrow().data.internal_methods.__call__;
-        func.expect("called `[[Call]]` for object without a `[[Call]]` internal method")(
-            self, this, args, context,
-        )
+        let func = func.expect("called `[[Call]]` for object without a `[[Call]]` internal method");
+
+        context.enter_call()?;
+        let result = func(self, this, args, context);
+        context.exit_call();
+        result
     }
 
     /// Internal method `[[Construct]]`
@@ -252,9 +256,13 @@ impl JsObject {
         context: &mut Context,
     ) -> JsResult<JsValue> {
         let func = self.borrow().data.internal_methods.__construct__;
-        func.expect("called `[[Construct]]` for object without a `[[Construct]]` internal method")(
-            self, args, new_target, context,
-        )
+        let func = func
+            .expect("called `[[Construct]]` for object without a `[[Construct]]` internal method");
+
+        context.enter_call()?;
+        let result = func(self, args, new_target, context);
+        context.exit_call();
+        result
     }
 }
 
@@ -936,8 +944,13 @@ where
         }
     }
     // 3. If Type(proto) is not Object, then
-    // TODO: handle realms
-    // a. Let realm be ? GetFunctionRealm(constructor).
-    // b. Set proto to realm's intrinsic object named intrinsicDefaultProto.
+    if let Some(object) = constructor.as_object() {
+        // a. Let realm be ? GetFunctionRealm(constructor).
+        let realm = object.get_function_realm(context)?;
+        if !Realm::ptr_eq(&realm, context.realm()) {
+            // b. Set proto to realm's intrinsic object named intrinsicDefaultProto.
+            return Ok(default(&realm.intrinsics().standard_objects).prototype());
+        }
+    }
     Ok(default(context.standard_objects()).prototype())
 }
@@ -4,6 +4,7 @@
 
 use super::{JsPrototype, NativeObject, Object};
 use crate::{
+    gc::{register_object, HeapEntry},
     object::{ObjectData, ObjectKind},
     property::{PropertyDescriptor, PropertyKey},
     value::PreferredType,
@@ -25,14 +26,23 @@ pub type Ref<'a, T> = GcCellRef<'a, T>;
 pub type RefMut<'a, T, U> = GcCellRefMut<'a, T, U>;
 
 /// Garbage collected `Object`.
-#[derive(Trace, Finalize, Clone, Default)]
+#[derive(Trace, Finalize, Clone)]
 pub struct JsObject(Gc<GcCell<Object>>);
 
+impl Default for JsObject {
+    #[inline]
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
 impl JsObject {
     /// Create a new `JsObject` from an internal `Object`.
     #[inline]
     fn from_object(object: Object) -> Self {
-        Self(Gc::new(GcCell::new(object)))
+        let inner = Gc::new(GcCell::new(object));
+        register_object(&inner);
+        Self(inner)
     }
 
     /// Create a new empty `JsObject`, with `prototype` set to `JsValue::Null`
@@ -52,6 +62,7 @@ impl JsObject {
             prototype: prototype.into(),
             extensible: true,
             properties: Default::default(),
+            heap_entry: HeapEntry::default(),
         })
     }
 
@@ -225,7 +236,7 @@ impl JsObject {
     /// Panics if the object is currently borrowed.
     #[inline]
     #[track_caller]
-    pub fn downcast_mut<T>(&mut self) -> Option<RefMut<'_, Object, T>>
+    pub fn downcast_mut<T>(&self) -> Option<RefMut<'_, Object, T>>
     where
         T: NativeObject,
     {
@@ -313,6 +324,17 @@ impl JsObject {
         self.borrow().is_map()
     }
 
+    /// Checks if it is a `Set` object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object is currently mutably borrowed.
+    #[inline]
+    #[track_caller]
+    pub fn is_set(&self) -> bool {
+        self.borrow().is_set()
+    }
+
     /// Checks if it's a `String` object.
     ///
     /// # Panics
@@ -390,6 +412,17 @@ impl JsObject {
         self.borrow().is_bigint()
     }
 
+    /// Checks if it's a `Date` object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object is currently mutably borrowed.
+    #[inline]
+    #[track_caller]
+    pub fn is_date(&self) -> bool {
+        self.borrow().is_date()
+    }
+
     /// Checks if it's a `RegExp` object.
     ///
     /// # Panics
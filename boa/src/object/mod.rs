@@ -5,7 +5,10 @@ use crate::{
         array::array_iterator::ArrayIterator,
         array_buffer::ArrayBuffer,
         function::arguments::{Arguments, MappedArguments},
-        function::{BoundFunction, Captures, Function, NativeFunctionSignature},
+        function::{
+            BoundFunction, Captures, Function, IntoNativeFunction, NativeFunction,
+            NativeFunctionSignature,
+        },
         map::map_iterator::MapIterator,
         map::ordered_map::OrderedMap,
         object::for_in_iterator::ForInIterator,
@@ -18,8 +21,9 @@ use crate::{
         DataView, Date, RegExp,
     },
     context::StandardConstructor,
-    gc::{Finalize, Trace},
+    gc::{Finalize, HeapEntry, Trace},
     property::{Attribute, PropertyDescriptor, PropertyKey},
+    realm::Realm,
     Context, JsBigInt, JsResult, JsString, JsSymbol, JsValue,
 };
 use std::{
@@ -28,6 +32,9 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+pub use builtins::{
+    JsArray, JsArrayIter, JsDate, JsFunction, JsMap, JsRegExp, JsSet, JsTypedArray,
+};
 pub use jsobject::{JsObject, RecursionLimiter, Ref, RefMut};
 pub use operations::IntegrityLevel;
 pub use property_map::*;
@@ -51,6 +58,7 @@ use self::internal_methods::{
 #[cfg(test)]
 mod tests;
 
+pub mod builtins;
 pub(crate) mod internal_methods;
 mod jsobject;
 mod operations;
@@ -95,6 +103,9 @@ pub struct Object {
     prototype: JsPrototype,
     /// Whether it can have new properties added to it.
     extensible: bool,
+    /// The entry of the object in the heap statistics.
+    #[unsafe_ignore_trace]
+    pub(crate) heap_entry: HeapEntry,
 }
 
 /// Defines the kind of an object and its internal methods
@@ -134,6 +145,7 @@ pub enum ObjectKind {
     Arguments(Arguments),
     NativeObject(Box<dyn NativeObject>),
     IntegerIndexed(IntegerIndexed),
+    ShadowRealm(Realm),
 }
 
 impl ObjectData {
@@ -217,6 +229,14 @@ impl ObjectData {
         }
     }
 
+    /// Create the `ShadowRealm` object data
+    pub fn shadow_realm(realm: Realm) -> Self {
+        Self {
+            kind: ObjectKind::ShadowRealm(realm),
+            internal_methods: &ORDINARY_INTERNAL_METHODS,
+        }
+    }
+
     /// Create the `ForInIterator` object data
     pub fn for_in_iterator(for_in_iterator: ForInIterator) -> Self {
         Self {
@@ -402,6 +422,7 @@ impl Display for ObjectKind {
             Self::NativeObject(_) => "NativeObject",
             Self::IntegerIndexed(_) => "TypedArray",
             Self::DataView(_) => "DataView",
+            Self::ShadowRealm(_) => "ShadowRealm",
         })
     }
 }
@@ -424,6 +445,7 @@ impl Default for Object {
             properties: PropertyMap::default(),
             prototype: None,
             extensible: true,
+            heap_entry: HeapEntry::default(),
         }
     }
 }
@@ -943,6 +965,18 @@ impl Object {
         }
     }
 
+    /// Returns the realm of a `ShadowRealm` object.
+    #[inline]
+    pub fn as_shadow_realm(&self) -> Option<&Realm> {
+        match &self.data {
+            ObjectData {
+                kind: ObjectKind::ShadowRealm(realm),
+                ..
+            } => Some(realm),
+            _ => None,
+        }
+    }
+
     /// Checks if it is an `Arguments` object.
     #[inline]
     pub fn is_arguments(&self) -> bool {
@@ -1247,11 +1281,13 @@ impl<'context> FunctionBuilder<'context> {
     /// Create a new `FunctionBuilder` for creating a native function.
     #[inline]
     pub fn native(context: &'context mut Context, function: NativeFunctionSignature) -> Self {
+        let realm = context.realm().clone();
         Self {
             context,
             function: Some(Function::Native {
                 function,
                 constructor: false,
+                realm,
             }),
             name: JsString::default(),
             length: 0,
@@ -1264,24 +1300,53 @@ impl<'context> FunctionBuilder<'context> {
     where
         F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
     {
+        let realm = context.realm().clone();
         Self {
             context,
             function: Some(Function::Closure {
                 function: Box::new(move |this, args, _, context| function(this, args, context)),
                 constructor: false,
                 captures: Captures::new(()),
+                realm,
             }),
             name: JsString::default(),
             length: 0,
         }
     }
 
+    /// Create a new `FunctionBuilder` for creating a function from a [`NativeFunction`].
+    #[inline]
+    pub fn native_function(context: &'context mut Context, function: NativeFunction) -> Self {
+        let realm = context.realm().clone();
+        Self {
+            context,
+            function: Some(function.into_function(false, realm)),
+            name: JsString::default(),
+            length: 0,
+        }
+    }
+
+    /// Create a new `FunctionBuilder` for creating a function from a typed Rust function.
+    ///
+    /// See [`IntoNativeFunction`] for the functions that can be used.
+    #[inline]
+    pub fn typed<F, Args>(context: &'context mut Context, function: F) -> Self
+    where
+        F: IntoNativeFunction<Args>,
+    {
+        Self::closure(context, move |this, args, context| {
+            function.call_native(this, args, context)
+        })
+    }
+
     /// Create a new closure function with additional captures.
     ///
     /// # Note
     ///
     /// You can only move variables that implement `Debug + Any + Trace + Clone`.
     /// In other words, only `NativeObject + Clone` objects are movable.
+    ///
+    /// The captures are mutably borrowed during a call, so the function cannot call itself.
     #[inline]
     pub fn closure_with_captures<F, C>(
         context: &'context mut Context,
@@ -1292,6 +1357,7 @@ impl<'context> FunctionBuilder<'context> {
         F: Fn(&JsValue, &[JsValue], &mut C, &mut Context) -> JsResult<JsValue> + Copy + 'static,
         C: NativeObject,
     {
+        let realm = context.realm().clone();
         Self {
             context,
             function: Some(Function::Closure {
@@ -1304,6 +1370,7 @@ impl<'context> FunctionBuilder<'context> {
                 }),
                 constructor: false,
                 captures: Captures::new(captures),
+                realm,
             }),
             name: JsString::default(),
             length: 0,
@@ -1433,11 +1500,25 @@ impl<'context> ObjectInitializer<'context> {
         binding: B,
         length: usize,
     ) -> &mut Self
+    where
+        B: Into<FunctionBinding>,
+    {
+        self.native_function(NativeFunction::from_fn_ptr(function), binding, length)
+    }
+
+    /// Add a [`NativeFunction`] to the object.
+    #[inline]
+    pub fn native_function<B>(
+        &mut self,
+        function: NativeFunction,
+        binding: B,
+        length: usize,
+    ) -> &mut Self
     where
         B: Into<FunctionBinding>,
     {
         let binding = binding.into();
-        let function = FunctionBuilder::native(self.context, function)
+        let function = FunctionBuilder::native_function(self.context, function)
             .name(binding.name)
             .length(length)
             .constructor(false)
@@ -1535,9 +1616,9 @@ impl<'context> ConstructorBuilder<'context> {
         Self {
             context,
             constructor_function: constructor,
-            constructor_object: object.constructor,
+            constructor_object: object.constructor(),
             constructor_has_prototype: true,
-            prototype: object.prototype,
+            prototype: object.prototype(),
             length: 0,
             name: JsString::default(),
             callable: true,
@@ -1781,6 +1862,7 @@ impl<'context> ConstructorBuilder<'context> {
         let function = Function::Native {
             function: self.constructor_function,
             constructor: self.constructor,
+            realm: self.context.realm().clone(),
         };
 
         let length = PropertyDescriptor::builder()
@@ -3,6 +3,7 @@ use crate::{
     context::{StandardConstructor, StandardObjects},
     object::JsObject,
     property::{PropertyDescriptor, PropertyKey, PropertyNameKind},
+    realm::Realm,
     symbol::WellKnownSymbols,
     value::Type,
     Context, JsResult, JsValue,
@@ -259,7 +260,6 @@ impl JsObject {
     // <https://tc39.es/ecma262/#sec-ecmascript-function-objects-call-thisargument-argumentslist>
     #[track_caller]
     #[inline]
-    #[cfg(not(feature = "vm"))]
     pub fn call(
         &self,
         this: &JsValue,
@@ -283,7 +283,6 @@ impl JsObject {
     // <https://tc39.es/ecma262/#sec-ecmascript-function-objects-construct-argumentslist-newtarget>
     #[track_caller]
     #[inline]
-    #[cfg(not(feature = "vm"))]
     pub fn construct(
         &self,
         args: &[JsValue],
@@ -562,7 +561,47 @@ impl JsObject {
         }
     }
 
-    // todo: GetFunctionRealm
+    /// Abstract operation `GetFunctionRealm ( obj )`
+    ///
+    /// Returns the realm in which a function object was created.
+    ///
+    /// More information:
+    /// - [EcmaScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-getfunctionrealm
+    pub(crate) fn get_function_realm(&self, context: &mut Context) -> JsResult<Realm> {
+        let object = self.borrow();
+
+        // 1. If obj has a [[Realm]] internal slot, then
+        if let Some(function) = object.as_function() {
+            // a. Return obj.[[Realm]].
+            return Ok(function.realm().clone());
+        }
+
+        // 2. If obj is a bound function exotic object, then
+        if let Some(bound_function) = object.as_bound_function() {
+            // a. Let target be obj.[[BoundTargetFunction]].
+            let target = bound_function.target_function().clone();
+            drop(object);
+
+            // b. Return ? GetFunctionRealm(target).
+            return target.get_function_realm(context);
+        }
+
+        // 3. If obj is a Proxy exotic object, then
+        if let Some(proxy) = object.as_proxy() {
+            // a. If obj.[[ProxyHandler]] is null, throw a TypeError exception.
+            // b. Let proxyTarget be obj.[[ProxyTarget]].
+            let (target, _) = proxy.try_data(context)?;
+            drop(object);
+
+            // c. Return ? GetFunctionRealm(proxyTarget).
+            return target.get_function_realm(context);
+        }
+
+        // 4. Return the current Realm Record.
+        Ok(context.realm().clone())
+    }
 
     // todo: CopyDataProperties
 
@@ -1,4 +1,10 @@
-use crate::{check_output, exec, TestAction};
+use crate::{
+    builtins::typed_array::TypedArrayName,
+    check_output, exec,
+    object::{JsArray, JsDate, JsFunction, JsMap, JsRegExp, JsSet, JsTypedArray},
+    property::Attribute,
+    Context, JsValue, TestAction,
+};
 
 #[test]
 fn ordinary_has_instance_nonobject_prototype() {
@@ -44,3 +50,126 @@ fn object_properties_return_order() {
         ),
     ]);
 }
+
+#[test]
+fn js_array_wrapper() {
+    let mut context = Context::new();
+
+    let array = JsArray::new(&mut context);
+    assert!(array.is_empty(&mut context).unwrap());
+
+    array
+        .push_items(&[3.into(), 1.into(), 2.into()], &mut context)
+        .unwrap();
+    array.sort(None, &mut context).unwrap();
+    let elements = array
+        .iter(&mut context)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(elements, vec![1.into(), 2.into(), 3.into()]);
+
+    assert_eq!(array.at(-1, &mut context).unwrap(), JsValue::new(3));
+    assert_eq!(array.index_of(2, &mut context).unwrap(), Some(1));
+    assert_eq!(array.index_of(4, &mut context).unwrap(), None);
+    assert!(array.includes(1, &mut context).unwrap());
+
+    let double = context.eval("(x => x * 2)").unwrap();
+    let double =
+        JsFunction::from_object(double.as_object().unwrap().clone(), &mut context).unwrap();
+    let doubled = array
+        .map(double, JsValue::undefined(), &mut context)
+        .unwrap();
+    assert_eq!(
+        doubled
+            .join(Some("-".into()), &mut context)
+            .unwrap()
+            .as_str(),
+        "2-4-6"
+    );
+
+    let object = context.eval("({})").unwrap();
+    assert!(JsArray::from_object(object.as_object().unwrap().clone(), &mut context).is_err());
+}
+
+#[test]
+fn js_function_wrapper() {
+    let mut context = Context::new();
+
+    let add = context.eval("(function (a, b) { return a + b; })").unwrap();
+    let add = JsFunction::from_object(add.as_object().unwrap().clone(), &mut context).unwrap();
+    assert_eq!(
+        add.call(&JsValue::undefined(), &[1.into(), 2.into()], &mut context)
+            .unwrap(),
+        JsValue::new(3)
+    );
+
+    let arrow = context.eval("(() => {})").unwrap();
+    let arrow = JsFunction::from_object(arrow.as_object().unwrap().clone(), &mut context).unwrap();
+    assert!(arrow.construct(&[], None, &mut context).is_err());
+
+    let object = context.eval("({})").unwrap();
+    assert!(JsFunction::from_object(object.as_object().unwrap().clone(), &mut context).is_err());
+}
+
+#[test]
+fn js_map_and_set_wrappers() {
+    let mut context = Context::new();
+
+    let map = JsMap::new(&mut context);
+    map.set("a", 1, &mut context).unwrap();
+    map.set("b", 2, &mut context).unwrap();
+    assert_eq!(map.size(), 2);
+    assert_eq!(map.get("b", &mut context).unwrap(), JsValue::new(2));
+    assert!(map.delete("a", &mut context).unwrap());
+    assert_eq!(map.entries(), vec![(JsValue::new("b"), JsValue::new(2))]);
+
+    let set = JsSet::new(&mut context);
+    set.add(1, &mut context).unwrap();
+    set.add(1, &mut context).unwrap();
+    set.add(2, &mut context).unwrap();
+    assert_eq!(set.size(), 2);
+    assert_eq!(set.values(), vec![JsValue::new(1), JsValue::new(2)]);
+
+    context.register_global_property("map", map, Attribute::all());
+    context.register_global_property("set", set, Attribute::all());
+    assert_eq!(
+        context.eval("map.get('b') + set.size").unwrap(),
+        JsValue::new(4)
+    );
+}
+
+#[test]
+fn js_date_regexp_and_typed_array_wrappers() {
+    let mut context = Context::new();
+
+    let date = JsDate::from_time(86_400_000.0, &mut context);
+    assert_eq!(
+        date.to_iso_string(&mut context).unwrap().as_str(),
+        "1970-01-02T00:00:00.000Z"
+    );
+    assert!(JsDate::from_time(f64::NAN, &mut context)
+        .to_iso_string(&mut context)
+        .is_err());
+
+    let regexp = JsRegExp::new("b(c)", "i", &mut context).unwrap();
+    assert_eq!(regexp.flags(&mut context).unwrap().as_str(), "i");
+    assert_eq!(regexp.source(&mut context).unwrap().as_str(), "b(c)");
+    assert!(regexp.test("ABC", &mut context).unwrap());
+    assert!(regexp.exec("xyz", &mut context).unwrap().is_none());
+    assert!(JsRegExp::new("(", "", &mut context).is_err());
+
+    let array = JsTypedArray::new(TypedArrayName::Int8Array, 3, &mut context).unwrap();
+    array.fill(-1, Some(1), None, &mut context).unwrap();
+    assert_eq!(array.kind(), TypedArrayName::Int8Array);
+    assert_eq!(array.at(2, &mut context).unwrap(), JsValue::new(-1));
+    assert_eq!(array.to_bytes(), Some(vec![0, 255, 255]));
+
+    context.register_global_property("array", array, Attribute::all());
+    context
+        .eval("new Int8Array(array.buffer, 1, 1)[0] = 5")
+        .unwrap();
+    let view = context.eval("new Int8Array(array.buffer, 1, 2)").unwrap();
+    let view = JsTypedArray::from_object(view.as_object().unwrap().clone(), &mut context).unwrap();
+    assert_eq!(view.byte_offset(), 1);
+    assert_eq!(view.to_bytes(), Some(vec![5, 255]));
+}
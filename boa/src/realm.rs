@@ -3,42 +3,107 @@
 //! and other associated state and resources.
 //!
 //! A realm is represented in this implementation as a Realm struct with the fields specified from the spec.
+//!
+//! A [`Context`](crate::Context) can hold several realms that share the same heap, see
+//! [`Context::create_realm`](crate::Context::create_realm).
 
 use crate::{
-    environment::{
-        global_environment_record::GlobalEnvironmentRecord, lexical_environment::LexicalEnvironment,
-    },
+    builtins::{intrinsics::IntrinsicObjects, iterable::IteratorPrototypes},
+    context::{StandardConstructor, StandardObjects},
+    environment::lexical_environment::LexicalEnvironment,
+    gc::{Finalize, Gc, GcCell, Trace},
     object::{JsObject, ObjectData},
     BoaProfiler,
 };
-use gc::Gc;
+use std::fmt;
+
+/// The intrinsic objects of a realm.
+#[derive(Debug, Clone, Default, Trace, Finalize)]
+pub(crate) struct Intrinsics {
+    /// Cached standard objects and their prototypes.
+    pub(crate) standard_objects: StandardObjects,
+
+    /// Cached iterator prototypes.
+    pub(crate) iterator_prototypes: IteratorPrototypes,
+
+    /// Cached TypedArray constructor.
+    pub(crate) typed_array_constructor: StandardConstructor,
+
+    /// Cached intrinsic objects
+    pub(crate) intrinsic_objects: IntrinsicObjects,
+}
 
 /// Representation of a Realm.
 ///
 /// In the specification these are called Realm Records.
-#[derive(Debug)]
+///
+/// A `Realm` is a handle, cloning it returns another handle to the same realm.
+#[derive(Clone, Trace, Finalize)]
 pub struct Realm {
-    pub global_object: JsObject,
-    pub global_env: Gc<GlobalEnvironmentRecord>,
-    pub environment: LexicalEnvironment,
+    inner: Gc<Inner>,
+}
+
+#[derive(Trace, Finalize)]
+struct Inner {
+    global_object: JsObject,
+
+    /// The environment stack of the realm, while the realm is not the current realm of the
+    /// context.
+    environment: GcCell<LexicalEnvironment>,
+
+    intrinsics: GcCell<Intrinsics>,
 }
 
 impl Realm {
-    #[allow(clippy::field_reassign_with_default)]
+    /// Creates a realm with a new global object and uninitialized intrinsics.
     pub fn create() -> Self {
         let _timer = BoaProfiler::global().start_event("Realm::create", "realm");
         // Create brand new global object
         // Global has no prototype to pass None to new_obj
         // Allow identification of the global object easily
-        let gc_global = JsObject::from_proto_and_data(None, ObjectData::global());
-
-        // We need to clone the global here because its referenced from separate places (only pointer is cloned)
-        let global_env = GlobalEnvironmentRecord::new(gc_global.clone(), gc_global.clone());
+        let global_object = JsObject::from_proto_and_data(None, ObjectData::global());
 
         Self {
-            global_object: gc_global.clone(),
-            global_env: Gc::new(global_env),
-            environment: LexicalEnvironment::new(gc_global),
+            inner: Gc::new(Inner {
+                environment: GcCell::new(LexicalEnvironment::new(global_object.clone())),
+                global_object,
+                intrinsics: GcCell::default(),
+            }),
         }
     }
+
+    /// Returns the global object of the realm.
+    #[inline]
+    pub fn global_object(&self) -> JsObject {
+        self.inner.global_object.clone()
+    }
+
+    /// Returns `true` if both handles refer to the same realm.
+    #[inline]
+    pub fn ptr_eq(lhs: &Self, rhs: &Self) -> bool {
+        Gc::ptr_eq(&lhs.inner, &rhs.inner)
+    }
+
+    /// Returns the intrinsic objects of the realm.
+    #[inline]
+    pub(crate) fn intrinsics(&self) -> Intrinsics {
+        self.inner.intrinsics.borrow().clone()
+    }
+
+    #[inline]
+    pub(crate) fn set_intrinsics(&self, intrinsics: Intrinsics) {
+        *self.inner.intrinsics.borrow_mut() = intrinsics;
+    }
+
+    /// Swaps the saved environment stack of the realm with `environment`.
+    #[inline]
+    pub(crate) fn swap_environment(&self, environment: &mut LexicalEnvironment) {
+        std::mem::swap(&mut *self.inner.environment.borrow_mut(), environment);
+    }
+}
+
+impl fmt::Debug for Realm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Realm { .. }")
+    }
 }
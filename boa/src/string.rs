@@ -1,6 +1,6 @@
 use crate::{
     builtins::string::is_trimmable_whitespace,
-    gc::{empty_trace, Finalize, Trace},
+    gc::{empty_trace, track_allocation, track_deallocation, Finalize, Trace},
 };
 use rustc_hash::FxHashSet;
 use std::{
@@ -216,6 +216,7 @@ impl Inner {
             .extend(Layout::array::<u8>(s.len()).unwrap())
             .unwrap();
 
+        track_allocation(layout.size());
         let inner = unsafe {
             let inner = alloc(layout) as *mut Inner;
 
@@ -256,6 +257,7 @@ impl Inner {
             .extend(Layout::array::<u8>(total_string_size).unwrap())
             .unwrap();
 
+        track_allocation(layout.size());
         let inner = unsafe {
             let inner = alloc(layout) as *mut Inner;
 
@@ -295,6 +297,7 @@ impl Inner {
             .extend(Layout::array::<u8>(len).unwrap())
             .unwrap();
 
+        track_deallocation(layout.size());
         dealloc(x.as_ptr() as _, layout);
     }
 }
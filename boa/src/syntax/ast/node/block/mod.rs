@@ -37,6 +37,8 @@ mod tests;
 pub struct Block {
     #[cfg_attr(feature = "deser", serde(flatten))]
     statements: StatementList,
+    #[cfg_attr(feature = "deser", serde(skip))]
+    label: Option<Box<str>>,
 }
 
 impl Block {
@@ -45,16 +47,34 @@ impl Block {
         self.statements.items()
     }
 
+    /// Gets the statement list of this block.
+    pub(crate) fn statements(&self) -> &StatementList {
+        &self.statements
+    }
+
     pub(crate) fn lexically_declared_names(&self) -> HashSet<&str> {
         self.statements.lexically_declared_names()
     }
 
+    /// Gets the label of this block, that `break` statements can target.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_ref().map(Box::as_ref)
+    }
+
+    /// Sets the label of this block.
+    pub fn set_label(&mut self, label: Box<str>) {
+        self.label = Some(label);
+    }
+
     pub(crate) fn var_declared_named(&self) -> HashSet<&str> {
         self.statements.var_declared_names()
     }
 
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        if let Some(ref label) = self.label {
+            write!(f, "{}: ", label)?;
+        }
         writeln!(f, "{{")?;
         self.statements.display(f, indentation + 1)?;
         write!(f, "{}}}", "    ".repeat(indentation))
@@ -104,6 +124,7 @@ impl Executable for Block {
         // pop the block env
         let _ = context.pop_environment();
 
+        context.complete_labelled_statement(self.label());
         Ok(obj)
     }
 }
@@ -115,6 +136,7 @@ where
     fn from(list: T) -> Self {
         Self {
             statements: list.into(),
+            label: None,
         }
     }
 }
@@ -31,6 +31,7 @@ pub struct If {
     cond: Box<Node>,
     body: Box<Node>,
     else_node: Option<Box<Node>>,
+    label: Option<Box<str>>,
 }
 
 impl If {
@@ -46,6 +47,16 @@ impl If {
         self.else_node.as_ref().map(Box::as_ref)
     }
 
+    /// Gets the label of this `if` statement, that `break` statements can target.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_ref().map(Box::as_ref)
+    }
+
+    /// Sets the label of this `if` statement.
+    pub fn set_label(&mut self, label: Box<str>) {
+        self.label = Some(label);
+    }
+
     /// Creates an `If` AST node.
     pub fn new<C, B, E, OE>(condition: C, body: B, else_node: OE) -> Self
     where
@@ -58,6 +69,7 @@ impl If {
             cond: Box::new(condition.into()),
             body: Box::new(body.into()),
             else_node: else_node.into().map(E::into).map(Box::new),
+            label: None,
         }
     }
 
@@ -66,6 +78,9 @@ impl If {
         f: &mut fmt::Formatter<'_>,
         indent: usize,
     ) -> fmt::Result {
+        if let Some(ref label) = self.label {
+            write!(f, "{}: ", label)?;
+        }
         write!(f, "if ({}) ", self.cond())?;
         match self.else_node() {
             Some(else_e) => {
@@ -80,13 +95,15 @@ impl If {
 
 impl Executable for If {
     fn run(&self, context: &mut Context) -> JsResult<JsValue> {
-        Ok(if self.cond().run(context)?.to_boolean() {
+        let result = if self.cond().run(context)?.to_boolean() {
             self.body().run(context)?
         } else if let Some(else_e) = self.else_node() {
             else_e.run(context)?
         } else {
             JsValue::undefined()
-        })
+        };
+        context.complete_labelled_statement(self.label());
+        Ok(result)
     }
 }
 
@@ -441,6 +441,13 @@ impl DeclarationPatternObject {
         self.init.as_ref()
     }
 
+    /// Gets the bindings for the object binding pattern.
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub(crate) fn bindings(&self) -> &[BindingPatternTypeObject] {
+        &self.bindings
+    }
+
     /// Initialize the values of an object binding pattern.
     ///
     /// More information:
@@ -649,6 +656,13 @@ impl DeclarationPatternArray {
         self.init.as_ref()
     }
 
+    /// Gets the bindings for the array binding pattern.
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub(crate) fn bindings(&self) -> &[BindingPatternTypeArray] {
+        &self.bindings
+    }
+
     /// Initialize the values of an array binding pattern.
     ///
     /// More information:
@@ -77,6 +77,7 @@ impl Executable for DoWhileLoop {
     fn run(&self, context: &mut Context) -> JsResult<JsValue> {
         let mut result;
         loop {
+            context.count_loop_iteration()?;
             result = self.body().run(context)?;
             match context.executor().get_current_state() {
                 InterpreterState::Break(label) => {
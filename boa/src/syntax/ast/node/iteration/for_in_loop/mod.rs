@@ -99,6 +99,7 @@ impl Executable for ForInLoop {
         let iterator = IteratorRecord::new(for_in_iterator, next_function);
 
         loop {
+            context.count_loop_iteration()?;
             {
                 let env = context.get_current_environment();
                 context.push_environment(DeclarativeEnvironmentRecord::new(Some(env)));
@@ -116,6 +116,7 @@ impl Executable for ForLoop {
             .transpose()?
             .unwrap_or(true)
         {
+            context.count_loop_iteration()?;
             let result = self.body().run(context)?;
 
             match context.executor().get_current_state() {
@@ -86,6 +86,7 @@ impl Executable for ForOfLoop {
         let mut result = JsValue::undefined();
 
         loop {
+            context.count_loop_iteration()?;
             {
                 let env = context.get_current_environment();
                 context.push_environment(DeclarativeEnvironmentRecord::new(Some(env)));
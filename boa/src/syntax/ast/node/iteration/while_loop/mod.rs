@@ -75,6 +75,7 @@ impl Executable for WhileLoop {
     fn run(&self, context: &mut Context) -> JsResult<JsValue> {
         let mut result = JsValue::undefined();
         while self.cond().run(context)?.to_boolean() {
+            context.count_loop_iteration()?;
             result = self.body().run(context)?;
             match context.executor().get_current_state() {
                 InterpreterState::Break(label) => {
@@ -437,7 +437,7 @@ pub struct FormalParameter {
 
 impl FormalParameter {
     /// Creates a new formal parameter.
-    pub(in crate::syntax) fn new<N>(name: N, init: Option<Node>, is_rest_param: bool) -> Self
+    pub(crate) fn new<N>(name: N, init: Option<Node>, is_rest_param: bool) -> Self
     where
         N: Into<Box<str>>,
     {
@@ -4,7 +4,10 @@ use crate::{
     context::StrictType,
     exec::{Executable, InterpreterState},
     gc::{empty_trace, Finalize, Trace},
-    syntax::ast::node::{Declaration, Node},
+    syntax::ast::{
+        node::{Declaration, Node},
+        Position,
+    },
     BoaProfiler, Context, JsResult, JsValue,
 };
 use std::{collections::HashSet, fmt, ops::Deref, rc::Rc};
@@ -24,20 +27,45 @@ mod tests;
 ///
 /// [spec]: https://tc39.es/ecma262/#prod-StatementList
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct StatementList {
     #[cfg_attr(feature = "deser", serde(flatten))]
     items: Box<[Node]>,
     strict: bool,
+    /// The start positions of the items in the source code, if known.
+    #[cfg_attr(feature = "deser", serde(skip))]
+    #[unsafe_ignore_trace]
+    positions: Box<[Position]>,
 }
 
 impl StatementList {
+    /// Creates a statement list from its items and the start positions of the items in the
+    /// source code.
+    pub(crate) fn with_positions<T>(items: T, positions: Box<[Position]>) -> Self
+    where
+        T: Into<Box<[Node]>>,
+    {
+        let items = items.into();
+        debug_assert_eq!(items.len(), positions.len());
+        Self {
+            items,
+            strict: false,
+            positions,
+        }
+    }
+
     /// Gets the list of items.
     #[inline]
     pub fn items(&self) -> &[Node] {
         &self.items
     }
 
+    /// Gets the start position in the source code of the item at `index`, if it is known.
+    #[inline]
+    pub fn position(&self, index: usize) -> Option<Position> {
+        self.positions.get(index).copied()
+    }
+
     /// Get the strict mode.
     #[inline]
     pub fn strict(&self) -> bool {
@@ -192,10 +220,19 @@ where
         Self {
             items: stm.into(),
             strict: false,
+            positions: Box::default(),
         }
     }
 }
 
+// The positions are left out of the comparison, so that parsed lists can be compared with lists
+// built in code.
+impl PartialEq for StatementList {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items && self.strict == other.strict
+    }
+}
+
 impl fmt::Display for StatementList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.display(f, 0)
@@ -29,6 +29,11 @@ impl TemplateLit {
     pub fn new(elements: Vec<TemplateElement>) -> Self {
         TemplateLit { elements }
     }
+
+    /// Gets the strings and expressions of the template literal, in source order.
+    pub fn elements(&self) -> &[TemplateElement] {
+        &self.elements
+    }
 }
 
 impl Executable for TemplateLit {
@@ -87,6 +92,26 @@ impl TaggedTemplate {
             exprs,
         }
     }
+
+    /// Gets the tag function of the template.
+    pub fn tag(&self) -> &Node {
+        &self.tag
+    }
+
+    /// Gets the raw strings of the template.
+    pub fn raws(&self) -> &[Box<str>] {
+        &self.raws
+    }
+
+    /// Gets the cooked strings of the template, `None` for invalid escape sequences.
+    pub fn cookeds(&self) -> &[Option<Box<str>>] {
+        &self.cookeds
+    }
+
+    /// Gets the substitution expressions of the template.
+    pub fn exprs(&self) -> &[Node] {
+        &self.exprs
+    }
 }
 
 impl Executable for TaggedTemplate {
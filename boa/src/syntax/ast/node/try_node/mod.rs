@@ -97,7 +97,14 @@ impl Try {
 impl Executable for Try {
     fn run(&self, context: &mut Context) -> JsResult<JsValue> {
         let _timer = BoaProfiler::global().start_event("Try", "exec");
-        let res = self.block().run(context).map_or_else(
+        let res = self.block().run(context);
+
+        // The termination of the script can not be caught, and skips the `finally` block.
+        if matches!(&res, Err(error) if error.as_termination().is_some()) {
+            return res;
+        }
+
+        let res = res.map_or_else(
             |err| {
                 if let Some(catch) = self.catch() {
                     let env = context.get_current_environment();
@@ -45,6 +45,7 @@ pub use crate::{profiler::BoaProfiler, syntax::ast::Position};
 use core::convert::TryFrom;
 pub use error::Error;
 use std::io::Read;
+pub(crate) use string::UTF16CodeUnitsBuffer;
 pub use token::{Token, TokenKind};
 
 trait Tokenizer<R> {
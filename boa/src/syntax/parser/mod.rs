@@ -122,9 +122,9 @@ where
     type Output = StatementList;
 
     fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+        let mut strict = cursor.strict_mode();
         match cursor.peek(0)? {
             Some(tok) => {
-                let mut strict = false;
                 match tok.kind() {
                     TokenKind::StringLiteral(string) if string.as_ref() == "use strict" => {
                         cursor.set_strict_mode(true);
@@ -281,12 +281,13 @@ where
     fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("StatementList", "Parsing");
         let mut items = Vec::new();
+        let mut positions = Vec::new();
 
         loop {
             match cursor.peek(0)? {
                 Some(token) if self.break_nodes.contains(token.kind()) => break,
+                Some(token) => positions.push(token.span().start()),
                 None => break,
-                _ => {}
             }
 
             let item = StatementListItem::new(
@@ -398,9 +399,14 @@ where
             }
         }
 
-        items.sort_by(Node::hoistable_order);
+        let mut items: Vec<_> = items.into_iter().zip(positions).collect();
+        items.sort_by(|(a, _), (b, _)| Node::hoistable_order(a, b));
+        let (items, positions): (Vec<_>, Vec<_>) = items.into_iter().unzip();
 
-        Ok(items.into())
+        Ok(node::StatementList::with_positions(
+            items,
+            positions.into_boxed_slice(),
+        ))
     }
 }
 
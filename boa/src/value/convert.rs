@@ -0,0 +1,442 @@
+//! Conversions between Rust types and `JsValue`s.
+//!
+//! [`TryFromJs`] converts a `JsValue` into a Rust type, throwing a `TypeError` if the value is not
+//! of the expected type. Values are not coerced, so converting the string `"1"` into an `f64`
+//! fails. [`IntoJs`] converts a Rust type into a `JsValue`, creating objects if needed.
+//!
+//! These traits are used to call typed Rust functions from JavaScript, see
+//! [`IntoNativeFunction`](crate::builtins::function::IntoNativeFunction).
+
+use crate::{
+    builtins::{Array, JsArgs},
+    object::{JsArray, JsDate, JsFunction, JsMap, JsObject, JsRegExp, JsSet, JsTypedArray},
+    property::{PropertyKey, PropertyNameKind},
+    Context, JsBigInt, JsResult, JsString, JsValue,
+};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    hash::{BuildHasher, Hash},
+};
+
+/// A type that can be converted from a `JsValue`.
+///
+/// # Examples
+/// ```
+///# use boa::{value::TryFromJs, Context, JsValue};
+/// let mut context = Context::new();
+///
+/// let value = context.eval("[1, 2, 3]").unwrap();
+/// let numbers = Vec::<u8>::try_from_js(&value, &mut context).unwrap();
+/// assert_eq!(numbers, vec![1, 2, 3]);
+///
+/// assert!(String::try_from_js(&JsValue::new(1), &mut context).is_err());
+/// ```
+pub trait TryFromJs: Sized {
+    /// Converts the value, throwing a `TypeError` if it is not of the expected type.
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self>;
+
+    /// Converts the argument at `index` of a call, or `undefined` if it is missing.
+    ///
+    /// If the conversion throws a `TypeError`, the position of the argument is added to its
+    /// message.
+    fn try_from_js_argument(
+        args: &[JsValue],
+        index: usize,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        Self::try_from_js(args.get_or_undefined(index), context).map_err(|error| {
+            let type_error = context.standard_objects().type_error_object().prototype();
+            let message = error
+                .as_object()
+                .filter(|object| object.is_error() && *object.prototype() == Some(type_error))
+                .and_then(|object| object.get("message", context).ok())
+                .and_then(|message| message.as_string().cloned());
+            match message {
+                Some(message) => {
+                    context.construct_type_error(format!("argument {}: {}", index + 1, message))
+                }
+                None => error,
+            }
+        })
+    }
+}
+
+/// A type that can be converted into a `JsValue`.
+///
+/// # Examples
+/// ```
+///# use boa::{value::IntoJs, Context};
+/// let mut context = Context::new();
+///
+/// let value = vec![(1, "one"), (2, "two")].into_js(&mut context);
+/// context.register_global_property("pairs", value, Default::default());
+///
+/// assert_eq!(context.eval("pairs[1][1]").unwrap().as_string().unwrap().as_str(), "two");
+/// ```
+pub trait IntoJs {
+    /// Converts the value.
+    fn into_js(self, context: &mut Context) -> JsValue;
+}
+
+/// The result of a function called from JavaScript, either a value or a `JsResult` of a value.
+pub trait IntoJsResult {
+    /// Converts the result, propagating the error if there is one.
+    fn into_js_result(self, context: &mut Context) -> JsResult<JsValue>;
+}
+
+impl<T: IntoJs> IntoJsResult for T {
+    #[inline]
+    fn into_js_result(self, context: &mut Context) -> JsResult<JsValue> {
+        Ok(self.into_js(context))
+    }
+}
+
+impl<T: IntoJs> IntoJsResult for JsResult<T> {
+    #[inline]
+    fn into_js_result(self, context: &mut Context) -> JsResult<JsValue> {
+        self.map(|value| value.into_js(context))
+    }
+}
+
+/// Throws the `TypeError` of a value that is not of the `expected` type.
+fn type_error<T>(expected: &str, value: &JsValue, context: &mut Context) -> JsResult<T> {
+    Err(context.construct_type_error(format!("expected {}, got {}", expected, value.type_of())))
+}
+
+/// Gets the object of a value, throwing a `TypeError` if it is not an object.
+fn object_of(value: &JsValue, expected: &str, context: &mut Context) -> JsResult<JsObject> {
+    match value.as_object() {
+        Some(object) => Ok(object.clone()),
+        None => type_error(expected, value, context),
+    }
+}
+
+impl TryFromJs for JsValue {
+    #[inline]
+    fn try_from_js(value: &JsValue, _: &mut Context) -> JsResult<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl TryFromJs for bool {
+    #[inline]
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.as_boolean() {
+            Some(boolean) => Ok(boolean),
+            None => type_error("a boolean", value, context),
+        }
+    }
+}
+
+impl TryFromJs for f64 {
+    #[inline]
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.as_number() {
+            Some(number) => Ok(number),
+            None => type_error("a number", value, context),
+        }
+    }
+}
+
+impl TryFromJs for f32 {
+    #[inline]
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        f64::try_from_js(value, context).map(|number| number as f32)
+    }
+}
+
+macro_rules! try_from_js_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl TryFromJs for $ty {
+                fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+                    let number = f64::try_from_js(value, context)?;
+                    // `MAX as f64` rounds up for 64-bit integers, so the upper bound is the
+                    // exact power of two above `MAX`, which is excluded.
+                    let end = (<$ty>::MAX / 2 + 1) as f64 * 2.0;
+                    if number.fract() == 0.0 && number >= <$ty>::MIN as f64 && number < end {
+                        Ok(number as $ty)
+                    } else {
+                        Err(context.construct_type_error(format!(
+                            "expected an integer that fits in `{}`, got {}",
+                            stringify!($ty),
+                            number
+                        )))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+try_from_js_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl TryFromJs for JsString {
+    #[inline]
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.as_string() {
+            Some(string) => Ok(string.clone()),
+            None => type_error("a string", value, context),
+        }
+    }
+}
+
+impl TryFromJs for String {
+    #[inline]
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        JsString::try_from_js(value, context).map(|string| string.as_str().to_owned())
+    }
+}
+
+impl TryFromJs for JsBigInt {
+    #[inline]
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.as_bigint() {
+            Some(bigint) => Ok(bigint.clone()),
+            None => type_error("a bigint", value, context),
+        }
+    }
+}
+
+/// `undefined` and `null` are converted into `None`.
+impl<T: TryFromJs> TryFromJs for Option<T> {
+    #[inline]
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        if value.is_null_or_undefined() {
+            Ok(None)
+        } else {
+            T::try_from_js(value, context).map(Some)
+        }
+    }
+}
+
+/// Only `Array` objects are converted into vectors.
+impl<T: TryFromJs> TryFromJs for Vec<T> {
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        let array = object_of(value, "an array", context)?;
+        if !array.is_array() {
+            return type_error("an array", value, context);
+        }
+        // The length is controlled by the script, so the vector is not preallocated for it.
+        let length = array.length_of_array_like(context)?;
+        let mut vec = Vec::new();
+        for index in 0..length {
+            let element = array.get(index, context)?;
+            vec.push(T::try_from_js(&element, context)?);
+        }
+        Ok(vec)
+    }
+}
+
+/// `Map` objects are converted from their entries, and other objects from their own enumerable
+/// string-keyed properties, whose keys are converted from strings.
+impl<K, V, S> TryFromJs for HashMap<K, V, S>
+where
+    K: TryFromJs + Eq + Hash,
+    V: TryFromJs,
+    S: BuildHasher + Default,
+{
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        let object = object_of(value, "an object", context)?;
+        let entries = if object.is_map() {
+            JsMap::from_object(object, context)?.entries()
+        } else {
+            let keys = object.enumerable_own_property_names(PropertyNameKind::Key, context)?;
+            let mut entries = Vec::with_capacity(keys.len());
+            for key in keys {
+                let value = object.get(key.to_property_key(context)?, context)?;
+                entries.push((key, value));
+            }
+            entries
+        };
+
+        let mut map = HashMap::with_capacity_and_hasher(entries.len(), S::default());
+        for (key, value) in entries {
+            map.insert(
+                K::try_from_js(&key, context)?,
+                V::try_from_js(&value, context)?,
+            );
+        }
+        Ok(map)
+    }
+}
+
+macro_rules! try_from_js_tuple {
+    ($length:literal; $($name:ident $index:literal),+) => {
+        /// Tuples are converted from `Array` objects with as many elements as the tuple.
+        impl<$($name: TryFromJs),+> TryFromJs for ($($name,)+) {
+            fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+                let expected = concat!("an array of length ", $length);
+                let array = object_of(value, expected, context)?;
+                if !array.is_array() || array.length_of_array_like(context)? != $length {
+                    return type_error(expected, value, context);
+                }
+                Ok(($($name::try_from_js(&array.get($index, context)?, context)?,)+))
+            }
+        }
+    };
+}
+
+try_from_js_tuple!(1; A 0);
+try_from_js_tuple!(2; A 0, B 1);
+try_from_js_tuple!(3; A 0, B 1, C 2);
+try_from_js_tuple!(4; A 0, B 1, C 2, D 3);
+try_from_js_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+try_from_js_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+impl TryFromJs for JsObject {
+    #[inline]
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        object_of(value, "an object", context)
+    }
+}
+
+macro_rules! try_from_js_wrapper {
+    ($($ty:ident $expected:literal),*) => {
+        $(
+            impl TryFromJs for $ty {
+                #[inline]
+                fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+                    let object = object_of(value, $expected, context)?;
+                    $ty::from_object(object, context)
+                }
+            }
+        )*
+    };
+}
+
+try_from_js_wrapper!(
+    JsArray "an array",
+    JsDate "a date",
+    JsFunction "a function",
+    JsMap "a map",
+    JsRegExp "a regular expression",
+    JsSet "a set",
+    JsTypedArray "a typed array"
+);
+
+macro_rules! into_js_from {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoJs for $ty {
+                #[inline]
+                fn into_js(self, _: &mut Context) -> JsValue {
+                    self.into()
+                }
+            }
+        )*
+    };
+}
+
+into_js_from!(
+    JsValue,
+    bool,
+    f64,
+    i32,
+    i64,
+    u32,
+    u64,
+    usize,
+    String,
+    &str,
+    JsString,
+    JsBigInt,
+    JsObject,
+    JsArray,
+    JsDate,
+    JsFunction,
+    JsMap,
+    JsRegExp,
+    JsSet,
+    JsTypedArray
+);
+
+macro_rules! into_js_as {
+    ($($ty:ty => $as:ty),*) => {
+        $(
+            impl IntoJs for $ty {
+                #[inline]
+                fn into_js(self, _: &mut Context) -> JsValue {
+                    <$as>::from(self).into()
+                }
+            }
+        )*
+    };
+}
+
+into_js_as!(f32 => f64, i8 => i32, i16 => i32, u8 => u32, u16 => u32);
+
+impl IntoJs for isize {
+    #[inline]
+    fn into_js(self, _: &mut Context) -> JsValue {
+        i64::try_from(self).map_or_else(|_| JsValue::new(self as f64), JsValue::new)
+    }
+}
+
+/// `()` is converted into `undefined`.
+impl IntoJs for () {
+    #[inline]
+    fn into_js(self, _: &mut Context) -> JsValue {
+        JsValue::undefined()
+    }
+}
+
+/// `None` is converted into `undefined`.
+impl<T: IntoJs> IntoJs for Option<T> {
+    #[inline]
+    fn into_js(self, context: &mut Context) -> JsValue {
+        match self {
+            Some(value) => value.into_js(context),
+            None => JsValue::undefined(),
+        }
+    }
+}
+
+/// Vectors are converted into `Array` objects.
+impl<T: IntoJs> IntoJs for Vec<T> {
+    fn into_js(self, context: &mut Context) -> JsValue {
+        let elements = self
+            .into_iter()
+            .map(|element| element.into_js(context))
+            .collect::<Vec<_>>();
+        Array::create_array_from_list(elements, context).into()
+    }
+}
+
+/// Maps are converted into ordinary objects, with a property for every entry.
+impl<K, V, S> IntoJs for HashMap<K, V, S>
+where
+    K: Into<PropertyKey>,
+    V: IntoJs,
+{
+    fn into_js(self, context: &mut Context) -> JsValue {
+        let object = context.construct_object();
+        for (key, value) in self {
+            let value = value.into_js(context);
+            object
+                .create_data_property_or_throw(key, value, context)
+                .expect("defining a property on a new ordinary object must not fail");
+        }
+        object.into()
+    }
+}
+
+macro_rules! into_js_tuple {
+    ($($name:ident $index:tt),+) => {
+        /// Tuples are converted into `Array` objects.
+        impl<$($name: IntoJs),+> IntoJs for ($($name,)+) {
+            fn into_js(self, context: &mut Context) -> JsValue {
+                let elements = vec![$(self.$index.into_js(context)),+];
+                Array::create_array_from_list(elements, context).into()
+            }
+        }
+    };
+}
+
+into_js_tuple!(A 0);
+into_js_tuple!(A 0, B 1);
+into_js_tuple!(A 0, B 1, C 2);
+into_js_tuple!(A 0, B 1, C 2, D 3);
+into_js_tuple!(A 0, B 1, C 2, D 3, E 4);
+into_js_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
@@ -0,0 +1,441 @@
+//! This module implements a [`serde::Deserializer`] that converts [`JsValue`]s into Rust values.
+//!
+//! JavaScript values are mapped to the `serde` data model as follows:
+//!  - `undefined` and `null` are unit values, or `None` when an `Option` is expected.
+//!  - numbers are integers when they are integral and safe, floats otherwise.
+//!  - `BigInt`s are 64-bit or 128-bit integers, depending on their size. [`JsValue::to_json`]
+//!    rejects them instead, like `JSON.stringify` does.
+//!  - arrays and typed arrays are sequences, other objects are maps of their own enumerable
+//!    string-keyed properties. Properties holding `undefined` are skipped, like `JSON.stringify`
+//!    does, so they are treated as missing fields.
+//!  - `ArrayBuffer`s are sequences of bytes. When a byte buffer is expected, both `ArrayBuffer`s and
+//!    typed arrays provide the bytes of their data block directly.
+//!  - enums use the externally tagged representation of `serde_json`.
+//!
+//! Symbols and functions cannot be deserialized.
+
+use super::{ser::SerdeError, JsValue};
+use crate::{
+    builtins::{array_buffer::ArrayBufferData, Number},
+    object::JsObject,
+    property::PropertyNameKind,
+    Context, JsString,
+};
+use num_traits::ToPrimitive;
+use serde::de::{
+    self, value::StringDeserializer, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer,
+    MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+use std::vec;
+
+/// The maximum nesting of arrays and objects, to prevent cyclic values from overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+/// A [`Deserializer`] that converts [`JsValue`]s into Rust values.
+///
+/// Most of the time [`JsValue::to_serde`] is more convenient to use.
+#[derive(Debug)]
+pub struct JsValueDeserializer<'a> {
+    value: JsValue,
+    context: &'a mut Context,
+    depth: usize,
+    /// Whether `BigInt`s are rejected, like `JSON.stringify` does.
+    json: bool,
+}
+
+impl<'a> JsValueDeserializer<'a> {
+    /// Creates a new deserializer for the given value.
+    #[inline]
+    pub fn new(value: JsValue, context: &'a mut Context) -> Self {
+        Self {
+            value,
+            context,
+            depth: 0,
+            json: false,
+        }
+    }
+
+    /// Creates a deserializer that rejects `BigInt`s, for converting values to JSON.
+    pub(crate) fn json(value: JsValue, context: &'a mut Context) -> Self {
+        Self {
+            value,
+            context,
+            depth: 0,
+            json: true,
+        }
+    }
+
+    /// Creates a deserializer for a value nested in the current one.
+    fn nested(value: JsValue, context: &'a mut Context, depth: usize, json: bool) -> Self {
+        Self {
+            value,
+            context,
+            depth,
+            json,
+        }
+    }
+
+    /// Returns the bytes of the data block viewed by an `ArrayBuffer` or a typed array.
+    fn buffer_bytes(object: &JsObject) -> Result<Option<Vec<u8>>, SerdeError> {
+        let object = object.borrow();
+        let (buffer, offset, length) = if let Some(buffer) = object.as_array_buffer() {
+            return Ok(Some(match &buffer.array_buffer_data {
+                Some(ArrayBufferData::Owned(block)) => block.to_vec(),
+                Some(ArrayBufferData::Shared(block)) => block.to_vec(),
+                None => {
+                    return Err(de::Error::custom(
+                        "cannot deserialize a detached ArrayBuffer",
+                    ))
+                }
+            }));
+        } else if let Some(typed_array) = object.as_typed_array() {
+            match typed_array.viewed_array_buffer() {
+                // A typed array that no longer fits its resizable buffer views no bytes.
+                Some(_) if typed_array.is_out_of_bounds() => return Ok(Some(Vec::new())),
+                Some(buffer) => (
+                    buffer.clone(),
+                    typed_array.byte_offset(),
+                    typed_array.byte_length(),
+                ),
+                None => return Ok(Some(Vec::new())),
+            }
+        } else {
+            return Ok(None);
+        };
+        drop(object);
+
+        let buffer = buffer.borrow();
+        let buffer = buffer
+            .as_array_buffer()
+            .expect("typed arrays must view an ArrayBuffer");
+        let range = offset..offset + length;
+        Ok(Some(
+            match &buffer.array_buffer_data {
+                Some(ArrayBufferData::Owned(block)) => block.get(range).map(<[u8]>::to_vec),
+                Some(ArrayBufferData::Shared(block)) => block.range_to_vec(range),
+                None => None,
+            }
+            .unwrap_or_default(),
+        ))
+    }
+}
+
+impl<'de, 'a> Deserializer<'de> for JsValueDeserializer<'a> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.value {
+            JsValue::Undefined | JsValue::Null => visitor.visit_unit(),
+            JsValue::Boolean(boolean) => visitor.visit_bool(boolean),
+            JsValue::Integer(integer) => visitor.visit_i32(integer),
+            JsValue::Rational(number) => {
+                if number.fract() == 0.0
+                    && number.abs() <= Number::MAX_SAFE_INTEGER
+                    && !(number == 0.0 && number.is_sign_negative())
+                {
+                    visitor.visit_i64(number as i64)
+                } else {
+                    visitor.visit_f64(number)
+                }
+            }
+            JsValue::String(ref string) => visitor.visit_str(string),
+            JsValue::BigInt(_) if self.json => {
+                Err(de::Error::custom("cannot convert a BigInt to JSON"))
+            }
+            JsValue::BigInt(ref bigint) => {
+                let bigint = bigint.as_inner();
+                if let Some(integer) = bigint.to_i64() {
+                    visitor.visit_i64(integer)
+                } else if let Some(integer) = bigint.to_u64() {
+                    visitor.visit_u64(integer)
+                } else if let Some(integer) = bigint.to_i128() {
+                    visitor.visit_i128(integer)
+                } else if let Some(integer) = bigint.to_u128() {
+                    visitor.visit_u128(integer)
+                } else {
+                    Err(de::Error::custom("BigInt is too large to be deserialized"))
+                }
+            }
+            JsValue::Symbol(_) => Err(de::Error::custom("cannot deserialize a Symbol")),
+            JsValue::Object(ref object) => {
+                if self.depth >= MAX_DEPTH {
+                    return Err(de::Error::custom(
+                        "maximum nesting depth exceeded, the value might be cyclic",
+                    ));
+                }
+
+                if object.is_callable() {
+                    return Err(de::Error::custom("cannot deserialize a function"));
+                }
+
+                if object.borrow().is_array_buffer() {
+                    let bytes = Self::buffer_bytes(object)?.unwrap_or_default();
+                    return visitor.visit_seq(bytes.into_deserializer());
+                }
+
+                if object.is_array() || object.borrow().is_typed_array() {
+                    let length = object.length_of_array_like(self.context)?;
+                    return visitor.visit_seq(ArrayAccess {
+                        array: object.clone(),
+                        index: 0,
+                        length,
+                        context: self.context,
+                        depth: self.depth + 1,
+                        json: self.json,
+                    });
+                }
+
+                let keys =
+                    object.enumerable_own_property_names(PropertyNameKind::Key, self.context)?;
+                let mut entries = Vec::with_capacity(keys.len());
+                for key in keys {
+                    let key = key
+                        .as_string()
+                        .expect("EnumerableOwnPropertyNames only returns strings")
+                        .clone();
+                    let value = object.get(key.clone(), self.context)?;
+                    if !value.is_undefined() {
+                        entries.push((key, value));
+                    }
+                }
+
+                visitor.visit_map(ObjectAccess {
+                    entries: entries.into_iter(),
+                    value: None,
+                    context: self.context,
+                    depth: self.depth + 1,
+                    json: self.json,
+                })
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        if self.value.is_null_or_undefined() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.value {
+            JsValue::Integer(integer) => visitor.visit_f64(integer.into()),
+            JsValue::Rational(number) => visitor.visit_f64(number),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        if let Some(object) = self.value.as_object() {
+            if let Some(bytes) = Self::buffer_bytes(object)? {
+                return visitor.visit_byte_buf(bytes);
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match self.value {
+            JsValue::String(ref variant) => {
+                let variant: StringDeserializer<SerdeError> =
+                    variant.to_string().into_deserializer();
+                visitor.visit_enum(variant)
+            }
+            JsValue::Object(ref object) if !object.is_array() => {
+                let keys =
+                    object.enumerable_own_property_names(PropertyNameKind::Key, self.context)?;
+                if keys.len() != 1 {
+                    return Err(de::Error::custom(
+                        "expected an object with a single key for an enum variant",
+                    ));
+                }
+                let variant = keys[0]
+                    .as_string()
+                    .expect("EnumerableOwnPropertyNames only returns strings")
+                    .clone();
+                let value = object.get(variant.clone(), self.context)?;
+                visitor.visit_enum(EnumValueAccess {
+                    variant,
+                    value,
+                    context: self.context,
+                    depth: self.depth + 1,
+                    json: self.json,
+                })
+            }
+            _ => Err(de::Error::custom(
+                "expected a string or an object for an enum variant",
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+/// Gives access to the elements of an array or a typed array.
+struct ArrayAccess<'a> {
+    array: JsObject,
+    index: usize,
+    length: usize,
+    context: &'a mut Context,
+    depth: usize,
+    json: bool,
+}
+
+impl<'de> SeqAccess<'de> for ArrayAccess<'_> {
+    type Error = SerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, SerdeError> {
+        if self.index >= self.length {
+            return Ok(None);
+        }
+        let value = self.array.get(self.index, self.context)?;
+        self.index += 1;
+        seed.deserialize(JsValueDeserializer::nested(
+            value,
+            self.context,
+            self.depth,
+            self.json,
+        ))
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.length - self.index)
+    }
+}
+
+/// Gives access to the properties of an ordinary object.
+struct ObjectAccess<'a> {
+    entries: vec::IntoIter<(JsString, JsValue)>,
+    value: Option<JsValue>,
+    context: &'a mut Context,
+    depth: usize,
+    json: bool,
+}
+
+impl<'de> MapAccess<'de> for ObjectAccess<'_> {
+    type Error = SerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, SerdeError> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                let key: StringDeserializer<SerdeError> = key.to_string().into_deserializer();
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, SerdeError> {
+        let value = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(JsValueDeserializer::nested(
+            value,
+            self.context,
+            self.depth,
+            self.json,
+        ))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// Gives access to an enum variant represented as an object with a single key.
+struct EnumValueAccess<'a> {
+    variant: JsString,
+    value: JsValue,
+    context: &'a mut Context,
+    depth: usize,
+    json: bool,
+}
+
+impl<'de, 'a> EnumAccess<'de> for EnumValueAccess<'a> {
+    type Error = SerdeError;
+    type Variant = JsValueDeserializer<'a>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, JsValueDeserializer<'a>), SerdeError> {
+        let variant: StringDeserializer<SerdeError> = self.variant.to_string().into_deserializer();
+        let variant = seed.deserialize(variant)?;
+        Ok((
+            variant,
+            JsValueDeserializer::nested(self.value, self.context, self.depth, self.json),
+        ))
+    }
+}
+
+impl<'de> VariantAccess<'de> for JsValueDeserializer<'_> {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, SerdeError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_map(visitor)
+    }
+}
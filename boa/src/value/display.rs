@@ -201,7 +201,7 @@ pub(crate) fn display_obj(v: &JsValue, print_internals: bool) -> String {
     let mut encounters = HashSet::new();
 
     if let JsValue::Object(object) = v {
-        if object.borrow().is_error() {
+        if object.borrow().is_error() || object.is::<Termination>() {
             let name = v
                 .get_property("name")
                 .as_ref()
@@ -8,8 +8,9 @@ mod tests;
 use crate::{
     builtins::{
         number::{f64_to_int32, f64_to_uint32},
-        Number,
+        Array, Number,
     },
+    limits::{Termination, TerminationReason},
     object::{JsObject, ObjectData},
     property::{PropertyDescriptor, PropertyKey},
     symbol::{JsSymbol, WellKnownSymbols},
@@ -20,6 +21,7 @@ use num_bigint::BigInt;
 use num_integer::Integer;
 use num_traits::Zero;
 use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     collections::HashSet,
     convert::TryFrom,
@@ -29,18 +31,24 @@ use std::{
 };
 
 mod conversions;
+mod convert;
+mod de;
 pub(crate) mod display;
 mod equality;
 mod hash;
 mod operations;
+mod ser;
 mod r#type;
 
 pub use conversions::*;
+pub use convert::{IntoJs, IntoJsResult, TryFromJs};
+pub use de::JsValueDeserializer;
 pub use display::ValueDisplay;
 pub use equality::*;
 pub use hash::*;
 pub use operations::*;
 pub use r#type::Type;
+pub use ser::{ArraySerializer, JsValueSerializer, ObjectSerializer, SerdeError};
 
 static TWO_E_64: Lazy<BigInt> = Lazy::new(|| {
     const TWO_E_64: u128 = 2u128.pow(64);
@@ -137,6 +145,17 @@ impl JsValue {
         }
     }
 
+    /// Returns the reason of the termination, if the value is the error that terminated a
+    /// script because it exceeded an execution limit or was interrupted.
+    ///
+    /// See the [`limits`](crate::limits) module.
+    #[inline]
+    pub fn as_termination(&self) -> Option<TerminationReason> {
+        self.as_object()?
+            .downcast_ref::<Termination>()
+            .map(|termination| termination.reason)
+    }
+
     /// It determines if the value is a callable function with a `[[Call]]` internal method.
     ///
     /// More information:
@@ -1059,6 +1078,113 @@ impl JsValue {
             Ok(false)
         }
     }
+
+    /// Converts a Rust value into a `JsValue` using its [`Serialize`] implementation.
+    ///
+    /// See [`JsValueSerializer`] for how the `serde` data model is mapped to JavaScript values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use boa::{Context, JsValue};
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// let mut context = Context::new();
+    /// let point = JsValue::from_serde(&Point { x: 1, y: 2 }, &mut context).unwrap();
+    /// context.register_global_property("point", point, Default::default());
+    ///
+    /// assert_eq!(context.eval("point.x + point.y").unwrap(), JsValue::new(3));
+    /// ```
+    pub fn from_serde<T>(value: &T, context: &mut Context) -> JsResult<Self>
+    where
+        T: Serialize + ?Sized,
+    {
+        value
+            .serialize(JsValueSerializer::new(context))
+            .map_err(|err| err.into_js_value(context))
+    }
+
+    /// Converts this value into a Rust value using its [`Deserialize`] implementation.
+    ///
+    /// Errors thrown while reading the value, for example by getters, are returned unchanged,
+    /// other conversion errors are returned as `TypeError`s. See [`JsValueDeserializer`] for how
+    /// JavaScript values are mapped to the `serde` data model.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use boa::Context;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Options {
+    ///     name: String,
+    ///     verbose: Option<bool>,
+    /// }
+    ///
+    /// let mut context = Context::new();
+    /// let value = context.eval("({ name: 'boa', verbose: undefined })").unwrap();
+    /// let options: Options = value.to_serde(&mut context).unwrap();
+    ///
+    /// assert_eq!(options.name, "boa");
+    /// assert_eq!(options.verbose, None);
+    /// ```
+    pub fn to_serde<T>(&self, context: &mut Context) -> JsResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        T::deserialize(JsValueDeserializer::new(self.clone(), context))
+            .map_err(|err| err.into_js_value(context))
+    }
+
+    /// Converts a [`serde_json::Value`] into a `JsValue`.
+    ///
+    /// The value is the same as the one returned by `JSON.parse`: objects and arrays are
+    /// created as ordinary JavaScript objects and arrays, and all numbers become `Number`s, so
+    /// integers that are not safe integers are rounded.
+    pub fn from_json(json: serde_json::Value, context: &mut Context) -> JsResult<Self> {
+        Ok(match json {
+            serde_json::Value::Null => Self::null(),
+            serde_json::Value::Bool(boolean) => Self::new(boolean),
+            serde_json::Value::Number(number) => match number.as_i64() {
+                Some(integer) if i32::try_from(integer).is_ok() => Self::new(integer as i32),
+                _ => Self::new(number.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(string) => Self::new(string),
+            serde_json::Value::Array(elements) => {
+                let elements = elements
+                    .into_iter()
+                    .map(|element| Self::from_json(element, context))
+                    .collect::<JsResult<Vec<_>>>()?;
+                Array::create_array_from_list(elements, context).into()
+            }
+            serde_json::Value::Object(properties) => {
+                let object = context.construct_object();
+                for (key, value) in properties {
+                    let value = Self::from_json(value, context)?;
+                    object.create_data_property_or_throw(key, value, context)?;
+                }
+                object.into()
+            }
+        })
+    }
+
+    /// Converts this value into a [`serde_json::Value`].
+    ///
+    /// `undefined` is converted to `null`, and object properties holding `undefined` are
+    /// skipped. Like in `JSON.stringify`, `NaN` and infinities are converted to `null`, and
+    /// `BigInt`s, symbols, functions and cyclic values cannot be converted and return a
+    /// `TypeError`.
+    pub fn to_json(&self, context: &mut Context) -> JsResult<serde_json::Value> {
+        serde_json::Value::deserialize(JsValueDeserializer::json(self.clone(), context))
+            .map_err(|err| err.into_js_value(context))
+    }
 }
 
 impl Default for JsValue {
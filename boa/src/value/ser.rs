@@ -0,0 +1,497 @@
+//! This module implements a [`serde::Serializer`] that converts Rust values into [`JsValue`]s.
+//!
+//! The data model of `serde` is mapped to JavaScript values as follows:
+//!  - booleans and strings are converted to their JavaScript counterparts, characters become
+//!    strings of length one.
+//!  - integers are converted to numbers when they are safe integers, otherwise they become
+//!    `BigInt`s so that no precision is lost. Floats are always converted to numbers.
+//!  - `None` and `()` become `null`, `Some(value)` is converted as `value`.
+//!  - sequences and tuples become arrays, maps and structs become ordinary objects.
+//!  - byte buffers become `Uint8Array`s.
+//!  - enum variants use the externally tagged representation of `serde_json`: unit variants are
+//!    strings and other variants are objects with the name of the variant as their only key.
+
+use super::JsValue;
+use crate::{
+    builtins::{
+        array_buffer::{ArrayBuffer, ArrayBufferData},
+        Array, Number,
+    },
+    object::{JsObject, ObjectData},
+    property::PropertyKey,
+    Context, JsBigInt,
+};
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+use std::{convert::TryFrom, fmt};
+
+/// The error type of the conversions between [`JsValue`]s and Rust values.
+#[derive(Debug, Clone)]
+pub enum SerdeError {
+    /// An error reported by `serde` or by the data structure being converted.
+    Message(String),
+    /// A JavaScript exception thrown while reading or creating a value, for example by a getter.
+    Thrown(JsValue),
+}
+
+impl SerdeError {
+    /// Converts this error into a value that can be thrown in JavaScript code.
+    ///
+    /// [`SerdeError::Message`] errors become `TypeError`s, thrown values are returned unchanged.
+    pub fn into_js_value(self, context: &mut Context) -> JsValue {
+        match self {
+            Self::Message(message) => context.construct_type_error(message),
+            Self::Thrown(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Message(message) => f.write_str(message),
+            Self::Thrown(value) => write!(f, "Uncaught {}", value.display()),
+        }
+    }
+}
+
+impl std::error::Error for SerdeError {}
+
+impl ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+impl serde::de::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+impl From<JsValue> for SerdeError {
+    #[inline]
+    fn from(value: JsValue) -> Self {
+        Self::Thrown(value)
+    }
+}
+
+/// A [`Serializer`] that converts Rust values into [`JsValue`]s.
+///
+/// Most of the time [`JsValue::from_serde`] is more convenient to use.
+#[derive(Debug)]
+pub struct JsValueSerializer<'a> {
+    context: &'a mut Context,
+}
+
+impl<'a> JsValueSerializer<'a> {
+    /// Creates a new serializer that creates its values in the given context.
+    #[inline]
+    pub fn new(context: &'a mut Context) -> Self {
+        Self { context }
+    }
+
+    /// Converts an integer to a number if it is a safe integer, or to a `BigInt` otherwise.
+    fn serialize_integer<T>(self, value: T) -> Result<JsValue, SerdeError>
+    where
+        T: Copy + Into<JsBigInt>,
+        i64: TryFrom<T>,
+    {
+        match i64::try_from(value) {
+            Ok(integer) if integer.abs() <= Number::MAX_SAFE_INTEGER as i64 => {
+                Ok(match i32::try_from(integer) {
+                    Ok(integer) => JsValue::Integer(integer),
+                    Err(_) => JsValue::Rational(integer as f64),
+                })
+            }
+            _ => Ok(JsValue::BigInt(value.into())),
+        }
+    }
+}
+
+/// Wraps `value` in an object with `variant` as its only property.
+fn wrap_variant(variant: &'static str, value: JsValue, context: &mut Context) -> JsValue {
+    let object = context.construct_object();
+    object
+        .create_data_property_or_throw(variant, value, context)
+        .expect("CreateDataPropertyOrThrow should never throw here");
+    object.into()
+}
+
+impl<'a> Serializer for JsValueSerializer<'a> {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    type SerializeSeq = ArraySerializer<'a>;
+    type SerializeTuple = ArraySerializer<'a>;
+    type SerializeTupleStruct = ArraySerializer<'a>;
+    type SerializeTupleVariant = ArraySerializer<'a>;
+    type SerializeMap = ObjectSerializer<'a>;
+    type SerializeStruct = ObjectSerializer<'a>;
+    type SerializeStructVariant = ObjectSerializer<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<JsValue, SerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<JsValue, SerdeError> {
+        Ok(i32::from(v).into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<JsValue, SerdeError> {
+        Ok(i32::from(v).into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<JsValue, SerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<JsValue, SerdeError> {
+        self.serialize_integer(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<JsValue, SerdeError> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_integer(v),
+            Err(_) => Ok(JsValue::BigInt(JsBigInt::new(num_bigint::BigInt::from(v)))),
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<JsValue, SerdeError> {
+        Ok(i32::from(v).into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<JsValue, SerdeError> {
+        Ok(i32::from(v).into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<JsValue, SerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<JsValue, SerdeError> {
+        self.serialize_integer(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<JsValue, SerdeError> {
+        match u64::try_from(v) {
+            Ok(v) => self.serialize_integer(v),
+            Err(_) => Ok(JsValue::BigInt(JsBigInt::new(num_bigint::BigInt::from(v)))),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<JsValue, SerdeError> {
+        Ok(f64::from(v).into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<JsValue, SerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_char(self, v: char) -> Result<JsValue, SerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<JsValue, SerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<JsValue, SerdeError> {
+        let buffer = JsObject::from_proto_and_data(
+            self.context
+                .standard_objects()
+                .array_buffer_object()
+                .prototype(),
+            ObjectData::array_buffer(ArrayBuffer {
+                array_buffer_data: Some(ArrayBufferData::Owned(v.to_vec().into())),
+                array_buffer_byte_length: v.len(),
+                array_buffer_detach_key: JsValue::undefined(),
+                array_buffer_max_byte_length: None,
+            }),
+        );
+
+        let constructor = self
+            .context
+            .standard_objects()
+            .typed_uint8_array_object()
+            .constructor();
+        Ok(constructor.construct(&[buffer.into()], &constructor.clone().into(), self.context)?)
+    }
+
+    fn serialize_none(self) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::null())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<JsValue, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::null())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::null())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<JsValue, SerdeError> {
+        Ok(variant.into())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<JsValue, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<JsValue, SerdeError> {
+        let value = value.serialize(JsValueSerializer::new(self.context))?;
+        Ok(wrap_variant(variant, value, self.context))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ArraySerializer<'a>, SerdeError> {
+        Ok(ArraySerializer {
+            values: Vec::with_capacity(len.unwrap_or_default()),
+            variant: None,
+            context: self.context,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ArraySerializer<'a>, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<ArraySerializer<'a>, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<ArraySerializer<'a>, SerdeError> {
+        Ok(ArraySerializer {
+            values: Vec::with_capacity(len),
+            variant: Some(variant),
+            context: self.context,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<ObjectSerializer<'a>, SerdeError> {
+        Ok(ObjectSerializer {
+            object: self.context.construct_object(),
+            key: None,
+            variant: None,
+            context: self.context,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<ObjectSerializer<'a>, SerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<ObjectSerializer<'a>, SerdeError> {
+        Ok(ObjectSerializer {
+            object: self.context.construct_object(),
+            key: None,
+            variant: Some(variant),
+            context: self.context,
+        })
+    }
+}
+
+/// Serializes sequences, tuples and tuple variants into arrays.
+#[derive(Debug)]
+pub struct ArraySerializer<'a> {
+    values: Vec<JsValue>,
+    variant: Option<&'static str>,
+    context: &'a mut Context,
+}
+
+impl ArraySerializer<'_> {
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let value = value.serialize(JsValueSerializer::new(self.context))?;
+        self.values.push(value);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<JsValue, SerdeError> {
+        let array = Array::create_array_from_list(self.values, self.context).into();
+        Ok(match self.variant {
+            Some(variant) => wrap_variant(variant, array, self.context),
+            None => array,
+        })
+    }
+}
+
+impl SerializeSeq for ArraySerializer<'_> {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        self.finish()
+    }
+}
+
+impl SerializeTuple for ArraySerializer<'_> {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        self.finish()
+    }
+}
+
+impl SerializeTupleStruct for ArraySerializer<'_> {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        self.finish()
+    }
+}
+
+impl SerializeTupleVariant for ArraySerializer<'_> {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        self.finish()
+    }
+}
+
+/// Serializes maps, structs and struct variants into ordinary objects.
+#[derive(Debug)]
+pub struct ObjectSerializer<'a> {
+    object: JsObject,
+    key: Option<PropertyKey>,
+    variant: Option<&'static str>,
+    context: &'a mut Context,
+}
+
+impl ObjectSerializer<'_> {
+    fn insert<K, T>(&mut self, key: K, value: &T) -> Result<(), SerdeError>
+    where
+        K: Into<PropertyKey>,
+        T: ?Sized + Serialize,
+    {
+        let value = value.serialize(JsValueSerializer::new(self.context))?;
+        self.object
+            .create_data_property_or_throw(key, value, self.context)?;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<JsValue, SerdeError> {
+        Ok(match self.variant {
+            Some(variant) => wrap_variant(variant, self.object.into(), self.context),
+            None => self.object.into(),
+        })
+    }
+}
+
+impl SerializeMap for ObjectSerializer<'_> {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), SerdeError> {
+        let key = key.serialize(JsValueSerializer::new(self.context))?;
+        if key.is_object() {
+            return Err(ser::Error::custom("map keys must be primitive values"));
+        }
+        self.key = Some(key.to_property_key(self.context)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        self.finish()
+    }
+}
+
+impl SerializeStruct for ObjectSerializer<'_> {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        self.finish()
+    }
+}
+
+impl SerializeStructVariant for ObjectSerializer<'_> {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        self.finish()
+    }
+}
@@ -1331,3 +1331,349 @@ mod abstract_relational_comparison {
         check_comparison!(context, "'InvalidBigInt' >= -100n" => false);
     }
 }
+
+mod serde_conversions {
+    use super::*;
+    use crate::property::Attribute;
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Options {
+        name: String,
+        count: i64,
+        ratio: f64,
+        tags: Vec<String>,
+        limit: Option<u8>,
+        #[serde(default)]
+        verbose: bool,
+        shapes: Vec<Shape>,
+        extra: BTreeMap<String, i32>,
+    }
+
+    #[test]
+    fn deserialize_struct() {
+        let mut context = Context::new();
+        let value = forward_val(
+            &mut context,
+            r#"({
+                name: "boa",
+                count: 2 ** 40,
+                ratio: 1,
+                tags: ["a", "b"],
+                limit: null,
+                verbose: undefined,
+                shapes: ["Empty", { Circle: 0.5 }, { Rect: { width: 2, height: 3 } }],
+                extra: { x: 1 }
+            })"#,
+        )
+        .unwrap();
+
+        let options: Options = value.to_serde(&mut context).unwrap();
+        assert_eq!(
+            options,
+            Options {
+                name: "boa".into(),
+                count: 1 << 40,
+                ratio: 1.0,
+                tags: vec!["a".into(), "b".into()],
+                limit: None,
+                verbose: false,
+                shapes: vec![
+                    Shape::Empty,
+                    Shape::Circle(0.5),
+                    Shape::Rect {
+                        width: 2,
+                        height: 3
+                    }
+                ],
+                extra: std::iter::once(("x".to_string(), 1)).collect(),
+            }
+        );
+    }
+
+    #[test]
+    fn serialize_struct() {
+        let mut context = Context::new();
+        let options = Options {
+            name: "boa".into(),
+            count: 1 << 60,
+            ratio: 0.25,
+            tags: vec!["a".into()],
+            limit: Some(3),
+            verbose: true,
+            shapes: vec![
+                Shape::Empty,
+                Shape::Rect {
+                    width: 1,
+                    height: 2,
+                },
+            ],
+            extra: BTreeMap::new(),
+        };
+
+        let value = JsValue::from_serde(&options, &mut context).unwrap();
+        context.register_global_property("options", value.clone(), Attribute::all());
+
+        assert_eq!(forward(&mut context, "options.name"), "\"boa\"");
+        assert_eq!(forward(&mut context, "typeof options.count"), "\"bigint\"");
+        assert_eq!(forward(&mut context, "options.tags.length"), "1");
+        assert_eq!(forward(&mut context, "options.limit"), "3");
+        assert_eq!(forward(&mut context, "options.shapes[0]"), "\"Empty\"");
+        assert_eq!(forward(&mut context, "options.shapes[1].Rect.height"), "2");
+
+        let round_trip: Options = value.to_serde(&mut context).unwrap();
+        assert_eq!(round_trip, options);
+    }
+
+    #[test]
+    fn bytes() {
+        let mut context = Context::new();
+
+        let value = JsValue::from_serde(&serde_bytes_like(&[1, 2, 255]), &mut context).unwrap();
+        context.register_global_property("bytes", value, Attribute::all());
+        assert_eq!(forward(&mut context, "bytes instanceof Uint8Array"), "true");
+        assert_eq!(forward(&mut context, "bytes.join()"), "\"1,2,255\"");
+
+        let value = forward_val(&mut context, "new Uint16Array([1, 2])").unwrap();
+        let elements: Vec<u16> = value.to_serde(&mut context).unwrap();
+        assert_eq!(elements, [1, 2]);
+
+        let value = forward_val(&mut context, "new Uint8Array([7, 8, 9]).subarray(1)").unwrap();
+        let bytes: ByteBuf = value.to_serde(&mut context).unwrap();
+        assert_eq!(bytes.0, [8, 9]);
+
+        let value = forward_val(&mut context, "new Uint8Array([4, 5]).buffer").unwrap();
+        let bytes: ByteBuf = value.to_serde(&mut context).unwrap();
+        assert_eq!(bytes.0, [4, 5]);
+
+        let value = forward_val(
+            &mut context,
+            "var shared = new Uint8Array(new SharedArrayBuffer(4)); shared.set([1, 2, 3, 4]); \
+             new Uint8Array(shared.buffer, 1, 2)",
+        )
+        .unwrap();
+        let bytes: ByteBuf = value.to_serde(&mut context).unwrap();
+        assert_eq!(bytes.0, [2, 3]);
+
+        // A typed array that no longer fits its resizable buffer views no bytes.
+        let value = forward_val(
+            &mut context,
+            "var ab = new ArrayBuffer(8, { maxByteLength: 16 }); \
+             var ta = new Uint8Array(ab, 4); \
+             ab.resize(2); \
+             ta",
+        )
+        .unwrap();
+        let bytes: ByteBuf = value.to_serde(&mut context).unwrap();
+        assert!(bytes.0.is_empty());
+    }
+
+    /// Serializes a slice as a byte buffer, like `serde_bytes` does.
+    fn serde_bytes_like(bytes: &[u8]) -> impl Serialize + '_ {
+        struct Bytes<'a>(&'a [u8]);
+        impl Serialize for Bytes<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+        Bytes(bytes)
+    }
+
+    /// Deserializes a byte buffer, like `serde_bytes::ByteBuf` does.
+    struct ByteBuf(Vec<u8>);
+
+    impl<'de> Deserialize<'de> for ByteBuf {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct ByteBufVisitor;
+            impl<'de> serde::de::Visitor<'de> for ByteBufVisitor {
+                type Value = ByteBuf;
+                fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str("a byte buffer")
+                }
+                fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+                    Ok(ByteBuf(v))
+                }
+            }
+            deserializer.deserialize_byte_buf(ByteBufVisitor)
+        }
+    }
+
+    #[test]
+    fn bigint() {
+        let mut context = Context::new();
+
+        let value = forward_val(&mut context, "2n ** 100n").unwrap();
+        let integer: u128 = value.to_serde(&mut context).unwrap();
+        assert_eq!(integer, 1 << 100);
+
+        let value = JsValue::from_serde(&(-1i128 << 100), &mut context).unwrap();
+        assert_eq!(
+            value.display().to_string(),
+            "-1267650600228229401496703205376n"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut context = Context::new();
+
+        let value = forward_val(&mut context, "({ name: 1 })").unwrap();
+        let error = value.to_serde::<Options>(&mut context).unwrap_err();
+        context.register_global_property("error", error, Attribute::all());
+        assert_eq!(forward(&mut context, "error.name"), "\"TypeError\"");
+
+        let value = forward_val(&mut context, "({ get name() { throw 'getter'; } })").unwrap();
+        let error = value.to_serde::<Options>(&mut context).unwrap_err();
+        assert_eq!(error.display().to_string(), "\"getter\"");
+
+        let value = forward_val(&mut context, "let a = [1]; a.push(a); a").unwrap();
+        assert!(value.to_json(&mut context).is_err());
+
+        let value = forward_val(&mut context, "Symbol()").unwrap();
+        assert!(value.to_serde::<String>(&mut context).is_err());
+    }
+
+    #[test]
+    fn json() {
+        let mut context = Context::new();
+
+        let json = serde_json::json!({
+            "a": [1, 2.5, "three", null, true],
+            "b": { "c": 9007199254740993u64 },
+        });
+        let value = JsValue::from_json(json, &mut context).unwrap();
+        context.register_global_property("value", value.clone(), Attribute::all());
+
+        assert_eq!(forward(&mut context, "value.a[1]"), "2.5");
+        assert_eq!(forward(&mut context, "value.a[3]"), "null");
+        // Like `JSON.parse`, integers that are not safe integers are rounded to numbers.
+        assert_eq!(forward(&mut context, "typeof value.b.c"), "\"number\"");
+        assert_eq!(
+            forward(&mut context, "value.b.c === JSON.parse('9007199254740993')"),
+            "true"
+        );
+        assert_eq!(
+            value.to_json(&mut context).unwrap(),
+            serde_json::json!({
+                "a": [1, 2.5, "three", null, true],
+                "b": { "c": 9007199254740992.0 },
+            })
+        );
+
+        // Like `JSON.stringify`, `BigInt`s cannot be converted.
+        let value = forward_val(&mut context, "({ big: 2n ** 64n })").unwrap();
+        let error = value.to_json(&mut context).unwrap_err();
+        context.register_global_property("error", error, Attribute::all());
+        assert_eq!(forward(&mut context, "error.name"), "\"TypeError\"");
+
+        let value = forward_val(
+            &mut context,
+            "({ x: undefined, y: NaN, z: [undefined, -0, 1.5] })",
+        )
+        .unwrap();
+        assert_eq!(
+            value.to_json(&mut context).unwrap(),
+            serde_json::json!({ "y": null, "z": [null, -0.0, 1.5] })
+        );
+    }
+}
+
+#[test]
+fn try_from_js_conversions() {
+    use std::collections::HashMap;
+
+    let mut context = Context::new();
+
+    let value = JsValue::new(42);
+    assert_eq!(u8::try_from_js(&value, &mut context).unwrap(), 42);
+    assert_eq!(f64::try_from_js(&value, &mut context).unwrap(), 42.0);
+    assert!(i8::try_from_js(&JsValue::new(300), &mut context).is_err());
+    assert!(i32::try_from_js(&JsValue::new(1.5), &mut context).is_err());
+    assert_eq!(
+        u8::try_from_js(&JsValue::new(255), &mut context).unwrap(),
+        255
+    );
+    assert!(u8::try_from_js(&JsValue::new(256), &mut context).is_err());
+    assert_eq!(
+        i8::try_from_js(&JsValue::new(-128), &mut context).unwrap(),
+        -128
+    );
+    assert!(i8::try_from_js(&JsValue::new(128), &mut context).is_err());
+    let two_pow_63 = JsValue::new(9_223_372_036_854_775_808.0);
+    let two_pow_64 = JsValue::new(18_446_744_073_709_551_616.0);
+    assert!(i64::try_from_js(&two_pow_63, &mut context).is_err());
+    assert!(u64::try_from_js(&two_pow_64, &mut context).is_err());
+    assert_eq!(
+        u64::try_from_js(&two_pow_63, &mut context).unwrap(),
+        1 << 63
+    );
+    assert_eq!(
+        i64::try_from_js(&JsValue::new(-9_223_372_036_854_775_808.0), &mut context).unwrap(),
+        i64::MIN
+    );
+    assert_eq!(
+        u64::try_from_js(&JsValue::new(18_446_744_073_709_549_568.0), &mut context).unwrap(),
+        u64::MAX - 2047
+    );
+    assert!(f64::try_from_js(&JsValue::new("1"), &mut context).is_err());
+    assert!(bool::try_from_js(&JsValue::new(1), &mut context).is_err());
+
+    assert_eq!(
+        Option::<String>::try_from_js(&JsValue::null(), &mut context).unwrap(),
+        None
+    );
+    assert_eq!(
+        Option::<String>::try_from_js(&JsValue::new("a"), &mut context).unwrap(),
+        Some("a".to_owned())
+    );
+
+    let value = forward_val(&mut context, "[[1, 'a'], [2, 'b']]").unwrap();
+    assert_eq!(
+        Vec::<(u32, String)>::try_from_js(&value, &mut context).unwrap(),
+        vec![(1, "a".to_owned()), (2, "b".to_owned())]
+    );
+    let value = forward_val(&mut context, "const a = ['a']; a.length = 4294967295; a").unwrap();
+    assert!(Vec::<u32>::try_from_js(&value, &mut context).is_err());
+    assert!(<(u32, String, bool)>::try_from_js(
+        &forward_val(&mut context, "[1, 'a']").unwrap(),
+        &mut context
+    )
+    .is_err());
+
+    let value = forward_val(&mut context, "({ a: 1, b: 2 })").unwrap();
+    let map = HashMap::<String, i32>::try_from_js(&value, &mut context).unwrap();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["b"], 2);
+
+    let value = forward_val(&mut context, "new Map([[1, true]])").unwrap();
+    let map = HashMap::<u8, bool>::try_from_js(&value, &mut context).unwrap();
+    assert_eq!(map.get(&1), Some(&true));
+}
+
+#[test]
+fn into_js_conversions() {
+    use std::collections::HashMap;
+
+    let mut context = Context::new();
+
+    assert!(().into_js(&mut context).is_undefined());
+    assert!(None::<i32>.into_js(&mut context).is_undefined());
+    assert_eq!(u8::MAX.into_js(&mut context), JsValue::new(255));
+
+    let mut map = HashMap::new();
+    map.insert("numbers", vec![Some(1.5), None]);
+    let value = map.into_js(&mut context);
+    context.register_global_property("value", value, Default::default());
+
+    assert_eq!(forward(&mut context, "value.numbers.length"), "2");
+    assert_eq!(forward(&mut context, "value.numbers[0]"), "1.5");
+    assert_eq!(forward(&mut context, "value.numbers[1]"), "undefined");
+}
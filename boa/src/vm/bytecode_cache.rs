@@ -0,0 +1,885 @@
+//! This module implements the binary format of compiled [`CodeBlock`]s, used to cache the
+//! bytecode of scripts and skip parsing and compiling them.
+//!
+//! A bytecode cache starts with a header, followed by the payload:
+//!
+//! | Field           | Encoding                                                      |
+//! |-----------------|---------------------------------------------------------------|
+//! | magic           | the bytes `BOAC`                                              |
+//! | format version  | `u32`                                                         |
+//! | opcode count    | `u8`, the number of opcodes of the instruction set            |
+//! | byte order      | `u8`, `1` for little endian and `2` for big endian            |
+//! | Boa version     | string                                                        |
+//! | checksum        | `u64`, the 64-bit FNV-1a hash of the payload                  |
+//! | payload         | the code block of the script                                  |
+//!
+//! Integers are little endian, and strings are a `u32` byte length followed by UTF-8 bytes. The
+//! operands in the bytecode itself are in the byte order of the platform that compiled them,
+//! so caches are only compatible with platforms of the same byte order.
+//!
+//! A code block is encoded as its name (string), length (`u32`), flags (`u8`), `this` mode
+//! (`u8`), parameters, bytecode, literals, names, inner functions, function bindings, scopes,
+//! tagged templates and source map, where every list is prefixed by its `u32` length. The
+//! bindings of a scope are encoded as their name (string) and kind (`u8`), and the strings of a
+//! tagged template as their raw string followed by a `u8` that is `1` if a cooked string
+//! follows.
+
+use crate::{
+    builtins::function::ThisMode,
+    environment::compile_time_environment::{BindingKind, DeclaredBinding, ScopeLayout},
+    syntax::ast::{
+        node::{FormalParameter, Node},
+        Const, Position,
+    },
+    vm::{opcode::OperandKind, CodeBlock, Opcode, TemplateSite},
+    JsBigInt, JsString, JsValue,
+};
+use gc::Gc;
+use std::{
+    collections::HashSet,
+    convert::{TryFrom, TryInto},
+    error::Error,
+    fmt,
+    mem::size_of,
+};
+
+/// The bytes that every bytecode cache starts with.
+const MAGIC: &[u8; 4] = b"BOAC";
+
+/// The version of the format.
+///
+/// Must be changed whenever the encoding or the meaning of the instructions changes.
+const FORMAT_VERSION: u32 = 3;
+
+/// The number of opcodes, to reject caches compiled with another instruction set.
+const OPCODE_COUNT: u8 = Opcode::Nop as u8 + 1;
+
+/// The byte order of the operands in the bytecode.
+#[cfg(target_endian = "little")]
+const BYTE_ORDER: u8 = 1;
+#[cfg(target_endian = "big")]
+const BYTE_ORDER: u8 = 2;
+
+/// The errors that reject a bytecode cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BytecodeCacheError {
+    /// The bytes are not a bytecode cache.
+    NotACache,
+    /// The cache was created by an incompatible version of Boa.
+    IncompatibleVersion { version: Box<str> },
+    /// The cache was created on a platform with a different byte order.
+    IncompatibleByteOrder,
+    /// The checksum of the payload does not match the one in the header.
+    ChecksumMismatch,
+    /// The payload is not a valid code block.
+    Malformed { message: &'static str },
+}
+
+impl fmt::Display for BytecodeCacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotACache => f.write_str("not a bytecode cache"),
+            Self::IncompatibleVersion { version } => write!(
+                f,
+                "bytecode cache created by Boa {}, which is incompatible with Boa {}",
+                version,
+                env!("CARGO_PKG_VERSION")
+            ),
+            Self::IncompatibleByteOrder => {
+                f.write_str("bytecode cache created on a platform with a different byte order")
+            }
+            Self::ChecksumMismatch => f.write_str("corrupted bytecode cache: checksum mismatch"),
+            Self::Malformed { message } => write!(f, "corrupted bytecode cache: {}", message),
+        }
+    }
+}
+
+impl Error for BytecodeCacheError {}
+
+/// Shorthand for a `Malformed` error.
+fn malformed<T>(message: &'static str) -> Result<T, BytecodeCacheError> {
+    Err(BytecodeCacheError::Malformed { message })
+}
+
+/// Computes the 64-bit FNV-1a hash of the bytes.
+fn checksum(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+impl CodeBlock {
+    /// Serializes this code block into a bytecode cache.
+    ///
+    /// The cache can be loaded back with [`CodeBlock::from_bytes`] by the same version of Boa.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut payload = Vec::new();
+        write_code_block(&mut payload, self);
+
+        let mut bytes = Vec::with_capacity(payload.len() + 32);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.push(OPCODE_COUNT);
+        bytes.push(BYTE_ORDER);
+        write_str(&mut bytes, env!("CARGO_PKG_VERSION"));
+        bytes.extend_from_slice(&checksum(&payload).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+        bytes
+    }
+
+    /// Deserializes a code block from a bytecode cache.
+    ///
+    /// Caches created by another version of Boa or on a platform with a different byte order
+    /// are rejected, as are corrupted caches. The bytecode is checked so that it can not crash
+    /// the VM, for example by popping values that were never pushed on the stack.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BytecodeCacheError> {
+        let mut reader = Reader { bytes, position: 0 };
+
+        if reader.bytes(MAGIC.len()).ok() != Some(MAGIC) {
+            return Err(BytecodeCacheError::NotACache);
+        }
+        let format_version = reader.u32()?;
+        let opcode_count = reader.u8()?;
+        let byte_order = reader.u8()?;
+        let version = reader.str()?;
+        if format_version != FORMAT_VERSION
+            || opcode_count != OPCODE_COUNT
+            || version != env!("CARGO_PKG_VERSION")
+        {
+            return Err(BytecodeCacheError::IncompatibleVersion {
+                version: version.into(),
+            });
+        }
+        if byte_order != BYTE_ORDER {
+            return Err(BytecodeCacheError::IncompatibleByteOrder);
+        }
+        let expected_checksum = reader.u64()?;
+        if checksum(&bytes[reader.position..]) != expected_checksum {
+            return Err(BytecodeCacheError::ChecksumMismatch);
+        }
+
+        let code_block = reader.code_block()?;
+        if reader.position != bytes.len() {
+            return malformed("trailing bytes");
+        }
+        validate_bytecode(&code_block, &[])?;
+        Ok(code_block)
+    }
+}
+
+fn write_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_len(bytes: &mut Vec<u8>, len: usize) {
+    write_u32(
+        bytes,
+        u32::try_from(len).expect("length does not fit in the bytecode cache"),
+    );
+}
+
+fn write_str(bytes: &mut Vec<u8>, value: &str) {
+    write_len(bytes, value.len());
+    bytes.extend_from_slice(value.as_bytes());
+}
+
+/// The flags of a code block.
+const STRICT: u8 = 0b001;
+const CONSTRUCTOR: u8 = 0b010;
+const ARGUMENTS_BINDING: u8 = 0b100;
+
+/// The flags of a parameter.
+const REST_PARAMETER: u8 = 0b01;
+const HAS_INITIALIZER: u8 = 0b10;
+
+/// The kinds of the bindings of a scope.
+const VAR_BINDING: u8 = 0;
+const LET_BINDING: u8 = 1;
+const CONST_BINDING: u8 = 2;
+
+/// The tags of the literals.
+const STRING_LITERAL: u8 = 0;
+const BIGINT_LITERAL: u8 = 1;
+
+fn write_code_block(bytes: &mut Vec<u8>, code: &CodeBlock) {
+    write_str(bytes, &code.name);
+    write_u32(bytes, code.length);
+
+    let mut flags = 0;
+    if code.strict {
+        flags |= STRICT;
+    }
+    if code.constructor {
+        flags |= CONSTRUCTOR;
+    }
+    if code.arguments_binding {
+        flags |= ARGUMENTS_BINDING;
+    }
+    bytes.push(flags);
+    bytes.push(match code.this_mode {
+        ThisMode::Lexical => 0,
+        ThisMode::Strict => 1,
+        ThisMode::Global => 2,
+    });
+
+    write_len(bytes, code.params.len());
+    for param in code.params.iter() {
+        write_str(bytes, param.name());
+        let mut flags = 0;
+        if param.is_rest_param() {
+            flags |= REST_PARAMETER;
+        }
+        if param.init().is_some() {
+            flags |= HAS_INITIALIZER;
+        }
+        bytes.push(flags);
+    }
+
+    write_len(bytes, code.code.len());
+    bytes.extend_from_slice(&code.code);
+
+    write_len(bytes, code.literals.len());
+    for literal in &code.literals {
+        match literal {
+            JsValue::String(string) => {
+                bytes.push(STRING_LITERAL);
+                write_str(bytes, string);
+            }
+            JsValue::BigInt(bigint) => {
+                bytes.push(BIGINT_LITERAL);
+                write_str(bytes, &bigint.to_string_radix(10));
+            }
+            _ => unreachable!("literals are strings or BigInts"),
+        }
+    }
+
+    write_len(bytes, code.variables.len());
+    for name in &code.variables {
+        write_str(bytes, name);
+    }
+
+    write_len(bytes, code.functions.len());
+    for function in &code.functions {
+        write_code_block(bytes, function);
+    }
+
+    write_layout(bytes, &code.function_layout);
+    write_len(bytes, code.scopes.len());
+    for layout in &code.scopes {
+        write_layout(bytes, layout);
+    }
+
+    write_len(bytes, code.templates.len());
+    for template in &code.templates {
+        write_len(bytes, template.raw.len());
+        for (raw, cooked) in template.raw.iter().zip(template.cooked.iter()) {
+            write_str(bytes, raw);
+            match cooked {
+                Some(cooked) => {
+                    bytes.push(1);
+                    write_str(bytes, cooked);
+                }
+                None => bytes.push(0),
+            }
+        }
+    }
+
+    write_len(bytes, code.source_map.len());
+    for (location, position) in &code.source_map {
+        write_u32(bytes, *location);
+        write_u32(bytes, position.line_number());
+        write_u32(bytes, position.column_number());
+    }
+}
+
+fn write_layout(bytes: &mut Vec<u8>, layout: &ScopeLayout) {
+    write_len(bytes, layout.bindings().len());
+    for binding in layout.bindings() {
+        write_str(bytes, &binding.name);
+        bytes.push(match binding.kind {
+            BindingKind::Var => VAR_BINDING,
+            BindingKind::Let => LET_BINDING,
+            BindingKind::Const => CONST_BINDING,
+        });
+    }
+}
+
+/// Reads the values of a bytecode cache.
+#[derive(Debug)]
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], BytecodeCacheError> {
+        let end = self.position.checked_add(len);
+        match end.and_then(|end| self.bytes.get(self.position..end)) {
+            Some(bytes) => {
+                self.position += len;
+                Ok(bytes)
+            }
+            None => malformed("unexpected end of the cache"),
+        }
+    }
+
+    fn u8(&mut self) -> Result<u8, BytecodeCacheError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, BytecodeCacheError> {
+        let bytes = self.bytes(size_of::<u32>())?;
+        Ok(u32::from_le_bytes(bytes.try_into().expect("read 4 bytes")))
+    }
+
+    fn u64(&mut self) -> Result<u64, BytecodeCacheError> {
+        let bytes = self.bytes(size_of::<u64>())?;
+        Ok(u64::from_le_bytes(bytes.try_into().expect("read 8 bytes")))
+    }
+
+    fn len(&mut self) -> Result<usize, BytecodeCacheError> {
+        Ok(self.u32()? as usize)
+    }
+
+    fn str(&mut self) -> Result<&'a str, BytecodeCacheError> {
+        let len = self.len()?;
+        match std::str::from_utf8(self.bytes(len)?) {
+            Ok(string) => Ok(string),
+            Err(_) => malformed("invalid UTF-8 string"),
+        }
+    }
+
+    fn layout(&mut self) -> Result<ScopeLayout, BytecodeCacheError> {
+        let binding_count = self.len()?;
+        let mut bindings = Vec::new();
+        for _ in 0..binding_count {
+            let name = self.str()?.into();
+            let kind = match self.u8()? {
+                VAR_BINDING => BindingKind::Var,
+                LET_BINDING => BindingKind::Let,
+                CONST_BINDING => BindingKind::Const,
+                _ => return malformed("invalid binding kind"),
+            };
+            bindings.push(DeclaredBinding { name, kind });
+        }
+        let layout = ScopeLayout::new(bindings);
+        if layout.bindings().len() != binding_count {
+            return malformed("duplicate binding");
+        }
+        Ok(layout)
+    }
+
+    fn code_block(&mut self) -> Result<CodeBlock, BytecodeCacheError> {
+        let name = JsString::new(self.str()?);
+        let length = self.u32()?;
+        let flags = self.u8()?;
+        let mut code = CodeBlock::new(name, length, flags & STRICT != 0, flags & CONSTRUCTOR != 0);
+        code.arguments_binding = flags & ARGUMENTS_BINDING != 0;
+        code.this_mode = match self.u8()? {
+            0 => ThisMode::Lexical,
+            1 => ThisMode::Strict,
+            2 => ThisMode::Global,
+            _ => return malformed("invalid this mode"),
+        };
+
+        let param_count = self.len()?;
+        let mut params = Vec::new();
+        for _ in 0..param_count {
+            let name = self.str()?;
+            let flags = self.u8()?;
+            // The initializers are compiled into the bytecode, only their presence is kept.
+            let init = (flags & HAS_INITIALIZER != 0).then(|| Node::Const(Const::Undefined));
+            params.push(FormalParameter::new(
+                name,
+                init,
+                flags & REST_PARAMETER != 0,
+            ));
+        }
+        code.params = params.into_boxed_slice();
+
+        let code_len = self.len()?;
+        code.code = self.bytes(code_len)?.to_vec();
+
+        let literal_count = self.len()?;
+        for _ in 0..literal_count {
+            let tag = self.u8()?;
+            let literal = self.str()?;
+            code.literals.push(match tag {
+                STRING_LITERAL => JsValue::new(literal),
+                BIGINT_LITERAL => match JsBigInt::from_string_radix(literal, 10) {
+                    Some(bigint) => JsValue::new(bigint),
+                    None => return malformed("invalid BigInt literal"),
+                },
+                _ => return malformed("invalid literal"),
+            });
+        }
+
+        let name_count = self.len()?;
+        for _ in 0..name_count {
+            code.variables.push(JsString::new(self.str()?));
+        }
+
+        let function_count = self.len()?;
+        for _ in 0..function_count {
+            code.functions.push(Gc::new(self.code_block()?));
+        }
+
+        code.function_layout = Gc::new(self.layout()?);
+        let scope_count = self.len()?;
+        for _ in 0..scope_count {
+            code.scopes.push(Gc::new(self.layout()?));
+        }
+
+        let template_count = self.len()?;
+        for _ in 0..template_count {
+            let string_count = self.len()?;
+            let mut raw = Vec::new();
+            let mut cooked = Vec::new();
+            for _ in 0..string_count {
+                raw.push(JsString::new(self.str()?));
+                cooked.push(match self.u8()? {
+                    0 => None,
+                    1 => Some(JsString::new(self.str()?)),
+                    _ => return malformed("invalid template string"),
+                });
+            }
+            code.templates
+                .push(TemplateSite::new(cooked.into(), raw.into()));
+        }
+
+        let source_map_len = self.len()?;
+        for _ in 0..source_map_len {
+            let location = self.u32()?;
+            let line = self.u32()?;
+            let column = self.u32()?;
+            if line == 0 || column == 0 {
+                return malformed("invalid source position");
+            }
+            code.source_map
+                .push((location, Position::new(line, column)));
+        }
+
+        Ok(code)
+    }
+}
+
+/// The greatest number of states that the validation explores per instruction, which bounds
+/// the validation of code whose stack grows in a loop.
+const MAX_STATES_PER_INSTRUCTION: usize = 64;
+
+/// The state of a call frame at an instruction, on one of the paths through the code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FrameState {
+    /// The number of values that the code pushed on the stack.
+    stack: usize,
+    /// The number of bindings of the environments that the code pushed, innermost last.
+    environments: Vec<usize>,
+    /// The exception handlers installed by `TryStart`.
+    handlers: Vec<HandlerState>,
+    /// The completions of the `finally` blocks that are running.
+    completions: Vec<CompletionState>,
+}
+
+/// An exception handler installed by `TryStart`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct HandlerState {
+    catch: Option<u32>,
+    finally: Option<u32>,
+    stack: usize,
+    environments: usize,
+    completions: usize,
+}
+
+/// How a `finally` block continues once it ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum CompletionState {
+    Normal,
+    Jump(u32),
+    Throw,
+}
+
+impl FrameState {
+    /// Pops `count` values off the stack.
+    fn pop(&mut self, count: u32) -> Result<(), BytecodeCacheError> {
+        match self.stack.checked_sub(count as usize) {
+            Some(stack) => {
+                self.stack = stack;
+                Ok(())
+            }
+            None => malformed("stack underflow"),
+        }
+    }
+
+    /// Pops `pops` values off the stack, then pushes `pushes` values.
+    fn apply(&mut self, pops: u32, pushes: usize) -> Result<(), BytecodeCacheError> {
+        self.pop(pops)?;
+        self.stack += pushes;
+        Ok(())
+    }
+
+    /// Returns the address and the state at which an exception thrown in this state is
+    /// handled, or `None` if it leaves the call frame.
+    ///
+    /// This mirrors the exception handling of the VM.
+    fn throw(&self) -> Option<(u32, Self)> {
+        let handler = *self.handlers.last()?;
+        let mut state = self.clone();
+        if handler.catch.is_some() && handler.finally.is_some() {
+            if let Some(last) = state.handlers.last_mut() {
+                last.catch = None;
+            }
+        } else {
+            let _ = state.handlers.pop();
+        }
+        state.completions.truncate(handler.completions);
+        state.stack = state.stack.min(handler.stack);
+        state.environments.truncate(handler.environments);
+
+        if let Some(catch) = handler.catch {
+            state.stack += 1;
+            Some((catch, state))
+        } else {
+            state.completions.push(CompletionState::Throw);
+            handler.finally.map(|finally| (finally, state))
+        }
+    }
+}
+
+/// Reads the operands of the instruction at `pc`, the `u32` operands are returned in order and
+/// the others as `0`.
+///
+/// Returns the opcode, the operands and the address of the next instruction.
+fn decode(code: &CodeBlock, pc: usize) -> (Opcode, [u32; 2], usize) {
+    let opcode = Opcode::try_from(code.code[pc]).expect("the opcode was validated");
+    let mut operands = [0; 2];
+    let mut next = pc + size_of::<Opcode>();
+    for (operand, kind) in operands.iter_mut().zip(opcode.operand_kinds()) {
+        if kind.size() == size_of::<u32>() {
+            *operand = code.read::<u32>(next);
+        }
+        next += kind.size();
+    }
+    (opcode, operands, next)
+}
+
+/// Checks that the bytecode of `code` and of the functions defined in it only has valid
+/// instructions, that their operands are in bounds, and that jumps land on instructions.
+///
+/// The paths through the code are then followed, to check that the instructions never pop more
+/// values, environments or `finally` completions than were pushed, and that bindings are
+/// accessed in the environment at their depth on every path. `outer` has the number of bindings
+/// of the declarative environments that enclose the code, innermost last.
+fn validate_bytecode(code: &CodeBlock, outer: &[usize]) -> Result<(), BytecodeCacheError> {
+    let mut instruction_starts = vec![false; code.code.len() + 1];
+    let mut instruction_count = 0;
+    let mut jump_targets = Vec::new();
+    let mut pc = 0;
+    while pc < code.code.len() {
+        instruction_starts[pc] = true;
+        instruction_count += 1;
+        let opcode = match Opcode::try_from(code.code[pc]) {
+            Ok(opcode) => opcode,
+            Err(_) => return malformed("invalid opcode"),
+        };
+        pc += size_of::<Opcode>();
+
+        for operand in opcode.operand_kinds() {
+            let size = operand.size();
+            if pc + size > code.code.len() {
+                return malformed("truncated instruction");
+            }
+            let value = if size == size_of::<u32>() {
+                code.read::<u32>(pc)
+            } else {
+                0
+            };
+            let in_bounds = match operand {
+                OperandKind::Literal => (value as usize) < code.literals.len(),
+                OperandKind::Name => (value as usize) < code.variables.len(),
+                OperandKind::Function => (value as usize) < code.functions.len(),
+                OperandKind::Scope => (value as usize) < code.scopes.len(),
+                OperandKind::Template => (value as usize) < code.templates.len(),
+                OperandKind::Address => {
+                    jump_targets.push(value);
+                    true
+                }
+                OperandKind::OptionalAddress => {
+                    if value != u32::MAX {
+                        jump_targets.push(value);
+                    }
+                    true
+                }
+                // Bindings and counts depend on the path to the instruction.
+                OperandKind::Depth
+                | OperandKind::Slot
+                | OperandKind::I8
+                | OperandKind::I16
+                | OperandKind::I32
+                | OperandKind::F64
+                | OperandKind::Count => true,
+            };
+            if !in_bounds {
+                return malformed("operand out of bounds");
+            }
+            pc += size;
+        }
+    }
+
+    // A jump may also target the end of the code.
+    instruction_starts[code.code.len()] = true;
+    if jump_targets.into_iter().any(|target| {
+        !instruction_starts
+            .get(target as usize)
+            .copied()
+            .unwrap_or(false)
+    }) {
+        return malformed("jump target is not an instruction");
+    }
+
+    let initial = FrameState {
+        stack: 0,
+        environments: Vec::new(),
+        handlers: Vec::new(),
+        completions: Vec::new(),
+    };
+    let mut visited = HashSet::new();
+    let mut pending = vec![(0, initial)];
+    let mut function_environments = HashSet::new();
+    let max_states = MAX_STATES_PER_INSTRUCTION * (instruction_count + 1);
+
+    while let Some((pc, state)) = pending.pop() {
+        // The end of the code returns the completion value.
+        if pc as usize == code.code.len() || !visited.insert((pc, state.clone())) {
+            continue;
+        }
+        if visited.len() > max_states {
+            return malformed("too many paths through the code");
+        }
+
+        let (opcode, [first, second], next) = decode(code, pc as usize);
+        let mut state = state;
+
+        // Most instructions can throw, in which case the exception handler continues.
+        if let Some(handled) = state.throw() {
+            pending.push(handled);
+        }
+
+        match opcode {
+            Opcode::Jump => {
+                pending.push((first, state));
+                continue;
+            }
+            Opcode::JumpIfFalse | Opcode::JumpIfTrue => {
+                state.pop(1)?;
+                pending.push((first, state.clone()));
+            }
+            Opcode::JumpIfNotLessThan => {
+                state.pop(2)?;
+                pending.push((first, state.clone()));
+            }
+            Opcode::LogicalAnd
+            | Opcode::LogicalOr
+            | Opcode::Coalesce
+            | Opcode::JumpIfNotUndefined => {
+                state.pop(1)?;
+                let mut jump = state.clone();
+                jump.stack += 1;
+                pending.push((first, jump));
+            }
+            Opcode::Case => {
+                state.pop(2)?;
+                pending.push((first, state.clone()));
+                state.stack += 1;
+            }
+            Opcode::Default => {
+                state.pop(1)?;
+                pending.push((first, state));
+                continue;
+            }
+            Opcode::IteratorStep => {
+                state.pop(3)?;
+                state.stack += 3;
+                pending.push((first, state.clone()));
+                state.stack += 1;
+            }
+            Opcode::Throw => {
+                state.pop(1)?;
+                if let Some(handled) = state.throw() {
+                    pending.push(handled);
+                }
+                continue;
+            }
+            Opcode::IteratorCloseWithException => {
+                state.pop(4)?;
+                if let Some(handled) = state.throw() {
+                    pending.push(handled);
+                }
+                continue;
+            }
+            Opcode::Return => {
+                state.pop(1)?;
+                continue;
+            }
+            Opcode::ReturnUndefined => continue,
+            Opcode::TryStart => {
+                if first == u32::MAX && second == u32::MAX {
+                    return malformed("exception handler without a target");
+                }
+                state.handlers.push(HandlerState {
+                    catch: (first != u32::MAX).then(|| first),
+                    finally: (second != u32::MAX).then(|| second),
+                    stack: state.stack,
+                    environments: state.environments.len(),
+                    completions: state.completions.len(),
+                });
+            }
+            Opcode::TryEnd => {
+                let _ = state.handlers.pop();
+            }
+            Opcode::FinallyStart => state.completions.push(CompletionState::Normal),
+            Opcode::FinallySetJump => state.completions.push(CompletionState::Jump(first)),
+            Opcode::FinallyDiscard => {
+                let _ = state.completions.pop();
+            }
+            Opcode::FinallyEnd => match state.completions.pop() {
+                Some(CompletionState::Normal) => {}
+                Some(CompletionState::Jump(address)) => {
+                    pending.push((address, state));
+                    continue;
+                }
+                Some(CompletionState::Throw) => {
+                    if let Some(handled) = state.throw() {
+                        pending.push(handled);
+                    }
+                    continue;
+                }
+                None => return malformed("finally block without a completion"),
+            },
+            Opcode::PushDeclarativeEnvironment => state
+                .environments
+                .push(code.scopes[first as usize].bindings().len()),
+            Opcode::PopEnvironment => {
+                if state.environments.pop().is_none() {
+                    return malformed("environment stack underflow");
+                }
+            }
+            Opcode::GetBinding | Opcode::SetBinding | Opcode::InitBinding => {
+                let bindings = state
+                    .environments
+                    .iter()
+                    .rev()
+                    .chain(outer.iter().rev())
+                    .nth(first as usize);
+                if !matches!(bindings, Some(&bindings) if (second as usize) < bindings) {
+                    return malformed("binding out of bounds");
+                }
+                if opcode == Opcode::GetBinding {
+                    state.stack += 1;
+                } else {
+                    state.pop(1)?;
+                }
+            }
+            Opcode::GetFunction => {
+                let mut environments = outer.to_vec();
+                environments.extend_from_slice(&state.environments);
+                function_environments.insert((first, environments));
+                state.stack += 1;
+            }
+            Opcode::RotateDown => {
+                // The value is inserted below the `count` values under it.
+                state.pop(first.saturating_add(1))?;
+                state.stack += first as usize + 1;
+            }
+            Opcode::Nop | Opcode::DefVar | Opcode::DefLet | Opcode::DefConst => {}
+            Opcode::PushUndefined
+            | Opcode::PushNull
+            | Opcode::PushTrue
+            | Opcode::PushFalse
+            | Opcode::PushZero
+            | Opcode::PushOne
+            | Opcode::PushInt8
+            | Opcode::PushInt16
+            | Opcode::PushInt32
+            | Opcode::PushRational
+            | Opcode::PushNaN
+            | Opcode::PushPositiveInfinity
+            | Opcode::PushNegativeInfinity
+            | Opcode::PushLiteral
+            | Opcode::PushEmptyObject
+            | Opcode::GetName
+            | Opcode::DeleteName
+            | Opcode::This
+            | Opcode::GetTemplateObject => state.stack += 1,
+            Opcode::Pop | Opcode::InitLexical | Opcode::SetName | Opcode::SetCompletionValue => {
+                state.pop(1)?
+            }
+            Opcode::Dup => state.apply(1, 2)?,
+            Opcode::Swap => state.apply(2, 2)?,
+            Opcode::Void
+            | Opcode::TypeOf
+            | Opcode::Pos
+            | Opcode::Neg
+            | Opcode::Inc
+            | Opcode::Dec
+            | Opcode::LogicalNot
+            | Opcode::BitNot
+            | Opcode::ToBoolean
+            | Opcode::ValueNotNullOrUndefined
+            | Opcode::GetPropertyByName
+            | Opcode::DeletePropertyByName => state.apply(1, 1)?,
+            Opcode::Add
+            | Opcode::Sub
+            | Opcode::Mul
+            | Opcode::Div
+            | Opcode::Pow
+            | Opcode::Mod
+            | Opcode::BitAnd
+            | Opcode::BitOr
+            | Opcode::BitXor
+            | Opcode::ShiftLeft
+            | Opcode::ShiftRight
+            | Opcode::UnsignedShiftRight
+            | Opcode::Eq
+            | Opcode::NotEq
+            | Opcode::StrictEq
+            | Opcode::StrictNotEq
+            | Opcode::GreaterThan
+            | Opcode::GreaterThanOrEq
+            | Opcode::LessThan
+            | Opcode::LessThanOrEq
+            | Opcode::In
+            | Opcode::InstanceOf
+            | Opcode::GetPropertyByValue
+            | Opcode::DeletePropertyByValue
+            | Opcode::PushValueToArray
+            | Opcode::NewSpread => state.apply(2, 1)?,
+            Opcode::SetPropertyByName
+            | Opcode::SetPropertyGetterByName
+            | Opcode::SetPropertySetterByName => state.pop(2)?,
+            Opcode::SetPropertyByValue
+            | Opcode::SetPropertyGetterByValue
+            | Opcode::SetPropertySetterByValue
+            | Opcode::IteratorClose => state.pop(3)?,
+            Opcode::CallSpread => state.apply(3, 1)?,
+            Opcode::PushIteratorToArray => state.apply(4, 1)?,
+            Opcode::GetIterator | Opcode::ForInLoopInitIterator => state.apply(1, 3)?,
+            Opcode::IteratorNext | Opcode::IteratorToArray => state.apply(3, 4)?,
+            Opcode::PushNewArray | Opcode::ConcatToString => state.apply(first, 1)?,
+            Opcode::Call => state.apply(first.saturating_add(2), 1)?,
+            Opcode::New => state.apply(first.saturating_add(1), 1)?,
+            Opcode::CopyDataProperties => state.apply(first.saturating_add(2), 1)?,
+        }
+
+        pending.push((next as u32, state));
+    }
+
+    for (index, mut environments) in function_environments {
+        let function = &code.functions[index as usize];
+        environments.push(function.function_layout.bindings().len());
+        validate_bytecode(function, &environments)?;
+    }
+    Ok(())
+}
@@ -2,7 +2,7 @@
 //! This module will provides everything needed to implement the CallFrame
 
 use super::CodeBlock;
-use crate::{environment::lexical_environment::Environment, JsValue};
+use crate::JsValue;
 use gc::Gc;
 
 #[derive(Debug)]
@@ -13,5 +13,73 @@ pub struct CallFrame {
     pub(crate) fp: usize,
     pub(crate) exit_on_return: bool,
     pub(crate) this: JsValue,
-    pub(crate) environment: Environment,
+
+    /// The value of the last expression statement, returned when the end of the code is reached.
+    pub(crate) completion_value: JsValue,
+
+    /// The depth of the environment stack when the frame was entered.
+    pub(crate) env_depth: usize,
+
+    /// The exception handlers of the `try` statements that are currently executing.
+    pub(crate) handlers: Vec<TryHandler>,
+
+    /// How the `finally` blocks that are currently executing were entered.
+    pub(crate) finally_completions: Vec<FinallyCompletion>,
+}
+
+impl CallFrame {
+    /// Creates a new call frame that starts executing `code` at its first instruction.
+    pub(crate) fn new(
+        code: Gc<CodeBlock>,
+        this: JsValue,
+        fp: usize,
+        exit_on_return: bool,
+        env_depth: usize,
+    ) -> Self {
+        Self {
+            prev: None,
+            code,
+            pc: 0,
+            fp,
+            exit_on_return,
+            this,
+            completion_value: JsValue::undefined(),
+            env_depth,
+            handlers: Vec::new(),
+            finally_completions: Vec::new(),
+        }
+    }
+}
+
+/// An exception handler, pushed by the `TryStart` opcode.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TryHandler {
+    /// The address of the `catch` block, if it has not been entered yet.
+    pub(crate) catch: Option<u32>,
+
+    /// The address of the `finally` block, if there is one.
+    pub(crate) finally: Option<u32>,
+
+    /// The length of the value stack when the `try` statement was entered.
+    pub(crate) stack_len: usize,
+
+    /// The depth of the environment stack when the `try` statement was entered.
+    pub(crate) env_depth: usize,
+
+    /// The number of `finally` blocks that were executing when the `try` statement was entered.
+    pub(crate) finally_depth: usize,
+}
+
+/// The way a `finally` block was entered, which decides what happens once it completes.
+#[derive(Debug, Clone)]
+pub(crate) enum FinallyCompletion {
+    /// The `try` or `catch` block completed normally, execution continues after the `finally` block.
+    Normal,
+
+    /// A `break`, `continue` or `return` left the `try` or `catch` block, execution continues at
+    /// the given address.
+    Jump(u32),
+
+    /// An exception was thrown, it is rethrown after the `finally` block.
+    Throw(JsValue),
 }
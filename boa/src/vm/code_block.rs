@@ -1,22 +1,29 @@
 use crate::{
-    builtins::function::{
-        Captures, ClosureFunctionSignature, Function, NativeFunctionSignature, ThisMode,
+    builtins::{
+        function::{arguments::Arguments, Function, ThisMode},
+        Array,
     },
     context::StandardObjects,
     environment::{
+        compile_time_environment::ScopeLayout,
         function_environment_record::{BindingStatus, FunctionEnvironmentRecord},
         lexical_environment::Environment,
     },
     gc::{Finalize, Trace},
-    object::{internal_methods::get_prototype_from_constructor, JsObject, ObjectData},
+    object::{
+        internal_methods::get_prototype_from_constructor, IntegrityLevel, JsObject, ObjectData,
+    },
     property::PropertyDescriptor,
-    syntax::ast::node::FormalParameter,
-    vm::Opcode,
+    syntax::ast::{
+        node::{FormalParameter, RcStatementList},
+        Position,
+    },
+    vm::{opcode::OperandKind, Opcode, RegisterCodeBlock},
     Context, JsResult, JsString, JsValue,
 };
-use gc::Gc;
+use gc::{Gc, GcCell};
 
-use std::{convert::TryInto, fmt::Write, mem::size_of};
+use std::{collections::BTreeMap, convert::TryInto, fmt::Write, mem::size_of};
 
 use super::CallFrame;
 
@@ -34,7 +41,7 @@ unsafe impl Readable for i64 {}
 unsafe impl Readable for f32 {}
 unsafe impl Readable for f64 {}
 
-#[derive(Debug, Trace, Finalize)]
+#[derive(Debug, Clone, Trace, Finalize)]
 pub struct CodeBlock {
     /// Name of this function
     pub(crate) name: JsString,
@@ -53,6 +60,13 @@ pub struct CodeBlock {
 
     pub(crate) params: Box<[FormalParameter]>,
 
+    /// The body of a function that uses syntax the compiler does not support yet, in which case
+    /// the function is run by the interpreter instead of the bytecode.
+    pub(crate) interpreted_body: Option<RcStatementList>,
+
+    /// Whether an `arguments` object is created when the function is called.
+    pub(crate) arguments_binding: bool,
+
     /// Bytecode
     pub(crate) code: Vec<u8>,
 
@@ -64,6 +78,98 @@ pub struct CodeBlock {
 
     // Functions inside this function
     pub(crate) functions: Vec<Gc<CodeBlock>>,
+
+    /// The bindings declared in the function environment when the function is called.
+    pub(crate) function_layout: Gc<ScopeLayout>,
+
+    /// The bindings declared in the environments pushed by `PushDeclarativeEnvironment`.
+    pub(crate) scopes: Vec<Gc<ScopeLayout>>,
+
+    /// The start positions of the statements in the source code, by the location of their
+    /// first instruction.
+    #[unsafe_ignore_trace]
+    pub(crate) source_map: Vec<(u32, Position)>,
+
+    /// The code for the experimental register machine, if the code block could be compiled
+    /// to it. It is executed instead of the bytecode.
+    pub(crate) register_code: Option<Gc<RegisterCodeBlock>>,
+
+    /// The tagged templates of this code block.
+    pub(crate) templates: Vec<TemplateSite>,
+}
+
+/// A tagged template of a code block, and its template object once it is created.
+///
+/// Every evaluation of the tagged template passes the same template object to the tag.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-gettemplateobject
+#[derive(Debug, Clone, Trace, Finalize)]
+pub(crate) struct TemplateSite {
+    /// The cooked strings, `None` for invalid escape sequences.
+    pub(crate) cooked: Box<[Option<JsString>]>,
+
+    /// The raw strings.
+    pub(crate) raw: Box<[JsString]>,
+
+    /// The cached template object.
+    pub(crate) object: GcCell<Option<JsObject>>,
+}
+
+impl TemplateSite {
+    /// Creates a tagged template whose template object is not created yet.
+    pub(crate) fn new(cooked: Box<[Option<JsString>]>, raw: Box<[JsString]>) -> Self {
+        Self {
+            cooked,
+            raw,
+            object: GcCell::new(None),
+        }
+    }
+
+    /// Abstract operation `GetTemplateObject ( templateLiteral )`
+    ///
+    /// Returns the frozen template object, creating it on the first evaluation.
+    pub(crate) fn template_object(&self, context: &mut Context) -> JsResult<JsObject> {
+        // 3-5. If the realm's [[TemplateMap]] has an entry for this site, return it.
+        if let Some(object) = &*self.object.borrow() {
+            return Ok(object.clone());
+        }
+
+        // 6-11. Let template be ! ArrayCreate(count), and rawObj be ! ArrayCreate(count).
+        let template = Array::create_array_from_list(
+            self.cooked
+                .iter()
+                .map(|cooked| cooked.clone().map_or_else(JsValue::undefined, JsValue::new)),
+            context,
+        );
+        let raw =
+            Array::create_array_from_list(self.raw.iter().cloned().map(JsValue::new), context);
+
+        // 12. Perform ! SetIntegrityLevel(rawObj, frozen).
+        raw.set_integrity_level(IntegrityLevel::Frozen, context)?;
+
+        // 13. Perform ! DefinePropertyOrThrow(template, "raw", PropertyDescriptor { [[Value]]: rawObj, [[Writable]]: false, [[Enumerable]]: false, [[Configurable]]: false }).
+        template.define_property_or_throw(
+            "raw",
+            PropertyDescriptor::builder()
+                .value(raw)
+                .writable(false)
+                .enumerable(false)
+                .configurable(false),
+            context,
+        )?;
+
+        // 14. Perform ! SetIntegrityLevel(template, frozen).
+        template.set_integrity_level(IntegrityLevel::Frozen, context)?;
+
+        // 15. Append the Record { [[Site]]: templateLiteral, [[Array]]: template } to templateRegistry.
+        *self.object.borrow_mut() = Some(template.clone());
+
+        // 16. Return template.
+        Ok(template)
+    }
 }
 
 impl CodeBlock {
@@ -73,12 +179,19 @@ impl CodeBlock {
             literals: Vec::new(),
             variables: Vec::new(),
             functions: Vec::new(),
+            function_layout: Gc::new(ScopeLayout::default()),
+            scopes: Vec::new(),
+            source_map: Vec::new(),
+            register_code: None,
+            templates: Vec::new(),
             name,
             length,
             strict,
             constructor,
             this_mode: ThisMode::Global,
             params: Vec::new().into_boxed_slice(),
+            interpreted_body: None,
+            arguments_binding: false,
         }
     }
 
@@ -103,6 +216,188 @@ impl CodeBlock {
         unsafe { self.read_unchecked(offset) }
     }
 
+    /// Gets the start position in the source code of the statement that the instruction at
+    /// `location` belongs to, if it is known.
+    pub fn source_position(&self, location: u32) -> Option<Position> {
+        let index = match self
+            .source_map
+            .binary_search_by_key(&location, |(start, _)| *start)
+        {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(index) => index - 1,
+        };
+        Some(self.source_map[index].1)
+    }
+
+    /// Returns `true` if this code block or one of the functions defined in it is run by the
+    /// interpreter.
+    pub(crate) fn uses_interpreter(&self) -> bool {
+        self.interpreted_body.is_some()
+            || self
+                .functions
+                .iter()
+                .any(|function| function.uses_interpreter())
+    }
+
+    /// Disassembles the bytecode of this code block and of the functions defined in it.
+    ///
+    /// Jump targets are given labels, the operands that index into the literals, names and
+    /// functions are resolved, and the first instruction of each statement is annotated with
+    /// the line and column of the statement in the source code.
+    pub fn disassemble(&self) -> String {
+        let mut output = String::new();
+        self.disassemble_into(&mut output, &format!("'{}'", self.name))
+            .expect("writing to a string cannot fail");
+        output
+    }
+
+    fn disassemble_into(&self, f: &mut String, path: &str) -> std::fmt::Result {
+        let labels: BTreeMap<u32, usize> = self
+            .jump_targets()
+            .into_iter()
+            .enumerate()
+            .map(|(label, address)| (address, label))
+            .collect();
+        let label = |address: u32| {
+            if address == u32::MAX {
+                "none".to_string()
+            } else {
+                format!("L{} ({:06})", labels[&address], address)
+            }
+        };
+
+        writeln!(
+            f,
+            "----------------- {} (length: {}, strict: {}) ------------------",
+            path, self.length, self.strict
+        )?;
+
+        writeln!(f, "    Location  Source      Opcode              Operands")?;
+        let mut pc = 0;
+        let mut source_map = self.source_map.iter().peekable();
+        while pc < self.code.len() {
+            let location = pc as u32;
+            if let Some(label) = labels.get(&location) {
+                writeln!(f, "  L{}:", label)?;
+            }
+            let source = match source_map.peek() {
+                Some((start, position)) if *start == location => {
+                    source_map.next();
+                    position.to_string()
+                }
+                _ => String::new(),
+            };
+
+            let opcode: Opcode = self.code[pc].try_into().unwrap();
+            write!(f, "    {:06}    {:<10}  ", pc, source)?;
+            let operands = match opcode {
+                Opcode::TryStart => {
+                    let catch = self.read::<u32>(pc + size_of::<Opcode>());
+                    let finally = self.read::<u32>(pc + size_of::<Opcode>() + size_of::<u32>());
+                    pc += size_of::<Opcode>() + 2 * size_of::<u32>();
+                    format!("catch: {}, finally: {}", label(catch), label(finally))
+                }
+                _ if opcode.operand_kinds() == [OperandKind::Address] => {
+                    let address = self.read::<u32>(pc + size_of::<Opcode>());
+                    pc += size_of::<Opcode>() + size_of::<u32>();
+                    label(address)
+                }
+                _ => self.instruction_operands(&mut pc),
+            };
+            if operands.is_empty() {
+                writeln!(f, "{}", opcode.as_str())?;
+            } else {
+                writeln!(f, "{:<19} {}", opcode.as_str(), operands)?;
+            }
+        }
+        // A jump can target the end of the code.
+        if let Some(label) = labels.get(&(self.code.len() as u32)) {
+            writeln!(f, "  L{}:", label)?;
+        }
+
+        f.write_char('\n')?;
+        self.write_tables(f)?;
+
+        for (i, function) in self.functions.iter().enumerate() {
+            f.write_char('\n')?;
+            function.disassemble_into(f, &format!("{} > {:04}: '{}'", path, i, function.name))?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the sorted addresses that are the target of a jump.
+    fn jump_targets(&self) -> Vec<u32> {
+        let mut targets = Vec::new();
+        let mut pc = 0;
+        while pc < self.code.len() {
+            let opcode: Opcode = self.code[pc].try_into().unwrap();
+            pc += size_of::<Opcode>();
+            for kind in opcode.operand_kinds() {
+                if kind.is_address() {
+                    targets.push(self.read::<u32>(pc));
+                }
+                pc += kind.size();
+            }
+        }
+        targets.retain(|address| *address != u32::MAX);
+        targets.sort_unstable();
+        targets.dedup();
+        targets
+    }
+
+    /// Writes the tables of literals, names, bindings and functions of this code block.
+    fn write_tables<W: Write>(&self, f: &mut W) -> std::fmt::Result {
+        f.write_str("Literals:\n")?;
+        if !self.literals.is_empty() {
+            for (i, value) in self.literals.iter().enumerate() {
+                writeln!(f, "    {:04}: <{}> {}", i, value.type_of(), value.display())?;
+            }
+        } else {
+            writeln!(f, "    <empty>")?;
+        }
+
+        f.write_char('\n')?;
+
+        f.write_str("Names:\n")?;
+        if !self.variables.is_empty() {
+            for (i, value) in self.variables.iter().enumerate() {
+                writeln!(f, "    {:04}: {}", i, value)?;
+            }
+        } else {
+            writeln!(f, "    <empty>")?;
+        }
+
+        f.write_char('\n')?;
+
+        f.write_str("Bindings:\n")?;
+        if !self.function_layout.is_empty() {
+            for (i, binding) in self.function_layout.bindings().iter().enumerate() {
+                writeln!(f, "    {:04}: {} ({:?})", i, binding.name, binding.kind)?;
+            }
+        } else {
+            writeln!(f, "    <empty>")?;
+        }
+
+        f.write_char('\n')?;
+
+        f.write_str("Functions:\n")?;
+        if !self.functions.is_empty() {
+            for (i, code) in self.functions.iter().enumerate() {
+                writeln!(
+                    f,
+                    "    {:04}: name: '{}' (length: {})",
+                    i, code.name, code.length
+                )?;
+            }
+        } else {
+            writeln!(f, "    <empty>")?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn instruction_operands(&self, pc: &mut usize) -> String {
         let opcode: Opcode = self.code[*pc].try_into().unwrap();
         *pc += size_of::<Opcode>();
@@ -127,8 +422,13 @@ impl CodeBlock {
                 *pc += size_of::<f64>();
                 ryu_js::Buffer::new().format(operand).to_string()
             }
-            Opcode::PushLiteral
-            | Opcode::PushNewArray
+            Opcode::PushLiteral => {
+                let operand = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let value = &self.literals[operand as usize];
+                format!("{:04}: <{}> {}", operand, value.type_of(), value.display())
+            }
+            Opcode::PushNewArray
             | Opcode::Jump
             | Opcode::JumpIfFalse
             | Opcode::JumpIfTrue
@@ -137,11 +437,52 @@ impl CodeBlock {
             | Opcode::LogicalAnd
             | Opcode::LogicalOr
             | Opcode::Coalesce
+            | Opcode::FinallySetJump
+            | Opcode::JumpIfNotUndefined
+            | Opcode::CopyDataProperties
+            | Opcode::IteratorStep
+            | Opcode::JumpIfNotLessThan
+            | Opcode::RotateDown
+            | Opcode::ConcatToString
             | Opcode::Call => {
                 let result = self.read::<u32>(*pc).to_string();
                 *pc += size_of::<u32>();
                 result
             }
+            Opcode::New => {
+                let argc = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let operand = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                format!(
+                    "{}, {:04}: '{}'",
+                    argc, operand, self.variables[operand as usize]
+                )
+            }
+            Opcode::TryStart => {
+                let catch = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let finally = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let address = |address: u32| {
+                    if address == u32::MAX {
+                        "none".to_string()
+                    } else {
+                        address.to_string()
+                    }
+                };
+                format!("catch: {}, finally: {}", address(catch), address(finally))
+            }
+            Opcode::GetTemplateObject => {
+                let operand = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let raw: Vec<_> = self.templates[operand as usize]
+                    .raw
+                    .iter()
+                    .map(|raw| format!("{:?}", raw.as_str()))
+                    .collect();
+                format!("{:04}: [{}]", operand, raw.join(", "))
+            }
             Opcode::GetFunction => {
                 let operand = self.read::<u32>(*pc);
                 *pc += size_of::<u32>();
@@ -152,6 +493,23 @@ impl CodeBlock {
                     self.functions[operand as usize].length
                 )
             }
+            Opcode::GetBinding | Opcode::SetBinding | Opcode::InitBinding => {
+                let depth = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let slot = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                format!("depth: {}, slot: {}", depth, slot)
+            }
+            Opcode::PushDeclarativeEnvironment => {
+                let operand = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let names: Vec<_> = self.scopes[operand as usize]
+                    .bindings()
+                    .iter()
+                    .map(|binding| binding.name.as_ref())
+                    .collect();
+                format!("{:04}: [{}]", operand, names.join(", "))
+            }
             Opcode::DefVar
             | Opcode::DefLet
             | Opcode::DefConst
@@ -162,7 +520,9 @@ impl CodeBlock {
             | Opcode::SetPropertyByName
             | Opcode::SetPropertyGetterByName
             | Opcode::SetPropertySetterByName
-            | Opcode::DeletePropertyByName => {
+            | Opcode::DeletePropertyByName
+            | Opcode::DeleteName
+            | Opcode::NewSpread => {
                 let operand = self.read::<u32>(*pc);
                 *pc += size_of::<u32>();
                 format!("{:04}: '{}'", operand, self.variables[operand as usize])
@@ -219,6 +579,23 @@ impl CodeBlock {
             | Opcode::Throw
             | Opcode::This
             | Opcode::Return
+            | Opcode::PopEnvironment
+            | Opcode::TryEnd
+            | Opcode::FinallyStart
+            | Opcode::FinallyEnd
+            | Opcode::FinallyDiscard
+            | Opcode::ValueNotNullOrUndefined
+            | Opcode::GetIterator
+            | Opcode::ForInLoopInitIterator
+            | Opcode::IteratorNext
+            | Opcode::IteratorToArray
+            | Opcode::IteratorClose
+            | Opcode::IteratorCloseWithException
+            | Opcode::PushValueToArray
+            | Opcode::PushIteratorToArray
+            | Opcode::CallSpread
+            | Opcode::SetCompletionValue
+            | Opcode::ReturnUndefined
             | Opcode::Nop => String::new(),
         }
     }
@@ -239,7 +616,7 @@ impl std::fmt::Display for CodeBlock {
             let opcode: Opcode = self.code[pc].try_into().unwrap();
             write!(
                 f,
-                "    {:06}    {:04}    {:<20}",
+                "    {:06}    {:04}    {:<19} ",
                 pc,
                 count,
                 opcode.as_str()
@@ -250,42 +627,7 @@ impl std::fmt::Display for CodeBlock {
 
         f.write_char('\n')?;
 
-        f.write_str("Literals:\n")?;
-        if !self.literals.is_empty() {
-            for (i, value) in self.literals.iter().enumerate() {
-                writeln!(f, "    {:04}: <{}> {}", i, value.type_of(), value.display())?;
-            }
-        } else {
-            writeln!(f, "    <empty>")?;
-        }
-
-        f.write_char('\n')?;
-
-        f.write_str("Names:\n")?;
-        if !self.variables.is_empty() {
-            for (i, value) in self.variables.iter().enumerate() {
-                writeln!(f, "    {:04}: {}", i, value)?;
-            }
-        } else {
-            writeln!(f, "    <empty>")?;
-        }
-
-        f.write_char('\n')?;
-
-        f.write_str("Functions:\n")?;
-        if !self.functions.is_empty() {
-            for (i, code) in self.functions.iter().enumerate() {
-                writeln!(
-                    f,
-                    "    {:04}: name: '{}' (length: {})",
-                    i, code.name, code.length
-                )?;
-            }
-        } else {
-            writeln!(f, "    <empty>")?;
-        }
-
-        Ok(())
+        self.write_tables(f)
     }
 }
 
@@ -316,7 +658,21 @@ impl JsVmFunction {
             .configurable(true)
             .build();
 
-        let function = Function::VmOrdinary { code, environment };
+        let function = match code.interpreted_body.clone() {
+            Some(body) => Function::Ordinary {
+                constructor: code.constructor,
+                this_mode: code.this_mode.clone(),
+                body,
+                params: code.params.clone(),
+                environment,
+                realm: context.realm().clone(),
+            },
+            None => Function::VmOrdinary {
+                code,
+                environment,
+                realm: context.realm().clone(),
+            },
+        };
 
         let constructor =
             JsObject::from_proto_and_data(function_prototype, ObjectData::function(function));
@@ -334,9 +690,9 @@ impl JsVmFunction {
 
         let prototype_property = PropertyDescriptor::builder()
             .value(prototype)
-            .writable(false)
+            .writable(true)
             .enumerable(false)
-            .configurable(true)
+            .configurable(false)
             .build();
 
         constructor
@@ -353,21 +709,74 @@ impl JsVmFunction {
     }
 }
 
-pub(crate) enum FunctionBody {
-    Ordinary {
-        code: Gc<CodeBlock>,
-        environment: Environment,
-    },
-    Native {
-        function: NativeFunctionSignature,
-    },
-    Closure {
-        function: Box<dyn ClosureFunctionSignature>,
-        captures: Captures,
-    },
+/// Binds the parameters of a function to the arguments of a call.
+///
+/// The parameters of register code that keeps its local variables in registers are pushed on
+/// the stack, where they become the first registers of the call frame.
+///
+/// <https://tc39.es/ecma262/#sec-functiondeclarationinstantiation>
+fn bind_parameters(
+    function: &JsObject,
+    code: &CodeBlock,
+    args: &[JsValue],
+    local_env: &Environment,
+    context: &mut Context,
+) -> JsResult<()> {
+    if let Some(register_code) = &code.register_code {
+        if register_code.parameters_in_registers {
+            for i in 0..code.params.len() {
+                context.vm.push(args.get(i).cloned().unwrap_or_default());
+            }
+            return Ok(());
+        }
+    }
+
+    if code.arguments_binding {
+        create_arguments_binding(function, code, args, local_env, context)?;
+    }
+
+    // Add argument bindings to the function environment
+    for (i, param) in code.params.iter().enumerate() {
+        // Rest Parameters
+        if param.is_rest_param() {
+            Function::add_rest_param(param, i, args, context, local_env);
+            break;
+        }
+
+        let value = match args.get(i).cloned() {
+            None => JsValue::undefined(),
+            Some(value) => value,
+        };
+
+        Function::add_arguments_to_environment(param, value, local_env, context);
+    }
+    Ok(())
+}
+
+/// Adds the `arguments` object of a call to the function environment.
+fn create_arguments_binding(
+    function: &JsObject,
+    code: &CodeBlock,
+    args: &[JsValue],
+    local_env: &Environment,
+    context: &mut Context,
+) -> JsResult<()> {
+    let is_simple_parameter_list = code
+        .params
+        .iter()
+        .all(|param| !param.is_rest_param() && param.init().is_none());
+
+    let arguments_obj = if code.strict || context.strict() || !is_simple_parameter_list {
+        Arguments::create_unmapped_arguments_object(args, context)
+    } else {
+        Arguments::create_mapped_arguments_object(function, &code.params, args, local_env, context)
+    };
+    local_env.create_mutable_binding("arguments", false, true, context)?;
+    local_env.initialize_binding("arguments", arguments_obj.into(), context)
 }
 
 // TODO: this should be modified to not take `exit_on_return` and then moved to `internal_methods`
+// These are only reached through `[[Call]]` and `[[Construct]]` of `Function::VmOrdinary` objects.
 impl JsObject {
     pub(crate) fn call_internal(
         &self,
@@ -377,109 +786,62 @@ impl JsObject {
         exit_on_return: bool,
     ) -> JsResult<JsValue> {
         let this_function_object = self.clone();
-        // let mut has_parameter_expressions = false;
-
-        if !self.is_callable() {
-            return context.throw_type_error("not a callable function");
-        }
 
-        let body = {
-            let object = self.borrow();
-            let function = object.as_function().unwrap();
-
-            match function {
-                Function::Native { function, .. } => FunctionBody::Native {
-                    function: *function,
-                },
-                Function::Closure {
-                    function, captures, ..
-                } => FunctionBody::Closure {
-                    function: function.clone(),
-                    captures: captures.clone(),
-                },
-                Function::VmOrdinary { code, environment } => FunctionBody::Ordinary {
-                    code: code.clone(),
-                    environment: environment.clone(),
-                },
-                Function::Ordinary { .. } => unreachable!(),
-            }
+        let (code, environment) = match self.borrow().as_function() {
+            Some(Function::VmOrdinary {
+                code, environment, ..
+            }) => (code.clone(), environment.clone()),
+            _ => unreachable!("not a bytecode function"),
         };
 
-        match body {
-            FunctionBody::Native { function } => function(this, args, context),
-            FunctionBody::Closure { function, captures } => {
-                (function)(this, args, captures, context)
-            }
-            FunctionBody::Ordinary { code, environment } => {
-                let lexical_this_mode = code.this_mode == ThisMode::Lexical;
-
-                // Create a new Function environment whose parent is set to the scope of the function declaration (self.environment)
-                // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
-                let local_env = FunctionEnvironmentRecord::new(
-                    this_function_object,
-                    if !lexical_this_mode {
-                        Some(this.clone())
-                    } else {
-                        None
-                    },
-                    Some(environment.clone()),
-                    // Arrow functions do not have a this binding https://tc39.es/ecma262/#sec-function-environment-records
-                    if lexical_this_mode {
-                        BindingStatus::Lexical
-                    } else {
-                        BindingStatus::Uninitialized
-                    },
-                    JsValue::undefined(),
-                    context,
-                )?;
-
-                // Turn local_env into Environment so it can be cloned
-                let local_env: Environment = local_env.into();
-
-                // Push the environment first so that it will be used by default parameters
-                context.push_environment(local_env.clone());
+        let lexical_this_mode = code.this_mode == ThisMode::Lexical;
+
+        // Create a new Function environment whose parent is set to the scope of the function declaration (self.environment)
+        // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
+        let mut local_env = FunctionEnvironmentRecord::new(
+            this_function_object,
+            if !lexical_this_mode {
+                Some(this.clone())
+            } else {
+                None
+            },
+            Some(environment.clone()),
+            // Arrow functions do not have a this binding https://tc39.es/ecma262/#sec-function-environment-records
+            if lexical_this_mode {
+                BindingStatus::Lexical
+            } else {
+                BindingStatus::Uninitialized
+            },
+            JsValue::undefined(),
+            context,
+        )?;
+        local_env
+            .declarative_record
+            .declare_layout(code.function_layout.clone());
 
-                // Add argument bindings to the function environment
-                for (i, param) in code.params.iter().enumerate() {
-                    // Rest Parameters
-                    if param.is_rest_param() {
-                        todo!("Rest parameter");
-                    }
+        // Turn local_env into Environment so it can be cloned
+        let local_env: Environment = local_env.into();
 
-                    let value = match args.get(i).cloned() {
-                        None => JsValue::undefined(),
-                        Some(value) => value,
-                    };
+        // Push the environment first so that it will be used by default parameters
+        context.push_environment(local_env.clone());
 
-                    Function::add_arguments_to_environment(param, value, &local_env, context);
-                }
+        let fp = context.vm.stack.len();
+        bind_parameters(self, &code, args, &local_env, context)?;
 
-                context.vm.push_frame(CallFrame {
-                    prev: None,
-                    code,
-                    this: this.clone(),
-                    pc: 0,
-                    fp: context.vm.stack.len(),
-                    exit_on_return,
-                    environment: local_env,
-                });
+        let env_depth = context.environment_stack_len();
+        context.vm.push_frame(CallFrame::new(
+            code,
+            this.clone(),
+            fp,
+            exit_on_return,
+            env_depth,
+        ));
 
-                let result = context.run();
+        let result = context.run();
 
-                context.pop_environment();
+        context.pop_environment();
 
-                result
-            }
-        }
-    }
-
-    pub fn call(
-        &self,
-        this: &JsValue,
-        args: &[JsValue],
-        context: &mut Context,
-    ) -> JsResult<JsValue> {
-        self.call_internal(this, args, context, true)
+        result
     }
 
     pub(crate) fn construct_internal(
@@ -490,116 +852,75 @@ impl JsObject {
         exit_on_return: bool,
     ) -> JsResult<JsValue> {
         let this_function_object = self.clone();
-        // let mut has_parameter_expressions = false;
-
-        if !self.is_constructor() {
-            return context.throw_type_error("not a constructor function");
-        }
 
-        let body = {
-            let object = self.borrow();
-            let function = object.as_function().unwrap();
-
-            match function {
-                Function::Native { function, .. } => FunctionBody::Native {
-                    function: *function,
-                },
-                Function::Closure {
-                    function, captures, ..
-                } => FunctionBody::Closure {
-                    function: function.clone(),
-                    captures: captures.clone(),
-                },
-                Function::VmOrdinary { code, environment } => FunctionBody::Ordinary {
-                    code: code.clone(),
-                    environment: environment.clone(),
-                },
-                Function::Ordinary { .. } => unreachable!(),
-            }
+        let (code, environment) = match self.borrow().as_function() {
+            Some(Function::VmOrdinary {
+                code, environment, ..
+            }) => (code.clone(), environment.clone()),
+            _ => unreachable!("not a bytecode function"),
         };
 
-        match body {
-            FunctionBody::Native { function, .. } => function(this_target, args, context),
-            FunctionBody::Closure { function, captures } => {
-                (function)(this_target, args, captures, context)
-            }
-            FunctionBody::Ordinary { code, environment } => {
-                let this: JsValue = {
-                    // If the prototype of the constructor is not an object, then use the default object
-                    // prototype as prototype for the new object
-                    // see <https://tc39.es/ecma262/#sec-ordinarycreatefromconstructor>
-                    // see <https://tc39.es/ecma262/#sec-getprototypefromconstructor>
-                    let prototype = get_prototype_from_constructor(
-                        this_target,
-                        StandardObjects::object_object,
-                        context,
-                    )?;
-                    JsObject::from_proto_and_data(prototype, ObjectData::ordinary()).into()
-                };
-                let lexical_this_mode = code.this_mode == ThisMode::Lexical;
-
-                // Create a new Function environment whose parent is set to the scope of the function declaration (self.environment)
-                // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
-                let local_env = FunctionEnvironmentRecord::new(
-                    this_function_object,
-                    Some(this.clone()),
-                    Some(environment),
-                    // Arrow functions do not have a this binding https://tc39.es/ecma262/#sec-function-environment-records
-                    if lexical_this_mode {
-                        BindingStatus::Lexical
-                    } else {
-                        BindingStatus::Uninitialized
-                    },
-                    JsValue::undefined(),
-                    context,
-                )?;
-
-                // Turn local_env into Environment so it can be cloned
-                let local_env: Environment = local_env.into();
-
-                // Push the environment first so that it will be used by default parameters
-                context.push_environment(local_env.clone());
+        let this: JsValue = {
+            // If the prototype of the constructor is not an object, then use the default object
+            // prototype as prototype for the new object
+            // see <https://tc39.es/ecma262/#sec-ordinarycreatefromconstructor>
+            // see <https://tc39.es/ecma262/#sec-getprototypefromconstructor>
+            let prototype = get_prototype_from_constructor(
+                this_target,
+                StandardObjects::object_object,
+                context,
+            )?;
+            JsObject::from_proto_and_data(prototype, ObjectData::ordinary()).into()
+        };
+        let lexical_this_mode = code.this_mode == ThisMode::Lexical;
+
+        // Create a new Function environment whose parent is set to the scope of the function declaration (self.environment)
+        // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
+        let mut local_env = FunctionEnvironmentRecord::new(
+            this_function_object,
+            Some(this.clone()),
+            Some(environment),
+            // Arrow functions do not have a this binding https://tc39.es/ecma262/#sec-function-environment-records
+            if lexical_this_mode {
+                BindingStatus::Lexical
+            } else {
+                BindingStatus::Uninitialized
+            },
+            JsValue::undefined(),
+            context,
+        )?;
+        local_env
+            .declarative_record
+            .declare_layout(code.function_layout.clone());
 
-                // Add argument bindings to the function environment
-                for (i, param) in code.params.iter().enumerate() {
-                    // Rest Parameters
-                    if param.is_rest_param() {
-                        todo!("Rest parameter");
-                    }
+        // Turn local_env into Environment so it can be cloned
+        let local_env: Environment = local_env.into();
 
-                    let value = match args.get(i).cloned() {
-                        None => JsValue::undefined(),
-                        Some(value) => value,
-                    };
+        // Push the environment first so that it will be used by default parameters
+        context.push_environment(local_env.clone());
 
-                    Function::add_arguments_to_environment(param, value, &local_env, context);
-                }
+        let fp = context.vm.stack.len();
+        bind_parameters(self, &code, args, &local_env, context)?;
 
-                context.vm.push_frame(CallFrame {
-                    prev: None,
-                    code,
-                    this,
-                    pc: 0,
-                    fp: context.vm.stack.len(),
-                    exit_on_return,
-                    environment: local_env,
-                });
+        let env_depth = context.environment_stack_len();
+        context.vm.push_frame(CallFrame::new(
+            code,
+            this.clone(),
+            fp,
+            exit_on_return,
+            env_depth,
+        ));
 
-                let _result = context.run();
+        let result = context.run();
 
-                context.pop_environment();
+        context.pop_environment();
 
-                context.get_this_binding()
-            }
+        // A constructor that returns an object replaces the constructed object.
+        let result = result?;
+        if result.is_object() {
+            Ok(result)
+        } else {
+            Ok(this)
         }
     }
-
-    pub fn construct(
-        &self,
-        args: &[JsValue],
-        this_target: &JsValue,
-        context: &mut Context,
-    ) -> JsResult<JsValue> {
-        self.construct_internal(args, this_target, context, true)
-    }
 }
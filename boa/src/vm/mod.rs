@@ -3,18 +3,36 @@
 //! plus an interpreter to execute those instructions
 
 use crate::{
-    builtins::Array, environment::lexical_environment::VariableScope, property::PropertyDescriptor,
-    vm::code_block::Readable, BoaProfiler, Context, JsResult, JsValue,
+    builtins::{iterable::IteratorRecord, Array, ForInIterator},
+    environment::{
+        declarative_environment_record::DeclarativeEnvironmentRecord,
+        lexical_environment::VariableScope,
+    },
+    property::{PropertyDescriptor, PropertyKey},
+    vm::{
+        call_frame::{FinallyCompletion, TryHandler},
+        code_block::Readable,
+    },
+    BoaProfiler, Context, JsResult, JsString, JsValue,
 };
 use std::{convert::TryInto, mem::size_of, time::Instant};
 
+mod bytecode_cache;
 mod call_frame;
 mod code_block;
 mod opcode;
+mod optimizer;
+mod register;
 
+pub use bytecode_cache::BytecodeCacheError;
 pub use call_frame::CallFrame;
+pub(crate) use code_block::TemplateSite;
 pub use code_block::{CodeBlock, JsVmFunction};
 pub use opcode::Opcode;
+pub use optimizer::OptimizationLevel;
+pub use register::RegisterCodeBlock;
+
+pub(crate) use register::FunctionIndices;
 
 #[cfg(test)]
 mod tests;
@@ -153,9 +171,35 @@ impl Context {
                 for _ in 0..count {
                     elements.push(self.vm.pop());
                 }
+                elements.reverse();
                 let array = Array::create_array_from_list(elements, self);
                 self.vm.push(array);
             }
+            Opcode::PushValueToArray => {
+                let value = self.vm.pop();
+                let array = self.vm.pop();
+                Array::add_to_array_object(&array, &[value], self)?;
+                self.vm.push(array);
+            }
+            Opcode::PushIteratorToArray => {
+                let done = self.vm.pop().to_boolean();
+                let next_function = self.vm.pop();
+                let iterator = self.vm.pop();
+                let array = self.vm.pop();
+
+                if !done {
+                    let record = IteratorRecord::new(iterator, next_function);
+                    loop {
+                        let next = record.next(self)?;
+                        if next.done {
+                            break;
+                        }
+                        Array::add_to_array_object(&array, &[next.value], self)?;
+                    }
+                }
+
+                self.vm.push(array);
+            }
             Opcode::Add => bin_op!(add),
             Opcode::Sub => bin_op!(sub),
             Opcode::Mul => bin_op!(mul),
@@ -197,15 +241,7 @@ impl Context {
             Opcode::In => {
                 let rhs = self.vm.pop();
                 let lhs = self.vm.pop();
-
-                if !rhs.is_object() {
-                    return Err(self.construct_type_error(format!(
-                        "right-hand side of 'in' should be an object, got {}",
-                        rhs.type_of()
-                    )));
-                }
-                let key = lhs.to_property_key(self)?;
-                let value = self.has_property(&rhs, &key)?;
+                let value = self.has_property_value(&lhs, &rhs)?;
                 self.vm.push(value);
             }
             Opcode::InstanceOf => {
@@ -246,20 +282,14 @@ impl Context {
             }
             Opcode::BitNot => {
                 let target = self.vm.pop();
-                let num = target.to_number(self)?;
-                let value = if num.is_nan() {
-                    -1
-                } else {
-                    // TODO: this is not spec compliant.
-                    !(num as i32)
-                };
+                let value = self.bit_not(&target)?;
                 self.vm.push(value);
             }
             Opcode::DefVar => {
                 let index = self.vm.read::<u32>();
                 let name = self.vm.frame().code.variables[index as usize].clone();
 
-                self.create_mutable_binding(name.as_ref(), false, VariableScope::Function)?;
+                self.def_var(&name)?;
             }
             Opcode::DefLet => {
                 let index = self.vm.read::<u32>();
@@ -292,13 +322,29 @@ impl Context {
                 let value = self.vm.pop();
                 let name = self.vm.frame().code.variables[index as usize].clone();
 
-                if self.has_binding(name.as_ref())? {
-                    // Binding already exists
-                    self.set_mutable_binding(name.as_ref(), value, self.strict())?;
-                } else {
-                    self.create_mutable_binding(name.as_ref(), true, VariableScope::Function)?;
-                    self.initialize_binding(name.as_ref(), value)?;
-                }
+                self.set_name(&name, value)?;
+            }
+            Opcode::GetBinding => {
+                let depth = self.vm.read::<u32>();
+                let slot = self.vm.read::<u32>();
+
+                let value = self.get_binding_value_by_slot(depth, slot)?;
+                self.vm.push(value);
+            }
+            Opcode::SetBinding => {
+                let depth = self.vm.read::<u32>();
+                let slot = self.vm.read::<u32>();
+                let value = self.vm.pop();
+
+                let strict = self.is_strict_code();
+                self.set_mutable_binding_by_slot(depth, slot, value, strict)?;
+            }
+            Opcode::InitBinding => {
+                let depth = self.vm.read::<u32>();
+                let slot = self.vm.read::<u32>();
+                let value = self.vm.pop();
+
+                self.initialize_binding_by_slot(depth, slot, value);
             }
             Opcode::Jump => {
                 let address = self.vm.read::<u32>();
@@ -316,12 +362,20 @@ impl Context {
                     self.vm.frame_mut().pc = address as usize;
                 }
             }
+            Opcode::JumpIfNotLessThan => {
+                let address = self.vm.read::<u32>();
+                let rhs = self.vm.pop();
+                let lhs = self.vm.pop();
+                if !lhs.lt(&rhs, self)? {
+                    self.vm.frame_mut().pc = address as usize;
+                }
+            }
             Opcode::LogicalAnd => {
                 let exit = self.vm.read::<u32>();
                 let lhs = self.vm.pop();
                 if !lhs.to_boolean() {
                     self.vm.frame_mut().pc = exit as usize;
-                    self.vm.push(false);
+                    self.vm.push(lhs);
                 }
             }
             Opcode::LogicalOr => {
@@ -329,7 +383,7 @@ impl Context {
                 let lhs = self.vm.pop();
                 if lhs.to_boolean() {
                     self.vm.frame_mut().pc = exit as usize;
-                    self.vm.push(true);
+                    self.vm.push(lhs);
                 }
             }
             Opcode::Coalesce => {
@@ -348,28 +402,17 @@ impl Context {
                 let index = self.vm.read::<u32>();
 
                 let value = self.vm.pop();
-                let object = if let Some(object) = value.as_object() {
-                    object.clone()
-                } else {
-                    value.to_object(self)?
-                };
-
                 let name = self.vm.frame().code.variables[index as usize].clone();
-                let result = object.get(name, self)?;
+                let result = self.get_property_of(&value, name.into())?;
 
                 self.vm.push(result)
             }
             Opcode::GetPropertyByValue => {
                 let value = self.vm.pop();
                 let key = self.vm.pop();
-                let object = if let Some(object) = value.as_object() {
-                    object.clone()
-                } else {
-                    value.to_object(self)?
-                };
 
                 let key = key.to_property_key(self)?;
-                let result = object.get(key, self)?;
+                let result = self.get_property_of(&value, key)?;
 
                 self.vm.push(result)
             }
@@ -378,28 +421,17 @@ impl Context {
 
                 let object = self.vm.pop();
                 let value = self.vm.pop();
-                let object = if let Some(object) = object.as_object() {
-                    object.clone()
-                } else {
-                    object.to_object(self)?
-                };
-
                 let name = self.vm.frame().code.variables[index as usize].clone();
 
-                object.set(name, value, true, self)?;
+                self.set_property_of(&object, name.into(), value)?;
             }
             Opcode::SetPropertyByValue => {
                 let object = self.vm.pop();
                 let key = self.vm.pop();
                 let value = self.vm.pop();
-                let object = if let Some(object) = object.as_object() {
-                    object.clone()
-                } else {
-                    object.to_object(self)?
-                };
 
                 let key = key.to_property_key(self)?;
-                object.set(key, value, true, self)?;
+                self.set_property_of(&object, key, value)?;
             }
             Opcode::SetPropertyGetterByName => {
                 let index = self.vm.read::<u32>();
@@ -499,6 +531,16 @@ impl Context {
                 let key = self.vm.frame().code.variables[index as usize].clone();
                 let object = self.vm.pop();
                 let result = object.to_object(self)?.__delete__(&key.into(), self)?;
+                if !result && self.is_strict_code() {
+                    return Err(self.construct_type_error("Cannot delete property"));
+                }
+                self.vm.push(result);
+            }
+            Opcode::DeleteName => {
+                let index = self.vm.read::<u32>();
+                let name = self.vm.frame().code.variables[index as usize].clone();
+
+                let result = self.delete_binding(name.as_ref())?;
                 self.vm.push(result);
             }
             Opcode::DeletePropertyByValue => {
@@ -507,6 +549,9 @@ impl Context {
                 let result = object
                     .to_object(self)?
                     .__delete__(&key.to_property_key(self)?, self)?;
+                if !result && self.is_strict_code() {
+                    return Err(self.construct_type_error("Cannot delete property"));
+                }
                 self.vm.push(result);
             }
             Opcode::Throw => {
@@ -536,7 +581,7 @@ impl Context {
             Opcode::GetFunction => {
                 let index = self.vm.read::<u32>();
                 let code = self.vm.frame().code.functions[index as usize].clone();
-                let environment = self.vm.frame().environment.clone();
+                let environment = self.get_current_environment();
                 let function = JsVmFunction::new(code, environment, self);
                 self.vm.push(function);
             }
@@ -545,56 +590,472 @@ impl Context {
                     return Err(self.construct_range_error("Maximum call stack size exceeded"));
                 }
                 let argc = self.vm.read::<u32>();
+                let mut args = Vec::with_capacity(argc as usize);
+                for _ in 0..argc {
+                    args.push(self.vm.pop());
+                }
+                args.reverse();
+                let func = self.vm.pop();
+                let this = self.vm.pop();
+
+                let result = self.call(&func, &this, &args)?;
+                self.vm.push(result);
+            }
+            Opcode::CallSpread => {
+                if self.vm.stack_size_limit <= self.vm.stack.len() {
+                    return Err(self.construct_range_error("Maximum call stack size exceeded"));
+                }
+                let args = self.vm.pop();
+                let args = self.array_to_list(&args)?;
                 let func = self.vm.pop();
                 let this = self.vm.pop();
+
+                let result = self.call(&func, &this, &args)?;
+                self.vm.push(result);
+            }
+            Opcode::New => {
+                if self.vm.stack_size_limit <= self.vm.stack.len() {
+                    return Err(self.construct_range_error("Maximum call stack size exceeded"));
+                }
+                let argc = self.vm.read::<u32>();
+                let index = self.vm.read::<u32>();
                 let mut args = Vec::with_capacity(argc as usize);
                 for _ in 0..argc {
                     args.push(self.vm.pop());
                 }
+                args.reverse();
+                let func = self.vm.pop();
+
+                let result = self.construct_value(&func, &args, index)?;
+                self.vm.push(result);
+            }
+            Opcode::NewSpread => {
+                if self.vm.stack_size_limit <= self.vm.stack.len() {
+                    return Err(self.construct_range_error("Maximum call stack size exceeded"));
+                }
+                let index = self.vm.read::<u32>();
+                let args = self.vm.pop();
+                let args = self.array_to_list(&args)?;
+                let func = self.vm.pop();
 
-                let object = match func {
-                    JsValue::Object(ref object) if object.is_callable() => object.clone(),
-                    _ => return Err(self.construct_type_error("not a callable function")),
+                let result = self.construct_value(&func, &args, index)?;
+                self.vm.push(result);
+            }
+            Opcode::Return => return Ok(true),
+            Opcode::ReturnUndefined => {
+                self.vm.push(JsValue::undefined());
+                return Ok(true);
+            }
+            Opcode::PushDeclarativeEnvironment => {
+                let index = self.vm.read::<u32>();
+                let layout = self.vm.frame().code.scopes[index as usize].clone();
+                let environment = self.get_current_environment();
+                self.push_environment(DeclarativeEnvironmentRecord::with_layout(
+                    Some(environment),
+                    layout,
+                ));
+            }
+            Opcode::PopEnvironment => {
+                let _ = self.pop_environment();
+            }
+            Opcode::TryStart => {
+                let catch = self.vm.read::<u32>();
+                let finally = self.vm.read::<u32>();
+                let handler = TryHandler {
+                    catch: (catch != u32::MAX).then(|| catch),
+                    finally: (finally != u32::MAX).then(|| finally),
+                    stack_len: self.vm.stack.len(),
+                    env_depth: self.environment_stack_len(),
+                    finally_depth: self.vm.frame().finally_completions.len(),
                 };
+                self.vm.frame_mut().handlers.push(handler);
+            }
+            Opcode::TryEnd => {
+                let _ = self.vm.frame_mut().handlers.pop();
+            }
+            Opcode::FinallyStart => {
+                self.vm
+                    .frame_mut()
+                    .finally_completions
+                    .push(FinallyCompletion::Normal);
+            }
+            Opcode::FinallyEnd => {
+                let completion = self
+                    .vm
+                    .frame_mut()
+                    .finally_completions
+                    .pop()
+                    .expect("finally block must have a completion");
+                match completion {
+                    FinallyCompletion::Normal => {}
+                    FinallyCompletion::Jump(address) => self.vm.frame_mut().pc = address as usize,
+                    FinallyCompletion::Throw(value) => return Err(value),
+                }
+            }
+            Opcode::FinallySetJump => {
+                let address = self.vm.read::<u32>();
+                self.vm
+                    .frame_mut()
+                    .finally_completions
+                    .push(FinallyCompletion::Jump(address));
+            }
+            Opcode::FinallyDiscard => {
+                let _ = self.vm.frame_mut().finally_completions.pop();
+            }
+            Opcode::JumpIfNotUndefined => {
+                let address = self.vm.read::<u32>();
+                let value = self.vm.pop();
+                if !value.is_undefined() {
+                    self.vm.frame_mut().pc = address as usize;
+                    self.vm.push(value);
+                }
+            }
+            Opcode::ValueNotNullOrUndefined => {
+                let value = self.vm.pop();
+                if value.is_null_or_undefined() {
+                    return Err(self.construct_type_error(format!(
+                        "cannot destructure '{}' as it is {}",
+                        value.display(),
+                        value.display()
+                    )));
+                }
+                self.vm.push(value);
+            }
+            Opcode::CopyDataProperties => {
+                let excluded_key_count = self.vm.read::<u32>();
+                let mut excluded_keys = Vec::with_capacity(excluded_key_count as usize);
+                for _ in 0..excluded_key_count {
+                    let key = self.vm.pop();
+                    excluded_keys.push(key.to_property_key(self)?);
+                }
+                let value = self.vm.pop();
+                let object = self.vm.pop();
+                if let Some(object) = object.as_object() {
+                    object.copy_data_properties::<PropertyKey>(&value, excluded_keys, self)?;
+                }
+                self.vm.push(object);
+            }
+            Opcode::GetIterator => {
+                let iterable = self.vm.pop();
+                let iterator = iterable.get_iterator(self, None, None)?;
+                self.vm.push(iterator.iterator_object().clone());
+                self.vm.push(iterator.next_function().clone());
+                self.vm.push(false);
+            }
+            Opcode::ForInLoopInitIterator => {
+                let value = self.vm.pop();
+                if value.is_null_or_undefined() {
+                    self.vm.push(JsValue::undefined());
+                    self.vm.push(JsValue::undefined());
+                    self.vm.push(true);
+                } else {
+                    let object = value.to_object(self)?;
+                    let iterator =
+                        ForInIterator::create_for_in_iterator(JsValue::new(object), self);
+                    let next_function = iterator
+                        .get_property("next")
+                        .as_ref()
+                        .map(|p| p.expect_value())
+                        .cloned()
+                        .ok_or_else(|| {
+                            self.construct_type_error("Could not find property `next`")
+                        })?;
+                    self.vm.push(iterator);
+                    self.vm.push(next_function);
+                    self.vm.push(false);
+                }
+            }
+            Opcode::IteratorStep => {
+                let address = self.vm.read::<u32>();
 
-                let result = object.call_internal(&this, &args, self, false)?;
+                let done = self.vm.pop().to_boolean();
+                if done {
+                    self.vm.push(true);
+                    self.vm.frame_mut().pc = address as usize;
+                } else {
+                    let next_function = self.vm.pop();
+                    let iterator = self.vm.pop();
+                    self.vm.push(iterator.clone());
+                    self.vm.push(next_function.clone());
+                    // The iterator is done if `next` throws.
+                    self.vm.push(true);
 
-                self.vm.push(result);
+                    let record = IteratorRecord::new(iterator, next_function);
+                    let next = record.next(self)?;
+
+                    if next.done {
+                        self.vm.frame_mut().pc = address as usize;
+                    } else {
+                        let _ = self.vm.pop();
+                        self.vm.push(false);
+                        self.vm.push(next.value);
+                    }
+                }
             }
-            Opcode::Return => {
-                let exit = self.vm.frame().exit_on_return;
+            Opcode::IteratorNext => {
+                let done = self.vm.pop().to_boolean();
+                let next_function = self.vm.pop();
+                let iterator = self.vm.pop();
+
+                let (done, value) = if done {
+                    (true, JsValue::undefined())
+                } else {
+                    let record = IteratorRecord::new(iterator.clone(), next_function.clone());
+                    let next = record.next(self)?;
+                    if next.done {
+                        (true, JsValue::undefined())
+                    } else {
+                        (false, next.value)
+                    }
+                };
 
-                let _ = self.vm.pop_frame();
+                self.vm.push(iterator);
+                self.vm.push(next_function);
+                self.vm.push(done);
+                self.vm.push(value);
+            }
+            Opcode::IteratorToArray => {
+                let done = self.vm.pop().to_boolean();
+                let next_function = self.vm.pop();
+                let iterator = self.vm.pop();
+
+                let mut values = Vec::new();
+                if !done {
+                    let record = IteratorRecord::new(iterator.clone(), next_function.clone());
+                    loop {
+                        let next = record.next(self)?;
+                        if next.done {
+                            break;
+                        }
+                        values.push(next.value);
+                    }
+                }
+                let array = Array::create_array_from_list(values, self);
 
-                if exit {
-                    return Ok(true);
+                self.vm.push(iterator);
+                self.vm.push(next_function);
+                self.vm.push(true);
+                self.vm.push(array);
+            }
+            Opcode::IteratorClose => {
+                let done = self.vm.pop().to_boolean();
+                let next_function = self.vm.pop();
+                let iterator = self.vm.pop();
+                if !done {
+                    let record = IteratorRecord::new(iterator, next_function);
+                    record.close(Ok(JsValue::undefined()), self)?;
+                }
+            }
+            Opcode::IteratorCloseWithException => {
+                let exception = self.vm.pop();
+                let done = self.vm.pop().to_boolean();
+                let next_function = self.vm.pop();
+                let iterator = self.vm.pop();
+                if !done {
+                    let record = IteratorRecord::new(iterator, next_function);
+                    let _ = record.close(Err(exception.clone()), self);
                 }
+                return Err(exception);
+            }
+            Opcode::ConcatToString => {
+                let count = self.vm.read::<u32>() as usize;
+                let values = self.vm.stack.split_off(self.vm.stack.len() - count);
+                let mut string = String::new();
+                for value in values {
+                    string.push_str(&value.to_string(self)?);
+                }
+                self.vm.push(string);
+            }
+            Opcode::GetTemplateObject => {
+                let index = self.vm.read::<u32>() as usize;
+                let code = self.vm.frame().code.clone();
+                let template = code.templates[index].template_object(self)?;
+                self.vm.push(template);
+            }
+            Opcode::RotateDown => {
+                let count = self.vm.read::<u32>() as usize;
+                let value = self.vm.pop();
+                let index = self.vm.stack.len() - count;
+                self.vm.stack.insert(index, value);
+            }
+            Opcode::SetCompletionValue => {
+                let value = self.vm.pop();
+                self.vm.frame_mut().completion_value = value;
             }
         }
 
         Ok(false)
     }
 
-    /// Unwind the stack.
-    fn unwind(&mut self) -> bool {
-        let mut fp = 0;
-        while let Some(mut frame) = self.vm.frame.take() {
-            fp = frame.fp;
-            if frame.exit_on_return {
-                break;
-            }
+    /// Returns `true` if the code of the current frame runs in strict mode.
+    fn is_strict_code(&self) -> bool {
+        self.vm.frame().code.strict || self.strict()
+    }
+
+    /// Declares a `var` binding, a declaration of an existing binding keeps its value.
+    fn def_var(&mut self, name: &JsString) -> JsResult<()> {
+        if !self.has_binding(name.as_ref())? {
+            self.create_mutable_binding(name.as_ref(), false, VariableScope::Function)?;
+            self.initialize_binding(name.as_ref(), JsValue::undefined())?;
+        }
+        Ok(())
+    }
+
+    /// Assigns a value to a binding, creating a global binding if it does not exist.
+    fn set_name(&mut self, name: &JsString, value: JsValue) -> JsResult<()> {
+        if self.has_binding(name.as_ref())? {
+            // Binding already exists
+            self.set_mutable_binding(name.as_ref(), value, self.is_strict_code())
+        } else {
+            self.create_mutable_binding(name.as_ref(), true, VariableScope::Function)?;
+            self.initialize_binding(name.as_ref(), value)
+        }
+    }
+
+    /// Gets the property `key` of a value, primitive values are converted to objects.
+    fn get_property_of(&mut self, value: &JsValue, key: PropertyKey) -> JsResult<JsValue> {
+        let object = if let Some(object) = value.as_object() {
+            object.clone()
+        } else {
+            value.to_object(self)?
+        };
+        object.get(key, self)
+    }
+
+    /// Sets the property `key` of a value, primitive values are converted to objects.
+    fn set_property_of(
+        &mut self,
+        value: &JsValue,
+        key: PropertyKey,
+        property: JsValue,
+    ) -> JsResult<()> {
+        let object = if let Some(object) = value.as_object() {
+            object.clone()
+        } else {
+            value.to_object(self)?
+        };
+        // A failed assignment only throws in strict mode code.
+        let strict = self.is_strict_code();
+        object.set(key, property, strict, self)?;
+        Ok(())
+    }
+
+    /// Evaluates `lhs in rhs`.
+    fn has_property_value(&mut self, lhs: &JsValue, rhs: &JsValue) -> JsResult<bool> {
+        if !rhs.is_object() {
+            return Err(self.construct_type_error(format!(
+                "right-hand side of 'in' should be an object, got {}",
+                rhs.type_of()
+            )));
+        }
+        let key = lhs.to_property_key(self)?;
+        self.has_property(rhs, &key)
+    }
+
+    /// Evaluates `~value`.
+    fn bit_not(&mut self, value: &JsValue) -> JsResult<JsValue> {
+        let num = value.to_number(self)?;
+        let value = if num.is_nan() {
+            -1
+        } else {
+            // TODO: this is not spec compliant.
+            !(num as i32)
+        };
+        Ok(value.into())
+    }
+
+    /// Calls the constructor `func` with the given arguments.
+    ///
+    /// `name_index` is the index of the source text of the constructor expression in the names.
+    fn construct_value(
+        &mut self,
+        func: &JsValue,
+        args: &[JsValue],
+        name_index: u32,
+    ) -> JsResult<JsValue> {
+        if let Some(constructor) = func.as_constructor() {
+            constructor.construct(args, func, self)
+        } else {
+            let name = self.vm.frame().code.variables[name_index as usize].clone();
+            Err(self.construct_type_error(format!("{} is not a constructor", name)))
+        }
+    }
+
+    /// Gets the elements of an array that was created for the arguments of a call.
+    fn array_to_list(&mut self, array: &JsValue) -> JsResult<Vec<JsValue>> {
+        array.create_list_from_array_like(&[], self)
+    }
+
+    /// Transfers control to the innermost exception handler of the current frame.
+    ///
+    /// Returns the exception back if the current frame has no handler for it, or if it
+    /// terminates the script.
+    fn handle_exception(&mut self, exception: JsValue) -> Result<(), JsValue> {
+        // The termination of the script can not be caught, and skips the `finally` blocks.
+        if exception.as_termination().is_some() {
+            return Err(exception);
+        }
+
+        let frame = self.vm.frame_mut();
+        let handler = match frame.handlers.last_mut() {
+            Some(handler) => handler,
+            None => return Err(exception),
+        };
+
+        let TryHandler {
+            catch,
+            finally,
+            stack_len,
+            env_depth,
+            finally_depth,
+        } = *handler;
+
+        // Exceptions thrown inside of the `catch` block are handled by the `finally` block.
+        if catch.is_some() && finally.is_some() {
+            handler.catch = None;
+        } else {
+            let _ = frame.handlers.pop();
+        }
+        frame.finally_completions.truncate(finally_depth);
 
-            self.vm.frame = frame.prev.take();
+        self.vm.stack.truncate(stack_len);
+        while self.environment_stack_len() > env_depth {
+            let _ = self.pop_environment();
         }
-        while self.vm.stack.len() > fp {
-            let _ = self.vm.pop();
+
+        if let Some(catch) = catch {
+            self.vm.frame_mut().pc = catch as usize;
+            self.vm.push(exception);
+        } else if let Some(finally) = finally {
+            let frame = self.vm.frame_mut();
+            frame.pc = finally as usize;
+            frame
+                .finally_completions
+                .push(FinallyCompletion::Throw(exception));
+        }
+
+        Ok(())
+    }
+
+    /// Pops the current frame, discarding the values and environments it left behind.
+    fn exit_frame(&mut self) {
+        let frame = self.vm.pop_frame().expect("there must be a frame to exit");
+        self.vm.stack.truncate(frame.fp);
+        while self.environment_stack_len() > frame.env_depth {
+            let _ = self.pop_environment();
         }
-        true
     }
 
     pub(crate) fn run(&mut self) -> JsResult<JsValue> {
         let _timer = BoaProfiler::global().start_event("run", "vm");
 
+        if let Some(register_code) = self.vm.frame().code.register_code.clone() {
+            if self.vm.trace {
+                println!("{}\n", register_code);
+            }
+            return self.run_registers(register_code);
+        }
+
         const COLUMN_WIDTH: usize = 24;
         const TIME_COLUMN_WIDTH: usize = COLUMN_WIDTH / 2;
         const OPCODE_COLUMN_WIDTH: usize = COLUMN_WIDTH;
@@ -627,6 +1088,7 @@ impl Context {
 
         self.vm.frame_mut().pc = 0;
         while self.vm.frame().pc < self.vm.frame().code.code.len() {
+            let pc = self.vm.frame().pc;
             let result = if self.vm.trace {
                 let mut pc = self.vm.frame().pc;
                 let opcode: Opcode = self.vm.frame().code.read::<u8>(pc).try_into().unwrap();
@@ -663,19 +1125,24 @@ impl Context {
                 self.execute_instruction()
             };
 
+            // A jump backwards starts a new iteration of a loop.
+            let result = match result {
+                Ok(false) if self.vm.frame().pc <= pc => self.count_loop_iteration().map(|_| false),
+                result => result,
+            };
+
             match result {
                 Ok(should_exit) => {
                     if should_exit {
                         let result = self.vm.pop();
+                        self.exit_frame();
                         return Ok(result);
                     }
                 }
                 Err(e) => {
-                    let should_exit = self.unwind();
-                    if should_exit {
+                    if let Err(e) = self.handle_exception(e) {
+                        self.exit_frame();
                         return Err(e);
-                    } else {
-                        self.vm.push(e);
                     }
                 }
             }
@@ -705,10 +1172,9 @@ impl Context {
             println!("\n");
         }
 
-        if self.vm.stack.is_empty() {
-            return Ok(JsValue::undefined());
-        }
+        let result = std::mem::take(&mut self.vm.frame_mut().completion_value);
+        self.exit_frame();
 
-        Ok(self.vm.pop())
+        Ok(result)
     }
 }
@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, mem::size_of};
 
 /// The opcodes of the vm.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -133,13 +133,27 @@ pub enum Opcode {
     /// Stack: **=>** object
     PushEmptyObject,
 
-    /// Push array object `{}` value on the stack.
+    /// Push array object `[]` value on the stack.
     ///
     /// Operands: n: `u32`
     ///
-    /// Stack: v1, v1, ... vn **=>** [v1, v2, ..., vn]
+    /// Stack: v1, v2, ... vn **=>** [v1, v2, ..., vn]
     PushNewArray,
 
+    /// Push a value to the end of an array.
+    ///
+    /// Operands:
+    ///
+    /// Stack: array, value **=>** array
+    PushValueToArray,
+
+    /// Push all the remaining values of an iterator to the end of an array.
+    ///
+    /// Operands:
+    ///
+    /// Stack: array, iterator, next_method, done **=>** array
+    PushIteratorToArray,
+
     /// Binary `+` operator.
     ///
     /// Operands:
@@ -378,7 +392,7 @@ pub enum Opcode {
     /// Stack: value **=>** (value - 1)
     Dec,
 
-    /// Declate `var` type variable.
+    /// Declate `var` type variable, initialized to `undefined` unless the binding already exists.
     ///
     /// Operands: name_index: `u32`
     ///
@@ -420,6 +434,30 @@ pub enum Opcode {
     /// Stack: value **=>**
     SetName,
 
+    /// Push the value of a binding that was resolved at compile time.
+    ///
+    /// The binding is in the slot `slot` of the environment that is `depth` environments up
+    /// the environment chain.
+    ///
+    /// Operands: depth: `u32`, slot: `u32`
+    ///
+    /// Stack: **=>** value
+    GetBinding,
+
+    /// Assign the value of a binding that was resolved at compile time.
+    ///
+    /// Operands: depth: `u32`, slot: `u32`
+    ///
+    /// Stack: value **=>**
+    SetBinding,
+
+    /// Initialize a lexical binding that was resolved at compile time.
+    ///
+    /// Operands: depth: `u32`, slot: `u32`
+    ///
+    /// Stack: value **=>**
+    InitBinding,
+
     /// Get a property by name from an object an push it on the stack.
     ///
     /// Like `object.name`
@@ -510,6 +548,15 @@ pub enum Opcode {
     /// Stack: key, object **=>**
     DeletePropertyByValue,
 
+    /// Deletes a binding from the environment.
+    ///
+    /// Like `delete name`
+    ///
+    /// Operands: name_index: `u32`
+    ///
+    /// Stack: **=>** deleted
+    DeleteName,
+
     /// Unconditional jump to address.
     ///
     /// Operands: address: `u32`
@@ -585,12 +632,226 @@ pub enum Opcode {
     ///
     /// Operands: argc: `u32`
     ///
-    /// Stack: `func`, `this`, `arg1`, `arg2`,...`argn` **=>**
+    /// Stack: `this`, `func`, `arg1`, `arg2`,...`argn` **=>** `result`
     Call,
 
+    /// Call a function with the arguments in an array.
+    ///
+    /// Operands:
+    ///
+    /// Stack: `this`, `func`, `arguments_array` **=>** `result`
+    CallSpread,
+
+    /// Call a constructor.
+    ///
+    /// The name operand is the source text of the constructor expression, used in the error
+    /// thrown if `func` is not a constructor.
+    ///
+    /// Operands: argc: `u32`, name_index: `u32`
+    ///
+    /// Stack: `func`, `arg1`, `arg2`,...`argn` **=>** `result`
+    New,
+
+    /// Call a constructor with the arguments in an array.
+    ///
+    /// Operands: name_index: `u32`
+    ///
+    /// Stack: `func`, `arguments_array` **=>** `result`
+    NewSpread,
+
     /// Return from a function.
     Return,
 
+    /// Push a declarative environment, with the bindings declared in a scope.
+    ///
+    /// Operands: scope_index: `u32`
+    ///
+    /// Stack: **=>**
+    PushDeclarativeEnvironment,
+
+    /// Pop the current environment.
+    ///
+    /// Operands:
+    ///
+    /// Stack: **=>**
+    PopEnvironment,
+
+    /// Start of a `try` statement, pushes an exception handler.
+    ///
+    /// If an exception is thrown while the handler is active, the value stack and the
+    /// environments are restored to their state at this instruction, and execution continues
+    /// at the `catch` block with the exception on the stack. If there is no `catch` block, or
+    /// the exception is thrown inside of it, execution continues at the `finally` block, which
+    /// rethrows the exception once it completes.
+    ///
+    /// A missing block is represented by the address `u32::MAX`.
+    ///
+    /// Operands: catch_address: `u32`, finally_address: `u32`
+    ///
+    /// Stack: **=>**
+    TryStart,
+
+    /// Pops the innermost exception handler.
+    ///
+    /// Operands:
+    ///
+    /// Stack: **=>**
+    TryEnd,
+
+    /// Start of a `finally` block that is entered after its `try` or `catch` block
+    /// completed normally.
+    ///
+    /// Operands:
+    ///
+    /// Stack: **=>**
+    FinallyStart,
+
+    /// End of a `finally` block, continues the completion that entered the block.
+    ///
+    /// Operands:
+    ///
+    /// Stack: **=>**
+    FinallyEnd,
+
+    /// Records that the next `finally` block is entered by a `break`, `continue` or `return`,
+    /// which continues at `address` once the `finally` block completes.
+    ///
+    /// Operands: address: `u32`
+    ///
+    /// Stack: **=>**
+    FinallySetJump,
+
+    /// Discards the completion of the innermost `finally` block, when a `break` or `continue`
+    /// leaves it.
+    ///
+    /// Operands:
+    ///
+    /// Stack: **=>**
+    FinallyDiscard,
+
+    /// Conditional jump to address.
+    ///
+    /// If the value popped is not `undefined`, push it back and jump to `address`.
+    ///
+    /// Operands: address: `u32`
+    ///
+    /// Stack: value **=>** value (if `value` is not `undefined`)
+    JumpIfNotUndefined,
+
+    /// Throws a `TypeError` if the value on the stack is `null` or `undefined`.
+    ///
+    /// Operands:
+    ///
+    /// Stack: value **=>** value
+    ValueNotNullOrUndefined,
+
+    /// Copies the own enumerable properties of a value into an object, excluding the given keys.
+    ///
+    /// Operands: excluded_key_count: `u32`
+    ///
+    /// Stack: object, value, excluded_key_1, ... excluded_key_n **=>** object
+    CopyDataProperties,
+
+    /// Gets the iterator of a value.
+    ///
+    /// Operands:
+    ///
+    /// Stack: value **=>** iterator, next_method, done
+    GetIterator,
+
+    /// Gets the iterator over the enumerable property keys of a value, for a `for..in` loop.
+    ///
+    /// The iterator is done if the value is `null` or `undefined`.
+    ///
+    /// Operands:
+    ///
+    /// Stack: value **=>** iterator, next_method, done
+    ForInLoopInitIterator,
+
+    /// Gets the next value of an iterator, jumps to `address` if it is done.
+    ///
+    /// Operands: address: `u32`
+    ///
+    /// Stack: iterator, next_method, done **=>** iterator, next_method, done, next_value
+    IteratorStep,
+
+    /// Gets the next value of an iterator, or `undefined` if it is done.
+    ///
+    /// Operands:
+    ///
+    /// Stack: iterator, next_method, done **=>** iterator, next_method, done, next_value
+    IteratorNext,
+
+    /// Collects the remaining values of an iterator into an array.
+    ///
+    /// Operands:
+    ///
+    /// Stack: iterator, next_method, done **=>** iterator, next_method, true, array
+    IteratorToArray,
+
+    /// Closes an iterator if it is not done.
+    ///
+    /// Operands:
+    ///
+    /// Stack: iterator, next_method, done **=>**
+    IteratorClose,
+
+    /// Closes an iterator if it is not done, then rethrows the exception.
+    ///
+    /// Errors thrown while closing the iterator are ignored.
+    ///
+    /// Operands:
+    ///
+    /// Stack: iterator, next_method, done, exception **=>**
+    IteratorCloseWithException,
+
+    /// Moves the value on the top of the stack below the `count` values beneath it.
+    ///
+    /// Operands: count: `u32`
+    ///
+    /// Stack: value_1, ... value_count, value **=>** value, value_1, ... value_count
+    RotateDown,
+
+    /// Concatenates the string conversions of the values, for a template literal.
+    ///
+    /// Operands: value_count: `u32`
+    ///
+    /// Stack: value_1, ... value_n **=>** string
+    ConcatToString,
+
+    /// Push the template object of a tagged template.
+    ///
+    /// Operands: index: `u32`
+    ///
+    /// Stack: **=>** template_object
+    GetTemplateObject,
+
+    /// Pops the value of an expression statement and stores it as the completion value of the
+    /// call frame, which is returned once the end of the code block is reached.
+    ///
+    /// Operands:
+    ///
+    /// Stack: value **=>**
+    SetCompletionValue,
+
+    /// Jumps to an address if the first value is not less than the second value.
+    ///
+    /// Superinstruction for `LessThan` followed by `JumpIfFalse`.
+    ///
+    /// Operands: address: `u32`
+    ///
+    /// Stack: lhs, rhs **=>**
+    JumpIfNotLessThan,
+
+    /// Returns `undefined` from the function.
+    ///
+    /// Superinstruction for `PushUndefined` followed by `Return`.
+    ///
+    /// Operands:
+    ///
+    /// Stack: **=>**
+    ReturnUndefined,
+
     /// No-operation instruction, does nothing.
     ///
     /// Operands:
@@ -632,6 +893,8 @@ impl Opcode {
             Opcode::PushLiteral => "PushLiteral",
             Opcode::PushEmptyObject => "PushEmptyObject",
             Opcode::PushNewArray => "PushNewArray",
+            Opcode::PushValueToArray => "PushValueToArray",
+            Opcode::PushIteratorToArray => "PushIteratorToArray",
             Opcode::Add => "Add",
             Opcode::Sub => "Sub",
             Opcode::Div => "Div",
@@ -671,6 +934,9 @@ impl Opcode {
             Opcode::InitLexical => "InitLexical",
             Opcode::GetName => "GetName",
             Opcode::SetName => "SetName",
+            Opcode::GetBinding => "GetBinding",
+            Opcode::SetBinding => "SetBinding",
+            Opcode::InitBinding => "InitBinding",
             Opcode::GetPropertyByName => "GetPropertyByName",
             Opcode::GetPropertyByValue => "GetPropertyByValue",
             Opcode::SetPropertyByName => "SetPropertyByName",
@@ -681,6 +947,7 @@ impl Opcode {
             Opcode::SetPropertySetterByValue => "SetPropertySetterByValue",
             Opcode::DeletePropertyByName => "DeletePropertyByName",
             Opcode::DeletePropertyByValue => "DeletePropertyByValue",
+            Opcode::DeleteName => "DeleteName",
             Opcode::Jump => "Jump",
             Opcode::JumpIfFalse => "JumpIfFalse",
             Opcode::JumpIfTrue => "JumpIfTrue",
@@ -691,10 +958,206 @@ impl Opcode {
             Opcode::Default => "Default",
             Opcode::GetFunction => "GetFunction",
             Opcode::Call => "Call",
+            Opcode::CallSpread => "CallSpread",
+            Opcode::New => "New",
+            Opcode::NewSpread => "NewSpread",
             Opcode::Return => "Return",
+            Opcode::PushDeclarativeEnvironment => "PushDeclarativeEnvironment",
+            Opcode::PopEnvironment => "PopEnvironment",
+            Opcode::TryStart => "TryStart",
+            Opcode::TryEnd => "TryEnd",
+            Opcode::FinallyStart => "FinallyStart",
+            Opcode::FinallyEnd => "FinallyEnd",
+            Opcode::FinallySetJump => "FinallySetJump",
+            Opcode::FinallyDiscard => "FinallyDiscard",
+            Opcode::JumpIfNotUndefined => "JumpIfNotUndefined",
+            Opcode::ValueNotNullOrUndefined => "ValueNotNullOrUndefined",
+            Opcode::CopyDataProperties => "CopyDataProperties",
+            Opcode::GetIterator => "GetIterator",
+            Opcode::ForInLoopInitIterator => "ForInLoopInitIterator",
+            Opcode::IteratorStep => "IteratorStep",
+            Opcode::IteratorNext => "IteratorNext",
+            Opcode::IteratorToArray => "IteratorToArray",
+            Opcode::IteratorClose => "IteratorClose",
+            Opcode::IteratorCloseWithException => "IteratorCloseWithException",
+            Opcode::RotateDown => "RotateDown",
+            Opcode::ConcatToString => "ConcatToString",
+            Opcode::GetTemplateObject => "GetTemplateObject",
+            Opcode::SetCompletionValue => "SetCompletionValue",
+            Opcode::JumpIfNotLessThan => "JumpIfNotLessThan",
+            Opcode::ReturnUndefined => "ReturnUndefined",
             Opcode::Nop => "Nop",
         }
     }
+
+    /// Gets the kinds of the operands of the instruction.
+    pub(crate) fn operand_kinds(self) -> &'static [OperandKind] {
+        match self {
+            Opcode::PushInt8 => &[OperandKind::I8],
+            Opcode::PushInt16 => &[OperandKind::I16],
+            Opcode::PushInt32 => &[OperandKind::I32],
+            Opcode::PushRational => &[OperandKind::F64],
+            Opcode::PushLiteral => &[OperandKind::Literal],
+            Opcode::PushNewArray
+            | Opcode::CopyDataProperties
+            | Opcode::RotateDown
+            | Opcode::ConcatToString
+            | Opcode::Call => &[OperandKind::Count],
+            Opcode::Jump
+            | Opcode::JumpIfFalse
+            | Opcode::JumpIfTrue
+            | Opcode::Case
+            | Opcode::Default
+            | Opcode::LogicalAnd
+            | Opcode::LogicalOr
+            | Opcode::Coalesce
+            | Opcode::FinallySetJump
+            | Opcode::JumpIfNotUndefined
+            | Opcode::IteratorStep
+            | Opcode::JumpIfNotLessThan => &[OperandKind::Address],
+            Opcode::New => &[OperandKind::Count, OperandKind::Name],
+            Opcode::TryStart => &[OperandKind::OptionalAddress, OperandKind::OptionalAddress],
+            Opcode::GetFunction => &[OperandKind::Function],
+            Opcode::GetTemplateObject => &[OperandKind::Template],
+            Opcode::GetBinding | Opcode::SetBinding | Opcode::InitBinding => {
+                &[OperandKind::Depth, OperandKind::Slot]
+            }
+            Opcode::PushDeclarativeEnvironment => &[OperandKind::Scope],
+            Opcode::DefVar
+            | Opcode::DefLet
+            | Opcode::DefConst
+            | Opcode::InitLexical
+            | Opcode::GetName
+            | Opcode::SetName
+            | Opcode::GetPropertyByName
+            | Opcode::SetPropertyByName
+            | Opcode::SetPropertyGetterByName
+            | Opcode::SetPropertySetterByName
+            | Opcode::DeletePropertyByName
+            | Opcode::DeleteName
+            | Opcode::NewSpread => &[OperandKind::Name],
+            Opcode::Pop
+            | Opcode::Dup
+            | Opcode::Swap
+            | Opcode::PushZero
+            | Opcode::PushOne
+            | Opcode::PushNaN
+            | Opcode::PushPositiveInfinity
+            | Opcode::PushNegativeInfinity
+            | Opcode::PushNull
+            | Opcode::PushTrue
+            | Opcode::PushFalse
+            | Opcode::PushUndefined
+            | Opcode::PushEmptyObject
+            | Opcode::PushValueToArray
+            | Opcode::PushIteratorToArray
+            | Opcode::Add
+            | Opcode::Sub
+            | Opcode::Div
+            | Opcode::Mul
+            | Opcode::Mod
+            | Opcode::Pow
+            | Opcode::ShiftRight
+            | Opcode::ShiftLeft
+            | Opcode::UnsignedShiftRight
+            | Opcode::BitOr
+            | Opcode::BitAnd
+            | Opcode::BitXor
+            | Opcode::BitNot
+            | Opcode::In
+            | Opcode::Eq
+            | Opcode::StrictEq
+            | Opcode::NotEq
+            | Opcode::StrictNotEq
+            | Opcode::GreaterThan
+            | Opcode::GreaterThanOrEq
+            | Opcode::LessThan
+            | Opcode::LessThanOrEq
+            | Opcode::InstanceOf
+            | Opcode::TypeOf
+            | Opcode::Void
+            | Opcode::LogicalNot
+            | Opcode::Pos
+            | Opcode::Neg
+            | Opcode::Inc
+            | Opcode::Dec
+            | Opcode::GetPropertyByValue
+            | Opcode::SetPropertyByValue
+            | Opcode::SetPropertyGetterByValue
+            | Opcode::SetPropertySetterByValue
+            | Opcode::DeletePropertyByValue
+            | Opcode::Throw
+            | Opcode::ToBoolean
+            | Opcode::This
+            | Opcode::CallSpread
+            | Opcode::Return
+            | Opcode::PopEnvironment
+            | Opcode::TryEnd
+            | Opcode::FinallyStart
+            | Opcode::FinallyEnd
+            | Opcode::FinallyDiscard
+            | Opcode::ValueNotNullOrUndefined
+            | Opcode::GetIterator
+            | Opcode::ForInLoopInitIterator
+            | Opcode::IteratorNext
+            | Opcode::IteratorToArray
+            | Opcode::IteratorClose
+            | Opcode::IteratorCloseWithException
+            | Opcode::SetCompletionValue
+            | Opcode::ReturnUndefined
+            | Opcode::Nop => &[],
+        }
+    }
+}
+
+/// The kinds of operands of the instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OperandKind {
+    /// An `i8` value.
+    I8,
+    /// An `i16` value.
+    I16,
+    /// An `i32` value.
+    I32,
+    /// An `f64` value.
+    F64,
+    /// A `u32` count of values.
+    Count,
+    /// A `u32` index into the literals.
+    Literal,
+    /// A `u32` index into the names.
+    Name,
+    /// A `u32` index into the functions.
+    Function,
+    /// A `u32` index into the scopes.
+    Scope,
+    /// A `u32` index into the tagged templates.
+    Template,
+    /// A `u32` number of environments to walk up the environment chain.
+    Depth,
+    /// A `u32` index into the bindings of an environment.
+    Slot,
+    /// A `u32` address in the code.
+    Address,
+    /// A `u32` address in the code, or `u32::MAX` for no address.
+    OptionalAddress,
+}
+
+impl OperandKind {
+    /// Gets the size in bytes of the operand.
+    pub(crate) fn size(self) -> usize {
+        match self {
+            Self::I8 => size_of::<i8>(),
+            Self::I16 => size_of::<i16>(),
+            Self::F64 => size_of::<f64>(),
+            _ => size_of::<u32>(),
+        }
+    }
+
+    /// Checks if the operand is an address in the code.
+    pub(crate) fn is_address(self) -> bool {
+        matches!(self, Self::Address | Self::OptionalAddress)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -0,0 +1,528 @@
+//! This module implements the optimization passes over the bytecode of a [`CodeBlock`].
+//!
+//! The bytecode is decoded into a list of instructions, where the addresses of the jumps are
+//! indices into the list. Each pass rewrites the list, replacing the instructions it removes with
+//! `Nop`s, which are dropped before the list is encoded back into bytecode.
+
+use crate::{
+    vm::{opcode::OperandKind, CodeBlock, Opcode},
+    Context, JsValue,
+};
+use gc::Gc;
+use std::{collections::HashSet, convert::TryInto, mem::size_of};
+
+/// The optimizations applied to the bytecode of scripts compiled by a [`Context`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OptimizationLevel {
+    /// Executes the bytecode as emitted by the compiler.
+    None,
+    /// Folds constant expressions, threads jumps, removes dead code and redundant instructions.
+    Basic,
+    /// Applies the `Basic` optimizations, then fuses common pairs of instructions into
+    /// superinstructions.
+    Full,
+}
+
+impl Default for OptimizationLevel {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// The maximum number of times the passes are repeated, while they still find optimizations.
+const MAX_ITERATIONS: usize = 16;
+
+/// A decoded instruction.
+#[derive(Debug, Clone)]
+struct Instruction {
+    opcode: Opcode,
+    /// The operands, as the bits of their values.
+    ///
+    /// Addresses are indices of instructions, where the length of the list is the end of the code.
+    operands: Vec<u64>,
+}
+
+impl Instruction {
+    fn new(opcode: Opcode, operands: Vec<u64>) -> Self {
+        Self { opcode, operands }
+    }
+
+    fn is_nop(&self) -> bool {
+        self.opcode == Opcode::Nop
+    }
+
+    /// Gets the addresses that this instruction can jump to.
+    fn targets(&self) -> impl Iterator<Item = usize> + '_ {
+        self.opcode
+            .operand_kinds()
+            .iter()
+            .zip(&self.operands)
+            .filter(|(kind, operand)| kind.is_address() && **operand != u64::from(u32::MAX))
+            .map(|(_, operand)| *operand as usize)
+    }
+
+    /// Gets the value pushed by this instruction, if it pushes a primitive constant.
+    fn constant(&self) -> Option<JsValue> {
+        Some(match self.opcode {
+            Opcode::PushZero => JsValue::new(0),
+            Opcode::PushOne => JsValue::new(1),
+            Opcode::PushInt8 => JsValue::new(i32::from(self.operands[0] as u8 as i8)),
+            Opcode::PushInt16 => JsValue::new(i32::from(self.operands[0] as u16 as i16)),
+            Opcode::PushInt32 => JsValue::new(self.operands[0] as u32 as i32),
+            Opcode::PushRational => JsValue::new(f64::from_bits(self.operands[0])),
+            Opcode::PushNaN => JsValue::nan(),
+            Opcode::PushPositiveInfinity => JsValue::positive_infinity(),
+            Opcode::PushNegativeInfinity => JsValue::negative_infinity(),
+            Opcode::PushNull => JsValue::null(),
+            Opcode::PushUndefined => JsValue::undefined(),
+            Opcode::PushTrue => JsValue::new(true),
+            Opcode::PushFalse => JsValue::new(false),
+            _ => return None,
+        })
+    }
+
+    /// Creates an instruction that pushes a primitive constant, if it has one.
+    fn push(value: &JsValue) -> Option<Self> {
+        Some(match *value {
+            JsValue::Integer(0) => Self::new(Opcode::PushZero, Vec::new()),
+            JsValue::Integer(1) => Self::new(Opcode::PushOne, Vec::new()),
+            JsValue::Integer(x) if x as i8 as i32 == x => {
+                Self::new(Opcode::PushInt8, vec![u64::from(x as i8 as u8)])
+            }
+            JsValue::Integer(x) if x as i16 as i32 == x => {
+                Self::new(Opcode::PushInt16, vec![u64::from(x as i16 as u16)])
+            }
+            JsValue::Integer(x) => Self::new(Opcode::PushInt32, vec![u64::from(x as u32)]),
+            JsValue::Rational(x) if x.is_nan() => Self::new(Opcode::PushNaN, Vec::new()),
+            JsValue::Rational(x) if x == f64::INFINITY => {
+                Self::new(Opcode::PushPositiveInfinity, Vec::new())
+            }
+            JsValue::Rational(x) if x == f64::NEG_INFINITY => {
+                Self::new(Opcode::PushNegativeInfinity, Vec::new())
+            }
+            JsValue::Rational(x) => Self::new(Opcode::PushRational, vec![x.to_bits()]),
+            JsValue::Null => Self::new(Opcode::PushNull, Vec::new()),
+            JsValue::Undefined => Self::new(Opcode::PushUndefined, Vec::new()),
+            JsValue::Boolean(true) => Self::new(Opcode::PushTrue, Vec::new()),
+            JsValue::Boolean(false) => Self::new(Opcode::PushFalse, Vec::new()),
+            _ => return None,
+        })
+    }
+}
+
+impl Context {
+    /// Optimizes the bytecode of a code block and of the functions defined in it.
+    pub(crate) fn optimize(&mut self, code: &mut CodeBlock, level: OptimizationLevel) {
+        if level == OptimizationLevel::None {
+            return;
+        }
+
+        for function in &mut code.functions {
+            let mut optimized = CodeBlock::clone(function);
+            self.optimize(&mut optimized, level);
+            *function = Gc::new(optimized);
+        }
+
+        let mut optimizer = Optimizer::decode(code);
+        for _ in 0..MAX_ITERATIONS {
+            let mut changed = optimizer.fold_constants(self);
+            changed |= optimizer.remove_redundant_instructions();
+            changed |= optimizer.thread_jumps();
+            changed |= optimizer.remove_dead_code();
+            optimizer.compact();
+            if !changed {
+                break;
+            }
+        }
+        if level == OptimizationLevel::Full {
+            optimizer.fuse_superinstructions();
+            optimizer.compact();
+        }
+        optimizer.encode(code);
+    }
+}
+
+/// The instructions of a code block being optimized.
+#[derive(Debug)]
+struct Optimizer {
+    instructions: Vec<Instruction>,
+    /// The indices of the instructions that start a statement, with the statement positions.
+    source_map: Vec<(usize, crate::syntax::ast::Position)>,
+}
+
+impl Optimizer {
+    /// Decodes the bytecode of a code block.
+    fn decode(code: &CodeBlock) -> Self {
+        let mut instructions = Vec::new();
+        let mut locations = Vec::new();
+        let mut pc = 0;
+        while pc < code.code.len() {
+            locations.push(pc as u32);
+            let opcode: Opcode = code.code[pc].try_into().expect("invalid opcode");
+            pc += size_of::<Opcode>();
+            let operands = opcode
+                .operand_kinds()
+                .iter()
+                .map(|kind| {
+                    let operand = match kind {
+                        OperandKind::I8 => u64::from(code.read::<u8>(pc)),
+                        OperandKind::I16 => u64::from(code.read::<u16>(pc)),
+                        OperandKind::F64 => code.read::<u64>(pc),
+                        _ => u64::from(code.read::<u32>(pc)),
+                    };
+                    pc += kind.size();
+                    operand
+                })
+                .collect();
+            instructions.push(Instruction::new(opcode, operands));
+        }
+        locations.push(code.code.len() as u32);
+
+        // Addresses become indices of instructions.
+        let index = |address: u32| {
+            locations
+                .binary_search(&address)
+                .expect("jump to the middle of an instruction")
+        };
+        for instruction in &mut instructions {
+            for (kind, operand) in instruction
+                .opcode
+                .operand_kinds()
+                .iter()
+                .zip(&mut instruction.operands)
+            {
+                if kind.is_address() && *operand != u64::from(u32::MAX) {
+                    *operand = index(*operand as u32) as u64;
+                }
+            }
+        }
+        let source_map = code
+            .source_map
+            .iter()
+            .map(|(location, position)| (index(*location), *position))
+            .collect();
+
+        Self {
+            instructions,
+            source_map,
+        }
+    }
+
+    /// Encodes the instructions into the bytecode of the code block.
+    fn encode(self, code: &mut CodeBlock) {
+        let mut locations = Vec::with_capacity(self.instructions.len() + 1);
+        let mut location = 0;
+        for instruction in &self.instructions {
+            locations.push(location as u32);
+            location += size_of::<Opcode>();
+            location += instruction
+                .opcode
+                .operand_kinds()
+                .iter()
+                .map(|kind| kind.size())
+                .sum::<usize>();
+        }
+        locations.push(location as u32);
+
+        let mut bytes = Vec::with_capacity(location);
+        for instruction in &self.instructions {
+            bytes.push(instruction.opcode as u8);
+            for (kind, operand) in instruction
+                .opcode
+                .operand_kinds()
+                .iter()
+                .zip(&instruction.operands)
+            {
+                match kind {
+                    OperandKind::I8 => bytes.push(*operand as u8),
+                    OperandKind::I16 => bytes.extend(&(*operand as u16).to_ne_bytes()),
+                    OperandKind::F64 => bytes.extend(&operand.to_ne_bytes()),
+                    _ if kind.is_address() && *operand != u64::from(u32::MAX) => {
+                        bytes.extend(&locations[*operand as usize].to_ne_bytes())
+                    }
+                    _ => bytes.extend(&(*operand as u32).to_ne_bytes()),
+                }
+            }
+        }
+        code.code = bytes;
+
+        code.source_map.clear();
+        for (index, position) in self.source_map {
+            let location = locations[index];
+            match code.source_map.last_mut() {
+                Some((last, last_position)) if *last == location => *last_position = position,
+                _ => code.source_map.push((location, position)),
+            }
+        }
+    }
+
+    /// Gets the indices of the instructions that are the target of a jump.
+    fn jump_targets(&self) -> HashSet<usize> {
+        self.instructions
+            .iter()
+            .flat_map(Instruction::targets)
+            .collect()
+    }
+
+    /// Replaces the instructions at the indices with `Nop`s.
+    fn remove(&mut self, indices: std::ops::Range<usize>) {
+        for instruction in &mut self.instructions[indices] {
+            *instruction = Instruction::new(Opcode::Nop, Vec::new());
+        }
+    }
+
+    /// Drops the `Nop`s, moving the jumps to a `Nop` to the next instruction.
+    fn compact(&mut self) {
+        let mut indices = Vec::with_capacity(self.instructions.len() + 1);
+        let mut index = 0;
+        for instruction in &self.instructions {
+            indices.push(index);
+            if !instruction.is_nop() {
+                index += 1;
+            }
+        }
+        indices.push(index);
+
+        self.instructions
+            .retain(|instruction| !instruction.is_nop());
+        for instruction in &mut self.instructions {
+            for (kind, operand) in instruction
+                .opcode
+                .operand_kinds()
+                .iter()
+                .zip(&mut instruction.operands)
+            {
+                if kind.is_address() && *operand != u64::from(u32::MAX) {
+                    *operand = indices[*operand as usize] as u64;
+                }
+            }
+        }
+        for (index, _) in &mut self.source_map {
+            *index = indices[*index];
+        }
+    }
+
+    /// Evaluates the operations on constants at compile time.
+    ///
+    /// Only the operations that cannot run user code, the ones on primitive values, are folded.
+    fn fold_constants(&mut self, context: &mut Context) -> bool {
+        let targets = self.jump_targets();
+        let mut changed = false;
+        let mut i = 0;
+        while i < self.instructions.len() {
+            let lhs = if let Some(value) = self.instructions[i].constant() {
+                value
+            } else {
+                i += 1;
+                continue;
+            };
+
+            // A binary operation on two constants.
+            let rhs = self.instructions.get(i + 1).and_then(Instruction::constant);
+            let operation = self.instructions.get(i + 2).map(|next| next.opcode);
+            if let (Some(rhs), Some(operation)) = (rhs, operation) {
+                if !targets.contains(&(i + 1)) && !targets.contains(&(i + 2)) {
+                    if let Some(push) = fold_binary(operation, &lhs, &rhs, context)
+                        .as_ref()
+                        .and_then(Instruction::push)
+                    {
+                        self.instructions[i] = push;
+                        self.remove(i + 1..i + 3);
+                        changed = true;
+                        i += 3;
+                        continue;
+                    }
+                }
+            }
+
+            // An unary operation on a constant, or a conditional jump on a constant.
+            let operation = match self.instructions.get(i + 1) {
+                Some(next) if !targets.contains(&(i + 1)) => next.clone(),
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+            match operation.opcode {
+                Opcode::JumpIfFalse | Opcode::JumpIfTrue => {
+                    if (operation.opcode == Opcode::JumpIfTrue) == lhs.to_boolean() {
+                        self.instructions[i] = Instruction::new(Opcode::Jump, operation.operands);
+                        self.remove(i + 1..i + 2);
+                    } else {
+                        self.remove(i..i + 2);
+                    }
+                    changed = true;
+                    i += 2;
+                    continue;
+                }
+                opcode => {
+                    if let Some(push) = fold_unary(opcode, &lhs, context)
+                        .as_ref()
+                        .and_then(Instruction::push)
+                    {
+                        self.instructions[i] = push;
+                        self.remove(i + 1..i + 2);
+                        changed = true;
+                        i += 2;
+                        continue;
+                    }
+                }
+            }
+
+            i += 1;
+        }
+        changed
+    }
+
+    /// Removes pairs of instructions that have no effect, like a `Dup` followed by a `Pop`, and
+    /// jumps to the next instruction.
+    fn remove_redundant_instructions(&mut self) -> bool {
+        let targets = self.jump_targets();
+        let mut changed = false;
+        for i in 0..self.instructions.len() {
+            let instruction = &self.instructions[i];
+            if instruction.opcode == Opcode::Jump && instruction.operands[0] as usize == i + 1 {
+                self.remove(i..i + 1);
+                changed = true;
+                continue;
+            }
+
+            let pushes_value =
+                instruction.opcode == Opcode::Dup || instruction.constant().is_some();
+            let popped = matches!(
+                self.instructions.get(i + 1),
+                Some(next) if next.opcode == Opcode::Pop
+            );
+            if pushes_value && popped && !targets.contains(&(i + 1)) {
+                self.remove(i..i + 2);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Makes the jumps to an unconditional jump go directly to its destination.
+    fn thread_jumps(&mut self) -> bool {
+        let mut changed = false;
+        for i in 0..self.instructions.len() {
+            if !matches!(
+                self.instructions[i].opcode,
+                Opcode::Jump | Opcode::JumpIfFalse | Opcode::JumpIfTrue | Opcode::JumpIfNotLessThan
+            ) {
+                continue;
+            }
+
+            let mut target = self.instructions[i].operands[0] as usize;
+            // Limits the threading, in case the jumps form a loop.
+            for _ in 0..self.instructions.len() {
+                match self.instructions.get(target) {
+                    Some(next)
+                        if next.opcode == Opcode::Jump && next.operands[0] as usize != target =>
+                    {
+                        target = next.operands[0] as usize;
+                    }
+                    _ => break,
+                }
+            }
+
+            if target != self.instructions[i].operands[0] as usize {
+                self.instructions[i].operands[0] = target as u64;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Removes the unreachable instructions after an unconditional jump, a return or a throw.
+    fn remove_dead_code(&mut self) -> bool {
+        let targets = self.jump_targets();
+        let mut changed = false;
+        let mut reachable = true;
+        for i in 0..self.instructions.len() {
+            if targets.contains(&i) {
+                reachable = true;
+            }
+            if !reachable {
+                if !self.instructions[i].is_nop() {
+                    self.remove(i..i + 1);
+                    changed = true;
+                }
+                continue;
+            }
+            if matches!(
+                self.instructions[i].opcode,
+                Opcode::Jump | Opcode::Return | Opcode::ReturnUndefined | Opcode::Throw
+            ) {
+                reachable = false;
+            }
+        }
+        changed
+    }
+
+    /// Fuses common pairs of instructions into superinstructions.
+    fn fuse_superinstructions(&mut self) {
+        let targets = self.jump_targets();
+        for i in 0..self.instructions.len().saturating_sub(1) {
+            if targets.contains(&(i + 1)) {
+                continue;
+            }
+            let fused = match (self.instructions[i].opcode, self.instructions[i + 1].opcode) {
+                (Opcode::LessThan, Opcode::JumpIfFalse) => Instruction::new(
+                    Opcode::JumpIfNotLessThan,
+                    self.instructions[i + 1].operands.clone(),
+                ),
+                (Opcode::PushUndefined, Opcode::Return) => {
+                    Instruction::new(Opcode::ReturnUndefined, Vec::new())
+                }
+                _ => continue,
+            };
+            self.instructions[i] = fused;
+            self.remove(i + 1..i + 2);
+        }
+    }
+}
+
+/// Evaluates a binary operation on two primitive constants.
+fn fold_binary(
+    opcode: Opcode,
+    lhs: &JsValue,
+    rhs: &JsValue,
+    context: &mut Context,
+) -> Option<JsValue> {
+    let result = match opcode {
+        Opcode::Add => lhs.add(rhs, context),
+        Opcode::Sub => lhs.sub(rhs, context),
+        Opcode::Mul => lhs.mul(rhs, context),
+        Opcode::Div => lhs.div(rhs, context),
+        Opcode::Pow => lhs.pow(rhs, context),
+        Opcode::Mod => lhs.rem(rhs, context),
+        Opcode::BitAnd => lhs.bitand(rhs, context),
+        Opcode::BitOr => lhs.bitor(rhs, context),
+        Opcode::BitXor => lhs.bitxor(rhs, context),
+        Opcode::ShiftLeft => lhs.shl(rhs, context),
+        Opcode::ShiftRight => lhs.shr(rhs, context),
+        Opcode::UnsignedShiftRight => lhs.ushr(rhs, context),
+        Opcode::Eq => lhs.equals(rhs, context).map(JsValue::new),
+        Opcode::NotEq => lhs.equals(rhs, context).map(|value| JsValue::new(!value)),
+        Opcode::StrictEq => Ok(JsValue::new(lhs.strict_equals(rhs))),
+        Opcode::StrictNotEq => Ok(JsValue::new(!lhs.strict_equals(rhs))),
+        Opcode::GreaterThan => lhs.gt(rhs, context).map(JsValue::new),
+        Opcode::GreaterThanOrEq => lhs.ge(rhs, context).map(JsValue::new),
+        Opcode::LessThan => lhs.lt(rhs, context).map(JsValue::new),
+        Opcode::LessThanOrEq => lhs.le(rhs, context).map(JsValue::new),
+        _ => return None,
+    };
+    result.ok()
+}
+
+/// Evaluates an unary operation on a primitive constant.
+fn fold_unary(opcode: Opcode, value: &JsValue, context: &mut Context) -> Option<JsValue> {
+    let result = match opcode {
+        Opcode::Neg => value.neg(context),
+        Opcode::Pos => value.to_number(context).map(JsValue::new),
+        Opcode::LogicalNot => Ok(JsValue::new(!value.to_boolean())),
+        Opcode::Void => Ok(JsValue::undefined()),
+        _ => return None,
+    };
+    result.ok()
+}
@@ -0,0 +1,1467 @@
+//! Compiles scripts and function bodies to register code.
+//!
+//! Registers are allocated like a stack: the local variables of a scope get the registers
+//! above the ones that are in use when the scope is entered, and the temporary values of an
+//! expression are released once the expression has been compiled. The number of registers of
+//! a code block is the largest number of registers in use at any point.
+//!
+//! Local variables are only kept in registers if no closure or `eval` can access them, that
+//! is if the code block does not define functions and does not reference `arguments` or
+//! `eval`. Otherwise all bindings stay in environments, and only temporary values use
+//! registers.
+
+use super::{Instruction, Register, RegisterCodeBlock};
+use crate::{
+    syntax::ast::{
+        node::{
+            iteration::ForLoop, Call, Declaration, DeclarationList, FormalParameter, New,
+            PropertyDefinition, PropertyName, StatementList,
+        },
+        op::{AssignOp, BinOp, BitOp, CompOp, LogOp, NumOp, UnaryOp},
+        Const, Node,
+    },
+    JsString, JsValue,
+};
+use gc::Gc;
+use std::collections::HashMap;
+
+/// The indices of the functions of a code block, by the address of their syntax node.
+///
+/// The functions are compiled by the bytecode compiler, the register code shares them with
+/// the bytecode.
+pub(crate) type FunctionIndices = HashMap<*const Node, u32>;
+
+/// The reasons why a code block can not be compiled to register code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompileError {
+    /// The local variables may be accessed by closures or `eval`, so they have to be bound in
+    /// environments.
+    NeedsEnvironment,
+    /// The code uses a feature that the register compiler does not support.
+    Unsupported,
+}
+
+type CompileResult<T> = Result<T, CompileError>;
+
+/// A local variable that lives in a register.
+#[derive(Debug, Clone, Copy)]
+struct Binding {
+    register: Register,
+    mutable: bool,
+    /// Whether the declaration of the binding has been compiled. Without closures, a binding
+    /// can only be accessed before its initialization by code that comes before its
+    /// declaration.
+    initialized: bool,
+}
+
+/// The target of an assignment, with its object and key already evaluated.
+#[derive(Debug, Clone, Copy)]
+enum Reference {
+    Local { binding: Binding, name: u32 },
+    Name { name: u32 },
+    Property { object: Register, name: u32 },
+    Element { object: Register, key: Register },
+}
+
+#[derive(Debug)]
+struct JumpInfo {
+    label: Option<Box<str>>,
+    breaks: Vec<usize>,
+    continues: Vec<usize>,
+    /// The number of environments pushed in the code block when the loop was entered.
+    env_depth: u32,
+}
+
+struct RegisterCompiler<'a> {
+    instructions: Vec<Instruction>,
+    literals: Vec<JsValue>,
+    literals_map: HashMap<JsString, u32>,
+    names: Vec<JsString>,
+    names_map: HashMap<JsString, u32>,
+    functions: &'a FunctionIndices,
+    locals_in_registers: bool,
+    parameters_in_registers: bool,
+    scopes: Vec<HashMap<Box<str>, Binding>>,
+    next_register: Register,
+    register_count: u32,
+    jump_info: Vec<JumpInfo>,
+    env_depth: u32,
+    /// The register that holds the completion value of a script.
+    completion: Option<Register>,
+}
+
+impl RegisterCodeBlock {
+    /// Compiles a script to register code.
+    ///
+    /// Returns `None` if the script uses a feature that the register compiler does not
+    /// support.
+    pub(crate) fn compile_script(
+        list: &StatementList,
+        functions: &FunctionIndices,
+    ) -> Option<Gc<Self>> {
+        Self::compile_with(functions, |compiler| compiler.script(list))
+    }
+
+    /// Compiles the body of a function to register code.
+    ///
+    /// Returns `None` if the function uses a feature that the register compiler does not
+    /// support.
+    pub(crate) fn compile_function(
+        parameters: &[FormalParameter],
+        body: &StatementList,
+        functions: &FunctionIndices,
+    ) -> Option<Gc<Self>> {
+        Self::compile_with(functions, |compiler| compiler.function(parameters, body))
+    }
+
+    fn compile_with<F>(functions: &FunctionIndices, compile: F) -> Option<Gc<Self>>
+    where
+        F: Fn(&mut RegisterCompiler<'_>) -> CompileResult<()>,
+    {
+        for locals_in_registers in [true, false] {
+            let mut compiler = RegisterCompiler::new(functions, locals_in_registers);
+            match compile(&mut compiler) {
+                Ok(()) => return Some(Gc::new(compiler.finish())),
+                Err(CompileError::NeedsEnvironment) if locals_in_registers => {}
+                Err(_) => return None,
+            }
+        }
+        None
+    }
+}
+
+impl<'a> RegisterCompiler<'a> {
+    /// Represents a placeholder jump target that will be patched later.
+    const DUMMY_TARGET: u32 = u32::MAX;
+
+    fn new(functions: &'a FunctionIndices, locals_in_registers: bool) -> Self {
+        Self {
+            instructions: Vec::new(),
+            literals: Vec::new(),
+            literals_map: HashMap::new(),
+            names: Vec::new(),
+            names_map: HashMap::new(),
+            functions,
+            locals_in_registers,
+            parameters_in_registers: false,
+            scopes: Vec::new(),
+            next_register: 0,
+            register_count: 0,
+            jump_info: Vec::new(),
+            env_depth: 0,
+            completion: None,
+        }
+    }
+
+    fn finish(self) -> RegisterCodeBlock {
+        RegisterCodeBlock {
+            instructions: self.instructions.into_boxed_slice(),
+            register_count: self.register_count,
+            parameters_in_registers: self.parameters_in_registers,
+            literals: self.literals.into_boxed_slice(),
+            names: self.names.into_boxed_slice(),
+        }
+    }
+
+    /// Compiles a script, the declarations of its top level are bound in the global
+    /// environment so that they can be accessed by other scripts.
+    fn script(&mut self, list: &StatementList) -> CompileResult<()> {
+        let completion = self.alloc();
+        self.completion = Some(completion);
+        self.emit(Instruction::LoadUndefined { dst: completion });
+
+        self.statement_list(list)?;
+
+        self.emit(Instruction::Return { src: completion });
+        Ok(())
+    }
+
+    fn function(
+        &mut self,
+        parameters: &[FormalParameter],
+        body: &StatementList,
+    ) -> CompileResult<()> {
+        if parameters
+            .iter()
+            .any(|parameter| parameter.init().is_some())
+        {
+            return Err(CompileError::Unsupported);
+        }
+
+        if self.locals_in_registers {
+            if parameters.iter().any(FormalParameter::is_rest_param) {
+                return Err(CompileError::NeedsEnvironment);
+            }
+
+            // The arguments are passed in the first registers, the `var` declarations are
+            // hoisted to the registers that follow.
+            let mut scope = HashMap::new();
+            for parameter in parameters {
+                let register = self.alloc();
+                scope.insert(parameter.name().into(), Binding::variable(register));
+            }
+            let mut var_names = Vec::new();
+            for node in body.items() {
+                var_declared_names(node, &mut var_names)?;
+            }
+            for name in var_names {
+                if !scope.contains_key(name) {
+                    let register = self.alloc();
+                    scope.insert(name.into(), Binding::variable(register));
+                }
+            }
+            self.scopes.push(scope);
+            self.parameters_in_registers = true;
+
+            self.block(body)?;
+        } else {
+            // The lexical declarations of the body are bound in the function environment.
+            self.statement_list(body)?;
+        }
+
+        let undefined = self.alloc();
+        self.emit(Instruction::LoadUndefined { dst: undefined });
+        self.emit(Instruction::Return { src: undefined });
+        Ok(())
+    }
+
+    #[inline]
+    fn emit(&mut self, instruction: Instruction) -> usize {
+        self.instructions.push(instruction);
+        self.instructions.len() - 1
+    }
+
+    #[inline]
+    fn next_location(&self) -> u32 {
+        self.instructions.len() as u32
+    }
+
+    fn patch(&mut self, index: usize, location: u32) {
+        match &mut self.instructions[index] {
+            Instruction::Jump { target }
+            | Instruction::JumpIfTrue { target, .. }
+            | Instruction::JumpIfFalse { target, .. }
+            | Instruction::JumpIfNotNullish { target, .. }
+            | Instruction::JumpIfNotLessThan { target, .. } => *target = location,
+            instruction => unreachable!("not a jump: {:?}", instruction),
+        }
+    }
+
+    #[inline]
+    fn patch_here(&mut self, index: usize) {
+        let location = self.next_location();
+        self.patch(index, location);
+    }
+
+    #[inline]
+    fn jump(&mut self) -> usize {
+        self.emit(Instruction::Jump {
+            target: Self::DUMMY_TARGET,
+        })
+    }
+
+    /// Allocates a register on top of the registers in use.
+    #[inline]
+    fn alloc(&mut self) -> Register {
+        self.alloc_many(1)
+    }
+
+    /// Allocates `count` consecutive registers, returns the first one.
+    #[inline]
+    fn alloc_many(&mut self, count: u32) -> Register {
+        let register = self.next_register;
+        self.next_register += count;
+        self.register_count = self.register_count.max(self.next_register);
+        register
+    }
+
+    /// Releases the registers allocated since `mark`.
+    #[inline]
+    fn release(&mut self, mark: Register) {
+        self.next_register = mark;
+    }
+
+    /// Returns the register that receives the result of an expression.
+    #[inline]
+    fn target(&mut self, dst: Option<Register>) -> Register {
+        match dst {
+            Some(dst) => dst,
+            None => self.alloc(),
+        }
+    }
+
+    fn name_index(&mut self, name: &str) -> u32 {
+        if let Some(index) = self.names_map.get(name) {
+            return *index;
+        }
+
+        let name = JsString::new(name);
+        let index = self.names.len() as u32;
+        self.names.push(name.clone());
+        self.names_map.insert(name, index);
+        index
+    }
+
+    fn string_literal(&mut self, value: &str) -> u32 {
+        if let Some(index) = self.literals_map.get(value) {
+            return *index;
+        }
+
+        let value = JsString::new(value);
+        let index = self.literals.len() as u32;
+        self.literals.push(value.clone().into());
+        self.literals_map.insert(value, index);
+        index
+    }
+
+    fn function_index(&self, node: &Node) -> CompileResult<u32> {
+        if self.locals_in_registers {
+            return Err(CompileError::NeedsEnvironment);
+        }
+        self.functions
+            .get(&(node as *const Node))
+            .copied()
+            .ok_or(CompileError::Unsupported)
+    }
+
+    fn lookup(&self, name: &str) -> Option<Binding> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())
+    }
+
+    /// Returns `true` if `register` holds a local variable.
+    fn is_local(&self, register: Register) -> bool {
+        self.scopes
+            .iter()
+            .any(|scope| scope.values().any(|binding| binding.register == register))
+    }
+
+    /// Declares the lexical bindings of a statement list in a new scope.
+    fn push_scope(&mut self, list: &StatementList) -> CompileResult<()> {
+        let mut scope = HashMap::new();
+        for node in list.items() {
+            let (list, mutable) = match node {
+                Node::LetDeclList(list) => (list, true),
+                Node::ConstDeclList(list) => (list, false),
+                _ => continue,
+            };
+            for declaration in list.as_ref() {
+                let register = self.alloc();
+                let binding = Binding {
+                    register,
+                    mutable,
+                    initialized: false,
+                };
+                scope.insert(declaration_name(declaration)?.into(), binding);
+            }
+        }
+        self.scopes.push(scope);
+        Ok(())
+    }
+
+    fn pop_scope(&mut self) {
+        let scope = self.scopes.pop().expect("there must be a scope to pop");
+        if let Some(base) = scope.values().map(|binding| binding.register).min() {
+            self.release(base);
+        }
+    }
+
+    fn push_environment(&mut self) {
+        self.emit(Instruction::PushDeclarativeEnvironment);
+        self.env_depth += 1;
+    }
+
+    fn pop_environment(&mut self) {
+        self.emit(Instruction::PopEnvironment);
+        self.env_depth -= 1;
+    }
+
+    fn statement_list(&mut self, list: &StatementList) -> CompileResult<()> {
+        for node in list.items() {
+            self.statement(node)?;
+        }
+        Ok(())
+    }
+
+    /// Compiles the statements of a block, in a new scope.
+    fn block(&mut self, list: &StatementList) -> CompileResult<()> {
+        if self.locals_in_registers {
+            self.push_scope(list)?;
+            self.statement_list(list)?;
+            self.pop_scope();
+            return Ok(());
+        }
+
+        let has_lexical_declarations = list
+            .items()
+            .iter()
+            .any(|node| matches!(node, Node::LetDeclList(_) | Node::ConstDeclList(_)));
+        if has_lexical_declarations {
+            self.push_environment();
+        }
+        self.statement_list(list)?;
+        if has_lexical_declarations {
+            self.pop_environment();
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self, node: &Node) -> CompileResult<()> {
+        match node {
+            Node::VarDeclList(list) | Node::LetDeclList(list) | Node::ConstDeclList(list) => {
+                self.declaration_list(list)
+            }
+            Node::If(node) => {
+                let jelse = self.condition_jump(node.cond(), false)?;
+                self.statement(node.body())?;
+                match node.else_node() {
+                    None => self.patch_here(jelse),
+                    Some(else_body) => {
+                        let exit = self.jump();
+                        self.patch_here(jelse);
+                        self.statement(else_body)?;
+                        self.patch_here(exit);
+                    }
+                }
+                Ok(())
+            }
+            Node::WhileLoop(while_) => {
+                let start = self.next_location();
+                self.push_loop(while_.label());
+                let exit = self.condition_jump(while_.cond(), false)?;
+                self.statement(while_.body())?;
+                self.emit(Instruction::Jump { target: start });
+                self.patch_here(exit);
+                self.pop_loop(start);
+                Ok(())
+            }
+            Node::DoWhileLoop(do_while) => {
+                let start = self.next_location();
+                self.push_loop(do_while.label());
+                self.statement(do_while.body())?;
+                let condition = self.next_location();
+                let jump = self.condition_jump(do_while.cond(), true)?;
+                self.patch(jump, start);
+                self.pop_loop(condition);
+                Ok(())
+            }
+            Node::ForLoop(for_loop) => self.for_loop(for_loop),
+            Node::Continue(node) => {
+                let target = self.find_jump_target(node.label())?;
+                self.leave_to(target);
+                let jump = self.jump();
+                self.jump_info[target].continues.push(jump);
+                Ok(())
+            }
+            Node::Break(node) => {
+                let target = self.find_jump_target(node.label())?;
+                self.leave_to(target);
+                let jump = self.jump();
+                self.jump_info[target].breaks.push(jump);
+                Ok(())
+            }
+            Node::Block(block) => self.block(block.statements()),
+            Node::Throw(throw) => {
+                let mark = self.next_register;
+                let src = self.expr(throw.expr(), None)?;
+                self.emit(Instruction::Throw { src });
+                self.release(mark);
+                Ok(())
+            }
+            Node::Return(ret) => {
+                let mark = self.next_register;
+                let src = if let Some(expr) = ret.expr() {
+                    self.expr(expr, None)?
+                } else {
+                    let dst = self.alloc();
+                    self.emit(Instruction::LoadUndefined { dst });
+                    dst
+                };
+                self.emit(Instruction::Return { src });
+                self.release(mark);
+                Ok(())
+            }
+            Node::FunctionDecl(function) => {
+                let index = self.function_index(node)?;
+                let name = self.name_index(function.name());
+                let mark = self.next_register;
+                let dst = self.alloc();
+                self.emit(Instruction::GetFunction { dst, index });
+                self.emit(Instruction::SetName { src: dst, name });
+                self.release(mark);
+                Ok(())
+            }
+            Node::Empty => Ok(()),
+            Node::Switch(_)
+            | Node::Try(_)
+            | Node::ForInLoop(_)
+            | Node::ForOfLoop(_)
+            | Node::AsyncFunctionDecl(_)
+            | Node::AsyncGeneratorDecl(_)
+            | Node::GeneratorDecl(_) => Err(CompileError::Unsupported),
+            expr => {
+                if let Some(completion) = self.completion {
+                    let mark = self.next_register;
+                    self.expr(expr, Some(completion))?;
+                    self.release(mark);
+                    Ok(())
+                } else {
+                    self.effect(expr)
+                }
+            }
+        }
+    }
+
+    fn for_loop(&mut self, for_loop: &ForLoop) -> CompileResult<()> {
+        // Without closures, the bindings of a `let` declaration do not have to be copied for
+        // each iteration, they can stay in the same registers.
+        let mut per_iteration_bindings = Vec::new();
+        let has_scope = match for_loop.init() {
+            Some(Node::LetDeclList(list)) | Some(Node::ConstDeclList(list)) => {
+                if self.locals_in_registers {
+                    let mutable = matches!(list, DeclarationList::Let(_));
+                    let mut scope = HashMap::new();
+                    for declaration in list.as_ref() {
+                        let binding = Binding {
+                            register: self.alloc(),
+                            mutable,
+                            initialized: false,
+                        };
+                        scope.insert(declaration_name(declaration)?.into(), binding);
+                    }
+                    self.scopes.push(scope);
+                } else {
+                    self.push_environment();
+                    if let DeclarationList::Let(declarations) = list {
+                        for declaration in declarations.iter() {
+                            per_iteration_bindings.push(declaration_name(declaration)?);
+                        }
+                    }
+                }
+                true
+            }
+            _ => false,
+        };
+
+        match for_loop.init() {
+            Some(Node::VarDeclList(list))
+            | Some(Node::LetDeclList(list))
+            | Some(Node::ConstDeclList(list)) => self.declaration_list(list)?,
+            Some(init) => self.effect(init)?,
+            None => {}
+        }
+        self.per_iteration_environment(&per_iteration_bindings);
+
+        let condition = self.jump();
+        let start = self.next_location();
+        self.push_loop(for_loop.label());
+
+        self.per_iteration_environment(&per_iteration_bindings);
+        if let Some(final_expr) = for_loop.final_expr() {
+            self.effect(final_expr)?;
+        }
+
+        self.patch_here(condition);
+        let exit = match for_loop.condition() {
+            Some(condition) => Some(self.condition_jump(condition, false)?),
+            None => None,
+        };
+
+        self.statement(for_loop.body())?;
+        self.emit(Instruction::Jump { target: start });
+
+        if let Some(exit) = exit {
+            self.patch_here(exit);
+        }
+        self.pop_loop(start);
+
+        if has_scope {
+            if self.locals_in_registers {
+                self.pop_scope();
+            } else {
+                self.pop_environment();
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the current environment with a new one that contains copies of `bindings`.
+    fn per_iteration_environment(&mut self, bindings: &[&str]) {
+        if bindings.is_empty() {
+            return;
+        }
+
+        let mark = self.next_register;
+        let start = self.alloc_many(bindings.len() as u32);
+        for (register, name) in (start..).zip(bindings) {
+            let name = self.name_index(name);
+            self.emit(Instruction::GetName {
+                dst: register,
+                name,
+            });
+        }
+        self.emit(Instruction::PopEnvironment);
+        self.emit(Instruction::PushDeclarativeEnvironment);
+        for (register, name) in (start..).zip(bindings) {
+            let name = self.name_index(name);
+            self.emit(Instruction::DefLet { name });
+            self.emit(Instruction::InitLexical {
+                src: register,
+                name,
+            });
+        }
+        self.release(mark);
+    }
+
+    fn push_loop(&mut self, label: Option<&str>) {
+        self.jump_info.push(JumpInfo {
+            label: label.map(Into::into),
+            breaks: Vec::new(),
+            continues: Vec::new(),
+            env_depth: self.env_depth,
+        });
+    }
+
+    /// Patches the `continue` jumps of the innermost loop to `continue_target`, and its
+    /// `break` jumps to the next instruction.
+    fn pop_loop(&mut self, continue_target: u32) {
+        let info = self.jump_info.pop().expect("there must be a loop to pop");
+        for jump in info.continues {
+            self.patch(jump, continue_target);
+        }
+        for jump in info.breaks {
+            self.patch_here(jump);
+        }
+    }
+
+    /// Finds the loop that is the target of a `break` or `continue` statement.
+    ///
+    /// Jumps out of labelled blocks and `switch` statements are not supported.
+    fn find_jump_target(&self, label: Option<&str>) -> CompileResult<usize> {
+        let target = match label {
+            Some(label) => self
+                .jump_info
+                .iter()
+                .rposition(|info| info.label.as_deref() == Some(label)),
+            None if self.jump_info.is_empty() => None,
+            None => Some(self.jump_info.len() - 1),
+        };
+        target.ok_or(CompileError::Unsupported)
+    }
+
+    /// Pops the environments that were pushed since the jump target was entered.
+    fn leave_to(&mut self, target: usize) {
+        for _ in self.jump_info[target].env_depth..self.env_depth {
+            self.emit(Instruction::PopEnvironment);
+        }
+    }
+
+    fn declaration_list(&mut self, list: &DeclarationList) -> CompileResult<()> {
+        for declaration in list.as_ref() {
+            let name = declaration_name(declaration)?;
+            let local = match list {
+                DeclarationList::Var(_) => self.lookup(name),
+                DeclarationList::Let(_) | DeclarationList::Const(_) => self
+                    .scopes
+                    .last()
+                    .and_then(|scope| scope.get(name).copied()),
+            };
+
+            let mark = self.next_register;
+            if let Some(binding) = local {
+                if let Some(init) = declaration.init() {
+                    if can_target(init) {
+                        self.expr(init, Some(binding.register))?;
+                    } else {
+                        let value = self.expr(init, None)?;
+                        self.emit(Instruction::Move {
+                            dst: binding.register,
+                            src: value,
+                        });
+                    }
+                } else if !matches!(list, DeclarationList::Var(_)) {
+                    self.emit(Instruction::LoadUndefined {
+                        dst: binding.register,
+                    });
+                }
+                if let Some(binding) = self
+                    .scopes
+                    .iter_mut()
+                    .rev()
+                    .find_map(|scope| scope.get_mut(name))
+                {
+                    binding.initialized = true;
+                }
+            } else {
+                let value = match declaration.init() {
+                    Some(init) => Some(self.expr(init, None)?),
+                    None => None,
+                };
+                let name = self.name_index(name);
+                match list {
+                    DeclarationList::Var(_) => {
+                        // `var` declarations without an initializer keep the current value.
+                        self.emit(Instruction::DefVar { name });
+                        if let Some(src) = value {
+                            self.emit(Instruction::SetName { src, name });
+                        }
+                    }
+                    DeclarationList::Let(_) | DeclarationList::Const(_) => {
+                        let src = if let Some(src) = value {
+                            src
+                        } else {
+                            let dst = self.alloc();
+                            self.emit(Instruction::LoadUndefined { dst });
+                            dst
+                        };
+                        if let DeclarationList::Let(_) = list {
+                            self.emit(Instruction::DefLet { name });
+                        } else {
+                            self.emit(Instruction::DefConst { name });
+                        }
+                        self.emit(Instruction::InitLexical { src, name });
+                    }
+                }
+            }
+            self.release(mark);
+        }
+        Ok(())
+    }
+
+    /// Compiles an expression whose value is not used.
+    fn effect(&mut self, node: &Node) -> CompileResult<()> {
+        let mark = self.next_register;
+        match node {
+            // The old value of a postfix update is not needed.
+            Node::UnaryOp(unary) if matches!(unary.op(), UnaryOp::IncrementPost) => {
+                self.update(unary.target(), true, true, None)?;
+            }
+            Node::UnaryOp(unary) if matches!(unary.op(), UnaryOp::DecrementPost) => {
+                self.update(unary.target(), false, true, None)?;
+            }
+            node => {
+                self.expr(node, None)?;
+            }
+        }
+        self.release(mark);
+        Ok(())
+    }
+
+    /// Emits a jump that is taken if the value of `cond` is `jump_if`.
+    fn condition_jump(&mut self, cond: &Node, jump_if: bool) -> CompileResult<usize> {
+        let mark = self.next_register;
+        let index = match cond {
+            Node::BinOp(binary)
+                if !jump_if && matches!(binary.op(), BinOp::Comp(CompOp::LessThan)) =>
+            {
+                let lhs = self.operand(binary.lhs(), &[binary.rhs()])?;
+                let rhs = self.expr(binary.rhs(), None)?;
+                self.emit(Instruction::JumpIfNotLessThan {
+                    lhs,
+                    rhs,
+                    target: Self::DUMMY_TARGET,
+                })
+            }
+            cond => {
+                let cond = self.expr(cond, None)?;
+                let target = Self::DUMMY_TARGET;
+                if jump_if {
+                    self.emit(Instruction::JumpIfTrue { cond, target })
+                } else {
+                    self.emit(Instruction::JumpIfFalse { cond, target })
+                }
+            }
+        };
+        self.release(mark);
+        Ok(index)
+    }
+
+    /// Compiles an operand that is followed by the expressions `later`.
+    ///
+    /// If the operand is a local variable that may be assigned by `later`, its current value is
+    /// copied to a temporary register.
+    fn operand(&mut self, node: &Node, later: &[&Node]) -> CompileResult<Register> {
+        let register = self.expr(node, None)?;
+        if self.is_local(register) && later.iter().any(|node| may_assign(node)) {
+            let dst = self.alloc();
+            self.emit(Instruction::Move { dst, src: register });
+            return Ok(dst);
+        }
+        Ok(register)
+    }
+
+    /// Compiles an expression, its value is placed in `dst` if it is given.
+    ///
+    /// Returns the register that holds the value, which can be the register of a local
+    /// variable if no `dst` is given.
+    fn expr(&mut self, node: &Node, dst: Option<Register>) -> CompileResult<Register> {
+        match node {
+            Node::Const(value) => {
+                let dst = self.target(dst);
+                let instruction = match value {
+                    Const::String(value) => Instruction::LoadLiteral {
+                        dst,
+                        index: self.string_literal(value),
+                    },
+                    Const::Int(value) => Instruction::LoadInteger { dst, value: *value },
+                    Const::Num(value) => Instruction::LoadRational { dst, value: *value },
+                    Const::BigInt(value) => {
+                        let index = self.literals.len() as u32;
+                        self.literals.push(value.clone().into());
+                        Instruction::LoadLiteral { dst, index }
+                    }
+                    Const::Bool(value) => Instruction::LoadBoolean { dst, value: *value },
+                    Const::Null => Instruction::LoadNull { dst },
+                    Const::Undefined => Instruction::LoadUndefined { dst },
+                };
+                self.emit(instruction);
+                Ok(dst)
+            }
+            Node::Identifier(_) => {
+                let reference = self.reference(node, &[])?;
+                Ok(self.get(reference, dst))
+            }
+            Node::This => {
+                let dst = self.target(dst);
+                self.emit(Instruction::This { dst });
+                Ok(dst)
+            }
+            Node::UnaryOp(unary) => {
+                let target = unary.target();
+                match unary.op() {
+                    UnaryOp::IncrementPre => return self.update(target, true, true, dst),
+                    UnaryOp::DecrementPre => return self.update(target, false, true, dst),
+                    UnaryOp::IncrementPost => return self.update(target, true, false, dst),
+                    UnaryOp::DecrementPost => return self.update(target, false, false, dst),
+                    UnaryOp::Delete => return Err(CompileError::Unsupported),
+                    _ => {}
+                }
+
+                let dst = self.target(dst);
+                let mark = self.next_register;
+                let src = self.expr(target, None)?;
+                self.emit(match unary.op() {
+                    UnaryOp::Minus => Instruction::Neg { dst, src },
+                    UnaryOp::Plus => Instruction::Pos { dst, src },
+                    UnaryOp::Not => Instruction::LogicalNot { dst, src },
+                    UnaryOp::Tilde => Instruction::BitNot { dst, src },
+                    UnaryOp::TypeOf => Instruction::TypeOf { dst, src },
+                    _ => Instruction::LoadUndefined { dst },
+                });
+                self.release(mark);
+                Ok(dst)
+            }
+            Node::BinOp(binary) => match binary.op() {
+                BinOp::Num(_) | BinOp::Bit(_) | BinOp::Comp(_) => {
+                    let dst = self.target(dst);
+                    let mark = self.next_register;
+                    let lhs = self.operand(binary.lhs(), &[binary.rhs()])?;
+                    let rhs = self.expr(binary.rhs(), None)?;
+                    self.emit(binary_instruction(binary.op(), dst, lhs, rhs));
+                    self.release(mark);
+                    Ok(dst)
+                }
+                BinOp::Log(op) => {
+                    let dst = self.target(dst);
+                    self.expr(binary.lhs(), Some(dst))?;
+                    let exit = self.short_circuit(op, dst);
+                    self.expr(binary.rhs(), Some(dst))?;
+                    self.patch_here(exit);
+                    Ok(dst)
+                }
+                BinOp::Assign(op) => self.compound_assign(op, binary.lhs(), binary.rhs(), dst),
+                BinOp::Comma => {
+                    self.effect(binary.lhs())?;
+                    self.expr(binary.rhs(), dst)
+                }
+            },
+            Node::Assign(assign) => self.assign(assign.lhs(), assign.rhs(), dst),
+            Node::GetConstField(_) | Node::GetField(_) => {
+                let dst = self.target(dst);
+                let mark = self.next_register;
+                let reference = self.reference(node, &[])?;
+                self.get(reference, Some(dst));
+                self.release(mark);
+                Ok(dst)
+            }
+            Node::ConditionalOp(op) => {
+                let dst = self.target(dst);
+                let jelse = self.condition_jump(op.cond(), false)?;
+                self.expr(op.if_true(), Some(dst))?;
+                let exit = self.jump();
+                self.patch_here(jelse);
+                self.expr(op.if_false(), Some(dst))?;
+                self.patch_here(exit);
+                Ok(dst)
+            }
+            Node::ArrayDecl(array) => {
+                let elements = array.as_ref();
+                if elements
+                    .iter()
+                    .any(|element| matches!(element, Node::Spread(_) | Node::Empty))
+                {
+                    return Err(CompileError::Unsupported);
+                }
+
+                let dst = self.target(dst);
+                let mark = self.next_register;
+                let start = self.alloc_many(elements.len() as u32);
+                for (register, element) in (start..).zip(elements) {
+                    self.expr(element, Some(register))?;
+                }
+                self.emit(Instruction::NewArray {
+                    dst,
+                    start,
+                    count: elements.len() as u32,
+                });
+                self.release(mark);
+                Ok(dst)
+            }
+            Node::Object(object) => {
+                let dst = self.target(dst);
+                self.emit(Instruction::NewObject { dst });
+                for property in object.properties() {
+                    let mark = self.next_register;
+                    match property {
+                        PropertyDefinition::IdentifierReference(name) => {
+                            let reference = self.identifier_reference(name)?;
+                            let src = self.get(reference, None);
+                            let name = self.name_index(name);
+                            self.emit(Instruction::SetPropertyByName {
+                                object: dst,
+                                name,
+                                src,
+                            });
+                        }
+                        PropertyDefinition::Property(PropertyName::Literal(name), node) => {
+                            let src = self.expr(node, None)?;
+                            let name = self.name_index(name);
+                            self.emit(Instruction::SetPropertyByName {
+                                object: dst,
+                                name,
+                                src,
+                            });
+                        }
+                        PropertyDefinition::Property(PropertyName::Computed(key), node) => {
+                            let key = self.operand(key, &[node])?;
+                            let src = self.expr(node, None)?;
+                            self.emit(Instruction::SetPropertyByValue {
+                                object: dst,
+                                key,
+                                src,
+                            });
+                        }
+                        PropertyDefinition::MethodDefinition(..)
+                        | PropertyDefinition::SpreadObject(_) => {
+                            return Err(CompileError::Unsupported)
+                        }
+                    }
+                    self.release(mark);
+                }
+                Ok(dst)
+            }
+            Node::Call(call) => self.call(call, dst),
+            Node::New(new) => self.new_object(new, dst),
+            Node::FunctionExpr(_) | Node::ArrowFunctionDecl(_) => {
+                let index = self.function_index(node)?;
+                let dst = self.target(dst);
+                self.emit(Instruction::GetFunction { dst, index });
+                Ok(dst)
+            }
+            _ => Err(CompileError::Unsupported),
+        }
+    }
+
+    /// Emits the jump of a logical operator, that skips its right-hand side.
+    fn short_circuit(&mut self, op: LogOp, value: Register) -> usize {
+        let target = Self::DUMMY_TARGET;
+        self.emit(match op {
+            LogOp::And => Instruction::JumpIfFalse {
+                cond: value,
+                target,
+            },
+            LogOp::Or => Instruction::JumpIfTrue {
+                cond: value,
+                target,
+            },
+            LogOp::Coalesce => Instruction::JumpIfNotNullish { src: value, target },
+        })
+    }
+
+    fn identifier_reference(&mut self, name: &str) -> CompileResult<Reference> {
+        if let Some(binding) = self.lookup(name) {
+            let name = self.name_index(name);
+            return Ok(Reference::Local { binding, name });
+        }
+        if self.locals_in_registers && (name == "arguments" || name == "eval") {
+            return Err(CompileError::NeedsEnvironment);
+        }
+        let name = self.name_index(name);
+        Ok(Reference::Name { name })
+    }
+
+    /// Evaluates the object and key of an assignment target, that is followed by `later`.
+    fn reference(&mut self, node: &Node, later: &[&Node]) -> CompileResult<Reference> {
+        match node {
+            Node::Identifier(name) => self.identifier_reference(name.as_ref()),
+            Node::GetConstField(field) => {
+                let object = self.operand(field.obj(), later)?;
+                let name = self.name_index(field.field());
+                Ok(Reference::Property { object, name })
+            }
+            Node::GetField(field) => {
+                let mut after_object = vec![field.field()];
+                after_object.extend_from_slice(later);
+                let object = self.operand(field.obj(), &after_object)?;
+                let key = self.operand(field.field(), later)?;
+                Ok(Reference::Element { object, key })
+            }
+            _ => Err(CompileError::Unsupported),
+        }
+    }
+
+    /// Reads the value of a reference, into `dst` if it is given.
+    fn get(&mut self, reference: Reference, dst: Option<Register>) -> Register {
+        match reference {
+            Reference::Local { binding, name } => {
+                if !binding.initialized {
+                    self.emit(Instruction::ThrowUninitialized { name });
+                    return self.target(dst);
+                }
+                match dst {
+                    Some(dst) if dst != binding.register => {
+                        self.emit(Instruction::Move {
+                            dst,
+                            src: binding.register,
+                        });
+                        dst
+                    }
+                    _ => binding.register,
+                }
+            }
+            Reference::Name { name } => {
+                let dst = self.target(dst);
+                self.emit(Instruction::GetName { dst, name });
+                dst
+            }
+            Reference::Property { object, name } => {
+                let dst = self.target(dst);
+                self.emit(Instruction::GetPropertyByName { dst, object, name });
+                dst
+            }
+            Reference::Element { object, key } => {
+                let dst = self.target(dst);
+                self.emit(Instruction::GetPropertyByValue { dst, object, key });
+                dst
+            }
+        }
+    }
+
+    /// Writes the value of `src` to a reference.
+    fn put(&mut self, reference: Reference, src: Register) {
+        match reference {
+            Reference::Local { binding, name } => {
+                if !binding.initialized {
+                    self.emit(Instruction::ThrowUninitialized { name });
+                } else if !binding.mutable {
+                    self.emit(Instruction::ThrowConstAssignment { name });
+                } else if binding.register != src {
+                    self.emit(Instruction::Move {
+                        dst: binding.register,
+                        src,
+                    });
+                }
+            }
+            Reference::Name { name } => {
+                self.emit(Instruction::SetName { src, name });
+            }
+            Reference::Property { object, name } => {
+                self.emit(Instruction::SetPropertyByName { object, name, src });
+            }
+            Reference::Element { object, key } => {
+                self.emit(Instruction::SetPropertyByValue { object, key, src });
+            }
+        }
+    }
+
+    fn assign(&mut self, lhs: &Node, rhs: &Node, dst: Option<Register>) -> CompileResult<Register> {
+        if let Node::Identifier(name) = lhs {
+            if let Some(binding) = self.lookup(name.as_ref()) {
+                if binding.initialized && binding.mutable && can_target(rhs) {
+                    let register = self.expr(rhs, Some(binding.register))?;
+                    return Ok(self.copy_to(register, dst));
+                }
+            }
+        }
+
+        let result = self.target(dst);
+        let mark = self.next_register;
+        let reference = self.reference(lhs, &[rhs])?;
+        self.expr(rhs, Some(result))?;
+        self.put(reference, result);
+        self.release(mark);
+        Ok(result)
+    }
+
+    fn compound_assign(
+        &mut self,
+        op: AssignOp,
+        lhs: &Node,
+        rhs: &Node,
+        dst: Option<Register>,
+    ) -> CompileResult<Register> {
+        let binary_op = match op {
+            AssignOp::Add => BinOp::Num(NumOp::Add),
+            AssignOp::Sub => BinOp::Num(NumOp::Sub),
+            AssignOp::Mul => BinOp::Num(NumOp::Mul),
+            AssignOp::Div => BinOp::Num(NumOp::Div),
+            AssignOp::Mod => BinOp::Num(NumOp::Mod),
+            AssignOp::Exp => BinOp::Num(NumOp::Exp),
+            AssignOp::And => BinOp::Bit(BitOp::And),
+            AssignOp::Or => BinOp::Bit(BitOp::Or),
+            AssignOp::Xor => BinOp::Bit(BitOp::Xor),
+            AssignOp::Shl => BinOp::Bit(BitOp::Shl),
+            AssignOp::Shr => BinOp::Bit(BitOp::Shr),
+            AssignOp::Ushr => BinOp::Bit(BitOp::UShr),
+            AssignOp::BoolAnd => return self.logical_assign(LogOp::And, lhs, rhs, dst),
+            AssignOp::BoolOr => return self.logical_assign(LogOp::Or, lhs, rhs, dst),
+            AssignOp::Coalesce => return self.logical_assign(LogOp::Coalesce, lhs, rhs, dst),
+        };
+
+        // A local variable is updated in place.
+        if let Node::Identifier(name) = lhs {
+            if let Some(binding) = self.lookup(name.as_ref()) {
+                if binding.initialized && binding.mutable {
+                    let mark = self.next_register;
+                    let current = self.operand(lhs, &[rhs])?;
+                    let value = self.expr(rhs, None)?;
+                    self.emit(binary_instruction(
+                        binary_op,
+                        binding.register,
+                        current,
+                        value,
+                    ));
+                    self.release(mark);
+                    return Ok(self.copy_to(binding.register, dst));
+                }
+            }
+        }
+
+        let result = self.target(dst);
+        let mark = self.next_register;
+        let reference = self.reference(lhs, &[rhs])?;
+        let current = self.get(reference, None);
+        let current = if self.is_local(current) && may_assign(rhs) {
+            self.copy_to(current, Some(result))
+        } else {
+            current
+        };
+        let value = self.expr(rhs, None)?;
+        self.emit(binary_instruction(binary_op, result, current, value));
+        self.put(reference, result);
+        self.release(mark);
+        Ok(result)
+    }
+
+    fn logical_assign(
+        &mut self,
+        op: LogOp,
+        lhs: &Node,
+        rhs: &Node,
+        dst: Option<Register>,
+    ) -> CompileResult<Register> {
+        let result = self.target(dst);
+        let mark = self.next_register;
+        let reference = self.reference(lhs, &[rhs])?;
+        self.get(reference, Some(result));
+        let exit = self.short_circuit(op, result);
+        self.expr(rhs, Some(result))?;
+        self.put(reference, result);
+        self.patch_here(exit);
+        self.release(mark);
+        Ok(result)
+    }
+
+    /// Compiles an increment or decrement of `target`.
+    fn update(
+        &mut self,
+        target: &Node,
+        increment: bool,
+        prefix: bool,
+        dst: Option<Register>,
+    ) -> CompileResult<Register> {
+        let update = |dst, src| {
+            if increment {
+                Instruction::Inc { dst, src }
+            } else {
+                Instruction::Dec { dst, src }
+            }
+        };
+
+        // A local variable is updated in place.
+        if let Node::Identifier(name) = target {
+            if let Some(binding) = self.lookup(name.as_ref()) {
+                if binding.initialized && binding.mutable {
+                    let register = binding.register;
+                    if prefix {
+                        self.emit(update(register, register));
+                        return Ok(self.copy_to(register, dst));
+                    }
+                    let dst = self.target(dst);
+                    self.emit(Instruction::ToNumeric { dst, src: register });
+                    self.emit(update(register, dst));
+                    return Ok(dst);
+                }
+            }
+        }
+
+        let result = self.target(dst);
+        let mark = self.next_register;
+        let reference = self.reference(target, &[])?;
+        if prefix {
+            self.get(reference, Some(result));
+            self.emit(update(result, result));
+            self.put(reference, result);
+        } else {
+            let current = self.get(reference, None);
+            self.emit(Instruction::ToNumeric {
+                dst: result,
+                src: current,
+            });
+            let value = self.alloc();
+            self.emit(update(value, result));
+            self.put(reference, value);
+        }
+        self.release(mark);
+        Ok(result)
+    }
+
+    /// Copies the value of `src` to `dst` if it is given.
+    fn copy_to(&mut self, src: Register, dst: Option<Register>) -> Register {
+        match dst {
+            Some(dst) if dst != src => {
+                self.emit(Instruction::Move { dst, src });
+                dst
+            }
+            _ => src,
+        }
+    }
+
+    /// Evaluates the arguments of a call to consecutive registers, returns the first one.
+    fn arguments(&mut self, args: &[Node]) -> CompileResult<Register> {
+        if args.iter().any(|arg| matches!(arg, Node::Spread(_))) {
+            return Err(CompileError::Unsupported);
+        }
+
+        let start = self.alloc_many(args.len() as u32);
+        for (register, arg) in (start..).zip(args) {
+            self.expr(arg, Some(register))?;
+        }
+        Ok(start)
+    }
+
+    fn call(&mut self, call: &Call, dst: Option<Register>) -> CompileResult<Register> {
+        let dst = self.target(dst);
+        let mark = self.next_register;
+
+        let this = self.alloc();
+        let function = self.alloc();
+        match call.expr() {
+            Node::GetConstField(field) => {
+                self.expr(field.obj(), Some(this))?;
+                let name = self.name_index(field.field());
+                self.emit(Instruction::GetPropertyByName {
+                    dst: function,
+                    object: this,
+                    name,
+                });
+            }
+            Node::GetField(field) => {
+                self.expr(field.obj(), Some(this))?;
+                let key = self.expr(field.field(), None)?;
+                self.emit(Instruction::GetPropertyByValue {
+                    dst: function,
+                    object: this,
+                    key,
+                });
+            }
+            callee => {
+                self.emit(Instruction::This { dst: this });
+                self.expr(callee, Some(function))?;
+            }
+        }
+
+        let args = self.arguments(call.args())?;
+        self.emit(Instruction::Call {
+            dst,
+            function,
+            this,
+            args,
+            argc: call.args().len() as u32,
+        });
+        self.release(mark);
+        Ok(dst)
+    }
+
+    fn new_object(&mut self, new: &New, dst: Option<Register>) -> CompileResult<Register> {
+        let dst = self.target(dst);
+        let mark = self.next_register;
+
+        let function = self.alloc();
+        self.expr(new.expr(), Some(function))?;
+        let name = self.name_index(&new.expr().to_string());
+        let args = self.arguments(new.args())?;
+        self.emit(Instruction::New {
+            dst,
+            function,
+            args,
+            argc: new.args().len() as u32,
+            name,
+        });
+        self.release(mark);
+        Ok(dst)
+    }
+}
+
+impl Binding {
+    /// A `var` declaration or parameter, which is initialized when the function is entered.
+    fn variable(register: Register) -> Self {
+        Self {
+            register,
+            mutable: true,
+            initialized: true,
+        }
+    }
+}
+
+/// Returns the name that is declared by a declaration, destructuring is not supported.
+fn declaration_name(declaration: &Declaration) -> CompileResult<&str> {
+    match declaration {
+        Declaration::Identifier { ident, .. } => Ok(ident.as_ref()),
+        Declaration::Pattern(_) => Err(CompileError::Unsupported),
+    }
+}
+
+/// Collects the names declared by the `var` declarations of a statement, including the ones
+/// in nested blocks and loops.
+fn var_declared_names<'n>(node: &'n Node, names: &mut Vec<&'n str>) -> CompileResult<()> {
+    match node {
+        Node::VarDeclList(list) => {
+            for declaration in list.as_ref() {
+                names.push(declaration_name(declaration)?);
+            }
+        }
+        Node::Block(block) => {
+            for node in block.items() {
+                var_declared_names(node, names)?;
+            }
+        }
+        Node::If(node) => {
+            var_declared_names(node.body(), names)?;
+            if let Some(else_node) = node.else_node() {
+                var_declared_names(else_node, names)?;
+            }
+        }
+        Node::WhileLoop(while_) => var_declared_names(while_.body(), names)?,
+        Node::DoWhileLoop(do_while) => var_declared_names(do_while.body(), names)?,
+        Node::ForLoop(for_loop) => {
+            if let Some(init) = for_loop.init() {
+                var_declared_names(init, names)?;
+            }
+            var_declared_names(for_loop.body(), names)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Returns `true` if an expression only writes its result once all of its operands have been
+/// evaluated, so that its result can be placed directly in the register of a local variable
+/// that it reads.
+fn can_target(node: &Node) -> bool {
+    match node {
+        Node::Const(_)
+        | Node::Identifier(_)
+        | Node::This
+        | Node::GetConstField(_)
+        | Node::GetField(_)
+        | Node::Call(_)
+        | Node::New(_)
+        | Node::ArrayDecl(_) => true,
+        Node::UnaryOp(unary) => !matches!(
+            unary.op(),
+            UnaryOp::IncrementPre
+                | UnaryOp::IncrementPost
+                | UnaryOp::DecrementPre
+                | UnaryOp::DecrementPost
+        ),
+        Node::BinOp(binary) => {
+            matches!(binary.op(), BinOp::Num(_) | BinOp::Bit(_) | BinOp::Comp(_))
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` if an expression may assign a local variable.
+///
+/// Local variables can only be assigned by the code block itself, since they are not captured
+/// by closures.
+fn may_assign(node: &Node) -> bool {
+    match node {
+        Node::Const(_) | Node::Identifier(_) | Node::This => false,
+        Node::UnaryOp(unary) => {
+            matches!(
+                unary.op(),
+                UnaryOp::IncrementPre
+                    | UnaryOp::IncrementPost
+                    | UnaryOp::DecrementPre
+                    | UnaryOp::DecrementPost
+            ) || may_assign(unary.target())
+        }
+        Node::BinOp(binary) => {
+            matches!(binary.op(), BinOp::Assign(_))
+                || may_assign(binary.lhs())
+                || may_assign(binary.rhs())
+        }
+        Node::GetConstField(field) => may_assign(field.obj()),
+        Node::GetField(field) => may_assign(field.obj()) || may_assign(field.field()),
+        Node::Call(call) => may_assign(call.expr()) || call.args().iter().any(may_assign),
+        Node::New(new) => may_assign(new.expr()) || new.args().iter().any(may_assign),
+        Node::ConditionalOp(op) => {
+            may_assign(op.cond()) || may_assign(op.if_true()) || may_assign(op.if_false())
+        }
+        Node::ArrayDecl(array) => array.as_ref().iter().any(may_assign),
+        _ => true,
+    }
+}
+
+/// Returns the instruction of an arithmetic, bitwise or comparison operator.
+fn binary_instruction(op: BinOp, dst: Register, lhs: Register, rhs: Register) -> Instruction {
+    match op {
+        BinOp::Num(NumOp::Add) => Instruction::Add { dst, lhs, rhs },
+        BinOp::Num(NumOp::Sub) => Instruction::Sub { dst, lhs, rhs },
+        BinOp::Num(NumOp::Mul) => Instruction::Mul { dst, lhs, rhs },
+        BinOp::Num(NumOp::Div) => Instruction::Div { dst, lhs, rhs },
+        BinOp::Num(NumOp::Exp) => Instruction::Pow { dst, lhs, rhs },
+        BinOp::Num(NumOp::Mod) => Instruction::Mod { dst, lhs, rhs },
+        BinOp::Bit(BitOp::And) => Instruction::BitAnd { dst, lhs, rhs },
+        BinOp::Bit(BitOp::Or) => Instruction::BitOr { dst, lhs, rhs },
+        BinOp::Bit(BitOp::Xor) => Instruction::BitXor { dst, lhs, rhs },
+        BinOp::Bit(BitOp::Shl) => Instruction::ShiftLeft { dst, lhs, rhs },
+        BinOp::Bit(BitOp::Shr) => Instruction::ShiftRight { dst, lhs, rhs },
+        BinOp::Bit(BitOp::UShr) => Instruction::UnsignedShiftRight { dst, lhs, rhs },
+        BinOp::Comp(CompOp::Equal) => Instruction::Eq { dst, lhs, rhs },
+        BinOp::Comp(CompOp::NotEqual) => Instruction::NotEq { dst, lhs, rhs },
+        BinOp::Comp(CompOp::StrictEqual) => Instruction::StrictEq { dst, lhs, rhs },
+        BinOp::Comp(CompOp::StrictNotEqual) => Instruction::StrictNotEq { dst, lhs, rhs },
+        BinOp::Comp(CompOp::GreaterThan) => Instruction::GreaterThan { dst, lhs, rhs },
+        BinOp::Comp(CompOp::GreaterThanOrEqual) => Instruction::GreaterThanOrEq { dst, lhs, rhs },
+        BinOp::Comp(CompOp::LessThan) => Instruction::LessThan { dst, lhs, rhs },
+        BinOp::Comp(CompOp::LessThanOrEqual) => Instruction::LessThanOrEq { dst, lhs, rhs },
+        BinOp::Comp(CompOp::In) => Instruction::In { dst, lhs, rhs },
+        BinOp::Comp(CompOp::InstanceOf) => Instruction::InstanceOf { dst, lhs, rhs },
+        op => unreachable!("not an arithmetic, bitwise or comparison operator: {}", op),
+    }
+}
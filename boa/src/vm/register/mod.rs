@@ -0,0 +1,653 @@
+//! An experimental register-based instruction set for the virtual machine.
+//!
+//! The stack machine of the [`Vm`](super::Vm) pushes and pops a value for every operand of
+//! every instruction. The instructions of this module instead read their operands from, and
+//! write their results to, registers: slots of the call frame that are addressed by index.
+//! Local variables that are not captured by closures live in registers for the whole call,
+//! and the temporary values of expressions live in registers that the compiler allocates
+//! per code block.
+//!
+//! Register code is only generated with [`Backend::Register`](crate::context::Backend), in
+//! addition to the bytecode of the stack machine. The code blocks that use a feature that the
+//! register compiler does not support run on the stack machine instead.
+
+use crate::{
+    builtins::Array,
+    environment::{
+        declarative_environment_record::DeclarativeEnvironmentRecord,
+        lexical_environment::VariableScope,
+    },
+    gc::{Finalize, Trace},
+    vm::JsVmFunction,
+    Context, JsResult, JsString, JsValue,
+};
+use gc::Gc;
+use std::fmt;
+
+mod compiler;
+
+pub(crate) use compiler::FunctionIndices;
+
+/// The index of a register, relative to the start of the registers of the call frame.
+pub(crate) type Register = u32;
+
+/// An instruction of the register machine.
+///
+/// `dst` is the register that receives the result of an instruction. Jump targets are indices
+/// into the instructions of the code block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Instruction {
+    LoadUndefined {
+        dst: Register,
+    },
+    LoadNull {
+        dst: Register,
+    },
+    LoadBoolean {
+        dst: Register,
+        value: bool,
+    },
+    LoadInteger {
+        dst: Register,
+        value: i32,
+    },
+    LoadRational {
+        dst: Register,
+        value: f64,
+    },
+    LoadLiteral {
+        dst: Register,
+        index: u32,
+    },
+    Move {
+        dst: Register,
+        src: Register,
+    },
+
+    Add {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    Sub {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    Mul {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    Div {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    Pow {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    Mod {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    BitAnd {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    BitOr {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    BitXor {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    ShiftLeft {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    ShiftRight {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    UnsignedShiftRight {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    Eq {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    NotEq {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    StrictEq {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    StrictNotEq {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    GreaterThan {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    GreaterThanOrEq {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    LessThan {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    LessThanOrEq {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    In {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    InstanceOf {
+        dst: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+
+    Neg {
+        dst: Register,
+        src: Register,
+    },
+    Pos {
+        dst: Register,
+        src: Register,
+    },
+    LogicalNot {
+        dst: Register,
+        src: Register,
+    },
+    BitNot {
+        dst: Register,
+        src: Register,
+    },
+    TypeOf {
+        dst: Register,
+        src: Register,
+    },
+    ToNumeric {
+        dst: Register,
+        src: Register,
+    },
+    Inc {
+        dst: Register,
+        src: Register,
+    },
+    Dec {
+        dst: Register,
+        src: Register,
+    },
+
+    Jump {
+        target: u32,
+    },
+    JumpIfTrue {
+        cond: Register,
+        target: u32,
+    },
+    JumpIfFalse {
+        cond: Register,
+        target: u32,
+    },
+    JumpIfNotNullish {
+        src: Register,
+        target: u32,
+    },
+    JumpIfNotLessThan {
+        lhs: Register,
+        rhs: Register,
+        target: u32,
+    },
+
+    GetName {
+        dst: Register,
+        name: u32,
+    },
+    SetName {
+        src: Register,
+        name: u32,
+    },
+    DefVar {
+        name: u32,
+    },
+    DefLet {
+        name: u32,
+    },
+    DefConst {
+        name: u32,
+    },
+    InitLexical {
+        src: Register,
+        name: u32,
+    },
+    PushDeclarativeEnvironment,
+    PopEnvironment,
+
+    This {
+        dst: Register,
+    },
+    GetPropertyByName {
+        dst: Register,
+        object: Register,
+        name: u32,
+    },
+    GetPropertyByValue {
+        dst: Register,
+        object: Register,
+        key: Register,
+    },
+    SetPropertyByName {
+        object: Register,
+        name: u32,
+        src: Register,
+    },
+    SetPropertyByValue {
+        object: Register,
+        key: Register,
+        src: Register,
+    },
+    NewObject {
+        dst: Register,
+    },
+    NewArray {
+        dst: Register,
+        start: Register,
+        count: u32,
+    },
+    GetFunction {
+        dst: Register,
+        index: u32,
+    },
+
+    /// Calls `function` with the `argc` arguments in the registers starting at `args`.
+    Call {
+        dst: Register,
+        function: Register,
+        this: Register,
+        args: Register,
+        argc: u32,
+    },
+    /// Constructs `function` with the `argc` arguments in the registers starting at `args`,
+    /// `name` is the source text of the constructor expression, for error messages.
+    New {
+        dst: Register,
+        function: Register,
+        args: Register,
+        argc: u32,
+        name: u32,
+    },
+
+    Throw {
+        src: Register,
+    },
+    /// Throws a `ReferenceError` for an access to a binding in its temporal dead zone.
+    ThrowUninitialized {
+        name: u32,
+    },
+    /// Throws a `TypeError` for an assignment to a constant.
+    ThrowConstAssignment {
+        name: u32,
+    },
+    Return {
+        src: Register,
+    },
+}
+
+/// The register code of a script or function.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct RegisterCodeBlock {
+    #[unsafe_ignore_trace]
+    pub(crate) instructions: Box<[Instruction]>,
+
+    /// The number of registers used by the code.
+    pub(crate) register_count: u32,
+
+    /// Whether the parameters are passed in the first registers, instead of being bound in
+    /// the function environment.
+    pub(crate) parameters_in_registers: bool,
+
+    /// Literals
+    pub(crate) literals: Box<[JsValue]>,
+
+    /// Names of bindings and properties.
+    pub(crate) names: Box<[JsString]>,
+}
+
+impl RegisterCodeBlock {
+    /// Returns the number of registers used by the code.
+    #[inline]
+    pub fn register_count(&self) -> u32 {
+        self.register_count
+    }
+
+    /// Returns the number of instructions of the code.
+    #[inline]
+    pub fn instruction_count(&self) -> usize {
+        self.instructions.len()
+    }
+}
+
+impl fmt::Display for RegisterCodeBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:-^70}",
+            format!(" Registers: {} ", self.register_count)
+        )?;
+        for (location, instruction) in self.instructions.iter().enumerate() {
+            writeln!(f, "    {:06}    {:?}", location, instruction)?;
+        }
+
+        writeln!(f, "\nLiterals:")?;
+        if self.literals.is_empty() {
+            writeln!(f, "    <empty>")?;
+        }
+        for (i, value) in self.literals.iter().enumerate() {
+            writeln!(f, "    {:04}: <{}> {}", i, value.type_of(), value.display())?;
+        }
+
+        writeln!(f, "\nNames:")?;
+        if self.names.is_empty() {
+            writeln!(f, "    <empty>")?;
+        }
+        for (i, name) in self.names.iter().enumerate() {
+            writeln!(f, "    {:04}: {}", i, name)?;
+        }
+        Ok(())
+    }
+}
+
+impl Context {
+    /// Runs the register code of the current frame.
+    ///
+    /// The registers are allocated on the value stack, above the frame pointer. The parameters
+    /// that are passed in registers have already been pushed by the caller.
+    pub(crate) fn run_registers(&mut self, code: Gc<RegisterCodeBlock>) -> JsResult<JsValue> {
+        let fp = self.vm.frame().fp;
+        self.vm
+            .stack
+            .resize(fp + code.register_count as usize, JsValue::undefined());
+
+        let result = self.execute_registers(&code, fp);
+        self.exit_frame();
+        result
+    }
+
+    fn execute_registers(&mut self, code: &RegisterCodeBlock, fp: usize) -> JsResult<JsValue> {
+        macro_rules! reg {
+            ($register:expr) => {
+                self.vm.stack[fp + $register as usize]
+            };
+        }
+
+        macro_rules! bin_op {
+            ($dst:expr, $lhs:expr, $rhs:expr, $op:ident) => {{
+                let lhs = reg!($lhs).clone();
+                let rhs = reg!($rhs).clone();
+                reg!($dst) = lhs.$op(&rhs, self)?.into();
+            }};
+        }
+
+        let mut pc = 0;
+        loop {
+            let start = pc;
+            let instruction = code.instructions[pc];
+            pc += 1;
+
+            match instruction {
+                Instruction::LoadUndefined { dst } => reg!(dst) = JsValue::undefined(),
+                Instruction::LoadNull { dst } => reg!(dst) = JsValue::null(),
+                Instruction::LoadBoolean { dst, value } => reg!(dst) = value.into(),
+                Instruction::LoadInteger { dst, value } => reg!(dst) = value.into(),
+                Instruction::LoadRational { dst, value } => reg!(dst) = value.into(),
+                Instruction::LoadLiteral { dst, index } => {
+                    reg!(dst) = code.literals[index as usize].clone()
+                }
+                Instruction::Move { dst, src } => reg!(dst) = reg!(src).clone(),
+                Instruction::Add { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, add),
+                Instruction::Sub { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, sub),
+                Instruction::Mul { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, mul),
+                Instruction::Div { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, div),
+                Instruction::Pow { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, pow),
+                Instruction::Mod { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, rem),
+                Instruction::BitAnd { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, bitand),
+                Instruction::BitOr { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, bitor),
+                Instruction::BitXor { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, bitxor),
+                Instruction::ShiftLeft { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, shl),
+                Instruction::ShiftRight { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, shr),
+                Instruction::UnsignedShiftRight { dst, lhs, rhs } => {
+                    bin_op!(dst, lhs, rhs, ushr)
+                }
+                Instruction::Eq { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, equals),
+                Instruction::NotEq { dst, lhs, rhs } => {
+                    let lhs = reg!(lhs).clone();
+                    let rhs = reg!(rhs).clone();
+                    reg!(dst) = (!lhs.equals(&rhs, self)?).into();
+                }
+                Instruction::StrictEq { dst, lhs, rhs } => {
+                    reg!(dst) = reg!(lhs).strict_equals(&reg!(rhs)).into()
+                }
+                Instruction::StrictNotEq { dst, lhs, rhs } => {
+                    reg!(dst) = (!reg!(lhs).strict_equals(&reg!(rhs))).into()
+                }
+                Instruction::GreaterThan { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, gt),
+                Instruction::GreaterThanOrEq { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, ge),
+                Instruction::LessThan { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, lt),
+                Instruction::LessThanOrEq { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, le),
+                Instruction::In { dst, lhs, rhs } => {
+                    let lhs = reg!(lhs).clone();
+                    let rhs = reg!(rhs).clone();
+                    reg!(dst) = self.has_property_value(&lhs, &rhs)?.into();
+                }
+                Instruction::InstanceOf { dst, lhs, rhs } => bin_op!(dst, lhs, rhs, instance_of),
+                Instruction::Neg { dst, src } => {
+                    let value = reg!(src).clone();
+                    reg!(dst) = value.neg(self)?;
+                }
+                Instruction::Pos { dst, src } => {
+                    let value = reg!(src).clone();
+                    reg!(dst) = value.to_number(self)?.into();
+                }
+                Instruction::LogicalNot { dst, src } => {
+                    reg!(dst) = (!reg!(src).to_boolean()).into()
+                }
+                Instruction::BitNot { dst, src } => {
+                    let value = reg!(src).clone();
+                    reg!(dst) = self.bit_not(&value)?;
+                }
+                Instruction::TypeOf { dst, src } => reg!(dst) = reg!(src).type_of().into(),
+                Instruction::ToNumeric { dst, src } => {
+                    let value = reg!(src).clone();
+                    reg!(dst) = value.to_numeric(self)?.into();
+                }
+                Instruction::Inc { dst, src } => {
+                    let value = reg!(src).clone();
+                    reg!(dst) = value.add(&JsValue::Integer(1), self)?;
+                }
+                Instruction::Dec { dst, src } => {
+                    let value = reg!(src).clone();
+                    reg!(dst) = value.sub(&JsValue::Integer(1), self)?;
+                }
+                Instruction::Jump { target } => pc = target as usize,
+                Instruction::JumpIfTrue { cond, target } => {
+                    if reg!(cond).to_boolean() {
+                        pc = target as usize;
+                    }
+                }
+                Instruction::JumpIfFalse { cond, target } => {
+                    if !reg!(cond).to_boolean() {
+                        pc = target as usize;
+                    }
+                }
+                Instruction::JumpIfNotNullish { src, target } => {
+                    if !reg!(src).is_null_or_undefined() {
+                        pc = target as usize;
+                    }
+                }
+                Instruction::JumpIfNotLessThan { lhs, rhs, target } => {
+                    let lhs = reg!(lhs).clone();
+                    let rhs = reg!(rhs).clone();
+                    if !lhs.lt(&rhs, self)? {
+                        pc = target as usize;
+                    }
+                }
+                Instruction::GetName { dst, name } => {
+                    reg!(dst) = self.get_binding_value(&code.names[name as usize])?;
+                }
+                Instruction::SetName { src, name } => {
+                    let value = reg!(src).clone();
+                    self.set_name(&code.names[name as usize], value)?;
+                }
+                Instruction::DefVar { name } => self.def_var(&code.names[name as usize])?,
+                Instruction::DefLet { name } => {
+                    self.create_mutable_binding(
+                        &code.names[name as usize],
+                        false,
+                        VariableScope::Block,
+                    )?;
+                }
+                Instruction::DefConst { name } => {
+                    self.create_immutable_binding(
+                        &code.names[name as usize],
+                        false,
+                        VariableScope::Block,
+                    )?;
+                }
+                Instruction::InitLexical { src, name } => {
+                    let value = reg!(src).clone();
+                    self.initialize_binding(&code.names[name as usize], value)?;
+                }
+                Instruction::PushDeclarativeEnvironment => {
+                    let environment = self.get_current_environment();
+                    self.push_environment(DeclarativeEnvironmentRecord::new(Some(environment)));
+                }
+                Instruction::PopEnvironment => {
+                    let _ = self.pop_environment();
+                }
+                Instruction::This { dst } => reg!(dst) = self.get_this_binding()?,
+                Instruction::GetPropertyByName { dst, object, name } => {
+                    let object = reg!(object).clone();
+                    let name = code.names[name as usize].clone();
+                    reg!(dst) = self.get_property_of(&object, name.into())?;
+                }
+                Instruction::GetPropertyByValue { dst, object, key } => {
+                    let object = reg!(object).clone();
+                    let key = reg!(key).clone().to_property_key(self)?;
+                    reg!(dst) = self.get_property_of(&object, key)?;
+                }
+                Instruction::SetPropertyByName { object, name, src } => {
+                    let object = reg!(object).clone();
+                    let value = reg!(src).clone();
+                    let name = code.names[name as usize].clone();
+                    self.set_property_of(&object, name.into(), value)?;
+                }
+                Instruction::SetPropertyByValue { object, key, src } => {
+                    let object = reg!(object).clone();
+                    let value = reg!(src).clone();
+                    let key = reg!(key).clone().to_property_key(self)?;
+                    self.set_property_of(&object, key, value)?;
+                }
+                Instruction::NewObject { dst } => reg!(dst) = self.construct_object().into(),
+                Instruction::NewArray { dst, start, count } => {
+                    let start = fp + start as usize;
+                    let elements = self.vm.stack[start..start + count as usize].to_vec();
+                    reg!(dst) = Array::create_array_from_list(elements, self).into();
+                }
+                Instruction::GetFunction { dst, index } => {
+                    let code = self.vm.frame().code.functions[index as usize].clone();
+                    let environment = self.get_current_environment();
+                    reg!(dst) = JsVmFunction::new(code, environment, self).into();
+                }
+                Instruction::Call {
+                    dst,
+                    function,
+                    this,
+                    args,
+                    argc,
+                } => {
+                    if self.vm.stack_size_limit <= self.vm.stack.len() {
+                        return Err(self.construct_range_error("Maximum call stack size exceeded"));
+                    }
+                    let function = reg!(function).clone();
+                    let this = reg!(this).clone();
+                    let start = fp + args as usize;
+                    let args = self.vm.stack[start..start + argc as usize].to_vec();
+
+                    reg!(dst) = self.call(&function, &this, &args)?;
+                }
+                Instruction::New {
+                    dst,
+                    function,
+                    args,
+                    argc,
+                    name,
+                } => {
+                    if self.vm.stack_size_limit <= self.vm.stack.len() {
+                        return Err(self.construct_range_error("Maximum call stack size exceeded"));
+                    }
+                    let function = reg!(function).clone();
+                    let start = fp + args as usize;
+                    let args = self.vm.stack[start..start + argc as usize].to_vec();
+
+                    reg!(dst) = if let Some(constructor) = function.as_constructor() {
+                        constructor.construct(&args, &function, self)?
+                    } else {
+                        return Err(self.construct_type_error(format!(
+                            "{} is not a constructor",
+                            code.names[name as usize]
+                        )));
+                    };
+                }
+                Instruction::Throw { src } => return Err(reg!(src).clone()),
+                Instruction::ThrowUninitialized { name } => {
+                    return Err(self.construct_reference_error(format!(
+                        "{} has not been initialized",
+                        code.names[name as usize]
+                    )));
+                }
+                Instruction::ThrowConstAssignment { name } => {
+                    return Err(self.construct_type_error(format!(
+                        "Cannot mutate an immutable binding {}",
+                        code.names[name as usize]
+                    )));
+                }
+                Instruction::Return { src } => return Ok(reg!(src).clone()),
+            }
+
+            // A jump backwards starts a new iteration of a loop.
+            if pc <= start {
+                self.count_loop_iteration()?;
+            }
+        }
+    }
+}
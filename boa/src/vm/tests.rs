@@ -27,3 +27,1108 @@ fn basic_op() {
     "#;
     assert_eq!(&exec(basic_op), "3");
 }
+
+#[test]
+fn try_catch() {
+    let scenario = r#"
+        let result;
+        try {
+            throw "error";
+        } catch (e) {
+            result = e;
+        }
+        result
+    "#;
+    assert_eq!(&exec(scenario), "\"error\"");
+}
+
+#[test]
+fn try_catch_native_error() {
+    let scenario = r#"
+        let result;
+        try {
+            null.property;
+        } catch (e) {
+            result = e instanceof TypeError;
+        }
+        result
+    "#;
+    assert_eq!(&exec(scenario), "true");
+}
+
+#[test]
+fn try_catch_restores_stack() {
+    let scenario = r#"
+        function thrower() {
+            throw 1;
+        }
+        function f() {
+            try {
+                return 10 + [1, 2, thrower()].length;
+            } catch {
+                return 5;
+            }
+        }
+        1 + f()
+    "#;
+    assert_eq!(&exec(scenario), "6");
+}
+
+#[test]
+fn try_catch_restores_environment() {
+    let scenario = r#"
+        let result;
+        let a = 1;
+        {
+            let a = 2;
+            try {
+                let a = 3;
+                {
+                    let a = 4;
+                    throw a;
+                }
+            } catch (e) {
+                result = a + e;
+            }
+        }
+        result
+    "#;
+    assert_eq!(&exec(scenario), "6");
+}
+
+#[test]
+fn uncaught_exception_across_calls() {
+    let scenario = r#"
+        function inner() {
+            throw "inner";
+        }
+        function outer() {
+            inner();
+            return "unreachable";
+        }
+        let result;
+        try {
+            outer();
+        } catch (e) {
+            result = e;
+        }
+        result
+    "#;
+    assert_eq!(&exec(scenario), "\"inner\"");
+}
+
+#[test]
+fn finally_order() {
+    let scenario = r#"
+        let log = [];
+        try {
+            log.push("try");
+        } catch (e) {
+            log.push("catch");
+        } finally {
+            log.push("finally");
+        }
+        try {
+            throw 1;
+        } catch (e) {
+            log.push("catch " + e);
+        } finally {
+            log.push("finally");
+        }
+        log.join()
+    "#;
+    assert_eq!(&exec(scenario), "\"try,finally,catch 1,finally\"");
+}
+
+#[test]
+fn finally_on_return() {
+    let scenario = r#"
+        let log = [];
+        function f() {
+            try {
+                return "value";
+            } finally {
+                log.push("finally");
+            }
+        }
+        log.push(f());
+        function g() {
+            try {
+                return 1;
+            } finally {
+                return 2;
+            }
+        }
+        log.push(g());
+        log.join()
+    "#;
+    assert_eq!(&exec(scenario), "\"finally,value,2\"");
+}
+
+#[test]
+fn finally_on_break_and_continue() {
+    let scenario = r#"
+        let log = [];
+        let i = 0;
+        while (i < 4) {
+            i++;
+            try {
+                if (i === 2) continue;
+                if (i === 3) break;
+                log.push("body " + i);
+            } finally {
+                log.push("finally " + i);
+            }
+        }
+        outer: while (true) {
+            try {
+                while (true) {
+                    try {
+                        break outer;
+                    } finally {
+                        log.push("inner");
+                    }
+                }
+            } finally {
+                log.push("outer");
+            }
+        }
+        log.join()
+    "#;
+    assert_eq!(
+        &exec(scenario),
+        "\"body 1,finally 1,finally 2,finally 3,inner,outer\""
+    );
+}
+
+#[test]
+fn finally_rethrows() {
+    let scenario = r#"
+        let log = [];
+        try {
+            try {
+                throw "error";
+            } finally {
+                log.push("finally");
+            }
+        } catch (e) {
+            log.push("caught " + e);
+        }
+        try {
+            try {
+                throw "first";
+            } catch (e) {
+                throw "second";
+            } finally {
+                log.push("finally");
+            }
+        } catch (e) {
+            log.push("caught " + e);
+        }
+        log.join()
+    "#;
+    assert_eq!(
+        &exec(scenario),
+        "\"finally,caught error,finally,caught second\""
+    );
+}
+
+#[test]
+fn catch_parameter_destructuring() {
+    let scenario = r#"
+        let result;
+        try {
+            throw { a: 1, b: [2, 3] };
+        } catch ({ a, b: [x, y = 10], c = 4 }) {
+            result = a + x + y + c;
+        }
+        try {
+            throw [1, 2, 3];
+        } catch ([first, ...rest]) {
+            result += first + rest.length;
+        }
+        result
+    "#;
+    assert_eq!(&exec(scenario), "13");
+}
+
+#[test]
+fn for_loop() {
+    let scenario = r#"
+        let sum = 0;
+        for (let i = 0; i < 5; i++) {
+            sum += i;
+        }
+        for (var j = 0; j < 3; j++) {}
+        for (;;) {
+            break;
+        }
+        sum + j
+    "#;
+    assert_eq!(&exec(scenario), "13");
+}
+
+#[test]
+fn for_loop_per_iteration_bindings() {
+    let scenario = r#"
+        let functions = [];
+        for (let i = 0; i < 3; i++) {
+            functions.push(() => i);
+        }
+        functions.map(f => f()).join()
+    "#;
+    assert_eq!(&exec(scenario), "\"0,1,2\"");
+}
+
+#[test]
+fn labelled_break_and_continue() {
+    let scenario = r#"
+        let log = [];
+        outer: for (let a = 0; a < 3; a++) {
+            for (let b = 0; b < 3; b++) {
+                if (b === 1) continue outer;
+                if (a === 2) break outer;
+                log.push(a + ":" + b);
+            }
+        }
+        log.join()
+    "#;
+    assert_eq!(&exec(scenario), "\"0:0,1:0\"");
+}
+
+#[test]
+fn for_in_loop() {
+    let scenario = r#"
+        let keys = [];
+        for (const key in { a: 1, b: 2 }) {
+            keys.push(key);
+        }
+        for (const key in null) {
+            keys.push("unreachable");
+        }
+        let object = {};
+        for (object.key in [1]) {}
+        keys.join() + object.key
+    "#;
+    assert_eq!(&exec(scenario), "\"a,b0\"");
+}
+
+#[test]
+fn for_of_loop() {
+    let scenario = r#"
+        let log = [];
+        for (const [a, b] of [[1, 2], [3, 4]]) {
+            log.push(a + b);
+        }
+        let functions = [];
+        for (let value of "ab") {
+            functions.push(() => value);
+        }
+        log.push(functions.map(f => f()).join(""));
+        log.join()
+    "#;
+    assert_eq!(&exec(scenario), "\"3,7,ab\"");
+}
+
+#[test]
+fn for_of_loop_closes_iterator() {
+    let scenario = r#"
+        let log = [];
+        let iterable = {
+            [Symbol.iterator]() {
+                let i = 0;
+                return {
+                    next() { return { value: i++, done: i > 5 }; },
+                    return() { log.push("close"); return {}; },
+                };
+            },
+        };
+        for (const value of iterable) {
+            if (value === 2) break;
+        }
+        function f() {
+            for (const value of iterable) {
+                return "return " + value;
+            }
+        }
+        log.push(f());
+        try {
+            for (const value of iterable) {
+                throw "error";
+            }
+        } catch (e) {
+            log.push(e);
+        }
+        for (const value of iterable) {}
+        log.join()
+    "#;
+    assert_eq!(&exec(scenario), "\"close,close,return 0,close,error\"");
+}
+
+#[test]
+fn new_expression() {
+    let scenario = r#"
+        function Point(x, y) {
+            this.x = x;
+            this.y = y;
+        }
+        Point.prototype.sum = function () {
+            return this.x + this.y;
+        };
+        function Replaced() {
+            return { replaced: true };
+        }
+        let point = new Point(1, 2);
+        point instanceof Point && new Replaced().replaced && new Date(5).getTime() + point.sum()
+    "#;
+    assert_eq!(&exec(scenario), "8");
+}
+
+#[test]
+fn new_expression_errors() {
+    let scenario = r#"
+        let log = [];
+        function Thrower() {
+            throw "constructor";
+        }
+        try {
+            new Thrower();
+        } catch (e) {
+            log.push(e);
+        }
+        try {
+            new (() => {})();
+        } catch (e) {
+            log.push(e instanceof TypeError);
+        }
+        log.join()
+    "#;
+    assert_eq!(&exec(scenario), "\"constructor,true\"");
+}
+
+#[test]
+fn array_spread() {
+    let scenario = r#"
+        let array = [1, ...[2, 3], 4, ..."ab"];
+        array.join("")
+    "#;
+    assert_eq!(&exec(scenario), "\"1234ab\"");
+}
+
+#[test]
+fn array_evaluation_order() {
+    let scenario = r#"
+        let order = [];
+        let array = [order.push("a"), order.push("b")];
+        order.join("") + array.join("")
+    "#;
+    assert_eq!(&exec(scenario), "\"ab12\"");
+}
+
+#[test]
+fn call_and_new_spread() {
+    let scenario = r#"
+        function Pair(a, b) {
+            this.value = a + b;
+        }
+        Math.max(...[1, 5, 3]) + new Pair(...[10, 20]).value
+    "#;
+    assert_eq!(&exec(scenario), "35");
+}
+
+#[test]
+fn object_spread() {
+    let scenario = r#"
+        let object = { a: 1, ...{ b: 2, c: 3 }, c: 4, ...null };
+        Object.keys(object).join("") + object.c
+    "#;
+    assert_eq!(&exec(scenario), "\"abc4\"");
+}
+
+#[test]
+fn template_literal() {
+    let scenario = r#"
+        let name = "world";
+        let object = { toString() { return "object"; } };
+        `hello ${name}, ${1 + 1} ${object}`
+    "#;
+    assert_eq!(&exec(scenario), "\"hello world, 2 object\"");
+}
+
+#[test]
+fn tagged_template() {
+    let scenario = r#"
+        let receiver = {
+            tag(strings, ...values) {
+                return this === receiver && strings.raw.join("|") + ":" + values.join();
+            },
+        };
+        receiver.tag`a${1}b\n${2}c`
+    "#;
+    assert_eq!(&exec(scenario), "\"a|b\\n|c:1,2\"");
+}
+
+#[test]
+fn tagged_template_object() {
+    let scenario = r#"
+        function tag(strings) { return strings; }
+        function site() { return tag`a${1}b\u{`; }
+        let first = site();
+        let second = site();
+        let other = tag`a${1}b\u{`;
+        [
+            first === second,
+            first !== other,
+            Object.isFrozen(first),
+            Object.isFrozen(first.raw),
+            first[1] === undefined,
+            first.raw[1],
+            Object.getOwnPropertyDescriptor(first, "raw").enumerable,
+        ].join()
+    "#;
+    assert_eq!(&exec(scenario), "\"true,true,true,true,true,b\\u{,false\"");
+}
+
+#[test]
+fn delete_identifier() {
+    let scenario = r#"
+        implicit = 1;
+        let declared = 2;
+        [delete implicit, delete declared, delete unresolved].join()
+    "#;
+    assert_eq!(&exec(scenario), "\"true,false,true\"");
+}
+
+#[test]
+fn rest_and_default_parameters() {
+    let scenario = r#"
+        function f(a, b = a + 1, ...rest) {
+            return a + b + rest.length;
+        }
+        [f(1), f(1, 5), f(1, undefined, 3, 4)].join()
+    "#;
+    assert_eq!(&exec(scenario), "\"3,6,5\"");
+}
+
+#[test]
+fn prefix_update_statements() {
+    let scenario = r#"
+        function f() {
+            let a = [1], o = { x: 1 }, i = 0, k = "x", s = 0;
+            ++a[0];
+            --o.x;
+            ++o[k];
+            for (let j = 0; j < 3; ++j) s += j;
+            --i;
+            return [a[0], o.x, i, s].join();
+        }
+        f()
+    "#;
+    assert_eq!(&exec(scenario), "\"2,1,-1,3\"");
+}
+
+#[test]
+fn labelled_block_and_if_break() {
+    let scenario = r#"
+        let log = [];
+        outer: {
+            log.push(1);
+            inner: if (log.length) {
+                log.push(2);
+                break inner;
+            }
+            for (let i = 0; i < 3; i++) {
+                if (i === 1) break outer;
+                log.push(`i${i}`);
+            }
+            log.push("unreachable");
+        }
+        l: if (true) { log.push(3); break l; } else { log.push("else"); }
+        log.join()
+    "#;
+    assert_eq!(&exec(scenario), "\"1,2,i0,3\"");
+}
+
+#[test]
+fn unsupported_syntax_falls_back_to_interpreter() {
+    use crate::{context::Backend, Context};
+
+    let scenario = r#"
+        var a;
+        ({ x: a } = { x: 1 });
+        function f() { return typeof function* () {}; }
+        async function g() {}
+        [a, f()].join()
+    "#;
+    let mut context = Context::new();
+    context.set_backend(Backend::Interpreter);
+    let expected = context.eval(scenario).unwrap().display().to_string();
+    assert_eq!(exec(scenario), expected);
+
+    let mut context = Context::new();
+    assert!(context.compile("function* generator() {}").is_err());
+    assert!(context
+        .compile("function f() { async function g() {} }")
+        .is_err());
+}
+
+#[test]
+fn unsupported_function_falls_back_to_interpreter() {
+    // Only the function with unsupported syntax runs in the interpreter, the rest of the script
+    // keeps the semantics of the VM.
+    let scenario = r#"
+        let closed = 0;
+        const iterable = {
+            [Symbol.iterator]() {
+                let n = 0;
+                return {
+                    next() { return { value: n++, done: false }; },
+                    return() { closed++; return {}; },
+                };
+            },
+        };
+        function legacy(x) {
+            async function unsupported() {}
+            closed += 100;
+            return x * 2;
+        }
+        function finish() {
+            try {
+                return legacy(21);
+            } finally {
+                closed += 10;
+            }
+        }
+        const closures = [];
+        for (let i = 0; i < 3; i++) {
+            closures.push(() => i);
+        }
+        for (const x of iterable) {
+            if (x === 1) break;
+        }
+        [finish(), closures.map((f) => f()).join(""), closed].join()
+    "#;
+    assert_eq!(&exec(scenario), "\"42,012,111\"");
+}
+
+#[test]
+fn logical_operators_return_operands() {
+    let scenario = r#"
+        let a = null;
+        a ||= "default";
+        [1 && "and", 0 && "unreachable", "" || "or", "first" || "unreachable", a].join()
+    "#;
+    assert_eq!(&exec(scenario), "\"and,0,or,first,default\"");
+}
+
+#[test]
+fn completion_value() {
+    let scenario = r#"
+        var x = 1;
+        if (x) { x + 1; } else { x - 1; }
+        for (let i = 0; i < 3; i++) { i; }
+    "#;
+    assert_eq!(&exec(scenario), "2");
+}
+
+#[test]
+fn interpreter_backend() {
+    use crate::{context::Backend, Context};
+
+    let mut context = Context::new();
+    context.set_backend(Backend::Interpreter);
+    assert_eq!(context.backend(), Backend::Interpreter);
+    let result = context.eval("let a = [1, 2, 3]; a.length").unwrap();
+    assert_eq!(result.display().to_string(), "3");
+}
+
+#[test]
+fn disassemble() {
+    use crate::{bytecompiler::ByteCompiler, parse, JsString};
+
+    let scenario = "let a = 1;\nif (a) {\n    a = 2;\n}\nfunction f() { return a; }";
+    let statement_list = parse(scenario, false).unwrap();
+    let mut compiler = ByteCompiler::new(JsString::new("<main>"), false);
+    compiler.compile_statement_list(&statement_list, true);
+    let code_block = compiler.finish();
+    let disassembly = code_block.disassemble();
+
+    assert!(disassembly.contains("JumpIfFalse         L0 ("));
+    assert!(disassembly.contains("  L0:\n"));
+    assert!(disassembly.contains("3:5         PushInt8            2"));
+    assert!(disassembly.contains("'<main>' > 0000: 'f'"));
+    assert_eq!(
+        code_block.source_position(0).map(|p| p.to_string()),
+        Some("5:1".to_owned())
+    );
+
+    let statement_list = parse("{ let i = 0; }", false).unwrap();
+    let mut compiler = ByteCompiler::new(JsString::new("<main>"), false);
+    compiler.compile_statement_list(&statement_list, true);
+    let code_block = compiler.finish();
+    assert!(code_block
+        .disassemble()
+        .contains("PushDeclarativeEnvironment 0000: [i]"));
+    assert!(code_block
+        .to_string()
+        .contains("PushDeclarativeEnvironment 0000: [i]"));
+}
+
+#[test]
+fn bytecode_cache_round_trip() {
+    use crate::{vm::CodeBlock, Context};
+
+    let scenario = r#"
+        function sum(first = 10n, ...rest) {
+            for (const n of rest) {
+                first += n;
+            }
+            return first + BigInt(arguments.length);
+        }
+        const raw = (strings) => strings.raw[0];
+        const counter = (() => {
+            let count = 0;
+            return () => {
+                {
+                    let step = 1;
+                    count += step;
+                }
+                return count;
+            };
+        })();
+        counter();
+        `${sum(1n, 2n, 3n)} ${sum()} ${raw`\n`} ${counter()}`
+    "#;
+
+    let mut context = Context::new();
+    let bytes = context.compile(scenario).unwrap().to_bytes();
+
+    let code_block = CodeBlock::from_bytes(&bytes).unwrap();
+    let mut context = Context::new();
+    let result = context.execute_bytecode(code_block).unwrap();
+    assert_eq!(result.display().to_string(), "\"9 10 \\n 2\"");
+}
+
+#[test]
+fn bytecode_cache_rejects_invalid_caches() {
+    use crate::{
+        vm::{BytecodeCacheError, CodeBlock, Opcode},
+        Context, JsString,
+    };
+
+    let bytes = Context::new().compile("let a = 1; a").unwrap().to_bytes();
+
+    assert_eq!(
+        CodeBlock::from_bytes(b"let a = 1;").unwrap_err(),
+        BytecodeCacheError::NotACache
+    );
+
+    let mut corrupted = bytes.clone();
+    *corrupted.last_mut().unwrap() ^= 0xff;
+    assert_eq!(
+        CodeBlock::from_bytes(&corrupted).unwrap_err(),
+        BytecodeCacheError::ChecksumMismatch
+    );
+
+    let mut incompatible = bytes.clone();
+    incompatible[4] = incompatible[4].wrapping_add(1);
+    assert!(matches!(
+        CodeBlock::from_bytes(&incompatible).unwrap_err(),
+        BytecodeCacheError::IncompatibleVersion { .. }
+    ));
+
+    assert!(matches!(
+        CodeBlock::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+        BytecodeCacheError::ChecksumMismatch
+    ));
+
+    // A jump into the operands of an instruction.
+    let mut code_block = CodeBlock::new(JsString::new("<main>"), 0, false, false);
+    code_block.code = vec![Opcode::Jump as u8, 1, 0, 0, 0];
+    assert!(matches!(
+        CodeBlock::from_bytes(&code_block.to_bytes()).unwrap_err(),
+        BytecodeCacheError::Malformed { .. }
+    ));
+
+    // A binding outside of the environments of the code.
+    code_block.code = vec![Opcode::GetBinding as u8, 0, 0, 0, 0, 0, 0, 0, 0];
+    assert!(matches!(
+        CodeBlock::from_bytes(&code_block.to_bytes()).unwrap_err(),
+        BytecodeCacheError::Malformed { .. }
+    ));
+
+    // Instructions that pop values that were never pushed.
+    code_block.code = vec![Opcode::Pop as u8];
+    assert!(matches!(
+        CodeBlock::from_bytes(&code_block.to_bytes()).unwrap_err(),
+        BytecodeCacheError::Malformed { .. }
+    ));
+    code_block.code = vec![Opcode::PushUndefined as u8, Opcode::Call as u8, 1, 0, 0, 0];
+    assert!(matches!(
+        CodeBlock::from_bytes(&code_block.to_bytes()).unwrap_err(),
+        BytecodeCacheError::Malformed { .. }
+    ));
+    code_block.code = vec![Opcode::PushNull as u8, Opcode::Return as u8];
+    assert!(CodeBlock::from_bytes(&code_block.to_bytes()).is_ok());
+
+    // A slot that only exists in another environment than the one at its depth.
+    let mut code_block = Context::new()
+        .compile("{ let a = 1; a; } { let b = 2, c = 3; c; }")
+        .unwrap();
+    assert_eq!(code_block.scopes.len(), 2);
+    code_block.source_map.clear();
+    let get_binding = |scope: u8| {
+        vec![
+            Opcode::PushDeclarativeEnvironment as u8,
+            scope,
+            0,
+            0,
+            0,
+            Opcode::GetBinding as u8,
+            0,
+            0,
+            0,
+            0,
+            1,
+            0,
+            0,
+            0,
+            Opcode::PopEnvironment as u8,
+        ]
+    };
+    code_block.code = get_binding(0);
+    assert!(matches!(
+        CodeBlock::from_bytes(&code_block.to_bytes()).unwrap_err(),
+        BytecodeCacheError::Malformed { .. }
+    ));
+    code_block.code = get_binding(1);
+    assert!(CodeBlock::from_bytes(&code_block.to_bytes()).is_ok());
+}
+
+#[test]
+fn optimization_levels() {
+    use crate::{vm::OptimizationLevel, Context};
+
+    let scenario = r#"
+        function collatz(n) {
+            let steps = 0;
+            while (n !== 1) {
+                n = n % 2 === 0 ? n / 2 : 3 * n + 1;
+                steps++;
+            }
+            return steps;
+        }
+        let results = [];
+        for (let i = 1; i < 2 ** 3 - -2; i++) {
+            if (false) {
+                results.push(-1);
+            }
+            try {
+                if (i === 7) throw i;
+                results.push(collatz(i));
+            } catch (e) {
+                results.push(`caught ${e}`);
+                continue;
+            } finally {
+                void 0;
+            }
+        }
+        results.join(", ")
+    "#;
+
+    for level in [
+        OptimizationLevel::None,
+        OptimizationLevel::Basic,
+        OptimizationLevel::Full,
+    ] {
+        let mut context = Context::new();
+        context.set_optimization_level(level);
+        let result = context.eval(scenario).unwrap();
+        assert_eq!(
+            result.display().to_string(),
+            "\"0, 1, 7, 2, 5, 8, caught 7, 3, 19\"",
+            "{:?}",
+            level
+        );
+    }
+}
+
+#[test]
+fn optimization_passes() {
+    use crate::{vm::OptimizationLevel, Context};
+
+    let mut context = Context::new();
+    context.set_optimization_level(OptimizationLevel::Full);
+    let code_block = context
+        .compile("let a = (1 + 2) * -3; if (false) { a = 42; } while (a < 0) { a++; }")
+        .unwrap();
+    let disassembly = code_block.disassemble();
+
+    // Constant folding.
+    assert!(disassembly.contains("PushRational        -9"));
+    assert!(!disassembly.contains("Mul"));
+    // Dead code elimination.
+    assert!(!disassembly.contains("PushInt8            42"));
+    // Superinstructions.
+    assert!(disassembly.contains("JumpIfNotLessThan"));
+    assert!(!disassembly.contains("LessThan    "));
+}
+
+#[test]
+fn register_backend() {
+    use crate::{context::Backend, Context};
+
+    let scenarios = [
+        (
+            r#"
+            function fib(n) { return n < 2 ? n : fib(n - 1) + fib(n - 2); }
+            fib(15)
+            "#,
+            "610",
+        ),
+        (
+            r#"
+            (function () {
+                let b = "hello";
+                for (let a = 10; a < 100; a += 5) {
+                    if (a < 50) { b += "!"; }
+                }
+                return b;
+            })()
+            "#,
+            "\"hello!!!!!!!!\"",
+        ),
+        (
+            r#"
+            function f(a) {
+                var total = 0;
+                outer: for (var i = 0; i < 5; i++) {
+                    let j = 0;
+                    do {
+                        j++;
+                        if (j === 2) continue;
+                        if (i === 3) continue outer;
+                        if (j > 3) break;
+                        total += a * j;
+                    } while (j < 10);
+                }
+                return total + (a = 1) + a++ + ++a;
+            }
+            f(2)
+            "#,
+            "37",
+        ),
+        (
+            r#"
+            function g(a) {
+                let o = { x: a, [a + "y"]: 2, a };
+                let arr = [o.x, o["1y"], o.a];
+                arr[1] *= 10;
+                o.x ||= 5;
+                o.z ??= 7;
+                return arr.join() + " " + o.z + " " + (a + (a = 5)) + " " + typeof o;
+            }
+            g(1)
+            "#,
+            "\"1,20,1 7 6 object\"",
+        ),
+        (
+            r#"
+            function counter() {
+                let count = 0;
+                return function () { return ++count; };
+            }
+            let c = counter();
+            c(); c();
+            c()
+            "#,
+            "3",
+        ),
+        (
+            r#"
+            function h() {
+                x = 1;
+                let x;
+            }
+            let message;
+            try { h(); } catch (e) { message = e.name; }
+            message
+            "#,
+            "\"ReferenceError\"",
+        ),
+        (
+            r#"
+            function k() { const c = 1; c += 1; }
+            let name;
+            try { k(); } catch (e) { name = e.name; }
+            name
+            "#,
+            "\"TypeError\"",
+        ),
+        (
+            r#"
+            function Point(x) { this.x = x; }
+            let p = new Point(3);
+            let s = 0;
+            { let i = 4; while (i) { s += p.x * i--; } }
+            s + (p instanceof Point ? 100 : 0)
+            "#,
+            "130",
+        ),
+    ];
+
+    for (scenario, expected) in scenarios {
+        let mut context = Context::new();
+        context.set_backend(Backend::Register);
+        let result = context.eval(scenario).unwrap();
+        assert_eq!(result.display().to_string(), expected, "{}", scenario);
+    }
+}
+
+#[test]
+fn register_code_fallback() {
+    use crate::{context::Backend, Context};
+
+    let mut context = Context::new();
+    context.set_backend(Backend::Register);
+
+    let code_block = context
+        .compile("function leaf(a) { return a + 1; } function closure(a) { return () => a; }")
+        .unwrap();
+    assert!(code_block.register_code.is_some());
+    let leaf = code_block.functions[0].register_code.as_ref().unwrap();
+    assert!(leaf.parameters_in_registers);
+    let closure = code_block.functions[1].register_code.as_ref().unwrap();
+    assert!(!closure.parameters_in_registers);
+
+    // Code blocks with unsupported features run on the stack machine.
+    let code_block = context
+        .compile("function f(a = 1) { return a; } try { f(); } catch (e) {}")
+        .unwrap();
+    assert!(code_block.register_code.is_none());
+    assert!(code_block.functions[0].register_code.is_none());
+
+    let code_block = Context::new().compile("1 + 2").unwrap();
+    assert!(code_block.register_code.is_none());
+}
+
+#[test]
+fn scope_analysis() {
+    use crate::Context;
+
+    let scenario = r#"
+        function outer(a, b = a + 1) {
+            var out = [];
+            var x = a * 10;
+            let y = b;
+            const fns = [];
+            for (let i = 0; i < 3; i++) {
+                fns.push(() => i + x + y);
+            }
+            {
+                let z = 5;
+                function inner() { return z + x; }
+                out.push(inner());
+            }
+            switch (a) {
+                case 1:
+                    let w = 7;
+                    out.push(w);
+                    break;
+            }
+            try {
+                throw 3;
+            } catch (e) {
+                var e2 = e;
+            }
+            for (const k of [1, 2]) {
+                out.push(k + e2);
+            }
+            x++;
+            x += 1;
+            out.push(fns.map(f => f()).join("/"), arguments.length, x);
+            return out.join(",");
+        }
+        outer(1)
+    "#;
+    assert_eq!(&exec(scenario), "\"15,7,4,5,14/15/16,1,12\"");
+
+    let tdz = r#"
+        function g() {
+            try {
+                value;
+            } catch (e) {
+                return e.message;
+            }
+            let value = 1;
+        }
+        g()
+    "#;
+    assert_eq!(&exec(tdz), "\"value is an uninitialized binding\"");
+
+    let const_assignment = r#"
+        function f() {
+            "use strict";
+            const c = 1;
+            try {
+                c = 2;
+            } catch (e) {
+                return e instanceof TypeError && c === 1;
+            }
+        }
+        f()
+    "#;
+    assert_eq!(&exec(const_assignment), "true");
+
+    let mut context = Context::new();
+    let disassembly = context
+        .compile("function f(a) { let b = a; return () => a + b; }")
+        .unwrap()
+        .disassemble();
+    assert!(disassembly.contains("GetBinding          depth: 1, slot: 1"));
+    assert!(disassembly.contains("InitBinding         depth: 0, slot: 2"));
+    assert!(!disassembly.contains("GetName             0000: 'a'"));
+}
+
+#[test]
+fn failed_property_assignment() {
+    let sloppy = r#"
+        const o = { a: 1 };
+        Object.freeze(o);
+        o.a = 2;
+        o["a"] = 3;
+        o.a += 1;
+        o.a++;
+        const g = { get b() { return 1; } };
+        g.b = 2;
+        g.b++;
+        const s = "abc";
+        s.length = 1;
+        [o.a, g.b, s.length].join()
+    "#;
+    assert_eq!(&exec(sloppy), "\"1,1,3\"");
+
+    let strict = r#"
+        "use strict";
+        const o = Object.freeze({ a: 1 });
+        const g = { get b() { return 1; } };
+        const throws = [
+            () => { o.a = 2; },
+            () => { o["a"] = 3; },
+            () => { o.a += 1; },
+            () => { o.a++; },
+            () => { g.b = 2; },
+            () => { g.b++; },
+            () => { "abc".length = 1; },
+        ].map((f) => {
+            try {
+                f();
+                return false;
+            } catch (e) {
+                return e instanceof TypeError;
+            }
+        });
+        throws.join() + "|" + o.a
+    "#;
+    assert_eq!(&exec(strict), "\"true,true,true,true,true,true,true|1\"");
+}
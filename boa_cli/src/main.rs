@@ -25,7 +25,11 @@
     clippy::as_conversions
 )]
 
-use boa::{syntax::ast::node::StatementList, Context};
+use boa::{
+    host::{FixedClock, FixedTimeZone},
+    syntax::ast::node::StatementList,
+    Context,
+};
 use colored::*;
 use rustyline::{config::Config, error::ReadlineError, EditMode, Editor};
 use std::{fs::read, path::PathBuf};
@@ -66,6 +70,11 @@ struct Opt {
     )]
     dump_ast: Option<Option<DumpFormat>>,
 
+    /// Dump the disassembled bytecode to stdout, without executing it.
+    #[cfg(feature = "vm")]
+    #[structopt(long = "dump-bytecode", short = "b")]
+    dump_bytecode: bool,
+
     /// Dump the AST to stdout with the given format.
     #[cfg(feature = "vm")]
     #[structopt(long = "trace", short = "t")]
@@ -74,11 +83,42 @@ struct Opt {
     /// Use vi mode in the REPL
     #[structopt(long = "vi")]
     vi_mode: bool,
+
+    /// Stop the clock at the given time, in milliseconds since the Unix epoch.
+    #[structopt(long, value_name = "MILLISECONDS", allow_hyphen_values = true)]
+    now: Option<i64>,
+
+    /// Seed the random numbers of `Math.random`, to make them reproducible.
+    #[structopt(long, value_name = "SEED")]
+    seed: Option<u64>,
+
+    /// Use a local time zone with a fixed offset from UTC, in minutes.
+    #[structopt(
+        long = "utc-offset",
+        value_name = "MINUTES",
+        allow_hyphen_values = true,
+        parse(try_from_str = parse_utc_offset)
+    )]
+    utc_offset: Option<i32>,
+}
+
+/// Parses an offset from UTC in minutes, which must be less than a day.
+fn parse_utc_offset(offset: &str) -> Result<i32, String> {
+    let offset: i32 = offset.parse().map_err(|e| format!("{}", e))?;
+    if offset.abs() < 24 * 60 {
+        Ok(offset)
+    } else {
+        Err("the offset must be less than a day".to_string())
+    }
 }
 
 impl Opt {
     /// Returns whether a dump flag has been used.
     fn has_dump_flag(&self) -> bool {
+        #[cfg(feature = "vm")]
+        if self.dump_bytecode {
+            return true;
+        }
         self.dump_ast.is_some()
     }
 }
@@ -140,6 +180,16 @@ fn dump<T: AsRef<[u8]>>(src: T, args: &Opt) -> Result<(), String> {
         }
     }
 
+    #[cfg(feature = "vm")]
+    if args.dump_bytecode {
+        use boa::{bytecompiler::ByteCompiler, JsString};
+
+        let ast = parse_tokens(src_bytes)?;
+        let mut compiler = ByteCompiler::new(JsString::new("<main>"), ast.strict());
+        compiler.compile_statement_list(&ast, true);
+        print!("{}", compiler.finish().disassemble());
+    }
+
     Ok(())
 }
 
@@ -152,6 +202,16 @@ pub fn main() -> Result<(), std::io::Error> {
     #[cfg(feature = "vm")]
     context.set_trace(args.trace);
 
+    if let Some(now) = args.now {
+        context.set_clock(FixedClock::new(now));
+    }
+    if let Some(seed) = args.seed {
+        context.seed_random(seed);
+    }
+    if let Some(offset) = args.utc_offset {
+        context.set_time_zone(FixedTimeZone::new(offset * 60));
+    }
+
     for file in &args.files {
         let buffer = read(file)?;
 
@@ -0,0 +1,507 @@
+//! Procedural macros of the Boa JavaScript engine.
+//!
+//! These macros are re-exported by `boa`, and should be used from there:
+//!  - `#[derive(Trace, Finalize)]`, re-exported in `boa::gc`, implement the garbage collector
+//!    traits without depending on the `gc` crate.
+//!  - `#[derive(Class)]`, re-exported in `boa::class`, declares the name of a native class and the
+//!    accessors of its fields.
+//!  - `#[boa::class]`, on an `impl` block of the class, declares its constructor and methods and
+//!    implements `boa::class::Class`.
+
+#![deny(
+    unused_qualifications,
+    unused_import_braces,
+    unused_lifetimes,
+    unreachable_pub,
+    trivial_numeric_casts,
+    missing_debug_implementations,
+    missing_copy_implementations,
+    meta_variable_misuse,
+    rust_2018_idioms,
+    future_incompatible,
+    nonstandard_style
+)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Error, Fields, FnArg,
+    ImplItem, ItemImpl, Lit, Meta, NestedMeta, ReturnType, Type,
+};
+use synstructure::{decl_derive, AddBounds, Structure};
+
+decl_derive!([Trace, attributes(unsafe_ignore_trace)] => derive_trace);
+
+/// Derives `boa::gc::Trace`, tracing every field not marked with `#[unsafe_ignore_trace]`.
+///
+/// Like the derive of the `gc` crate, this also implements `Drop` to call
+/// `boa::gc::Finalize::finalize` when it is safe to do so.
+fn derive_trace(mut s: Structure<'_>) -> TokenStream2 {
+    s.filter(|bi| {
+        !bi.ast()
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("unsafe_ignore_trace"))
+    });
+    let trace_body = s.each(|bi| quote!(mark(#bi)));
+
+    s.add_bounds(AddBounds::Fields);
+    s.underscore_const(true);
+    let trace_impl = s.unsafe_bound_impl(
+        quote!(::boa::gc::Trace),
+        quote! {
+            #[inline] unsafe fn trace(&self) {
+                #[allow(dead_code)]
+                #[inline]
+                unsafe fn mark<T: ::boa::gc::Trace + ?Sized>(it: &T) {
+                    ::boa::gc::Trace::trace(it);
+                }
+                match *self { #trace_body }
+            }
+            #[inline] unsafe fn root(&self) {
+                #[allow(dead_code)]
+                #[inline]
+                unsafe fn mark<T: ::boa::gc::Trace + ?Sized>(it: &T) {
+                    ::boa::gc::Trace::root(it);
+                }
+                match *self { #trace_body }
+            }
+            #[inline] unsafe fn unroot(&self) {
+                #[allow(dead_code)]
+                #[inline]
+                unsafe fn mark<T: ::boa::gc::Trace + ?Sized>(it: &T) {
+                    ::boa::gc::Trace::unroot(it);
+                }
+                match *self { #trace_body }
+            }
+            #[inline] fn finalize_glue(&self) {
+                ::boa::gc::Finalize::finalize(self);
+                #[allow(dead_code)]
+                #[inline]
+                fn mark<T: ::boa::gc::Trace + ?Sized>(it: &T) {
+                    ::boa::gc::Trace::finalize_glue(it);
+                }
+                match *self { #trace_body }
+            }
+        },
+    );
+
+    let drop_impl = s.unbound_impl(
+        quote!(::std::ops::Drop),
+        quote! {
+            fn drop(&mut self) {
+                if ::boa::gc::finalizer_safe() {
+                    ::boa::gc::Finalize::finalize(self);
+                }
+            }
+        },
+    );
+
+    quote! {
+        #trace_impl
+        #drop_impl
+    }
+}
+
+decl_derive!([Finalize] => derive_finalize);
+
+/// Derives `boa::gc::Finalize`, with an empty finalizer.
+fn derive_finalize(mut s: Structure<'_>) -> TokenStream2 {
+    s.underscore_const(true);
+    s.unbound_impl(quote!(::boa::gc::Finalize), quote!())
+}
+
+/// The options of a `#[class(...)]` attribute.
+#[derive(Default)]
+struct ClassOptions {
+    rename: Option<String>,
+    get: bool,
+    set: bool,
+    constructor: bool,
+    skip: bool,
+}
+
+impl ClassOptions {
+    /// Parses the options of the `#[class(...)]` attributes in `attrs`.
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("class")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(Error::new_spanned(meta, "expected `#[class(...)]`")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("get") => {
+                        options.get = true
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("set") => {
+                        options.set = true
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("constructor") => {
+                        options.constructor = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                        options.skip = true;
+                    }
+                    NestedMeta::Meta(Meta::NameValue(name_value))
+                        if name_value.path.is_ident("rename") =>
+                    {
+                        match name_value.lit {
+                            Lit::Str(name) => options.rename = Some(name.value()),
+                            lit => return Err(Error::new_spanned(lit, "expected a string")),
+                        }
+                    }
+                    nested => return Err(Error::new_spanned(nested, "unknown class option")),
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Converts a Rust `snake_case` name into a JavaScript `camelCase` name.
+fn camel_case(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.trim_start_matches('_').chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            camel.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
+
+/// Declares the name of a native class and the accessors of its fields.
+///
+/// The name of the class is the name of the type, unless it is given with
+/// `#[class(rename = "Name")]`. The fields marked with `#[class(get)]` and `#[class(set)]` get an
+/// accessor property on the prototype of the class, named like the field in `camelCase` or with
+/// `#[class(rename = "name")]`. The type of the field must implement `Clone` and
+/// `boa::value::IntoJs` to have a getter, and `boa::value::TryFromJs` to have a setter.
+///
+/// This implements `boa::class::ClassFields`, which is used by `#[boa::class]`.
+#[proc_macro_derive(Class, attributes(class))]
+pub fn derive_class(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_derive_class(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_derive_class(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "a class cannot have generic parameters",
+        ));
+    }
+
+    let ident = &input.ident;
+    let name = ClassOptions::parse(&input.attrs)?
+        .rename
+        .unwrap_or_else(|| ident.to_string());
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect(),
+            _ => Vec::new(),
+        },
+        Data::Enum(_) => Vec::new(),
+        Data::Union(_) => {
+            return Err(Error::new_spanned(ident, "a class cannot be a union"));
+        }
+    };
+
+    let mut accessors = Vec::new();
+    for field in fields {
+        let options = ClassOptions::parse(&field.attrs)?;
+        if !options.get && !options.set {
+            continue;
+        }
+
+        let field_ident = field.ident.as_ref().expect("named fields must have a name");
+        let ty = &field.ty;
+        let js_name = options
+            .rename
+            .unwrap_or_else(|| camel_case(&field_ident.to_string()));
+
+        let getter = if options.get {
+            let getter_name = format!("get {}", js_name);
+            quote! {
+                Some(
+                    ::boa::object::FunctionBuilder::closure(
+                        class.context(),
+                        |this: &::boa::JsValue, _: &[::boa::JsValue], context: &mut ::boa::Context| {
+                            let value = ::std::clone::Clone::clone(
+                                &::boa::class::this_ref::<Self>(this, context)?.#field_ident,
+                            );
+                            Ok(::boa::value::IntoJs::into_js(value, context))
+                        },
+                    )
+                    .name(#getter_name)
+                    .build(),
+                )
+            }
+        } else {
+            quote!(None)
+        };
+
+        let setter = if options.set {
+            let setter_name = format!("set {}", js_name);
+            quote! {
+                Some(
+                    ::boa::object::FunctionBuilder::closure(
+                        class.context(),
+                        |this: &::boa::JsValue, args: &[::boa::JsValue], context: &mut ::boa::Context| {
+                            let value = <#ty as ::boa::value::TryFromJs>::try_from_js_argument(
+                                args, 0, context,
+                            )?;
+                            ::boa::class::this_mut::<Self>(this, context)?.#field_ident = value;
+                            Ok(::boa::JsValue::undefined())
+                        },
+                    )
+                    .name(#setter_name)
+                    .length(1)
+                    .build(),
+                )
+            }
+        } else {
+            quote!(None)
+        };
+
+        accessors.push(quote! {
+            let getter = #getter;
+            let setter = #setter;
+            class.accessor(
+                #js_name,
+                getter,
+                setter,
+                ::boa::property::Attribute::CONFIGURABLE | ::boa::property::Attribute::NON_ENUMERABLE,
+            );
+        });
+    }
+
+    Ok(quote! {
+        impl ::boa::class::ClassFields for #ident {
+            const NAME: &'static str = #name;
+
+            fn init_fields(class: &mut ::boa::class::ClassBuilder<'_>) -> ::boa::JsResult<()> {
+                #(#accessors)*
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Implements `boa::class::Class` from the constructor and methods of an `impl` block.
+///
+/// The type must also derive `Class`, which declares the name of the class and the accessors of
+/// its fields.
+///
+/// In the `impl` block:
+///  - The function marked with `#[class(constructor)]` is the constructor of the class. It
+///    returns `Self` or a `JsResult<Self>`.
+///  - The methods taking `&self` or `&mut self` are added to the prototype of the class, and the
+///    other functions to the class itself.
+///  - The functions marked with `#[class(skip)]` are not added to the class.
+///
+/// The functions are named like in Rust in `camelCase`, or with `#[class(rename = "name")]`.
+/// Their parameters must implement `boa::value::TryFromJs`, and their return type
+/// `boa::value::IntoJsResult`. An argument that cannot be converted throws a `TypeError`, and a
+/// method called on a `this` value that is not an instance of the class throws a `TypeError`.
+#[proc_macro_attribute]
+pub fn class(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return Error::new(
+            proc_macro2::Span::call_site(),
+            "`#[class]` does not take arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let mut item = parse_macro_input!(item as ItemImpl);
+    expand_class(&mut item)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_class(item: &mut ItemImpl) -> syn::Result<TokenStream2> {
+    if let Some((_, path, _)) = &item.trait_ {
+        return Err(Error::new_spanned(
+            path,
+            "`#[class]` must be used on an inherent `impl` block",
+        ));
+    }
+    if !item.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &item.generics,
+            "a class cannot have generic parameters",
+        ));
+    }
+
+    let self_ty = item.self_ty.clone();
+    let mut constructor = None;
+    let mut methods = Vec::new();
+
+    for impl_item in &mut item.items {
+        let method = match impl_item {
+            ImplItem::Method(method) => method,
+            _ => continue,
+        };
+        let options = ClassOptions::parse(&method.attrs)?;
+        method.attrs.retain(|attr| !attr.path.is_ident("class"));
+        if options.skip {
+            continue;
+        }
+
+        let ident = &method.sig.ident;
+        let mut receiver = None;
+        let mut types = Vec::new();
+        for input in &method.sig.inputs {
+            match input {
+                FnArg::Receiver(self_arg) if self_arg.reference.is_some() => {
+                    receiver = Some(self_arg.mutability.is_some());
+                }
+                FnArg::Receiver(self_arg) => {
+                    return Err(Error::new_spanned(
+                        self_arg,
+                        "the methods of a class must take `self` by reference",
+                    ));
+                }
+                FnArg::Typed(pat_type) => types.push(pat_type.ty.as_ref().clone()),
+            }
+        }
+
+        let length = types.len();
+        let values = (0..length)
+            .map(|index| format_ident!("arg_{}", index))
+            .collect::<Vec<_>>();
+        let conversions = types
+            .iter()
+            .zip(&values)
+            .enumerate()
+            .map(|(index, (ty, value))| {
+                quote! {
+                    let #value = <#ty as ::boa::value::TryFromJs>::try_from_js_argument(
+                        args, #index, context,
+                    )?;
+                }
+            });
+        let conversions = quote!(#(#conversions)*);
+
+        if options.constructor {
+            if receiver.is_some() {
+                return Err(Error::new_spanned(
+                    &method.sig,
+                    "the constructor of a class cannot take `self`",
+                ));
+            }
+            if constructor.is_some() {
+                return Err(Error::new_spanned(
+                    &method.sig,
+                    "a class can only have one constructor",
+                ));
+            }
+            let call = quote!(<#self_ty>::#ident(#(#values),*));
+            let body = if returns_result(&method.sig.output) {
+                call
+            } else {
+                quote!(Ok(#call))
+            };
+            constructor = Some((length, conversions, body));
+            continue;
+        }
+
+        let js_name = options
+            .rename
+            .unwrap_or_else(|| camel_case(&ident.to_string()));
+        let args = if length == 0 { quote!(_) } else { quote!(args) };
+        methods.push(match receiver {
+            Some(mutable) => {
+                let this = if mutable {
+                    quote!(&mut *::boa::class::this_mut::<Self>(this, context)?)
+                } else {
+                    quote!(&*::boa::class::this_ref::<Self>(this, context)?)
+                };
+                quote! {
+                    class.method(
+                        #js_name,
+                        #length,
+                        |this: &::boa::JsValue, #args: &[::boa::JsValue], context: &mut ::boa::Context|
+                            -> ::boa::JsResult<::boa::JsValue>
+                        {
+                            #conversions
+                            let result = <#self_ty>::#ident(#this, #(#values),*);
+                            ::boa::value::IntoJsResult::into_js_result(result, context)
+                        },
+                    );
+                }
+            }
+            None => quote! {
+                class.static_method(
+                    #js_name,
+                    #length,
+                    |_: &::boa::JsValue, #args: &[::boa::JsValue], context: &mut ::boa::Context|
+                        -> ::boa::JsResult<::boa::JsValue>
+                    {
+                        #conversions
+                        let result = <#self_ty>::#ident(#(#values),*);
+                        ::boa::value::IntoJsResult::into_js_result(result, context)
+                    },
+                );
+            },
+        });
+    }
+
+    let (length, conversions, body) = constructor.ok_or_else(|| {
+        Error::new(
+            item.self_ty.span(),
+            "a class needs a constructor marked with `#[class(constructor)]`",
+        )
+    })?;
+
+    Ok(quote! {
+        #item
+
+        impl ::boa::class::Class for #self_ty {
+            const NAME: &'static str = <Self as ::boa::class::ClassFields>::NAME;
+            const LENGTH: usize = #length;
+
+            #[allow(unused_variables)]
+            fn constructor(
+                _: &::boa::JsValue,
+                args: &[::boa::JsValue],
+                context: &mut ::boa::Context,
+            ) -> ::boa::JsResult<Self> {
+                #conversions
+                #body
+            }
+
+            fn init(class: &mut ::boa::class::ClassBuilder<'_>) -> ::boa::JsResult<()> {
+                <Self as ::boa::class::ClassFields>::init_fields(class)?;
+                #(#methods)*
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Checks if a return type is a `Result` or a `JsResult`.
+fn returns_result(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => match ty.as_ref() {
+            Type::Path(path) => path.path.segments.last().map_or(false, |segment| {
+                segment.ident == "Result" || segment.ident == "JsResult"
+            }),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
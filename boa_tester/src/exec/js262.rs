@@ -27,10 +27,12 @@ pub(super) fn init(context: &mut Context) -> JsObject {
 ///
 /// Creates a new ECMAScript Realm, defines this API on the new realm's global object, and
 /// returns the `$262` property of the new realm's global object.
-fn create_realm(_this: &JsValue, _: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+fn create_realm(_this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
     // eprintln!("called $262.createRealm()");
 
+    let backend = context.backend();
     let mut context = Context::new();
+    context.set_backend(backend);
 
     // add the $262 object.
     let js_262 = init(&mut context);
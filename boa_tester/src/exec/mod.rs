@@ -3,17 +3,17 @@
 mod js262;
 
 use super::{
-    Harness, Outcome, Phase, SuiteResult, Test, TestFlags, TestOutcomeResult, TestResult,
-    TestSuite, IGNORED,
+    BackendMode, Harness, Outcome, Phase, SuiteResult, Test, TestFlags, TestOutcomeResult,
+    TestResult, TestSuite, IGNORED,
 };
-use boa::{parse, Context, JsValue};
+use boa::{context::Backend, parse, Context, JsValue};
 use colored::Colorize;
 use rayon::prelude::*;
 use std::panic;
 
 impl TestSuite {
     /// Runs the test suite.
-    pub(crate) fn run(&self, harness: &Harness, verbose: u8) -> SuiteResult {
+    pub(crate) fn run(&self, harness: &Harness, verbose: u8, backend: BackendMode) -> SuiteResult {
         if verbose != 0 {
             println!("Suite {}:", self.name);
         }
@@ -21,13 +21,13 @@ impl TestSuite {
         let suites: Vec<_> = self
             .suites
             .par_iter()
-            .map(|suite| suite.run(harness, verbose))
+            .map(|suite| suite.run(harness, verbose, backend))
             .collect();
 
         let tests: Vec<_> = self
             .tests
             .par_iter()
-            .map(|test| test.run(harness, verbose))
+            .map(|test| test.run(harness, verbose, backend))
             .flatten()
             .collect();
 
@@ -39,6 +39,7 @@ impl TestSuite {
         let mut passed = 0;
         let mut ignored = 0;
         let mut panic = 0;
+        let mut divergent = 0;
         for test in &tests {
             match test.result {
                 TestOutcomeResult::Passed => passed += 1,
@@ -46,6 +47,9 @@ impl TestSuite {
                 TestOutcomeResult::Panic => panic += 1,
                 TestOutcomeResult::Failed => {}
             }
+            if test.divergence.is_some() {
+                divergent += 1;
+            }
         }
 
         // Count total tests
@@ -55,6 +59,7 @@ impl TestSuite {
             passed += suite.passed;
             ignored += suite.ignored;
             panic += suite.panic;
+            divergent += suite.divergent;
         }
 
         if verbose != 0 {
@@ -77,6 +82,7 @@ impl TestSuite {
             passed,
             ignored,
             panic,
+            divergent,
             suites,
             tests,
         }
@@ -85,21 +91,32 @@ impl TestSuite {
 
 impl Test {
     /// Runs the test.
-    pub(crate) fn run(&self, harness: &Harness, verbose: u8) -> Vec<TestResult> {
+    pub(crate) fn run(
+        &self,
+        harness: &Harness,
+        verbose: u8,
+        backend: BackendMode,
+    ) -> Vec<TestResult> {
         let mut results = Vec::new();
         if self.flags.contains(TestFlags::STRICT) {
-            results.push(self.run_once(harness, true, verbose));
+            results.push(self.run_once(harness, true, verbose, backend));
         }
 
         if self.flags.contains(TestFlags::NO_STRICT) || self.flags.contains(TestFlags::RAW) {
-            results.push(self.run_once(harness, false, verbose));
+            results.push(self.run_once(harness, false, verbose, backend));
         }
 
         results
     }
 
     /// Runs the test once, in strict or non-strict mode
-    fn run_once(&self, harness: &Harness, strict: bool, verbose: u8) -> TestResult {
+    fn run_once(
+        &self,
+        harness: &Harness,
+        strict: bool,
+        verbose: u8,
+        backend: BackendMode,
+    ) -> TestResult {
         if verbose > 1 {
             println!(
                 "Starting `{}`{}",
@@ -108,7 +125,7 @@ impl Test {
             );
         }
 
-        let (result, result_text) = if !IGNORED.contains_any_flag(self.flags)
+        let (result, result_text, divergence) = if !IGNORED.contains_any_flag(self.flags)
             && !IGNORED.contains_test(&self.name)
             && !IGNORED.contains_any_feature(&self.features)
             && (matches!(self.expected_outcome, Outcome::Positive)
@@ -133,99 +150,22 @@ impl Test {
                         error_type: _,
                     }
                 )) {
-            let res = panic::catch_unwind(|| match self.expected_outcome {
-                Outcome::Positive => {
-                    // TODO: implement async and add `harness/doneprintHandle.js` to the includes.
-
-                    match self.set_up_env(harness, strict) {
-                        Ok(mut context) => {
-                            if strict {
-                                context.set_strict_mode_global();
-                            }
-                            let res = context.eval(&self.content.as_ref());
-
-                            let passed = res.is_ok();
-                            let text = match res {
-                                Ok(val) => format!("{}", val.display()),
-                                Err(e) => format!("Uncaught {}", e.display()),
-                            };
-
-                            (passed, text)
-                        }
-                        Err(e) => (false, e),
-                    }
-                }
-                Outcome::Negative {
-                    phase: Phase::Parse,
-                    ref error_type,
-                }
-                | Outcome::Negative {
-                    phase: Phase::Early,
-                    ref error_type,
-                } => {
-                    assert_eq!(
-                        error_type.as_ref(),
-                        "SyntaxError",
-                        "non-SyntaxError parsing/early error found in {}",
-                        self.name
-                    );
-
-                    match parse(&self.content.as_ref(), strict) {
-                        Ok(n) => (false, format!("{:?}", n)),
-                        Err(e) => (true, format!("Uncaught {}", e)),
-                    }
-                }
-                Outcome::Negative {
-                    phase: Phase::Resolution,
-                    error_type: _,
-                } => todo!("check module resolution errors"),
-                Outcome::Negative {
-                    phase: Phase::Runtime,
-                    ref error_type,
-                } => {
-                    if let Err(e) = parse(&self.content.as_ref(), strict) {
-                        (false, format!("Uncaught {}", e))
-                    } else {
-                        match self.set_up_env(harness, strict) {
-                            Ok(mut context) => {
-                                if strict {
-                                    context.set_strict_mode_global();
-                                }
-                                match context.eval(&self.content.as_ref()) {
-                                    Ok(res) => (false, format!("{}", res.display())),
-                                    Err(e) => {
-                                        let passed =
-                                            e.display().to_string().contains(error_type.as_ref());
-
-                                        (passed, format!("Uncaught {}", e.display()))
-                                    }
-                                }
-                            }
-                            Err(e) => (false, e),
-                        }
-                    }
-                }
-            });
+            let (result, result_text) = self.evaluate(harness, strict, backend.primary());
 
-            let result = res
-                .map(|(res, text)| {
-                    if res {
-                        (TestOutcomeResult::Passed, text)
-                    } else {
-                        (TestOutcomeResult::Failed, text)
-                    }
-                })
-                .unwrap_or_else(|_| {
-                    eprintln!("last panic was on test \"{}\"", self.name);
-                    (TestOutcomeResult::Panic, String::new())
-                });
+            // Runs the test with the fallback backend as well, to compare the outcomes.
+            let divergence = if backend == BackendMode::Both {
+                let (fallback_result, _) = self.evaluate(harness, strict, Backend::Interpreter);
+                (fallback_result != result).then(|| fallback_result)
+            } else {
+                None
+            };
 
             if verbose > 1 {
                 println!(
                     "Result: {}",
-                    if matches!(result, (TestOutcomeResult::Passed, _)) {
+                    if result == TestOutcomeResult::Passed {
                         "Passed".green()
-                    } else if matches!(result, (TestOutcomeResult::Failed, _)) {
+                    } else if result == TestOutcomeResult::Failed {
                         "Failed".red()
                     } else {
                         "⚠ Panic ⚠".red()
@@ -234,7 +174,7 @@ impl Test {
             } else {
                 print!(
                     "{}",
-                    if matches!(result, (TestOutcomeResult::Passed, _)) {
+                    if result == TestOutcomeResult::Passed {
                         ".".green()
                     } else {
                         ".".red()
@@ -242,14 +182,23 @@ impl Test {
                 );
             }
 
-            result
+            if let Some(fallback_result) = divergence {
+                if verbose > 1 {
+                    println!(
+                        "{}",
+                        format!("Diverges from the interpreter: {:?}", fallback_result).yellow()
+                    );
+                }
+            }
+
+            (result, result_text, divergence)
         } else {
             if verbose > 1 {
                 println!("Result: {}", "Ignored".yellow());
             } else {
                 print!("{}", ".".yellow());
             }
-            (TestOutcomeResult::Ignored, String::new())
+            (TestOutcomeResult::Ignored, String::new(), None)
         };
 
         if verbose > 2 {
@@ -263,13 +212,117 @@ impl Test {
             strict,
             result,
             result_text: result_text.into_boxed_str(),
+            divergence,
         }
     }
 
+    /// Evaluates the test once with the given backend, in strict or non-strict mode.
+    fn evaluate(
+        &self,
+        harness: &Harness,
+        strict: bool,
+        backend: Backend,
+    ) -> (TestOutcomeResult, String) {
+        let res = panic::catch_unwind(|| match self.expected_outcome {
+            Outcome::Positive => {
+                // TODO: implement async and add `harness/doneprintHandle.js` to the includes.
+
+                match self.set_up_env(harness, strict, backend) {
+                    Ok(mut context) => {
+                        if strict {
+                            context.set_strict_mode_global();
+                        }
+                        let res = context.eval(&self.content.as_ref());
+
+                        let passed = res.is_ok();
+                        let text = match res {
+                            Ok(val) => format!("{}", val.display()),
+                            Err(e) => format!("Uncaught {}", e.display()),
+                        };
+
+                        (passed, text)
+                    }
+                    Err(e) => (false, e),
+                }
+            }
+            Outcome::Negative {
+                phase: Phase::Parse,
+                ref error_type,
+            }
+            | Outcome::Negative {
+                phase: Phase::Early,
+                ref error_type,
+            } => {
+                assert_eq!(
+                    error_type.as_ref(),
+                    "SyntaxError",
+                    "non-SyntaxError parsing/early error found in {}",
+                    self.name
+                );
+
+                match parse(&self.content.as_ref(), strict) {
+                    Ok(n) => (false, format!("{:?}", n)),
+                    Err(e) => (true, format!("Uncaught {}", e)),
+                }
+            }
+            Outcome::Negative {
+                phase: Phase::Resolution,
+                error_type: _,
+            } => todo!("check module resolution errors"),
+            Outcome::Negative {
+                phase: Phase::Runtime,
+                ref error_type,
+            } => {
+                if let Err(e) = parse(&self.content.as_ref(), strict) {
+                    (false, format!("Uncaught {}", e))
+                } else {
+                    match self.set_up_env(harness, strict, backend) {
+                        Ok(mut context) => {
+                            if strict {
+                                context.set_strict_mode_global();
+                            }
+                            match context.eval(&self.content.as_ref()) {
+                                Ok(res) => (false, format!("{}", res.display())),
+                                Err(e) => {
+                                    let passed =
+                                        e.display().to_string().contains(error_type.as_ref());
+
+                                    (passed, format!("Uncaught {}", e.display()))
+                                }
+                            }
+                        }
+                        Err(e) => (false, e),
+                    }
+                }
+            }
+        });
+
+        res.map(|(res, text)| {
+            if res {
+                (TestOutcomeResult::Passed, text)
+            } else {
+                (TestOutcomeResult::Failed, text)
+            }
+        })
+        .unwrap_or_else(|_| {
+            eprintln!(
+                "last panic was on test \"{}\" ({:?} backend)",
+                self.name, backend
+            );
+            (TestOutcomeResult::Panic, String::new())
+        })
+    }
+
     /// Sets the environment up to run the test.
-    fn set_up_env(&self, harness: &Harness, strict: bool) -> Result<Context, String> {
+    fn set_up_env(
+        &self,
+        harness: &Harness,
+        strict: bool,
+        backend: Backend,
+    ) -> Result<Context, String> {
         // Create new Realm
         let mut context = Context::new();
+        context.set_backend(backend);
 
         // Register the print() function.
         context
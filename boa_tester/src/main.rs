@@ -42,9 +42,10 @@ mod results;
 
 use self::{
     read::{read_harness, read_suite, read_test, MetaData, Negative, TestFlag},
-    results::{compare_results, write_json},
+    results::{compare_results, list_divergences, write_json},
 };
 use bitflags::bitflags;
+use boa::context::Backend;
 use colored::Colorize;
 use fxhash::{FxHashMap, FxHashSet};
 use once_cell::sync::Lazy;
@@ -52,6 +53,7 @@ use serde::{Deserialize, Serialize};
 use std::{
     fs,
     path::{Path, PathBuf},
+    str::FromStr,
 };
 use structopt::StructOpt;
 
@@ -180,6 +182,13 @@ enum Cli {
         /// Optional output folder for the full results information.
         #[structopt(short, long, parse(from_os_str))]
         output: Option<PathBuf>,
+
+        /// Which backend runs the tests: `vm`, `interpreter` or `both`.
+        ///
+        /// With `both`, the results are the ones of the VM, and the tests where the outcome of
+        /// the interpreter is different are reported.
+        #[structopt(short, long, default_value = "vm")]
+        backend: BackendMode,
     },
     Compare {
         /// Base results of the suite.
@@ -204,12 +213,14 @@ fn main() {
             test262_path,
             suite,
             output,
+            backend,
         } => {
             run_test_suite(
                 verbose,
                 test262_path.as_path(),
                 suite.as_path(),
                 output.as_deref(),
+                backend,
             );
         }
         Cli::Compare {
@@ -221,7 +232,13 @@ fn main() {
 }
 
 /// Runs the full test suite.
-fn run_test_suite(verbose: u8, test262_path: &Path, suite: &Path, output: Option<&Path>) {
+fn run_test_suite(
+    verbose: u8,
+    test262_path: &Path,
+    suite: &Path,
+    output: Option<&Path>,
+    backend: BackendMode,
+) {
     if let Some(path) = output {
         if path.exists() {
             if !path.is_dir() {
@@ -244,7 +261,7 @@ fn run_test_suite(verbose: u8, test262_path: &Path, suite: &Path, output: Option
         if verbose != 0 {
             println!("Test loaded, starting...");
         }
-        test.run(&harness, verbose);
+        test.run(&harness, verbose, backend);
 
         println!();
     } else {
@@ -254,7 +271,7 @@ fn run_test_suite(verbose: u8, test262_path: &Path, suite: &Path, output: Option
         if verbose != 0 {
             println!("Test suite loaded, starting tests...");
         }
-        let results = suite.run(&harness, verbose);
+        let results = suite.run(&harness, verbose, backend);
 
         println!();
         println!("Results:");
@@ -273,6 +290,18 @@ fn run_test_suite(verbose: u8, test262_path: &Path, suite: &Path, output: Option
             (results.passed as f64 / results.total as f64) * 100.0
         );
 
+        if backend == BackendMode::Both {
+            println!(
+                "Backend divergences: {}",
+                results.divergent.to_string().yellow()
+            );
+            if verbose != 0 {
+                for test in list_divergences(&results) {
+                    println!("{}", test);
+                }
+            }
+        }
+
         write_json(results, output, verbose)
             .expect("could not write the results to the output JSON file");
     }
@@ -307,6 +336,8 @@ struct SuiteResult {
     ignored: usize,
     #[serde(rename = "p")]
     panic: usize,
+    #[serde(rename = "d", default, skip_serializing_if = "is_zero")]
+    divergent: usize,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     #[serde(rename = "s")]
     suites: Vec<SuiteResult>,
@@ -326,6 +357,49 @@ struct TestResult {
     result_text: Box<str>,
     #[serde(rename = "r")]
     result: TestOutcomeResult,
+    /// The outcome of the interpreter, if it differs from the outcome of the VM.
+    #[serde(rename = "d", default, skip_serializing_if = "Option::is_none")]
+    divergence: Option<TestOutcomeResult>,
+}
+
+/// Checks if a count is zero, to skip it when serializing.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
+/// The backends that run the test suite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BackendMode {
+    /// Runs the tests in the bytecode VM.
+    Vm,
+    /// Runs the tests in the tree-walking interpreter.
+    Interpreter,
+    /// Runs the tests in both backends, and reports the tests where their outcomes differ.
+    Both,
+}
+
+impl BackendMode {
+    /// The backend whose outcome is reported as the result of the tests.
+    fn primary(self) -> Backend {
+        match self {
+            Self::Vm | Self::Both => Backend::Vm,
+            Self::Interpreter => Backend::Interpreter,
+        }
+    }
+}
+
+impl FromStr for BackendMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "vm" => Ok(Self::Vm),
+            "interpreter" => Ok(Self::Interpreter),
+            "both" => Ok(Self::Both),
+            _ => Err(format!("unknown backend: {}", s)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -34,6 +34,8 @@ struct ReducedResultInfo {
     ignored: usize,
     #[serde(rename = "p")]
     panic: usize,
+    #[serde(rename = "d", default, skip_serializing_if = "super::is_zero")]
+    divergent: usize,
 }
 
 impl From<ResultInfo> for ReducedResultInfo {
@@ -46,6 +48,7 @@ impl From<ResultInfo> for ReducedResultInfo {
             passed: info.results.passed,
             ignored: info.results.ignored,
             panic: info.results.panic,
+            divergent: info.results.divergent,
         }
     }
 }
@@ -166,6 +169,35 @@ fn update_gh_pages_repo(path: &Path, verbose: u8) {
     }
 }
 
+/// Lists the tests whose outcome in the interpreter differs from their outcome in the VM.
+pub(crate) fn list_divergences(results: &SuiteResult) -> Vec<Box<str>> {
+    fn collect(path: &Path, suite: &SuiteResult, list: &mut Vec<Box<str>>) {
+        for test in &suite.tests {
+            if let Some(interpreter) = test.divergence {
+                list.push(
+                    format!(
+                        "{}/{}.js {}(vm: {:?}, interpreter: {:?})",
+                        path.display(),
+                        test.name,
+                        if test.strict { "[strict mode] " } else { "" },
+                        test.result,
+                        interpreter
+                    )
+                    .into_boxed_str(),
+                );
+            }
+        }
+
+        for sub_suite in &suite.suites {
+            collect(&path.join(sub_suite.name.as_ref()), sub_suite, list);
+        }
+    }
+
+    let mut list = Vec::new();
+    collect(Path::new(results.name.as_ref()), results, &mut list);
+    list
+}
+
 /// Compares the results of two test suite runs.
 pub(crate) fn compare_results(base: &Path, new: &Path, markdown: bool) {
     let base_results: ResultInfo = serde_json::from_reader(BufReader::new(